@@ -2,7 +2,7 @@
 
 use crate::config::LauncherMode;
 use crate::error::IpcError;
-use crate::items::ApplicationItem;
+use crate::items::{ApplicationItem, ListItem};
 use tokio::sync::oneshot;
 
 /// Response type for IPC operations.
@@ -55,6 +55,19 @@ pub enum DaemonEvent {
 
     /// Applications have been updated (from file watcher)
     ApplicationsChanged { applications: Vec<ApplicationItem> },
+
+    /// Applications rescanned and config reloaded in place (from SIGHUP)
+    Refreshed {
+        applications: Vec<ApplicationItem>,
+        config_reloaded: bool,
+    },
+
+    /// Launcher items built ahead of the first open (from prewarming),
+    /// tagged with the generation of the applications they were built from
+    Prewarmed {
+        items: Vec<ListItem>,
+        generation: u64,
+    },
 }
 
 impl From<WindowEvent> for DaemonEvent {
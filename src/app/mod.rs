@@ -1,4 +1,5 @@
 pub mod events;
+pub mod placement;
 pub mod state;
 pub mod window;
 
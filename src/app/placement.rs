@@ -0,0 +1,112 @@
+//! Choosing the monitor the launcher opens on.
+
+use crate::config::LauncherPlacement;
+
+/// A monitor's bounds in the global compositor layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MonitorRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Whether this is the primary monitor
+    pub primary: bool,
+}
+
+impl MonitorRect {
+    /// Whether a point lies on this monitor.
+    pub fn contains(&self, (x, y): (f64, f64)) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+/// Pick the index of the monitor to open the launcher on.
+///
+/// `focused` is a point on the focused monitor and `cursor` the pointer
+/// position, when the compositor reports them. Falls back to the primary
+/// monitor (or the first one) when the desired monitor isn't found.
+pub fn select_monitor(
+    monitors: &[MonitorRect],
+    placement: LauncherPlacement,
+    focused: Option<(f64, f64)>,
+    cursor: Option<(f64, f64)>,
+) -> Option<usize> {
+    let containing = |point: Option<(f64, f64)>| {
+        point.and_then(|point| monitors.iter().position(|m| m.contains(point)))
+    };
+
+    let desired = match placement {
+        LauncherPlacement::Focused => containing(focused),
+        LauncherPlacement::Cursor => containing(cursor),
+        LauncherPlacement::Monitor(index) => (index < monitors.len()).then_some(index),
+    };
+
+    desired.or_else(|| {
+        monitors
+            .iter()
+            .position(|m| m.primary)
+            .or((!monitors.is_empty()).then_some(0))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitors() -> Vec<MonitorRect> {
+        vec![
+            MonitorRect {
+                x: 0.0,
+                y: 0.0,
+                width: 1920.0,
+                height: 1080.0,
+                primary: false,
+            },
+            MonitorRect {
+                x: 1920.0,
+                y: 0.0,
+                width: 2560.0,
+                height: 1440.0,
+                primary: true,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_select_monitor_under_cursor() {
+        let monitors = monitors();
+        let select = |cursor| select_monitor(&monitors, LauncherPlacement::Cursor, None, cursor);
+
+        assert_eq!(select(Some((100.0, 100.0))), Some(0));
+        assert_eq!(select(Some((1920.0, 500.0))), Some(1));
+        // Off-screen or unknown cursor falls back to the primary monitor
+        assert_eq!(select(Some((9000.0, 0.0))), Some(1));
+        assert_eq!(select(None), Some(1));
+    }
+
+    #[test]
+    fn test_select_focused_and_fixed_monitor() {
+        let monitors = monitors();
+        assert_eq!(
+            select_monitor(
+                &monitors,
+                LauncherPlacement::Focused,
+                Some((0.0, 0.0)),
+                None
+            ),
+            Some(0)
+        );
+        assert_eq!(
+            select_monitor(&monitors, LauncherPlacement::Monitor(0), None, None),
+            Some(0)
+        );
+        assert_eq!(
+            select_monitor(&monitors, LauncherPlacement::Monitor(5), None, None),
+            Some(1)
+        );
+        assert_eq!(
+            select_monitor(&[], LauncherPlacement::Focused, None, None),
+            None
+        );
+    }
+}
@@ -1,11 +1,12 @@
+use crate::app::placement::{MonitorRect, select_monitor};
 use crate::app::{DaemonEvent, DaemonEventSender, WindowEvent};
 use crate::compositor::Compositor;
-use crate::config::{ConfigModule, LauncherMode, get_combined_modules};
-use crate::items::{ApplicationItem, ListItem, WindowItem};
+use crate::config::{ConfigModule, LauncherMode, LauncherPlacement, get_combined_modules};
+use crate::items::{ApplicationItem, ListItem, WindowItem, group_windows, icon_for_class};
 use crate::ui::LauncherView;
 use gpui::{
-    App, AppContext, Bounds, Entity, WindowBackgroundAppearance, WindowBounds, WindowDecorations,
-    WindowHandle, WindowKind, WindowOptions,
+    App, AppContext, Bounds, DisplayId, Entity, WindowBackgroundAppearance, WindowBounds,
+    WindowDecorations, WindowHandle, WindowKind, WindowOptions,
     layer_shell::{Anchor, KeyboardInteractivity, Layer, LayerShellOptions},
     point, px, size,
 };
@@ -19,8 +20,11 @@ pub struct LauncherWindow {
     pub launcher_view: Entity<LauncherView>,
 }
 
+/// Open the launcher, reusing `prewarmed` items in place of the
+/// applications when prewarming left some.
 pub fn create_and_show_window(
     applications: Vec<ApplicationItem>,
+    prewarmed: Option<Vec<ListItem>>,
     compositor: Arc<dyn Compositor>,
     modes: Vec<LauncherMode>,
     event_tx: DaemonEventSender,
@@ -28,29 +32,48 @@ pub fn create_and_show_window(
 ) -> anyhow::Result<LauncherWindow> {
     // Fetch open windows from compositor (if not disabled)
     let combined_modules = get_combined_modules();
-    let windows = if combined_modules.contains(&ConfigModule::Windows) {
-        fetch_windows(compositor.as_ref())
+    let windows = if combined_modules.contains(&ConfigModule::Windows)
+        || modes.contains(&LauncherMode::Switcher)
+    {
+        fetch_windows(compositor.as_ref(), &applications)
     } else {
         Vec::new()
     };
-    create_and_show_window_impl(applications, compositor, windows, modes, event_tx, cx)
+    let items = launcher_items(windows, applications, prewarmed);
+    create_and_show_window_impl(items, compositor, modes, event_tx, cx)
 }
 
-fn create_and_show_window_impl(
-    applications: Vec<ApplicationItem>,
-    compositor: Arc<dyn Compositor>,
+/// Combine windows and applications into the launcher's items.
+///
+/// Prewarmed items replace the applications. Their windows are dropped,
+/// since windows may have opened or closed since prewarming.
+fn launcher_items(
     windows: Vec<WindowItem>,
-    modes: Vec<LauncherMode>,
-    event_tx: DaemonEventSender,
-    cx: &mut App,
-) -> anyhow::Result<LauncherWindow> {
-    // Combine windows and applications into items list
+    applications: Vec<ApplicationItem>,
+    prewarmed: Option<Vec<ListItem>>,
+) -> Vec<ListItem> {
     // Built-in actions and submenus are added by the delegate
     // Order doesn't matter here - sort_priority in delegate handles display order
     let mut items: Vec<ListItem> = Vec::with_capacity(windows.len() + applications.len());
     items.extend(windows.into_iter().map(ListItem::Window));
-    items.extend(applications.into_iter().map(ListItem::Application));
+    match prewarmed {
+        Some(prewarmed) => items.extend(
+            prewarmed
+                .into_iter()
+                .filter(|item| !matches!(item, ListItem::Window(_))),
+        ),
+        None => items.extend(applications.into_iter().map(ListItem::Application)),
+    }
+    items
+}
 
+fn create_and_show_window_impl(
+    items: Vec<ListItem>,
+    compositor: Arc<dyn Compositor>,
+    modes: Vec<LauncherMode>,
+    event_tx: DaemonEventSender,
+    cx: &mut App,
+) -> anyhow::Result<LauncherWindow> {
     // Get display size based on config
     let config = crate::config::config();
     let (launcher_w, launcher_h) = config.get_launcher_size();
@@ -84,6 +107,7 @@ fn create_and_show_window_impl(
 
     let options = WindowOptions {
         window_bounds: Some(WindowBounds::Windowed(fullscreen_bounds)),
+        display_id: launcher_display(compositor.as_ref(), cx),
         titlebar: None,
         focus: true,
         show: true,
@@ -138,24 +162,75 @@ fn create_and_show_window_impl(
     })
 }
 
+/// Pick the display to open the launcher on, following `launcher_placement`.
+///
+/// Returns None (letting the compositor decide) when no displays are known.
+fn launcher_display(compositor: &dyn Compositor, cx: &App) -> Option<DisplayId> {
+    let displays = cx.displays();
+    let primary = cx.primary_display().map(|d| d.id());
+    let monitors: Vec<MonitorRect> = displays
+        .iter()
+        .map(|display| {
+            let bounds = display.bounds();
+            MonitorRect {
+                x: f32::from(bounds.origin.x) as f64,
+                y: f32::from(bounds.origin.y) as f64,
+                width: f32::from(bounds.size.width) as f64,
+                height: f32::from(bounds.size.height) as f64,
+                primary: Some(display.id()) == primary,
+            }
+        })
+        .collect();
+
+    let placement = crate::config::config().launcher_placement;
+    let (focused, cursor) = match placement {
+        LauncherPlacement::Focused => (compositor.focused_monitor_position(), None),
+        LauncherPlacement::Cursor => (None, compositor.cursor_position()),
+        LauncherPlacement::Monitor(_) => (None, None),
+    };
+    // Without compositor support, leave the focused monitor to the compositor
+    if placement == LauncherPlacement::Focused && focused.is_none() {
+        return None;
+    }
+
+    let index = select_monitor(&monitors, placement, focused, cursor)?;
+    Some(displays[index].id())
+}
+
 pub fn close_window(handle: &WindowHandle<Root>, cx: &mut App) {
     let _ = handle.update(cx, |_root, window, _cx| {
         window.remove_window();
     });
 }
 
-/// Fetch open windows from the compositor and convert to WindowItems.
-fn fetch_windows(compositor: &dyn Compositor) -> Vec<WindowItem> {
+/// Fetch open windows from the compositor and convert to WindowItems,
+/// grouped by application if `group_windows` is set.
+///
+/// Without window switching support (no compositor detected) there are
+/// no windows, and nothing is queried or logged.
+pub(crate) fn fetch_windows(
+    compositor: &dyn Compositor,
+    applications: &[ApplicationItem],
+) -> Vec<WindowItem> {
+    if !compositor.capabilities().window_switching {
+        return Vec::new();
+    }
     match compositor.list_windows() {
         Ok(windows) => {
-            windows
+            let windows: Vec<WindowItem> = windows
                 .into_iter()
                 .map(|info| {
-                    // Try to resolve icon from app class
-                    let icon_path = resolve_window_icon(&info.class);
+                    // Prefer the icon of the matching application, then the icon theme
+                    let icon_path = icon_for_class(&info.class, applications)
+                        .or_else(|| resolve_window_icon(&info.class));
                     WindowItem::from_window_info(info, icon_path)
                 })
-                .collect()
+                .collect();
+            if crate::config::config().group_windows {
+                group_windows(windows)
+            } else {
+                windows
+            }
         }
         Err(e) => {
             warn!(%e, "Failed to list windows");
@@ -191,3 +266,33 @@ fn resolve_window_icon(app_class: &str) -> Option<std::path::PathBuf> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositor::NoopCompositor;
+    use crate::test_utils::{mock_application, mock_applications, mock_window};
+
+    #[test]
+    fn test_no_compositor_has_no_windows() {
+        let windows = fetch_windows(&NoopCompositor, &[mock_application("Firefox")]);
+        assert!(windows.is_empty());
+    }
+
+    #[test]
+    fn test_first_open_uses_prewarmed_items() {
+        let prewarmed = vec![
+            ListItem::Window(mock_window("Old", "kitty")),
+            ListItem::Application(mock_application("Prewarmed")),
+        ];
+        let windows = vec![mock_window("Current", "firefox")];
+
+        let items = launcher_items(windows, mock_applications(), Some(prewarmed));
+        let ids: Vec<&str> = items.iter().map(|item| item.id()).collect();
+        // Windows are always the current ones
+        assert_eq!(ids, vec!["window-current", "app-prewarmed"]);
+
+        let items = launcher_items(Vec::new(), mock_applications(), None);
+        assert_eq!(items.len(), mock_applications().len());
+    }
+}
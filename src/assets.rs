@@ -72,6 +72,9 @@ pub enum PhosphorIcon {
     YoutubeLogo,
     Brain,
     Palette,
+    Calculator,
+    Folder,
+    CaretRight,
 }
 
 impl PhosphorIcon {
@@ -97,6 +100,9 @@ impl PhosphorIcon {
             Self::YoutubeLogo => "icons/youtube-logo-bold.svg",
             Self::Brain => "icons/brain-bold.svg",
             Self::Palette => "icons/palette-bold.svg",
+            Self::Calculator => "icons/calculator-bold.svg",
+            Self::Folder => "icons/folder-bold.svg",
+            Self::CaretRight => "icons/caret-right-bold.svg",
         }
     }
 
@@ -122,7 +128,22 @@ impl PhosphorIcon {
             "youtube-logo" => Some(Self::YoutubeLogo),
             "brain" => Some(Self::Brain),
             "palette" => Some(Self::Palette),
+            "calculator" => Some(Self::Calculator),
+            "folder" => Some(Self::Folder),
+            "caret-right" => Some(Self::CaretRight),
             _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caret_right_icon_is_embedded() {
+        let icon = PhosphorIcon::from_name("caret-right").unwrap();
+        assert_eq!(icon, PhosphorIcon::CaretRight);
+        assert!(PhosphorAssets::get(icon.path()).is_some());
+    }
+}
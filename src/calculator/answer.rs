@@ -0,0 +1,98 @@
+//! Persistent last calculator answer.
+//!
+//! The results of the last confirmed calculations are kept across launcher
+//! sessions so new expressions can refer to them as `ans` (or `ans1`),
+//! `ans2`, ...
+
+use crate::store::PersistedStore;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+lazy_static! {
+    /// Last answer shared by all launcher sessions.
+    static ref LAST_ANSWER: PersistedStore<LastAnswer> =
+        PersistedStore::open("last_answer.json");
+}
+
+/// Number of answers kept, so `ans9` is the oldest one.
+pub const MAX_ANSWERS: usize = 9;
+
+/// The stored results of the last confirmed calculations.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastAnswer {
+    /// Raw result value (e.g. "42" or "3.1415926535").
+    pub value: Option<String>,
+    /// Results before `value`, most recent first.
+    #[serde(default)]
+    pub earlier: Vec<String>,
+}
+
+impl LastAnswer {
+    /// All stored answers, most recent first.
+    pub fn answers(&self) -> impl Iterator<Item = &String> {
+        self.value.iter().chain(self.earlier.iter())
+    }
+
+    /// Make `value` the last answer, keeping at most [`MAX_ANSWERS`].
+    pub fn push(&mut self, value: String) {
+        if let Some(previous) = self.value.replace(value) {
+            self.earlier.insert(0, previous);
+            self.earlier.truncate(MAX_ANSWERS - 1);
+        }
+    }
+}
+
+/// Get the last answer, if remembering answers is enabled.
+pub fn last_answer() -> Option<String> {
+    if !crate::config::config().calculator_remember_answer {
+        return None;
+    }
+    LAST_ANSWER.snapshot().value.clone()
+}
+
+/// Get the remembered answers, most recent first.
+pub fn recent_answers() -> Vec<String> {
+    if !crate::config::config().calculator_remember_answer {
+        return Vec::new();
+    }
+    LAST_ANSWER.snapshot().answers().cloned().collect()
+}
+
+/// Store a calculation result as the last answer and persist it.
+///
+/// With remembering disabled, a previously stored answer is forgotten instead.
+pub fn record_answer(value: &str) {
+    if crate::config::config().calculator_remember_answer {
+        LAST_ANSWER.update(|answers| answers.push(value.to_string()));
+    } else if LAST_ANSWER.snapshot().value.is_some() {
+        clear_last_answer();
+    }
+}
+
+/// Forget the remembered answers.
+pub fn clear_last_answer() {
+    LAST_ANSWER.update(|answers| *answers = LastAnswer::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_keeps_recent_answers() {
+        let mut answers = LastAnswer::default();
+        for i in 1..=12 {
+            answers.push(i.to_string());
+        }
+        let recent: Vec<_> = answers.answers().map(String::as_str).collect();
+        assert_eq!(recent.len(), MAX_ANSWERS);
+        assert_eq!(recent[..3], ["12", "11", "10"]);
+        assert_eq!(recent.last(), Some(&"4"));
+    }
+
+    #[test]
+    fn test_load_answer_without_history() {
+        let answer: LastAnswer = serde_json::from_str(r#"{"value":"7"}"#).unwrap();
+        assert_eq!(answer.answers().collect::<Vec<_>>(), ["7"]);
+    }
+}
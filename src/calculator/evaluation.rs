@@ -3,33 +3,139 @@
 //! Wraps fend to provide a simple interface for evaluating
 //! mathematical expressions and formatting results.
 
+use crate::config::{CalculatorTrigger, DigitGrouping, config};
 use crate::items::CalculatorItem;
 use fend_core::Context;
 use std::sync::{Mutex, OnceLock};
 
 static CONTEXT: OnceLock<Mutex<Context>> = OnceLock::new();
 
+/// Constants usable without parentheses, with the fend expression they bind to.
+const CONSTANTS: [(&str, &str); 4] = [
+    ("pi", "pi"),
+    ("e", "e"),
+    ("tau", "(2 pi)"),
+    ("phi", "((1 + 5^0.5) / 2)"),
+];
+
+/// Functions that may appear in a query made of constants (`sin(pi/2)`).
+const FUNCTIONS: [&str; 16] = [
+    "sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh", "tanh", "ln", "log", "log2",
+    "log10", "exp", "abs", "sqrt",
+];
+
+/// Name referring to the last answer.
+const ANSWER: &str = "ans";
+
+/// Longest expression the calculator evaluates; longer input is not math.
+const MAX_EXPRESSION_LENGTH: usize = 256;
+
+/// Operators that mark a query made of constants as an expression.
+const OPERATORS: [char; 7] = ['+', '-', '*', '/', '^', '(', ')'];
+
+/// Boolean operators by increasing precedence. Within a level, two-character
+/// operators come first so `<=` isn't read as `<`.
+const BOOLEAN_OPERATORS: [&[&str]; 3] = [&["||"], &["&&"], &["==", "!=", "<=", ">=", "<", ">"]];
+
+/// Extract the expression to evaluate from a query.
+///
+/// Returns None if the query shouldn't trigger the calculator. In explicit
+/// mode the `=` prefix is required and stripped. In auto mode the query
+/// needs a digit, or a constant combined with an operator (`pi/e`) where
+/// every word is a constant, function or answer, so `pi-hole` stays a
+/// search. Queries with digits may contain other words, e.g. units.
+pub fn calculator_expression(query: &str, trigger: CalculatorTrigger) -> Option<&str> {
+    let trimmed = query.trim();
+    if trimmed.len() > MAX_EXPRESSION_LENGTH {
+        return None;
+    }
+    match trigger {
+        CalculatorTrigger::Auto => {
+            let has_digit = trimmed.chars().any(|c| c.is_numeric());
+            let is_value = |word: &str| constant(word).is_some() || answer_index(word).is_some();
+            let has_constant = words(trimmed).any(|(_, word)| is_value(word))
+                && words(trimmed).all(|(_, word)| is_value(word) || FUNCTIONS.contains(&word))
+                && (trimmed.contains(OPERATORS) || split_boolean(trimmed).is_some());
+            (has_digit || has_constant).then_some(trimmed)
+        }
+        CalculatorTrigger::Explicit => trimmed
+            .strip_prefix('=')
+            .map(str::trim)
+            .filter(|expression| !expression.is_empty()),
+    }
+}
+
 /// Evaluate a mathematical expression.
 ///
 /// Returns `Ok(CalculatorItem)` if the expression can be parsed,
 /// or `None` if parsing fails entirely.
 pub fn evaluate_expression(input: &str) -> Result<CalculatorItem, String> {
+    evaluate_expression_with_answer(input, None)
+}
+
+/// Evaluate a mathematical expression where `ans` refers to `answer`.
+pub fn evaluate_expression_with_answer(
+    input: &str,
+    answer: Option<&str>,
+) -> Result<CalculatorItem, String> {
+    evaluate_expression_with_answers(input, answer.as_slice())
+}
+
+/// Evaluate a mathematical expression with previous results, most recent
+/// first, bound as `ans`/`ans1`, `ans2`, ...
+///
+/// Referring to more results than are stored (`ans9` with three) is an error.
+pub fn evaluate_expression_with_answers(
+    input: &str,
+    answers: &[&str],
+) -> Result<CalculatorItem, String> {
     let expression = input.trim().to_string();
 
     let mut context = CONTEXT
         .get_or_init(|| Mutex::new(Context::new()))
         .lock()
         .unwrap();
-    match fend_core::evaluate(&expression, &mut context) {
+    if let Some(name) = reserved_assignment(&expression) {
+        return Ok(CalculatorItem {
+            id: "calculator-result".to_string(),
+            display_result: format!("Can't assign to {name}"),
+            expression,
+            clipboard_result: None,
+            is_error: true,
+            delta: None,
+            bindings: Vec::new(),
+        });
+    }
+    if let Some(name) = missing_answer(&expression, answers) {
+        return Ok(CalculatorItem {
+            id: "calculator-result".to_string(),
+            display_result: format!("No result for {name}"),
+            expression,
+            clipboard_result: None,
+            is_error: true,
+            delta: None,
+            bindings: Vec::new(),
+        });
+    }
+    if let Some(item) = evaluate_percent_delta(&expression, answers, &mut context) {
+        return Ok(item);
+    }
+    if let Some(item) = evaluate_boolean(&expression, answers, &mut context) {
+        return Ok(item);
+    }
+    match fend_core::evaluate(&bind_constants(&expression, answers), &mut context) {
         Ok(value) => {
-            let value = value.get_main_result();
+            let value = value.get_main_result().to_string();
             let calc_value = value.trim_start_matches("approx. ");
+            let bindings = evaluate_bindings(&expression, answers, &mut context);
             Ok(CalculatorItem {
                 id: "calculator-result".to_string(),
-                expression,
-                display_result: format_display(value),
                 clipboard_result: Some(calc_value.to_string()),
+                display_result: format_display(&value),
+                expression,
                 is_error: false,
+                delta: None,
+                bindings,
             })
         }
         Err(err) => {
@@ -40,6 +146,8 @@ pub fn evaluate_expression(input: &str) -> Result<CalculatorItem, String> {
                     display_result: "Infinity".to_string(),
                     clipboard_result: None,
                     is_error: true,
+                    delta: None,
+                    bindings: Vec::new(),
                 })
             } else {
                 Err(err.to_string())
@@ -48,57 +156,390 @@ pub fn evaluate_expression(input: &str) -> Result<CalculatorItem, String> {
     }
 }
 
-/// Format a number for display with thousand separators.
+/// Evaluate `A + B%` / `A - B%` as adding or removing B percent of A,
+/// keeping the added amount as the item's delta.
+fn evaluate_percent_delta(
+    expression: &str,
+    answers: &[&str],
+    context: &mut Context,
+) -> Option<CalculatorItem> {
+    let (base, sign, percent) = percent_delta(expression)?;
+    let base = bind_constants(base, answers);
+
+    let mut evaluate = |expr: String| {
+        let value = fend_core::evaluate(&expr, context).ok()?;
+        Some(value.get_main_result().to_string())
+    };
+    let delta = evaluate(format!("({base}) * {percent} / 100"))?;
+    let total = evaluate(format!("({base}) {sign} ({base}) * {percent} / 100"))?;
+
+    Some(CalculatorItem {
+        id: "calculator-result".to_string(),
+        expression: expression.to_string(),
+        display_result: format_display(&total),
+        clipboard_result: Some(total.trim_start_matches("approx. ").to_string()),
+        is_error: false,
+        delta: Some(format!("{sign}{}", format_display(&delta))),
+        bindings: Vec::new(),
+    })
+}
+
+/// Evaluate a comparison or logical expression (`5 > 3`, `1 && 0`) to
+/// `true` or `false`.
+fn evaluate_boolean(
+    expression: &str,
+    answers: &[&str],
+    context: &mut Context,
+) -> Option<CalculatorItem> {
+    if expression.contains(';') {
+        return None;
+    }
+    split_boolean(expression)?;
+    let result = truth(expression, answers, context)?.to_string();
+
+    Some(CalculatorItem {
+        id: "calculator-result".to_string(),
+        expression: expression.to_string(),
+        display_result: result.clone(),
+        clipboard_result: Some(result),
+        is_error: false,
+        delta: None,
+        bindings: Vec::new(),
+    })
+}
+
+/// The truth value of an expression; numbers are true unless zero.
+fn truth(expression: &str, answers: &[&str], context: &mut Context) -> Option<bool> {
+    let Some((left, operator, right)) = split_boolean(expression) else {
+        return number(expression, answers, context).map(|value| value != 0.0);
+    };
+    match operator {
+        "||" => Some(truth(left, answers, context)? || truth(right, answers, context)?),
+        "&&" => Some(truth(left, answers, context)? && truth(right, answers, context)?),
+        _ => {
+            let left = number(left, answers, context)?;
+            let right = number(right, answers, context)?;
+            Some(match operator {
+                "==" => left == right,
+                "!=" => left != right,
+                "<=" => left <= right,
+                ">=" => left >= right,
+                "<" => left < right,
+                _ => left > right,
+            })
+        }
+    }
+}
+
+/// Evaluate an expression to a plain number.
+fn number(expression: &str, answers: &[&str], context: &mut Context) -> Option<f64> {
+    let value = fend_core::evaluate(&bind_constants(expression, answers), context).ok()?;
+    let value = value.get_main_result();
+    value.trim_start_matches("approx. ").parse().ok()
+}
+
+/// Split an expression at its lowest-precedence boolean operator outside
+/// parentheses.
+fn split_boolean(expression: &str) -> Option<(&str, &'static str, &str)> {
+    BOOLEAN_OPERATORS.iter().find_map(|operators| {
+        let mut depth = 0;
+        for (i, c) in expression.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ if depth == 0 => {
+                    let found = operators
+                        .iter()
+                        .find(|op| expression[i..].starts_with(**op))
+                        .filter(|op| !is_shift_or_arrow(expression, i, op));
+                    if let Some(op) = found {
+                        return Some((&expression[..i], *op, &expression[i + op.len()..]));
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    })
+}
+
+/// Whether the operator at `index` is part of a shift (`<<`, `>>`) or of
+/// fend's `->` conversion rather than a comparison.
+fn is_shift_or_arrow(expression: &str, index: usize, operator: &str) -> bool {
+    let bytes = expression.as_bytes();
+    let first = operator.as_bytes()[0];
+    if !matches!(first, b'<' | b'>') {
+        return false;
+    }
+    let before = index.checked_sub(1).map(|i| bytes[i]);
+    let after = bytes.get(index + 1).copied();
+    before == Some(first) || after == Some(first) || (first == b'>' && before == Some(b'-'))
+}
+
+/// Evaluate the variables assigned by a multi-statement expression.
+///
+/// Single statements have no bindings; their value is the result.
+fn evaluate_bindings(
+    expression: &str,
+    answers: &[&str],
+    context: &mut Context,
+) -> Vec<(String, String)> {
+    assignments(expression)
+        .into_iter()
+        .filter_map(|(name, statements)| {
+            let expr = bind_constants(&format!("{statements}; {name}"), answers);
+            let value = fend_core::evaluate(&expr, context).ok()?;
+            Some((name.to_string(), format_display(value.get_main_result())))
+        })
+        .collect()
+}
+
+/// Find the assignments in a `;` separated expression.
+///
+/// Returns each assigned name with the statements up to and including its
+/// assignment. Comparisons like `a <= b` or `a == b` are not assignments.
+fn assignments(expression: &str) -> Vec<(&str, &str)> {
+    if !expression.contains(';') {
+        return Vec::new();
+    }
+    let mut result = Vec::new();
+    let mut end = 0;
+    for statement in expression.split(';') {
+        end += statement.len();
+        if let Some(name) = assigned_name(statement) {
+            result.push((name, &expression[..end]));
+        }
+        end += 1;
+    }
+    result
+}
+
+/// The name a statement assigns to, if it is an assignment.
+fn assigned_name(statement: &str) -> Option<&str> {
+    let (name, value) = statement.split_once('=')?;
+    let name = name.trim();
+    let is_identifier = name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    (is_identifier && !value.starts_with('=')).then_some(name)
+}
+
+/// Find an assignment to an answer or a constant, which can't be reassigned.
+fn reserved_assignment(expression: &str) -> Option<&str> {
+    expression
+        .split(';')
+        .filter_map(assigned_name)
+        .find(|name| answer_index(name).is_some() || constant(name).is_some())
+}
+
+/// Index into the answers (most recent first) a name refers to.
+///
+/// `ans` and `ans1` are the last answer, `ans2` the one before, and so on.
+fn answer_index(word: &str) -> Option<usize> {
+    let digits = word.strip_prefix(ANSWER)?;
+    if digits.is_empty() {
+        return Some(0);
+    }
+    if !digits.bytes().all(|b| b.is_ascii_digit()) || digits.starts_with('0') {
+        return None;
+    }
+    digits.parse::<usize>().ok()?.checked_sub(1)
+}
+
+/// Find a numbered answer (`ans9`) beyond the stored answers.
+fn missing_answer<'a>(expression: &'a str, answers: &[&str]) -> Option<&'a str> {
+    words(expression)
+        .map(|(_, word)| word)
+        .find(|word| *word != ANSWER && answer_index(word).is_some_and(|i| i >= answers.len()))
+}
+
+/// Split `A + B%` or `A - B%` into the base expression, sign and percentage.
+fn percent_delta(expression: &str) -> Option<(&str, char, &str)> {
+    let rest = expression.trim_end().strip_suffix('%')?;
+    let percent_start = rest
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+        .len();
+    let percent = &rest[percent_start..];
+    percent.parse::<f64>().ok()?;
+
+    let before = rest[..percent_start].trim_end();
+    let sign = before.chars().last().filter(|c| matches!(c, '+' | '-'))?;
+    let base = before[..before.len() - 1].trim_end();
+    // "-5%" alone or "2 * -5%" are not a delta
+    let last = base.chars().last()?;
+    (last.is_alphanumeric() || matches!(last, ')' | '.')).then_some((base, sign, percent))
+}
+
+/// Look up the binding of a constant name.
+fn constant(word: &str) -> Option<&'static str> {
+    CONSTANTS
+        .iter()
+        .find(|(name, _)| *name == word)
+        .map(|(_, value)| *value)
+}
+
+/// Iterate over the standalone words of an expression with their byte offsets.
+///
+/// Letters directly following a digit (as in `1e5`) are not a word.
+fn words(expression: &str) -> impl Iterator<Item = (usize, &str)> {
+    let bytes = expression.as_bytes();
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        while pos < bytes.len() {
+            if !bytes[pos].is_ascii_alphabetic() {
+                pos += 1;
+                continue;
+            }
+            let start = pos;
+            while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+                pos += 1;
+            }
+            if start == 0 || !(bytes[start - 1].is_ascii_alphanumeric() || bytes[start - 1] == b'.')
+            {
+                return Some((start, &expression[start..pos]));
+            }
+        }
+        None
+    })
+}
+
+/// Replace bare constants and `ans`, `ans1`, `ans2`, ... with their bindings.
+///
+/// `pi()` style calls are accepted for backward compatibility.
+fn bind_constants(expression: &str, answers: &[&str]) -> String {
+    let mut result = String::with_capacity(expression.len());
+    let mut last = 0;
+    for (start, word) in words(expression) {
+        let value = match answer_index(word).and_then(|i| answers.get(i)) {
+            Some(answer) => format!("({answer})"),
+            None => match constant(word) {
+                Some(value) => value.to_string(),
+                None => continue,
+            },
+        };
+        let mut end = start + word.len();
+        if expression[end..].starts_with("()") {
+            end += 2;
+        }
+        result.push_str(&expression[last..start]);
+        result.push_str(&value);
+        last = end;
+    }
+    result.push_str(&expression[last..]);
+    result
+}
+
+/// Magnitude from which results are no longer formatted through `f64`.
+///
+/// Above this, not every integer survives the `f64`/`i64` round-trip,
+/// so the exact digits from fend are grouped instead.
+const EXACT_DISPLAY_LIMIT: f64 = 1e15;
+
+/// Format a number for display with the configured digit grouping.
 fn format_display(value: &str) -> String {
+    format_display_grouped(value, config().calculator_grouping)
+}
+
+/// Format a number for display with the given digit grouping.
+fn format_display_grouped(value: &str, grouping: DigitGrouping) -> String {
     // Convert to f64, else return the original string
-    let Ok(value) = value.parse::<f64>() else {
+    let Ok(number) = value.parse::<f64>() else {
         return value.to_string();
     };
-    if value.fract() == 0.0 && value.abs() < 1e15 {
+    if !number.is_finite() {
+        return value.to_string();
+    }
+
+    if number.abs() >= EXACT_DISPLAY_LIMIT {
+        // Precise display from fend's digits, scientific if they aren't plain decimal
+        return group_decimal_str(value, grouping).unwrap_or_else(|| format!("{:e}", number));
+    }
+
+    if number.fract() == 0.0
+        && let Some(int) = exact_i64(number)
+    {
         // Integer display with thousand separators
-        format_with_separators(value as i64)
+        return format_with_separators(int, grouping);
+    }
+
+    // Decimal display
+    let formatted = format!("{:.10}", number);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        // Negative values that round to zero
+        return "0".to_string();
+    }
+
+    // Add thousand separators to the integer part, keeping the sign of
+    // values between -1 and 0
+    group_decimal_str(trimmed, grouping).unwrap_or_else(|| trimmed.to_string())
+}
+
+/// Convert to i64 only if the value survives the round-trip unchanged.
+fn exact_i64(value: f64) -> Option<i64> {
+    let int = value as i64;
+    (int as f64 == value).then_some(int)
+}
+
+/// Add digit group separators to a plain decimal string (e.g. "-1234567.5").
+///
+/// Returns None if the string isn't an optionally signed decimal number.
+fn group_decimal_str(value: &str, grouping: DigitGrouping) -> Option<String> {
+    let (sign, unsigned) = match value.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", value),
+    };
+    let (int_part, dec_part) = match unsigned.split_once('.') {
+        Some((int_part, dec_part)) => (int_part, Some(dec_part)),
+        None => (unsigned, None),
+    };
+
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(int_part) || !dec_part.is_none_or(is_digits) {
+        return None;
+    }
+
+    let mut result = format!("{}{}", sign, group_digits(int_part, grouping));
+    if let Some(dec_part) = dec_part {
+        result.push('.');
+        result.push_str(dec_part);
+    }
+    Some(result)
+}
+
+/// Format an integer with digit group separators.
+fn format_with_separators(value: i64, grouping: DigitGrouping) -> String {
+    let formatted = group_digits(&value.unsigned_abs().to_string(), grouping);
+    if value < 0 {
+        format!("-{}", formatted)
     } else {
-        // Decimal display
-        let formatted = format!("{:.10}", value);
-        let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
-
-        // Add thousand separators to the integer part
-        if let Some(dot_pos) = trimmed.find('.') {
-            let (int_part, dec_part) = trimmed.split_at(dot_pos);
-            let int_val: i64 = int_part.parse().unwrap_or(0);
-            format!("{}{}", format_with_separators(int_val), dec_part)
-        } else {
-            let int_val: i64 = trimmed.parse().unwrap_or(0);
-            format_with_separators(int_val)
-        }
+        formatted
     }
 }
 
-/// Format an integer with thousand separators.
-fn format_with_separators(value: i64) -> String {
-    let is_negative = value < 0;
-    let abs_value = value.abs();
-    let s = abs_value.to_string();
+/// Insert group separators into a string of digits.
+fn group_digits(digits: &str, grouping: DigitGrouping) -> String {
+    // Whether a separator goes before the i-th digit from the right
+    let starts_group = |i: usize| match grouping {
+        DigitGrouping::Western => i % 3 == 0,
+        DigitGrouping::Indian => i == 3 || (i > 3 && (i - 3) % 2 == 0),
+        DigitGrouping::Myriad => i % 4 == 0,
+        DigitGrouping::None => false,
+    };
 
     let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
+    for (i, c) in digits.chars().rev().enumerate() {
+        if i > 0 && starts_group(i) {
             result.push(',');
         }
         result.push(c);
     }
 
-    let formatted: String = result.chars().rev().collect();
-    if is_negative {
-        format!("-{}", formatted)
-    } else {
-        formatted
-    }
+    result.chars().rev().collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::evaluate_expression;
+    use super::*;
 
     #[test]
     fn test_basic_evaluation() {
@@ -146,4 +587,314 @@ mod tests {
         let result = evaluate_expression("sin(0)").unwrap();
         assert_eq!(result.display_result, "0");
     }
+
+    #[test]
+    fn test_integer_display_boundary() {
+        assert_eq!(format_display("999999999999999"), "999,999,999,999,999");
+        assert_eq!(format_display("1000000000000000"), "1,000,000,000,000,000");
+        assert_eq!(
+            format_display("-1000000000000001"),
+            "-1,000,000,000,000,001"
+        );
+    }
+
+    #[test]
+    fn test_large_integer_keeps_exact_digits() {
+        // Not representable as f64, and beyond i64::MAX respectively
+        assert_eq!(
+            format_display("123456789012345678"),
+            "123,456,789,012,345,678"
+        );
+        let result = evaluate_expression("2^64").unwrap();
+        assert_eq!(result.display_result, "18,446,744,073,709,551,616");
+        assert_eq!(result.text_for_clipboard(), "18446744073709551616");
+    }
+
+    #[test]
+    fn test_exact_i64_round_trip() {
+        assert_eq!(exact_i64(999_999_999_999_999.0), Some(999_999_999_999_999));
+        // Saturates to i64::MAX, which doesn't round-trip
+        assert_eq!(exact_i64(1e19), None);
+    }
+
+    #[test]
+    fn test_large_non_decimal_falls_back_to_scientific() {
+        assert_eq!(format_display("1e20"), "1e20");
+    }
+
+    #[test]
+    fn test_calculator_expression_auto() {
+        assert_eq!(
+            calculator_expression(" 2+2 ", CalculatorTrigger::Auto),
+            Some("2+2")
+        );
+        assert_eq!(
+            calculator_expression("firefox", CalculatorTrigger::Auto),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bind_constants() {
+        assert_eq!(bind_constants("2*pi", &[]), "2*pi");
+        assert_eq!(bind_constants("pi() + tau", &[]), "pi + (2 pi)");
+        assert_eq!(bind_constants("phi^2", &[]), "((1 + 5^0.5) / 2)^2");
+        // Exponent notation and longer names are left alone
+        assert_eq!(bind_constants("1e5 + pie", &[]), "1e5 + pie");
+        assert_eq!(bind_constants("ans * 2", &["-4"]), "(-4) * 2");
+        assert_eq!(bind_constants("ans * 2", &[]), "ans * 2");
+        assert_eq!(bind_constants("ans2 - ans1", &["1", "5"]), "(5) - (1)");
+    }
+
+    #[test]
+    fn test_bare_constant_detection() {
+        assert_eq!(
+            calculator_expression("2*pi", CalculatorTrigger::Auto),
+            Some("2*pi")
+        );
+        assert_eq!(
+            calculator_expression("tau / e", CalculatorTrigger::Auto),
+            Some("tau / e")
+        );
+        assert_eq!(
+            calculator_expression("sin(pi/2)", CalculatorTrigger::Auto),
+            Some("sin(pi/2)")
+        );
+        // A lone constant name is still a search
+        assert_eq!(calculator_expression("pi", CalculatorTrigger::Auto), None);
+        assert_eq!(calculator_expression("epic", CalculatorTrigger::Auto), None);
+        // So are names made of a constant and other words
+        assert_eq!(
+            calculator_expression("pi-hole", CalculatorTrigger::Auto),
+            None
+        );
+        assert_eq!(
+            calculator_expression("e-mail (work)", CalculatorTrigger::Auto),
+            None
+        );
+    }
+
+    #[test]
+    fn test_constant_evaluation() {
+        let result = evaluate_expression("2*pi").unwrap();
+        assert!(result.display_result.contains("6.283"));
+        let result = evaluate_expression("tau").unwrap();
+        assert!(result.display_result.contains("6.283"));
+        let result = evaluate_expression("pi()").unwrap();
+        assert!(result.display_result.contains("3.141"));
+        let result = evaluate_expression("phi").unwrap();
+        assert!(result.display_result.contains("1.618"));
+    }
+
+    #[test]
+    fn test_evaluate_with_answer() {
+        let result = evaluate_expression_with_answer("ans * 2", Some("21")).unwrap();
+        assert_eq!(result.display_result, "42");
+        assert_eq!(
+            calculator_expression("ans+ans", CalculatorTrigger::Auto),
+            Some("ans+ans")
+        );
+    }
+
+    #[test]
+    fn test_evaluate_with_numbered_answers() {
+        let answers = ["10", "4", "2"];
+        let result = evaluate_expression_with_answers("ans1 * ans2 + ans3", &answers).unwrap();
+        assert_eq!(result.display_result, "42");
+        let result = evaluate_expression_with_answers("ans - ans1", &answers).unwrap();
+        assert_eq!(result.display_result, "0");
+
+        assert_eq!(answer_index("ans"), Some(0));
+        assert_eq!(answer_index("ans12"), Some(11));
+        assert_eq!(answer_index("ans0"), None);
+        assert_eq!(answer_index("answer"), None);
+    }
+
+    #[test]
+    fn test_out_of_range_answer_is_an_error() {
+        let result = evaluate_expression_with_answers("ans9 + 1", &["1", "2", "3"]).unwrap();
+        assert!(result.is_error);
+        assert_eq!(result.display_result, "No result for ans9");
+        assert_eq!(result.clipboard_result, None);
+
+        let result = evaluate_expression_with_answers("ans1 = 5", &["1"]).unwrap();
+        assert_eq!(result.display_result, "Can't assign to ans1");
+    }
+
+    #[test]
+    fn test_negative_grouping() {
+        let cases = [
+            ("-12", "-12", "-12"),
+            ("-999", "-999", "-999"),
+            ("-1000", "-1,000", "-1000"),
+            ("0 - 100000", "-100,000", "-100000"),
+            ("0 - 1000000", "-1,000,000", "-1000000"),
+            ("-1234.5", "-1,234.5", "-1234.5"),
+            ("-0.5", "-0.5", "-0.5"),
+        ];
+        for (expression, display, clipboard) in cases {
+            let result = evaluate_expression(expression).unwrap();
+            assert_eq!(result.display_result, display, "{expression}");
+            assert_eq!(result.text_for_clipboard(), clipboard, "{expression}");
+        }
+
+        assert_eq!(format_display("-0.00000000001"), "0");
+        assert_eq!(format_display("-1000000.25"), "-1,000,000.25");
+        assert_eq!(
+            format_with_separators(i64::MIN, DigitGrouping::Western),
+            "-9,223,372,036,854,775,808"
+        );
+    }
+
+    #[test]
+    fn test_digit_grouping() {
+        let cases = [
+            (DigitGrouping::Western, "1,234,567", "-1,234,567.25"),
+            (DigitGrouping::Indian, "12,34,567", "-12,34,567.25"),
+            (DigitGrouping::Myriad, "123,4567", "-123,4567.25"),
+            (DigitGrouping::None, "1234567", "-1234567.25"),
+        ];
+        for (grouping, int, decimal) in cases {
+            assert_eq!(
+                format_display_grouped("1234567", grouping),
+                int,
+                "{grouping:?}"
+            );
+            assert_eq!(
+                format_display_grouped("-1234567.25", grouping),
+                decimal,
+                "{grouping:?}"
+            );
+        }
+
+        assert_eq!(format_display_grouped("123", DigitGrouping::Indian), "123");
+        assert_eq!(
+            format_display_grouped("1234567890", DigitGrouping::Indian),
+            "1,23,45,67,890"
+        );
+
+        // The clipboard value stays ungrouped
+        let result = evaluate_expression("1234567").unwrap();
+        assert_eq!(result.text_for_clipboard(), "1234567");
+    }
+
+    #[test]
+    fn test_assignment_bindings() {
+        let result = evaluate_expression("a=2; b=3; a+b").unwrap();
+        assert_eq!(result.display_result, "5");
+        assert_eq!(
+            result.bindings,
+            vec![
+                ("a".to_string(), "2".to_string()),
+                ("b".to_string(), "3".to_string())
+            ]
+        );
+
+        assert!(evaluate_expression("2 + 3").unwrap().bindings.is_empty());
+        assert_eq!(assignments("x = 1; x <= 2; y == 3"), vec![("x", "x = 1")]);
+    }
+
+    #[test]
+    fn test_reserved_names_cannot_be_assigned() {
+        for expression in ["pi = 4", "ans = 5", "x = 1; e = 2; x + e", "tau=1"] {
+            let result = evaluate_expression_with_answer(expression, Some("3")).unwrap();
+            assert!(result.is_error, "{expression}");
+            assert_eq!(result.clipboard_result, None);
+        }
+        assert_eq!(
+            evaluate_expression("pi = 4").unwrap().display_result,
+            "Can't assign to pi"
+        );
+
+        // Other names still work, and comparisons aren't assignments
+        let result = evaluate_expression("rate = 2; rate * 3").unwrap();
+        assert!(!result.is_error);
+        assert_eq!(result.display_result, "6");
+        assert_eq!(reserved_assignment("pi == 3"), None);
+        assert_eq!(reserved_assignment("x = pi"), None);
+    }
+
+    #[test]
+    fn test_boolean_expressions() {
+        let cases = [
+            ("5 > 3", "true"),
+            ("2 == 2", "true"),
+            ("2 != 2", "false"),
+            ("3 <= 2", "false"),
+            ("0.1 + 0.2 == 0.3", "true"),
+            ("1 && 0", "false"),
+            ("1 || 0", "true"),
+            ("2 > 1 && (1 + 1) >= 2", "true"),
+        ];
+        for (expression, display) in cases {
+            let result = evaluate_expression(expression).unwrap();
+            assert_eq!(result.display_result, display, "{expression}");
+            assert_eq!(result.text_for_clipboard(), display, "{expression}");
+        }
+
+        assert_eq!(split_boolean("1 << 2"), None);
+        assert_eq!(split_boolean("8 >> 1"), None);
+        assert_eq!(split_boolean("5 m -> ft"), None);
+        assert_eq!(split_boolean("(1 < 2)"), None);
+        assert_eq!(split_boolean("1 <= 2"), Some(("1 ", "<=", " 2")));
+        assert_eq!(
+            calculator_expression("pi > e", CalculatorTrigger::Auto),
+            Some("pi > e")
+        );
+    }
+
+    #[test]
+    fn test_percent_delta_parsing() {
+        assert_eq!(percent_delta("85 + 18%"), Some(("85", '+', "18")));
+        assert_eq!(
+            percent_delta("(2 * 40)-12.5 %"),
+            Some(("(2 * 40)", '-', "12.5"))
+        );
+        assert_eq!(percent_delta("18%"), None);
+        assert_eq!(percent_delta("2 * -5%"), None);
+        assert_eq!(percent_delta("85 * 1.2"), None);
+    }
+
+    #[test]
+    fn test_percent_delta_evaluation() {
+        let result = evaluate_expression("85 + 18%").unwrap();
+        assert_eq!(result.display_result, "100.3");
+        assert_eq!(result.text_for_clipboard(), "100.3");
+        assert_eq!(result.delta.as_deref(), Some("+15.3"));
+
+        let result = evaluate_expression("50 - 10%").unwrap();
+        assert_eq!(result.display_result, "45");
+        assert_eq!(result.delta.as_deref(), Some("-5"));
+
+        let result = evaluate_expression("85 * 1.2").unwrap();
+        assert_eq!(result.display_result, "102");
+        assert_eq!(result.delta, None);
+    }
+
+    #[test]
+    fn test_calculator_expression_too_long() {
+        let long = "1+".repeat(5000) + "1";
+        assert_eq!(calculator_expression(&long, CalculatorTrigger::Auto), None);
+        let explicit = format!("={long}");
+        assert_eq!(
+            calculator_expression(&explicit, CalculatorTrigger::Explicit),
+            None
+        );
+    }
+
+    #[test]
+    fn test_calculator_expression_explicit() {
+        assert_eq!(
+            calculator_expression("= 2+2", CalculatorTrigger::Explicit),
+            Some("2+2")
+        );
+        assert_eq!(
+            calculator_expression("2+2", CalculatorTrigger::Explicit),
+            None
+        );
+        assert_eq!(
+            calculator_expression("=", CalculatorTrigger::Explicit),
+            None
+        );
+    }
 }
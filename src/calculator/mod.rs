@@ -1,8 +1,20 @@
 //! Calculator module for evaluating mathematical expressions.
 //!
 //! This module provides functionality to:
+//! - Decide whether a query should trigger the calculator
 //! - Evaluate expressions using fend
+//! - Remember recent answers across launcher sessions
+//! - Keep a running history of expressions in the scratchpad
 
+mod answer;
 mod evaluation;
+mod scratchpad;
 
-pub use evaluation::evaluate_expression;
+pub use answer::{
+    LastAnswer, MAX_ANSWERS, clear_last_answer, last_answer, recent_answers, record_answer,
+};
+pub use evaluation::{
+    calculator_expression, evaluate_expression, evaluate_expression_with_answer,
+    evaluate_expression_with_answers,
+};
+pub use scratchpad::Scratchpad;
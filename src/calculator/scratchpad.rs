@@ -0,0 +1,162 @@
+//! Calculator scratchpad with a running history.
+//!
+//! Each submitted expression is evaluated and appended like a REPL line,
+//! with `ans` referring to the result of the previous line and `ans2`,
+//! `ans3`, ... to earlier ones.
+
+use crate::items::CalculatorItem;
+
+use super::evaluate_expression_with_answers;
+
+/// Evaluated lines of a scratchpad session, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct Scratchpad {
+    lines: Vec<CalculatorItem>,
+    selected: Option<usize>,
+}
+
+impl Scratchpad {
+    /// Create an empty scratchpad.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluate `expression` and append it, selecting the new line.
+    ///
+    /// Blank input is ignored. Expressions that fail to evaluate are kept
+    /// as error lines so the history shows what was entered.
+    pub fn push(&mut self, expression: &str) -> Option<&CalculatorItem> {
+        let expression = expression.trim();
+        if expression.is_empty() {
+            return None;
+        }
+
+        let mut line = evaluate_expression_with_answers(expression, &self.answers())
+            .unwrap_or_else(|err| CalculatorItem {
+                id: String::new(),
+                expression: expression.to_string(),
+                display_result: err,
+                clipboard_result: None,
+                is_error: true,
+                delta: None,
+                bindings: Vec::new(),
+            });
+        line.id = format!("scratchpad-{}", self.lines.len());
+
+        self.lines.push(line);
+        self.selected = Some(self.lines.len() - 1);
+        self.lines.last()
+    }
+
+    /// The value `ans` refers to: the result of the last line that evaluated.
+    pub fn answer(&self) -> Option<&str> {
+        self.answers().first().copied()
+    }
+
+    /// Results of the lines that evaluated, most recent first.
+    pub fn answers(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .rev()
+            .filter(|line| !line.is_error)
+            .filter_map(|line| line.clipboard_result.as_deref())
+            .collect()
+    }
+
+    /// All lines, oldest first.
+    pub fn lines(&self) -> &[CalculatorItem] {
+        &self.lines
+    }
+
+    /// Whether no expression has been entered yet.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Index of the selected line.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Select the next (newer) line.
+    pub fn select_next(&mut self) {
+        if let Some(index) = self.selected {
+            self.selected = Some((index + 1).min(self.lines.len() - 1));
+        }
+    }
+
+    /// Select the previous (older) line.
+    pub fn select_prev(&mut self) {
+        if let Some(index) = self.selected {
+            self.selected = Some(index.saturating_sub(1));
+        }
+    }
+
+    /// Text to copy for the line at `index`.
+    pub fn copy_text(&self, index: usize) -> Option<&str> {
+        self.lines
+            .get(index)
+            .map(CalculatorItem::text_for_clipboard)
+    }
+
+    /// Text to copy for the selected line.
+    pub fn selected_copy_text(&self) -> Option<&str> {
+        self.copy_text(self.selected?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_appends_evaluated_lines() {
+        let mut pad = Scratchpad::new();
+        assert!(pad.push("   ").is_none());
+        assert!(pad.is_empty());
+
+        assert_eq!(pad.push("2 + 3").unwrap().text_for_clipboard(), "5");
+        assert_eq!(pad.push("10 * 4").unwrap().text_for_clipboard(), "40");
+
+        let expressions: Vec<_> = pad.lines().iter().map(|l| l.expression.as_str()).collect();
+        assert_eq!(expressions, vec!["2 + 3", "10 * 4"]);
+        assert_eq!(pad.lines()[0].id, "scratchpad-0");
+        assert_eq!(pad.selected_index(), Some(1));
+        assert_eq!(pad.copy_text(0), Some("5"));
+        assert_eq!(pad.copy_text(2), None);
+    }
+
+    #[test]
+    fn test_ans_refers_to_previous_line() {
+        let mut pad = Scratchpad::new();
+        pad.push("6 * 7");
+        assert_eq!(pad.push("ans + 1").unwrap().text_for_clipboard(), "43");
+        assert_eq!(pad.push("ans / 43").unwrap().text_for_clipboard(), "1");
+        assert_eq!(pad.answer(), Some("1"));
+        assert_eq!(pad.push("ans3 - ans2").unwrap().text_for_clipboard(), "-1");
+    }
+
+    #[test]
+    fn test_error_lines_keep_previous_answer() {
+        let mut pad = Scratchpad::new();
+        pad.push("12");
+        assert!(pad.push("1 +* 2").unwrap().is_error);
+        assert_eq!(pad.lines().len(), 2);
+        assert_eq!(pad.push("ans * 2").unwrap().text_for_clipboard(), "24");
+    }
+
+    #[test]
+    fn test_selection_stays_in_bounds() {
+        let mut pad = Scratchpad::new();
+        pad.select_next();
+        assert_eq!(pad.selected_index(), None);
+
+        pad.push("1");
+        pad.push("2");
+        pad.select_next();
+        assert_eq!(pad.selected_copy_text(), Some("2"));
+        pad.select_prev();
+        pad.select_prev();
+        assert_eq!(pad.selected_copy_text(), Some("1"));
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
-use crate::config::LauncherMode;
+use crate::compositor::{detect_compositor, windows_to_json};
+use crate::config::{LauncherMode, init_config};
+use crate::desktop::{find_application, launch_application, load_applications};
 use crate::ipc::client;
 
 #[derive(Parser)]
@@ -32,11 +35,40 @@ pub enum Commands {
     Quit,
     /// Reload the daemon (fully restart the process)
     Reload,
+    /// Print the windows reported by the compositor as JSON and exit
+    Windows,
+    /// Launch an application by desktop entry id (e.g. firefox) and exit
+    Launch {
+        /// Desktop entry id, with or without the .desktop suffix
+        id: String,
+    },
     /// Theme management
     Theme {
         #[command(subcommand)]
         action: Option<ThemeCommands>,
     },
+    /// Clipboard history backup
+    Clipboard {
+        #[command(subcommand)]
+        action: ClipboardCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ClipboardCommands {
+    /// Export the clipboard history to a JSON file
+    Export {
+        /// File to write
+        path: PathBuf,
+        /// Include images (as base64 encoded PNG)
+        #[arg(long)]
+        images: bool,
+    },
+    /// Merge an exported history file into the clipboard history
+    Import {
+        /// File to read
+        path: PathBuf,
+    },
 }
 
 #[derive(Subcommand)]
@@ -52,7 +84,8 @@ pub enum ThemeCommands {
 
 /// Handle a client command by sending it to the running daemon.
 pub fn handle_client_command(cmd: Commands) -> Result<()> {
-    if !client::is_daemon_running() {
+    // Window listing and launching work directly, no daemon needed
+    if !matches!(cmd, Commands::Windows | Commands::Launch { .. }) && !client::is_daemon_running() {
         anyhow::bail!("zlaunch daemon is not running. Start it first by running: zlaunch");
     }
 
@@ -73,6 +106,15 @@ pub fn handle_client_command(cmd: Commands) -> Result<()> {
             client::reload()?;
             println!("Daemon is reloading...");
         }
+        Commands::Windows => {
+            let windows = detect_compositor().list_windows()?;
+            println!("{}", windows_to_json(&windows)?);
+        }
+        Commands::Launch { id } => {
+            init_config();
+            let entries = load_applications();
+            launch_application(find_application(&entries, &id)?)?;
+        }
         Commands::Theme { action } => match action {
             None => {
                 // No subcommand - show current theme
@@ -96,6 +138,16 @@ pub fn handle_client_command(cmd: Commands) -> Result<()> {
                 println!("Theme set to '{}'", name);
             }
         },
+        Commands::Clipboard { action } => match action {
+            ClipboardCommands::Export { path, images } => {
+                let count = client::export_clipboard(&path, images)?;
+                println!("Exported {} entries to {}", count, path.display());
+            }
+            ClipboardCommands::Import { path } => {
+                let count = client::import_clipboard(&path)?;
+                println!("Imported {} new entries from {}", count, path.display());
+            }
+        },
     }
 
     Ok(())
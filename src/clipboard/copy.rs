@@ -1,19 +1,35 @@
 //! Clipboard copy utilities.
 
-use arboard::Clipboard;
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
 
+use arboard::{Clipboard, LinuxClipboardKind, SetExtLinux};
+use lazy_static::lazy_static;
+
+use super::item::{ClipboardContent, ClipboardItem, ClipboardSelection};
 use crate::error::ClipboardError;
 
+/// Attempts made when the clipboard is temporarily unavailable.
+const COPY_ATTEMPTS: usize = 3;
+
+/// Pause between attempts.
+const RETRY_DELAY: Duration = Duration::from_millis(50);
+
+lazy_static! {
+    /// Clipboard handle kept for the daemon's lifetime.
+    ///
+    /// The owner of a selection serves its contents on request, so a handle
+    /// dropped right after copying can lose the value (notably on X11
+    /// without a clipboard manager) once the launcher hides.
+    static ref CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+}
+
 /// Copy text to the system clipboard.
 ///
 /// Returns `Ok(())` on success, or a `ClipboardError` on failure.
 pub fn copy_to_clipboard(text: &str) -> Result<(), ClipboardError> {
-    let mut clipboard =
-        Clipboard::new().map_err(|e| ClipboardError::AccessFailed(e.to_string()))?;
-
-    clipboard
-        .set_text(text.to_string())
-        .map_err(|e| ClipboardError::CopyFailed(e.to_string()))
+    with_clipboard(|clipboard| clipboard.set_text(text.to_string()))
 }
 
 /// Copy an RGBA image to the system clipboard.
@@ -24,16 +40,142 @@ pub fn copy_image_to_clipboard(
     height: usize,
     rgba_bytes: &[u8],
 ) -> Result<(), ClipboardError> {
-    let mut clipboard =
-        Clipboard::new().map_err(|e| ClipboardError::AccessFailed(e.to_string()))?;
-
-    let image_data = arboard::ImageData {
-        width,
-        height,
-        bytes: std::borrow::Cow::Borrowed(rgba_bytes),
-    };
-
-    clipboard
-        .set_image(image_data)
-        .map_err(|e| ClipboardError::CopyFailed(e.to_string()))
+    with_clipboard(|clipboard| {
+        clipboard.set_image(arboard::ImageData {
+            width,
+            height,
+            bytes: std::borrow::Cow::Borrowed(rgba_bytes),
+        })
+    })
+}
+
+/// Put a history entry back on the clipboard.
+///
+/// Entries captured from PRIMARY also go to CLIPBOARD, so the restored
+/// entry pastes with Ctrl+V.
+pub fn restore_item(item: &ClipboardItem) -> Result<(), ClipboardError> {
+    let kind = linux_kind(ClipboardSelection::Clipboard);
+
+    with_clipboard(|clipboard| match &item.content {
+        ClipboardContent::Image {
+            width,
+            height,
+            rgba_bytes,
+        } => clipboard.set().clipboard(kind).image(arboard::ImageData {
+            width: *width,
+            height: *height,
+            bytes: std::borrow::Cow::Borrowed(rgba_bytes),
+        }),
+        _ => clipboard.set().clipboard(kind).text(item.full_content()),
+    })
+}
+
+/// The arboard clipboard kind of a selection.
+pub(crate) fn linux_kind(selection: ClipboardSelection) -> LinuxClipboardKind {
+    match selection {
+        ClipboardSelection::Clipboard => LinuxClipboardKind::Clipboard,
+        ClipboardSelection::Primary => LinuxClipboardKind::Primary,
+    }
+}
+
+/// Run a clipboard operation on the shared handle, retrying transient
+/// failures. The handle is reopened after a failed attempt.
+fn with_clipboard(
+    mut op: impl FnMut(&mut Clipboard) -> Result<(), arboard::Error>,
+) -> Result<(), ClipboardError> {
+    retry(COPY_ATTEMPTS, RETRY_DELAY, || {
+        let mut shared = CLIPBOARD.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut clipboard = match shared.take() {
+            Some(clipboard) => clipboard,
+            None => Clipboard::new()?,
+        };
+        op(&mut clipboard)?;
+        *shared = Some(clipboard);
+        Ok(())
+    })
+    .map_err(|e| match e {
+        arboard::Error::ClipboardNotSupported => ClipboardError::AccessFailed(e.to_string()),
+        _ => ClipboardError::CopyFailed(e.to_string()),
+    })
+}
+
+/// Run `op` until it succeeds, fails permanently or runs out of attempts.
+fn retry<T>(
+    attempts: usize,
+    delay: Duration,
+    mut op: impl FnMut() -> Result<T, arboard::Error>,
+) -> Result<T, arboard::Error> {
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(e) if attempt < attempts && is_transient(&e) => {
+                tracing::debug!(%e, attempt, "Clipboard unavailable, retrying");
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Whether a clipboard failure may go away on its own, e.g. while another
+/// client holds the clipboard.
+fn is_transient(error: &arboard::Error) -> bool {
+    matches!(
+        error,
+        arboard::Error::ClipboardOccupied | arboard::Error::Unknown { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// A clipboard sink failing a fixed number of times before accepting.
+    fn flaky_sink(
+        failures: usize,
+        permanent: bool,
+    ) -> (impl FnMut() -> Result<(), arboard::Error>, Rc<Cell<usize>>) {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let sink = move || {
+            counter.set(counter.get() + 1);
+            match counter.get() <= failures {
+                true if permanent => Err(arboard::Error::ClipboardNotSupported),
+                true => Err(arboard::Error::ClipboardOccupied),
+                false => Ok(()),
+            }
+        };
+        (sink, calls)
+    }
+
+    #[test]
+    fn test_retry_recovers_from_transient_failures() {
+        let (sink, calls) = flaky_sink(2, false);
+        assert!(retry(3, Duration::ZERO, sink).is_ok());
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_retry_gives_up_after_attempts() {
+        let (sink, calls) = flaky_sink(5, false);
+        assert!(matches!(
+            retry(3, Duration::ZERO, sink),
+            Err(arboard::Error::ClipboardOccupied)
+        ));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_retry_stops_on_permanent_failure() {
+        let (sink, calls) = flaky_sink(5, true);
+        assert!(matches!(
+            retry(3, Duration::ZERO, sink),
+            Err(arboard::Error::ClipboardNotSupported)
+        ));
+        assert_eq!(calls.get(), 1);
+    }
 }
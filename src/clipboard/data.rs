@@ -1,6 +1,7 @@
 //! Clipboard history data storage and search.
 
-use super::item::{ClipboardContent, ClipboardItem};
+use super::item::{ClipboardContent, ClipboardItem, ClipboardSelection};
+use super::query::ClipboardQuery;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use std::collections::VecDeque;
@@ -17,12 +18,30 @@ pub fn init() {
     }
 }
 
-/// Add a new item to clipboard history.
+/// Whether clipboard history is enabled and initialized.
+pub fn is_initialized() -> bool {
+    CLIPBOARD_HISTORY.read().unwrap().is_some()
+}
+
+/// Add a new item copied to the clipboard to the history.
 /// If the item is identical to the most recent one, it won't be added.
 pub fn add_item(content: ClipboardContent) {
+    add_item_from(content, ClipboardSelection::Clipboard);
+}
+
+/// Add a new item captured from `selection` to the history.
+pub fn add_item_from(content: ClipboardContent, selection: ClipboardSelection) {
     let mut history = CLIPBOARD_HISTORY.write().unwrap();
     let history = history.as_mut().expect("Clipboard history not initialized");
+    push_item(history, content, selection);
+}
 
+/// Push an item unless it repeats the most recent one.
+fn push_item(
+    history: &mut VecDeque<ClipboardItem>,
+    content: ClipboardContent,
+    selection: ClipboardSelection,
+) {
     // Don't add duplicate consecutive items
     if let Some(last) = history.front()
         && is_same_content(&last.content, &content)
@@ -30,8 +49,34 @@ pub fn add_item(content: ClipboardContent) {
         return;
     }
 
-    let item = ClipboardItem::new(content);
-    history.push_front(item);
+    history.push_front(ClipboardItem::new(content).with_selection(selection));
+}
+
+/// Merge items (e.g. from an import) into the history, skipping content
+/// that is already present. Returns the number of items added.
+pub fn merge_items(items: Vec<ClipboardItem>) -> usize {
+    let mut history = CLIPBOARD_HISTORY.write().unwrap();
+    let history = history.as_mut().expect("Clipboard history not initialized");
+    merge_into(history, items)
+}
+
+/// Merge items into a history, keeping it ordered newest first.
+fn merge_into(history: &mut VecDeque<ClipboardItem>, items: Vec<ClipboardItem>) -> usize {
+    let mut added = 0;
+    for item in items {
+        if !history
+            .iter()
+            .any(|existing| is_same_content(&existing.content, &item.content))
+        {
+            history.push_back(item);
+            added += 1;
+        }
+    }
+    // Stable, so entries with equal timestamps keep their order
+    history
+        .make_contiguous()
+        .sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    added
 }
 
 /// Check if two clipboard contents are the same.
@@ -66,6 +111,8 @@ fn is_same_content(a: &ClipboardContent, b: &ClipboardContent) -> bool {
 }
 
 /// Get all clipboard items, optionally filtered by a search query.
+///
+/// A `/pattern/` query is matched as a regex; an invalid pattern matches nothing.
 pub fn search_items(query: &str) -> Vec<ClipboardItem> {
     let history = CLIPBOARD_HISTORY.read().unwrap();
     let history = history.as_ref().expect("Clipboard history not initialized");
@@ -74,23 +121,25 @@ pub fn search_items(query: &str) -> Vec<ClipboardItem> {
         return history.iter().cloned().collect();
     }
 
+    let query = match ClipboardQuery::parse(query) {
+        ClipboardQuery::Text(query) => query,
+        // Regex matches keep history order
+        ClipboardQuery::Regex(regex) => {
+            return history
+                .iter()
+                .filter(|item| regex.is_match(&search_text(item)))
+                .cloned()
+                .collect();
+        }
+        ClipboardQuery::InvalidRegex => return Vec::new(),
+    };
+
     let matcher = SkimMatcherV2::default();
     let mut scored: Vec<(ClipboardItem, i64)> = history
         .iter()
         .filter_map(|item| {
-            let search_text = match &item.content {
-                ClipboardContent::Text(text) => text.clone(),
-                ClipboardContent::Image { .. } => "image".to_string(),
-                ClipboardContent::FilePaths(paths) => paths
-                    .iter()
-                    .filter_map(|p| p.to_str())
-                    .collect::<Vec<_>>()
-                    .join(" "),
-                ClipboardContent::RichText { plain, .. } => plain.clone(),
-            };
-
             matcher
-                .fuzzy_match(&search_text, query)
+                .fuzzy_match(&search_text(item), &query)
                 .map(|score| (item.clone(), score))
         })
         .collect();
@@ -99,6 +148,20 @@ pub fn search_items(query: &str) -> Vec<ClipboardItem> {
     scored.into_iter().map(|(item, _)| item).collect()
 }
 
+/// Text of an item that search queries are matched against.
+fn search_text(item: &ClipboardItem) -> String {
+    match &item.content {
+        ClipboardContent::Text(text) => text.clone(),
+        ClipboardContent::Image { .. } => "image".to_string(),
+        ClipboardContent::FilePaths(paths) => paths
+            .iter()
+            .filter_map(|p| p.to_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        ClipboardContent::RichText { plain, .. } => plain.clone(),
+    }
+}
+
 /// Get the total number of items in history.
 pub fn item_count() -> usize {
     let history = CLIPBOARD_HISTORY.read().unwrap();
@@ -113,3 +176,60 @@ pub fn clear_history() {
         h.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn text(text: &str, secs: u64) -> ClipboardItem {
+        ClipboardItem::new(ClipboardContent::Text(text.to_string()))
+            .with_timestamp(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn test_items_are_tagged_with_their_selection() {
+        let mut history = VecDeque::new();
+        push_item(
+            &mut history,
+            ClipboardContent::Text("highlighted".to_string()),
+            ClipboardSelection::Primary,
+        );
+        push_item(
+            &mut history,
+            ClipboardContent::Text("copied".to_string()),
+            ClipboardSelection::Clipboard,
+        );
+        // Copying the highlighted text again doesn't duplicate it
+        push_item(
+            &mut history,
+            ClipboardContent::Text("copied".to_string()),
+            ClipboardSelection::Primary,
+        );
+
+        let tags: Vec<(String, ClipboardSelection)> = history
+            .iter()
+            .map(|i| (i.full_content(), i.selection))
+            .collect();
+        assert_eq!(
+            tags,
+            [
+                ("copied".to_string(), ClipboardSelection::Clipboard),
+                ("highlighted".to_string(), ClipboardSelection::Primary),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_dedupes_and_orders_by_time() {
+        let mut history = VecDeque::from([text("c", 30), text("a", 10)]);
+        let added = merge_into(
+            &mut history,
+            vec![text("d", 40), text("c", 35), text("b", 20)],
+        );
+
+        assert_eq!(added, 2);
+        let order: Vec<String> = history.iter().map(|i| i.full_content()).collect();
+        assert_eq!(order, ["d", "c", "b", "a"]);
+    }
+}
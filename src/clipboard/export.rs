@@ -0,0 +1,236 @@
+//! Clipboard history export and import.
+//!
+//! History is written as JSON, newest entry first, with millisecond
+//! timestamps. Images are stored as base64 encoded PNG when requested and
+//! left out otherwise. Imports are validated completely before anything is
+//! merged, so a malformed file leaves the history untouched.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::{Deserialize, Serialize};
+
+use super::data;
+use super::item::{ClipboardContent, ClipboardItem};
+use crate::error::ClipboardHistoryError;
+
+/// Version of the history file format.
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct HistoryFile {
+    version: u32,
+    entries: Vec<HistoryEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HistoryEntry {
+    /// Milliseconds since the Unix epoch.
+    timestamp: u64,
+    #[serde(flatten)]
+    content: EntryContent,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum EntryContent {
+    Text { text: String },
+    RichText { plain: String, html: String },
+    Files { paths: Vec<PathBuf> },
+    Image { png: String },
+}
+
+/// Write the clipboard history to `path`. Returns the number of entries written.
+pub fn export_history(path: &Path, include_images: bool) -> Result<usize, ClipboardHistoryError> {
+    if !data::is_initialized() {
+        return Err(ClipboardHistoryError::Disabled);
+    }
+    let file = to_history_file(&data::search_items(""), include_images);
+    std::fs::write(path, serde_json::to_string_pretty(&file)?)?;
+    Ok(file.entries.len())
+}
+
+/// Merge the entries of a history file into the clipboard history.
+/// Returns the number of entries that weren't in the history yet.
+pub fn import_history(path: &Path) -> Result<usize, ClipboardHistoryError> {
+    if !data::is_initialized() {
+        return Err(ClipboardHistoryError::Disabled);
+    }
+    let items = parse_history(&std::fs::read_to_string(path)?)?;
+    Ok(data::merge_items(items))
+}
+
+fn to_history_file(items: &[ClipboardItem], include_images: bool) -> HistoryFile {
+    let entries = items
+        .iter()
+        .filter_map(|item| {
+            let content = match &item.content {
+                ClipboardContent::Text(text) => EntryContent::Text { text: text.clone() },
+                ClipboardContent::RichText { plain, html } => EntryContent::RichText {
+                    plain: plain.clone(),
+                    html: html.clone(),
+                },
+                ClipboardContent::FilePaths(paths) => EntryContent::Files {
+                    paths: paths.clone(),
+                },
+                ClipboardContent::Image {
+                    width,
+                    height,
+                    rgba_bytes,
+                } if include_images => EntryContent::Image {
+                    png: BASE64.encode(encode_png(*width, *height, rgba_bytes)?),
+                },
+                ClipboardContent::Image { .. } => return None,
+            };
+            let timestamp = item
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis() as u64);
+            Some(HistoryEntry { timestamp, content })
+        })
+        .collect();
+
+    HistoryFile {
+        version: FORMAT_VERSION,
+        entries,
+    }
+}
+
+/// Parse a history file, rejecting it as a whole if any entry is invalid.
+fn parse_history(json: &str) -> Result<Vec<ClipboardItem>, ClipboardHistoryError> {
+    let file: HistoryFile = serde_json::from_str(json)?;
+    if file.version != FORMAT_VERSION {
+        return Err(ClipboardHistoryError::UnsupportedVersion(file.version));
+    }
+
+    file.entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let content = match entry.content {
+                EntryContent::Text { text } => ClipboardContent::Text(text),
+                EntryContent::RichText { plain, html } => {
+                    ClipboardContent::RichText { plain, html }
+                }
+                EntryContent::Files { paths } => ClipboardContent::FilePaths(paths),
+                EntryContent::Image { png } => BASE64
+                    .decode(&png)
+                    .ok()
+                    .and_then(|bytes| decode_png(&bytes))
+                    .ok_or(ClipboardHistoryError::InvalidImage(index))?,
+            };
+            Ok(ClipboardItem::new(content)
+                .with_timestamp(UNIX_EPOCH + Duration::from_millis(entry.timestamp)))
+        })
+        .collect()
+}
+
+fn encode_png(width: usize, height: usize, rgba_bytes: &[u8]) -> Option<Vec<u8>> {
+    let image = image::RgbaImage::from_raw(width as u32, height as u32, rgba_bytes.to_vec())?;
+    let mut png = Cursor::new(Vec::new());
+    image.write_to(&mut png, image::ImageFormat::Png).ok()?;
+    Some(png.into_inner())
+}
+
+fn decode_png(png: &[u8]) -> Option<ClipboardContent> {
+    let image = image::load_from_memory_with_format(png, image::ImageFormat::Png)
+        .ok()?
+        .to_rgba8();
+    let (width, height) = image.dimensions();
+    ClipboardContent::image(width as usize, height as usize, image.into_raw())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(content: ClipboardContent, secs: u64) -> ClipboardItem {
+        ClipboardItem::new(content).with_timestamp(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    fn serialize_history(items: &[ClipboardItem], include_images: bool) -> String {
+        serde_json::to_string(&to_history_file(items, include_images)).unwrap()
+    }
+
+    fn history() -> Vec<ClipboardItem> {
+        vec![
+            item(ClipboardContent::Text("newest".to_string()), 300),
+            item(
+                ClipboardContent::image(1, 1, vec![255, 0, 0, 255]).unwrap(),
+                200,
+            ),
+            item(
+                ClipboardContent::RichText {
+                    plain: "bold".to_string(),
+                    html: "<b>bold</b>".to_string(),
+                },
+                150,
+            ),
+            item(
+                ClipboardContent::FilePaths(vec![PathBuf::from("/tmp/a b.txt")]),
+                100,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_keeps_order_and_timestamps() {
+        let json = serialize_history(&history(), true);
+        let imported = parse_history(&json).unwrap();
+
+        assert_eq!(imported.len(), 4);
+        for (original, imported) in history().iter().zip(&imported) {
+            assert_eq!(original.timestamp, imported.timestamp);
+            assert_eq!(original.full_content(), imported.full_content());
+        }
+        assert!(matches!(
+            &imported[1].content,
+            ClipboardContent::Image { width: 1, height: 1, rgba_bytes } if rgba_bytes == &[255, 0, 0, 255]
+        ));
+    }
+
+    #[test]
+    fn test_images_skipped_without_flag() {
+        let json = serialize_history(&history(), false);
+        let imported = parse_history(&json).unwrap();
+        assert_eq!(imported.len(), 3);
+        assert!(
+            imported
+                .iter()
+                .all(|i| !matches!(i.content, ClipboardContent::Image { .. }))
+        );
+    }
+
+    #[test]
+    fn test_malformed_files_are_rejected() {
+        assert!(matches!(
+            parse_history("not json"),
+            Err(ClipboardHistoryError::Malformed(_))
+        ));
+        assert!(matches!(
+            parse_history(r#"{"version": 9, "entries": []}"#),
+            Err(ClipboardHistoryError::UnsupportedVersion(9))
+        ));
+        assert!(matches!(
+            parse_history(
+                r#"{"version": 1, "entries": [
+                    {"timestamp": 1, "type": "text", "text": "ok"},
+                    {"timestamp": 2, "type": "image", "png": "AAAA"}
+                ]}"#
+            ),
+            Err(ClipboardHistoryError::InvalidImage(1))
+        ));
+        // Not base64 at all
+        assert!(matches!(
+            parse_history(
+                r#"{"version": 1, "entries": [
+                    {"timestamp": 1, "type": "image", "png": "Zm9v!"}
+                ]}"#
+            ),
+            Err(ClipboardHistoryError::InvalidImage(0))
+        ));
+    }
+}
@@ -1,13 +1,37 @@
 //! Clipboard item data structures.
 
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::SystemTime;
 
+/// Id given to the next clipboard item.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Bytes per pixel of image content (8-bit RGBA).
+const RGBA_BYTES_PER_PIXEL: usize = 4;
+
+/// Default number of characters shown in the list preview.
+pub const DEFAULT_PREVIEW_LENGTH: usize = 30;
+
 /// Represents a single clipboard history entry.
 #[derive(Clone, Debug)]
 pub struct ClipboardItem {
+    /// Id identifying the entry within this session.
+    id: u64,
     pub content: ClipboardContent,
     pub timestamp: SystemTime,
+    /// Selection the entry was captured from.
+    pub selection: ClipboardSelection,
+}
+
+/// The selections on Linux.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClipboardSelection {
+    /// The regular clipboard (Ctrl+C / Ctrl+V).
+    #[default]
+    Clipboard,
+    /// The primary selection (highlight / middle-click).
+    Primary,
 }
 
 /// The content type of a clipboard item.
@@ -27,23 +51,62 @@ pub enum ClipboardContent {
     RichText { plain: String, html: String },
 }
 
+impl ClipboardContent {
+    /// Create image content from 8-bit RGBA pixels.
+    ///
+    /// Returns None for empty images and for buffers whose length doesn't
+    /// match the dimensions (e.g. 16-bit samples or truncated data), which
+    /// would otherwise be misread when rendered.
+    pub fn image(width: usize, height: usize, rgba_bytes: Vec<u8>) -> Option<Self> {
+        let expected = width
+            .checked_mul(height)?
+            .checked_mul(RGBA_BYTES_PER_PIXEL)?;
+        (expected > 0 && rgba_bytes.len() == expected).then_some(Self::Image {
+            width,
+            height,
+            rgba_bytes,
+        })
+    }
+}
+
 impl ClipboardItem {
     /// Create a new clipboard item with the current timestamp.
     pub fn new(content: ClipboardContent) -> Self {
         Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
             content,
             timestamp: SystemTime::now(),
+            selection: ClipboardSelection::Clipboard,
         }
     }
 
+    /// Set when the item was copied (builder pattern).
+    pub fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Get the id identifying the entry within this session.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Set the selection the item was captured from (builder pattern).
+    pub fn with_selection(mut self, selection: ClipboardSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
     /// Get a short preview string for display in the list.
     pub fn preview(&self) -> String {
-        const MAX_LENGTH: usize = 30;
+        self.preview_with_length(DEFAULT_PREVIEW_LENGTH)
+    }
 
+    /// Get a preview string truncated to `max_length` characters.
+    pub fn preview_with_length(&self, max_length: usize) -> String {
         match &self.content {
             ClipboardContent::Text(text) => {
-                let first_line = text.lines().next().unwrap_or("");
-                truncate_preview_line(first_line, MAX_LENGTH)
+                truncate_preview_line(&normalize_whitespace(text), max_length)
             }
             ClipboardContent::Image { .. } => "[Image]".to_string(),
             ClipboardContent::FilePaths(paths) => {
@@ -58,12 +121,51 @@ impl ClipboardItem {
                 }
             }
             ClipboardContent::RichText { plain, .. } => {
-                let first_line = plain.lines().next().unwrap_or("");
-                truncate_preview_line(first_line, MAX_LENGTH)
+                truncate_preview_line(&normalize_whitespace(plain), max_length)
             }
         }
     }
 
+    /// Get the text lines of this item, if it holds text.
+    fn text_lines(&self) -> Option<Vec<&str>> {
+        let text = match &self.content {
+            ClipboardContent::Text(text) => text,
+            ClipboardContent::RichText { plain, .. } => plain,
+            _ => return None,
+        };
+        Some(text.trim().lines().collect())
+    }
+
+    /// Build the list preview for this item.
+    ///
+    /// Multi-line text is expanded to its first `max_lines` lines while
+    /// selected and collapsed to a single whitespace-normalized line otherwise.
+    pub fn list_preview(
+        &self,
+        selected: bool,
+        max_length: usize,
+        max_lines: usize,
+    ) -> ClipboardPreview {
+        let lines = self.text_lines().unwrap_or_default();
+        let line_count = lines.len().max(1);
+
+        if selected && line_count > 1 && max_lines > 1 {
+            return ClipboardPreview {
+                lines: lines
+                    .iter()
+                    .take(max_lines)
+                    .map(|line| truncate_preview_line(line.trim_end(), max_length))
+                    .collect(),
+                line_count,
+            };
+        }
+
+        ClipboardPreview {
+            lines: vec![self.preview_with_length(max_length)],
+            line_count,
+        }
+    }
+
     /// Get the full content as a string for preview panel.
     pub fn full_content(&self) -> String {
         match &self.content {
@@ -105,6 +207,32 @@ impl ClipboardItem {
     }
 }
 
+/// Preview of a clipboard item as rendered in the list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClipboardPreview {
+    /// Lines to render (a single line unless expanded).
+    pub lines: Vec<String>,
+    /// Total number of lines in the content.
+    pub line_count: usize,
+}
+
+impl ClipboardPreview {
+    /// Whether more than one line is rendered.
+    pub fn is_expanded(&self) -> bool {
+        self.lines.len() > 1
+    }
+
+    /// Indicator for multi-line content, e.g. "12 lines".
+    pub fn line_count_label(&self) -> Option<String> {
+        (self.line_count > 1).then(|| format!("{} lines", self.line_count))
+    }
+}
+
+/// Collapse all whitespace runs (including newlines) into single spaces.
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 /// Truncate wihtout splitting emojis
 fn truncate_preview_line(line: &str, max: usize) -> String {
     let truncated: String = line.chars().take(max).collect();
@@ -115,3 +243,59 @@ fn truncate_preview_line(line: &str, max: usize) -> String {
         truncated
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_item(text: &str) -> ClipboardItem {
+        ClipboardItem::new(ClipboardContent::Text(text.to_string()))
+    }
+
+    #[test]
+    fn test_image_validates_buffer_size() {
+        assert!(ClipboardContent::image(2, 2, vec![0; 16]).is_some());
+        // 16-bit samples: twice the expected length
+        assert!(ClipboardContent::image(2, 2, vec![0; 32]).is_none());
+        // Truncated buffer
+        assert!(ClipboardContent::image(2, 2, vec![0; 12]).is_none());
+        assert!(ClipboardContent::image(0, 2, Vec::new()).is_none());
+        assert!(ClipboardContent::image(usize::MAX, 2, vec![0; 4]).is_none());
+    }
+
+    #[test]
+    fn test_preview_short_text_unchanged() {
+        let preview = text_item("hello").list_preview(true, 30, 5);
+        assert_eq!(preview.lines, vec!["hello"]);
+        assert!(!preview.is_expanded());
+        assert_eq!(preview.line_count_label(), None);
+    }
+
+    #[test]
+    fn test_preview_long_text_truncated() {
+        let item = text_item("a very long   line\tthat should be cut");
+        let preview = item.list_preview(false, 10, 5);
+        assert_eq!(preview.lines, vec!["a very lon..."]);
+        assert_eq!(
+            item.preview_with_length(100),
+            "a very long line that should be cut"
+        );
+    }
+
+    #[test]
+    fn test_preview_multiline_collapsed_and_expanded() {
+        let item = text_item("first\nsecond\nthird\nfourth");
+
+        let collapsed = item.list_preview(false, 30, 2);
+        assert_eq!(collapsed.lines, vec!["first second third fourth"]);
+        assert_eq!(collapsed.line_count_label().as_deref(), Some("4 lines"));
+
+        let expanded = item.list_preview(true, 30, 2);
+        assert!(expanded.is_expanded());
+        assert_eq!(expanded.lines, vec!["first", "second"]);
+        assert_eq!(expanded.line_count, 4);
+
+        // Expansion disabled
+        assert!(!item.list_preview(true, 30, 1).is_expanded());
+    }
+}
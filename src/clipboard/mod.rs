@@ -2,8 +2,11 @@
 
 mod copy;
 pub mod data;
+pub mod export;
 pub mod item;
 pub mod monitor;
+mod query;
 
-pub use copy::{copy_image_to_clipboard, copy_to_clipboard};
-pub use item::{ClipboardContent, ClipboardItem};
+pub use copy::{copy_image_to_clipboard, copy_to_clipboard, restore_item};
+pub use item::{ClipboardContent, ClipboardItem, ClipboardPreview, ClipboardSelection};
+pub use query::ClipboardQuery;
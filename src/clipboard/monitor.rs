@@ -1,8 +1,9 @@
 //! Clipboard monitoring using Wayland data-control protocol.
 
+use super::copy::linux_kind;
 use super::data;
-use super::item::ClipboardContent;
-use arboard::Clipboard;
+use super::item::{ClipboardContent, ClipboardSelection};
+use arboard::{Clipboard, GetExtLinux};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
@@ -169,8 +170,13 @@ impl Dispatch<zwlr_data_control_device_v1::ZwlrDataControlDeviceV1, ()> for Clip
                     }
                 }
             }
-            zwlr_data_control_device_v1::Event::PrimarySelection { .. } => {
-                // Ignore primary selection for now
+            zwlr_data_control_device_v1::Event::PrimarySelection { id } => {
+                if id.is_some() && crate::config::config().clipboard_capture_primary {
+                    debug!("Primary selection changed");
+                    if let Err(e) = read_primary_selection() {
+                        error!("Failed to read primary selection: {}", e);
+                    }
+                }
             }
             _ => {}
         }
@@ -231,11 +237,10 @@ fn read_clipboard_content() -> Result<(), Box<dyn std::error::Error>> {
             image.height,
             image.bytes.len()
         );
-        data::add_item(ClipboardContent::Image {
-            width: image.width,
-            height: image.height,
-            rgba_bytes: image.bytes.to_vec(),
-        });
+        match ClipboardContent::image(image.width, image.height, image.bytes.to_vec()) {
+            Some(content) => data::add_item(content),
+            None => debug!("Ignoring clipboard image with mismatched buffer size"),
+        }
         return Ok(());
     }
 
@@ -250,3 +255,21 @@ fn read_clipboard_content() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Read the primary selection and add it to history.
+///
+/// Only text is recorded; highlighting doesn't produce images.
+fn read_primary_selection() -> Result<(), Box<dyn std::error::Error>> {
+    thread::sleep(Duration::from_millis(50));
+
+    let mut clipboard = Clipboard::new()?;
+    let text = clipboard
+        .get()
+        .clipboard(linux_kind(ClipboardSelection::Primary))
+        .text()?;
+    if !text.trim().is_empty() {
+        debug!("Adding primary selection to history: {} chars", text.len());
+        data::add_item_from(ClipboardContent::Text(text), ClipboardSelection::Primary);
+    }
+    Ok(())
+}
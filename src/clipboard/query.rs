@@ -0,0 +1,74 @@
+//! Clipboard search query parsing.
+//!
+//! A query wrapped in slashes (`/pattern/`) is matched as a regular
+//! expression; anything else is a plain search.
+
+use regex::Regex;
+
+/// A parsed clipboard search query.
+#[derive(Clone, Debug)]
+pub enum ClipboardQuery {
+    /// Plain text, matched fuzzily or as a substring.
+    Text(String),
+    /// A compiled `/pattern/` query.
+    Regex(Regex),
+    /// A `/pattern/` query that failed to compile; matches nothing.
+    InvalidRegex,
+}
+
+impl ClipboardQuery {
+    /// Parse a query, compiling `/pattern/` as a regex.
+    pub fn parse(query: &str) -> Self {
+        match regex_pattern(query) {
+            Some(pattern) => match Regex::new(pattern) {
+                Ok(regex) => Self::Regex(regex),
+                Err(_) => Self::InvalidRegex,
+            },
+            None => Self::Text(query.to_string()),
+        }
+    }
+
+    /// Whether the query is a regex that failed to compile.
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, Self::InvalidRegex)
+    }
+}
+
+/// Extract the pattern of a `/pattern/` query.
+fn regex_pattern(query: &str) -> Option<&str> {
+    query
+        .strip_prefix('/')?
+        .strip_suffix('/')
+        .filter(|pattern| !pattern.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_regex_query() {
+        let ClipboardQuery::Regex(regex) = ClipboardQuery::parse(r"/^\d{3}-\d{4}$/") else {
+            panic!("expected a regex query");
+        };
+        assert!(regex.is_match("555-1234"));
+        assert!(!regex.is_match("call 555-1234"));
+    }
+
+    #[test]
+    fn test_parse_text_query() {
+        for query in ["hello", "/", "//", "/usr/bin", "a/b/"] {
+            assert!(
+                matches!(ClipboardQuery::parse(query), ClipboardQuery::Text(ref text) if text == query),
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_regex() {
+        let query = ClipboardQuery::parse("/(unclosed/");
+        assert!(query.is_invalid());
+        assert!(!ClipboardQuery::parse("/ok/").is_invalid());
+    }
+}
@@ -15,6 +15,8 @@ pub struct CompositorCapabilities {
     pub workspace_info: bool,
     /// Whether focus state tracking is accurate.
     pub focus_tracking: bool,
+    /// Whether windows can be kept above others (see `Compositor::set_keep_above`).
+    pub keep_above: bool,
 }
 
 impl CompositorCapabilities {
@@ -26,6 +28,7 @@ impl CompositorCapabilities {
             window_switching: true,
             workspace_info: true,
             focus_tracking: true,
+            keep_above: false,
         }
     }
 
@@ -37,6 +40,7 @@ impl CompositorCapabilities {
             window_switching: true,
             workspace_info: false,
             focus_tracking: false,
+            keep_above: false,
         }
     }
 
@@ -103,6 +107,7 @@ mod tests {
                 class: "firefox".to_string(),
                 workspace: 1,
                 focused: false,
+                keep_above: false,
             },
             WindowInfo {
                 address: "2".to_string(),
@@ -110,6 +115,7 @@ mod tests {
                 class: "zlaunch".to_string(),
                 workspace: 1,
                 focused: true,
+                keep_above: false,
             },
         ];
 
@@ -1,22 +1,33 @@
 //! Compositor detection logic.
 
 use super::Compositor;
+use super::error::CompositorError;
 use super::hyprland::HyprlandCompositor;
 use super::kwin::KwinCompositor;
 use super::niri::NiriCompositor;
 use super::noop::NoopCompositor;
+use std::sync::Mutex;
 use tracing::{info, warn};
 
+/// Notice about a compositor that was expected but couldn't be reached,
+/// shown once in the launcher.
+static NOTICE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Take the compositor notice, so it is only shown once.
+pub fn take_compositor_notice() -> Option<String> {
+    NOTICE.lock().ok()?.take()
+}
+
 /// Detect and create the appropriate compositor client.
 ///
 /// Detection order:
 /// 1. Hyprland (via HYPRLAND_INSTANCE_SIGNATURE env var)
 /// 2. KDE/KWin (via KDE_SESSION_VERSION env var)
 /// 3. Niri     (via NIRI_SOCKET env var)
-/// 3. Fallback to NoopCompositor
+/// 4. Fallback to NoopCompositor
 ///
 /// The NoopCompositor allows the launcher to function (with applications only)
-/// even on unsupported compositors.
+/// even on unsupported compositors. The fallback is logged once, here.
 pub fn detect_compositor() -> Box<dyn Compositor> {
     // Try Hyprland first
     if let Some(compositor) = HyprlandCompositor::new() {
@@ -25,9 +36,13 @@ pub fn detect_compositor() -> Box<dyn Compositor> {
     }
 
     // Try KWin
-    if let Some(compositor) = KwinCompositor::new() {
-        info!("Detected KWin compositor");
-        return Box::new(compositor);
+    match KwinCompositor::new() {
+        Ok(Some(compositor)) => {
+            info!("Detected KWin compositor");
+            return Box::new(compositor);
+        }
+        Ok(None) => {}
+        Err(e) => report_unavailable("KDE", &e),
     }
 
     // Try Niri
@@ -37,6 +52,59 @@ pub fn detect_compositor() -> Box<dyn Compositor> {
     }
 
     // Fallback to no-op
-    warn!("No supported compositor detected, window switching disabled");
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP").ok();
+    let session = std::env::var("XDG_SESSION_TYPE").ok();
+    warn!(
+        "No supported compositor detected ({}), window switching disabled",
+        describe_environment(desktop.as_deref(), session.as_deref())
+    );
     Box::new(NoopCompositor)
 }
+
+/// Warn that a detected desktop's compositor can't be reached and leave a
+/// notice for the launcher. Applications and other modules keep working.
+fn report_unavailable(desktop: &str, error: &CompositorError) {
+    warn!("{desktop} session detected, but window switching is unavailable: {error}");
+    if let Ok(mut notice) = NOTICE.lock() {
+        *notice = Some(format!("Window switching is unavailable: {error}"));
+    }
+}
+
+/// Describe the desktop environment for the fallback message.
+fn describe_environment(desktop: Option<&str>, session_type: Option<&str>) -> String {
+    let desktop = desktop
+        .filter(|d| !d.is_empty())
+        .unwrap_or("unknown desktop");
+    match session_type.filter(|s| !s.is_empty()) {
+        Some(session_type) => format!("{desktop} on {session_type}"),
+        None => desktop.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_environment() {
+        assert_eq!(
+            describe_environment(Some("sway"), Some("wayland")),
+            "sway on wayland"
+        );
+        assert_eq!(describe_environment(Some("GNOME"), None), "GNOME");
+        assert_eq!(describe_environment(None, Some("")), "unknown desktop");
+    }
+
+    #[test]
+    fn test_unavailable_compositor_leaves_one_time_notice() {
+        report_unavailable(
+            "KDE",
+            &CompositorError::DbusUnavailable("connection refused".to_string()),
+        );
+        assert_eq!(
+            take_compositor_notice().as_deref(),
+            Some("Window switching is unavailable: Session D-Bus unavailable: connection refused")
+        );
+        assert_eq!(take_compositor_notice(), None);
+    }
+}
@@ -25,6 +25,10 @@ pub enum CompositorError {
     #[error("D-Bus error: {0}")]
     DbusError(String),
 
+    /// The session D-Bus couldn't be reached (for KWin).
+    #[error("Session D-Bus unavailable: {0}")]
+    DbusUnavailable(String),
+
     /// Command execution failed (for fallback methods).
     #[error("Command execution failed: {0}")]
     CommandFailed(String),
@@ -52,8 +52,10 @@ impl Compositor for HyprlandCompositor {
     fn list_windows(&self) -> Result<Vec<WindowInfo>> {
         // j/clients returns JSON output
         let json = self.send_command("j/clients")?;
-        let clients: Vec<HyprlandClient> =
+        let mut clients: Vec<HyprlandClient> =
             serde_json::from_str(&json).context("Failed to parse Hyprland clients JSON")?;
+        // Most recently focused first
+        clients.sort_by_key(|c| c.focus_history_id);
 
         let windows = clients
             .into_iter()
@@ -82,6 +84,7 @@ impl Compositor for HyprlandCompositor {
                     class: c.class,
                     workspace,
                     focused,
+                    keep_above: c.pinned,
                 }
             })
             .collect();
@@ -102,6 +105,34 @@ impl Compositor for HyprlandCompositor {
     fn capabilities(&self) -> CompositorCapabilities {
         CompositorCapabilities::full()
     }
+
+    fn cursor_position(&self) -> Option<(f64, f64)> {
+        let json = self.send_command("j/cursorpos").ok()?;
+        let pos: HyprlandCursor = serde_json::from_str(&json).ok()?;
+        Some((pos.x, pos.y))
+    }
+
+    fn focused_monitor_position(&self) -> Option<(f64, f64)> {
+        let json = self.send_command("j/monitors").ok()?;
+        let monitors: Vec<HyprlandMonitor> = serde_json::from_str(&json).ok()?;
+        monitors.into_iter().find(|m| m.focused).map(|m| (m.x, m.y))
+    }
+}
+
+/// Pointer position from IPC.
+#[derive(Debug, Deserialize)]
+struct HyprlandCursor {
+    x: f64,
+    y: f64,
+}
+
+/// Hyprland monitor information from IPC.
+#[derive(Debug, Deserialize)]
+struct HyprlandMonitor {
+    x: f64,
+    y: f64,
+    #[serde(default)]
+    focused: bool,
 }
 
 /// Hyprland client (window) information from IPC.
@@ -118,6 +149,9 @@ struct HyprlandClient {
     mapped: bool,
     #[serde(default)]
     hidden: bool,
+    /// Pinned floating windows stay above others on every workspace
+    #[serde(default)]
+    pinned: bool,
 }
 
 impl HyprlandClient {
@@ -3,12 +3,21 @@
 //! Uses KWin's krunner interface via D-Bus to enumerate and focus windows.
 //! This approach uses the /WindowsRunner D-Bus path which provides direct
 //! window listing without needing to capture script print() signals.
+//! The runner doesn't report whether a window is kept above others, so that
+//! is read per window through `getWindowInfo` and set through a short-lived
+//! KWin script.
 
 use super::base::CompositorCapabilities;
+use super::error::CompositorError;
 use super::{Compositor, WindowInfo};
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
 use std::process::Command;
+use std::sync::Mutex;
 use zbus::blocking::{Connection, Proxy};
 use zbus::zvariant::OwnedValue;
 
@@ -23,34 +32,63 @@ type KRunnerMatch = (
     HashMap<String, OwnedValue>,
 );
 
+/// Plugin name the keep-above script is loaded under.
+const KEEP_ABOVE_SCRIPT: &str = "zlaunch-keep-above";
+
+/// Action index of the activate action in WindowsRunner match ids.
+const ACTIVATE_ACTION: u32 = 0;
+
 /// KWin compositor client using D-Bus WindowsRunner API.
 pub struct KwinCompositor {
     connection: Connection,
+    /// Runner match id used to activate each listed window.
+    match_ids: Mutex<HashMap<String, String>>,
 }
 
 impl KwinCompositor {
     /// Create a new KWin compositor client.
     ///
-    /// Returns None if KDE session is not detected or KWin is not available.
-    pub fn new() -> Option<Self> {
+    /// Returns None if KDE session is not detected or KWin is not available,
+    /// and an error if a KDE session is detected but the session bus isn't.
+    pub fn new() -> Result<Option<Self>, CompositorError> {
         // Check if we're in a KDE session
         if std::env::var("KDE_SESSION_VERSION").is_err() {
-            return None;
+            return Ok(None);
         }
 
-        // Connect to session D-Bus
-        let connection = Connection::session().ok()?;
+        Self::with_connection(Connection::session())
+    }
 
+    /// Create a client on a session bus connection attempt.
+    fn with_connection(
+        connection: zbus::Result<Connection>,
+    ) -> Result<Option<Self>, CompositorError> {
+        let connection = connection.map_err(|e| CompositorError::DbusUnavailable(e.to_string()))?;
+        Ok(Self::from_connection(connection))
+    }
+
+    /// Create a client if KWin answers on the connection.
+    fn from_connection(connection: Connection) -> Option<Self> {
         // Verify KWin is available by calling supportInformation
         let kwin_proxy = Proxy::new(&connection, "org.kde.KWin", "/KWin", "org.kde.KWin").ok()?;
 
         let _: String = kwin_proxy.call("supportInformation", &()).ok()?;
 
-        Some(Self { connection })
+        // Make sure windows can actually be listed, otherwise let detection
+        // fall through to another backend
+        let compositor = Self {
+            connection,
+            match_ids: Mutex::new(HashMap::new()),
+        };
+        if let Err(e) = compositor.list_windows_via_runner() {
+            tracing::warn!("KWin detected but WindowsRunner is unusable: {:#}", e);
+            return None;
+        }
+
+        Some(compositor)
     }
 
     /// List windows using the WindowsRunner krunner interface.
-    /// Returns tuples of (match_id, title, subtext, type, relevance, properties)
     fn list_windows_via_runner(&self) -> Result<Vec<WindowInfo>> {
         // Create proxy for WindowsRunner
         let runner_proxy = Proxy::new(
@@ -67,33 +105,35 @@ impl KwinCompositor {
             .call("Match", &("",))
             .context("Failed to call WindowsRunner.Match")?;
 
-        let windows: Vec<WindowInfo> = result
-            .into_iter()
-            .map(
-                |(match_id, title, _subtext, _type_id, _relevance, _props)| {
-                    // match_id format: "0_{uuid}" - extract the window ID
-                    // The "0_" prefix indicates action index (0 = activate)
-                    let window_id = match_id
-                        .strip_prefix("0_")
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| match_id.clone());
-
-                    // Try to extract app class from the title (often "Title - AppName")
-                    // This is a heuristic - the actual class isn't directly available
-                    let class = title.rsplit(" - ").next().unwrap_or(&title).to_string();
-
-                    WindowInfo {
-                        address: window_id,
-                        title: title.clone(),
-                        class,
-                        workspace: 1,   // WindowsRunner doesn't expose workspace info
-                        focused: false, // We can't easily determine this from krunner
-                    }
-                },
-            )
-            .collect();
+        let mut windows = windows_from_matches(result)?;
+        for (window, _) in &mut windows {
+            window.keep_above = self.is_kept_above(&window.address);
+        }
+        if let Ok(mut match_ids) = self.match_ids.lock() {
+            *match_ids = windows
+                .iter()
+                .map(|(window, match_id)| (window.address.clone(), match_id.clone()))
+                .collect();
+        }
+        Ok(windows.into_iter().map(|(window, _)| window).collect())
+    }
 
-        Ok(windows)
+    /// Whether KWin keeps a window above others, false if it can't be asked.
+    fn is_kept_above(&self, window_id: &str) -> bool {
+        let info: Option<HashMap<String, OwnedValue>> =
+            Proxy::new(&self.connection, "org.kde.KWin", "/KWin", "org.kde.KWin")
+                .ok()
+                .and_then(|kwin| kwin.call("getWindowInfo", &(window_id,)).ok());
+        info.is_some_and(|info| keep_above_from_info(&info))
+    }
+
+    /// The runner match id activating a window, as seen in the last listing.
+    fn activate_match_id(&self, window_id: &str) -> String {
+        self.match_ids
+            .lock()
+            .ok()
+            .and_then(|match_ids| match_ids.get(window_id).cloned())
+            .unwrap_or_else(|| format!("{ACTIVATE_ACTION}_{window_id}"))
     }
 
     /// Focus a window using the WindowsRunner Run method.
@@ -106,8 +146,8 @@ impl KwinCompositor {
         )
         .context("Failed to create WindowsRunner proxy")?;
 
-        // match_id needs the "0_" prefix for the activate action
-        let match_id = format!("0_{}", window_id);
+        // match_id needs the action prefix, "0_" for activate
+        let match_id = self.activate_match_id(window_id);
 
         // Run with empty action_id (default action = activate)
         let _: () = runner_proxy
@@ -116,6 +156,172 @@ impl KwinCompositor {
 
         Ok(())
     }
+
+    /// Load, run and unload a KWin script.
+    fn run_script(&self, source: &str) -> Result<()> {
+        let path = write_script(source)?;
+
+        let scripting = Proxy::new(
+            &self.connection,
+            "org.kde.KWin",
+            "/Scripting",
+            "org.kde.kwin.Scripting",
+        )
+        .context("Failed to create Scripting proxy")?;
+
+        // A leftover script of a failed run would make loading fail
+        let _: std::result::Result<bool, _> = scripting.call("unloadScript", &(KEEP_ABOVE_SCRIPT,));
+        let id: i32 = scripting
+            .call(
+                "loadScript",
+                &(path.to_string_lossy().as_ref(), KEEP_ABOVE_SCRIPT),
+            )
+            .context("Failed to load KWin script")?;
+
+        // KWin 6 exposes scripts under /Scripting/Script<id>, KWin 5 under /<id>
+        let result = [format!("/Scripting/Script{id}"), format!("/{id}")]
+            .iter()
+            .find_map(|path| {
+                let script = Proxy::new(
+                    &self.connection,
+                    "org.kde.KWin",
+                    path.as_str(),
+                    "org.kde.kwin.Script",
+                )
+                .ok()?;
+                let _: () = script.call("run", &()).ok()?;
+                Some(())
+            })
+            .context("Failed to run KWin script");
+
+        let _: std::result::Result<bool, _> = scripting.call("unloadScript", &(KEEP_ABOVE_SCRIPT,));
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+}
+
+/// Write a KWin script to a new file only the user can read.
+///
+/// Uses `$XDG_RUNTIME_DIR` like the IPC socket. The file is created
+/// exclusively, so a file planted at the path is never followed or reused.
+fn write_script(source: &str) -> Result<PathBuf> {
+    let dir = std::env::var("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir());
+
+    for attempt in 0..16 {
+        let path = dir.join(format!(
+            "{KEEP_ABOVE_SCRIPT}-{}-{attempt}.js",
+            std::process::id()
+        ));
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+        {
+            Ok(mut file) => {
+                file.write_all(source.as_bytes())
+                    .context("Failed to write KWin script")?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e).context("Failed to create KWin script"),
+        }
+    }
+    bail!("No free path for the KWin script in {}", dir.display())
+}
+
+/// Read the keep-above state from a `getWindowInfo` reply.
+fn keep_above_from_info(info: &HashMap<String, OwnedValue>) -> bool {
+    info.get("keepAbove")
+        .and_then(|value| bool::try_from(value).ok())
+        .unwrap_or(false)
+}
+
+/// Build the KWin script setting a window's keep-above state.
+///
+/// Runner ids and KWin's `internalId` are compared without braces. The id
+/// is reduced to UUID characters so it can't break out of the string.
+fn keep_above_script(window_id: &str, on: bool) -> String {
+    let id: String = window_id
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit() || *c == '-')
+        .collect();
+    format!(
+        r#"const windows = workspace.windowList ? workspace.windowList() : workspace.clientList();
+for (const window of windows) {{
+    if (window.internalId.toString().replace(/[{{}}]/g, "") === "{id}") {{
+        window.keepAbove = {on};
+    }}
+}}
+"#
+    )
+}
+
+/// Split a WindowsRunner match id ("{action}_{uuid}") into its parts.
+fn parse_match_id(match_id: &str) -> Option<(u32, &str)> {
+    let (action, uuid) = match_id.split_once('_')?;
+    let action = action.parse().ok()?;
+    (!uuid.is_empty()).then_some((action, uuid))
+}
+
+/// Turn WindowsRunner matches into windows, each with the match id that
+/// activates it.
+///
+/// The runner returns one "{action}_{uuid}" match per window action. Matches
+/// are grouped by uuid and the activate action (index 0) is preferred, else
+/// the lowest index, so a change of the index scheme still lists windows.
+/// An empty match list means there are no windows; matches of which none is
+/// a window mean the runner answered in a shape we don't understand, which
+/// is an error.
+fn windows_from_matches(matches: Vec<KRunnerMatch>) -> Result<Vec<(WindowInfo, String)>> {
+    let raw_count = matches.len();
+    let rank = |action: u32| (action != ACTIVATE_ACTION, action);
+
+    // (uuid, action, match id, title) per window, in first-seen order
+    let mut representatives: Vec<(String, u32, String, String)> = Vec::new();
+    for (match_id, title, _subtext, _type_id, _relevance, _props) in matches {
+        let Some((action, uuid)) = parse_match_id(&match_id) else {
+            continue;
+        };
+        match representatives.iter_mut().find(|(id, ..)| id == uuid) {
+            Some(known) if rank(action) < rank(known.1) => {
+                known.1 = action;
+                known.2 = match_id;
+                known.3 = title;
+            }
+            Some(_) => {}
+            None => representatives.push((uuid.to_string(), action, match_id.clone(), title)),
+        }
+    }
+
+    let windows: Vec<(WindowInfo, String)> = representatives
+        .into_iter()
+        .map(|(window_id, _action, match_id, title)| {
+            // Try to extract app class from the title (often "Title - AppName")
+            // This is a heuristic - the actual class isn't directly available
+            let class = title.rsplit(" - ").next().unwrap_or(&title).to_string();
+
+            let window = WindowInfo {
+                address: window_id,
+                title,
+                class,
+                workspace: 1,   // WindowsRunner doesn't expose workspace info
+                focused: false, // We can't easily determine this from krunner
+                keep_above: false,
+            };
+            (window, match_id)
+        })
+        .collect();
+
+    if raw_count > 0 && windows.is_empty() {
+        bail!(
+            "WindowsRunner.Match returned {} matches but no windows",
+            raw_count
+        );
+    }
+    Ok(windows)
 }
 
 impl Compositor for KwinCompositor {
@@ -135,7 +341,7 @@ impl Compositor for KwinCompositor {
                 "org.kde.KWin",
                 "/WindowsRunner",
                 "org.kde.krunner1.Run",
-                &format!("0_{}", window_id),
+                &self.activate_match_id(window_id),
                 "",
             ])
             .status()
@@ -148,11 +354,133 @@ impl Compositor for KwinCompositor {
         }
     }
 
+    fn set_keep_above(&self, window_id: &str, on: bool) -> Result<()> {
+        self.run_script(&keep_above_script(window_id, on))
+    }
+
     fn name(&self) -> &'static str {
         "KWin"
     }
 
     fn capabilities(&self) -> CompositorCapabilities {
-        CompositorCapabilities::limited()
+        CompositorCapabilities {
+            keep_above: true,
+            ..CompositorCapabilities::limited()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runner_match(match_id: &str, title: &str) -> KRunnerMatch {
+        (
+            match_id.to_string(),
+            title.to_string(),
+            String::new(),
+            0,
+            1.0,
+            HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_failed_bus_connection_is_reported() {
+        let result = KwinCompositor::with_connection(Err(zbus::Error::Failure(
+            "no session bus".to_string(),
+        )));
+        assert!(matches!(
+            result,
+            Err(CompositorError::DbusUnavailable(ref msg)) if msg.contains("no session bus")
+        ));
+    }
+
+    #[test]
+    fn test_windows_from_matches() {
+        // No matches: genuinely no windows
+        assert!(windows_from_matches(vec![]).unwrap().is_empty());
+
+        let windows = windows_from_matches(vec![
+            runner_match("1_abc", "Switch to desktop"),
+            runner_match("0_abc", "README.md - Kate"),
+        ])
+        .unwrap();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].0.address, "abc");
+        assert_eq!(windows[0].0.class, "Kate");
+        assert_eq!(windows[0].1, "0_abc");
+
+        // Matches without any window means the query went wrong
+        assert!(windows_from_matches(vec![runner_match("abc", "Firefox")]).is_err());
+        assert!(windows_from_matches(vec![runner_match("x_abc", "Firefox")]).is_err());
+        assert!(windows_from_matches(vec![runner_match("0_", "Firefox")]).is_err());
+    }
+
+    #[test]
+    fn test_windows_from_matches_without_activate_index_zero() {
+        // A scheme where activate isn't action 0 still lists every window once
+        let windows = windows_from_matches(vec![
+            runner_match("3_abc", "README.md - Kate"),
+            runner_match("2_abc", "README.md - Kate"),
+            runner_match("2_def", "Inbox - Thunderbird"),
+        ])
+        .unwrap();
+        let listed: Vec<(&str, &str, &str)> = windows
+            .iter()
+            .map(|(window, match_id)| {
+                (
+                    window.address.as_str(),
+                    window.class.as_str(),
+                    match_id.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            listed,
+            [("abc", "Kate", "2_abc"), ("def", "Thunderbird", "2_def")]
+        );
+    }
+
+    #[test]
+    fn test_write_script_creates_new_files() {
+        let first = write_script("// first").unwrap();
+        let second = write_script("// second").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "// first");
+
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&first).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let _ = std::fs::remove_file(first);
+        let _ = std::fs::remove_file(second);
+    }
+
+    #[test]
+    fn test_keep_above_script() {
+        let script = keep_above_script("{0a1b-2c3d}", true);
+        assert!(script.contains(r#"=== "0a1b-2c3d""#));
+        assert!(script.contains("window.keepAbove = true;"));
+        assert!(keep_above_script("0a1b", false).contains("window.keepAbove = false;"));
+
+        // Anything but UUID characters is dropped
+        let script = keep_above_script(r#"x"); evil("#, true);
+        assert!(script.contains(r#"=== "e""#));
+    }
+
+    #[test]
+    fn test_keep_above_from_info() {
+        let info = |value: zbus::zvariant::Value<'static>| {
+            HashMap::from([(
+                "keepAbove".to_string(),
+                OwnedValue::try_from(value).unwrap(),
+            )])
+        };
+        assert!(keep_above_from_info(&info(true.into())));
+        assert!(!keep_above_from_info(&info(false.into())));
+        // Missing or mistyped state reads as not kept above
+        assert!(!keep_above_from_info(&HashMap::new()));
+        assert!(!keep_above_from_info(&info("yes".into())));
     }
 }
@@ -13,13 +13,15 @@ mod niri;
 mod noop;
 
 pub use base::CompositorCapabilities;
-pub use detect::detect_compositor;
+pub use detect::{detect_compositor, take_compositor_notice};
 pub use error::CompositorError;
+pub use noop::NoopCompositor;
 
+use serde::Serialize;
 use std::fmt;
 
 /// Information about an open window from the compositor.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct WindowInfo {
     /// Unique window identifier (compositor-specific, e.g., "0x5678abcd" for Hyprland)
     pub address: String,
@@ -31,6 +33,8 @@ pub struct WindowInfo {
     pub workspace: i32,
     /// Whether this window is currently focused
     pub focused: bool,
+    /// Whether this window is kept above others ("always on top")
+    pub keep_above: bool,
 }
 
 /// Trait for compositor window management operations.
@@ -42,6 +46,8 @@ pub trait Compositor: Send + Sync {
     ///
     /// Returns only "normal" user windows - layer shell windows (panels, bars),
     /// the launcher itself, and other special windows should be filtered out.
+    /// Where the compositor tracks focus history, the most recently focused
+    /// windows come first.
     fn list_windows(&self) -> anyhow::Result<Vec<WindowInfo>>;
 
     /// Focus/activate a window by its address.
@@ -52,12 +58,34 @@ pub trait Compositor: Send + Sync {
     /// Get the compositor name for logging/debugging.
     fn name(&self) -> &'static str;
 
+    /// Keep a window above others, or stop doing so.
+    ///
+    /// Only supported where `capabilities().keep_above` is set.
+    fn set_keep_above(&self, _window_id: &str, _on: bool) -> anyhow::Result<()> {
+        anyhow::bail!("{} can't keep windows above others", self.name())
+    }
+
     /// Get the capabilities of this compositor implementation.
     ///
     /// Default implementation returns no capabilities (same as NoopCompositor).
     fn capabilities(&self) -> CompositorCapabilities {
         CompositorCapabilities::none()
     }
+
+    /// Get the pointer position in global layout coordinates, if known.
+    fn cursor_position(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    /// Get a point on the focused monitor (its top-left corner), if known.
+    fn focused_monitor_position(&self) -> Option<(f64, f64)> {
+        None
+    }
+}
+
+/// Serialize windows as pretty-printed JSON for `zlaunch windows`.
+pub fn windows_to_json(windows: &[WindowInfo]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(windows)
 }
 
 impl fmt::Debug for dyn Compositor {
@@ -65,3 +93,34 @@ impl fmt::Debug for dyn Compositor {
         write!(f, "Compositor({})", self.name())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_to_json() {
+        let windows = vec![WindowInfo {
+            address: "0x5678abcd".to_string(),
+            title: "Mozilla Firefox".to_string(),
+            class: "firefox".to_string(),
+            workspace: 2,
+            focused: true,
+            keep_above: false,
+        }];
+
+        let json: serde_json::Value =
+            serde_json::from_str(&windows_to_json(&windows).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "address": "0x5678abcd",
+                "title": "Mozilla Firefox",
+                "class": "firefox",
+                "workspace": 2,
+                "focused": true,
+                "keep_above": false,
+            }])
+        );
+    }
+}
@@ -70,6 +70,8 @@ impl Compositor for NiriCompositor {
                 class: window.app_id,
                 workspace: window.workspace_id as i32,
                 focused: window.is_focused,
+                // Niri has no windows kept above others
+                keep_above: false,
             });
         }
 
@@ -17,12 +17,18 @@ mod types;
 pub mod validation;
 
 // Re-export types
-pub use types::{AppConfig, ConfigModule, ConfigSearchProvider, FuzzyMatchConfig, LauncherMode};
+pub use types::{
+    ActionIndicatorStyle, AppConfig, CalculatorTrigger, CaseMatching, ConfigMenuEntry,
+    ConfigModule, ConfigSearchProvider, DescriptionSource, DigitGrouping, EmojiCopyFormat,
+    EmptySearchTrigger, FileIndexConfig, FuzzyMatchConfig, LauncherMode, LauncherPlacement,
+    ResultGrouping, SectionHeaders, WindowCopyFormat,
+};
 
 // Re-export service functions
 pub use service::{
-    ConfigProvider, ConfigService, config, config_file_exists, get_combined_modules,
-    get_default_modes, init_config, launcher_size, load_configured_theme, update_config,
+    ConfigProvider, ConfigService, combined_modules_for, config, config_file_exists,
+    get_combined_modules, get_default_modes, init_config, launcher_size, load_configured_theme,
+    reload_config, update_config,
 };
 
 // Re-export theme functions
@@ -71,41 +71,44 @@ pub fn config_file_exists() -> bool {
         .unwrap_or(false)
 }
 
-/// Load application config from `~/.config/zlaunch/config.toml`.
+/// Read and parse `~/.config/zlaunch/config.toml`.
 ///
-/// Returns `None` if the config file doesn't exist.
-/// Logs warning and returns `None` if parsing fails.
-fn load_app_config() -> Option<AppConfig> {
-    let config_path = config_dir()?.join("config.toml");
+/// Returns `Ok(None)` if the config file doesn't exist and an error
+/// message if it can't be read or parsed.
+fn read_app_config() -> Result<Option<AppConfig>, String> {
+    let Some(config_path) = config_dir().map(|dir| dir.join("config.toml")) else {
+        return Ok(None);
+    };
 
     if !config_path.exists() {
         tracing::debug!("Config file not found at {:?}, using defaults", config_path);
-        return None;
+        return Ok(None);
     }
 
-    match std::fs::read_to_string(&config_path) {
-        Ok(content) => match toml::from_str::<AppConfig>(&content) {
-            Ok(config) => {
-                tracing::info!("Loaded app config from {:?}", config_path);
-                Some(config)
-            }
-            Err(e) => {
-                tracing::warn!(
-                    "Failed to parse config file at {:?}: {}, using defaults",
-                    config_path,
-                    e
-                );
-                None
-            }
-        },
-        Err(e) => {
-            tracing::warn!(
-                "Failed to read config file at {:?}: {}, using defaults",
-                config_path,
-                e
-            );
-            None
-        }
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config file at {:?}: {}", config_path, e))?;
+    let config = toml::from_str::<AppConfig>(&content)
+        .map_err(|e| format!("Failed to parse config file at {:?}: {}", config_path, e))?;
+
+    tracing::info!("Loaded app config from {:?}", config_path);
+    Ok(Some(config))
+}
+
+/// Load application config from `~/.config/zlaunch/config.toml`.
+///
+/// Returns `None` if the config file doesn't exist.
+/// Logs warning and returns `None` if parsing fails.
+fn load_app_config() -> Option<AppConfig> {
+    read_app_config().unwrap_or_else(|e| {
+        tracing::warn!("{}, using defaults", e);
+        None
+    })
+}
+
+/// Log validation warnings for a configuration.
+fn log_validation_warnings(config: &AppConfig) {
+    for warning in super::validation::validate_config(config) {
+        tracing::warn!("Config validation: {} - {}", warning.field, warning.message);
     }
 }
 
@@ -117,13 +120,31 @@ pub fn init_config() {
     let loaded = load_app_config().unwrap_or_default();
 
     // Validate configuration and log warnings
-    let warnings = super::validation::validate_config(&loaded);
-    for warning in warnings {
-        tracing::warn!("Config validation: {} - {}", warning.field, warning.message);
-    }
+    log_validation_warnings(&loaded);
+
+    let mut config = CONFIG.write().unwrap();
+    *config = loaded;
+}
+
+/// Reload config from file (e.g. on SIGHUP).
+///
+/// The file is fully parsed before the current config is replaced, so a
+/// half-written or invalid file never takes effect. Returns whether the
+/// new config was applied.
+pub fn reload_config() -> bool {
+    let loaded = match read_app_config() {
+        Ok(loaded) => loaded.unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!("{}, keeping current config", e);
+            return false;
+        }
+    };
+
+    log_validation_warnings(&loaded);
 
     let mut config = CONFIG.write().unwrap();
     *config = loaded;
+    true
 }
 
 /// Get a clone of the current config.
@@ -184,8 +205,11 @@ pub fn load_configured_theme() -> LauncherTheme {
 ///
 /// Handles backwards compatibility with deprecated `disabled_modules`.
 pub fn get_combined_modules() -> Vec<ConfigModule> {
-    let cfg = config();
+    combined_modules_for(&config())
+}
 
+/// Get the modules to include in combined view for the given config.
+pub fn combined_modules_for(cfg: &AppConfig) -> Vec<ConfigModule> {
     // Priority: disabled_modules (deprecated) > combined_modules > all modules
     if let Some(disabled) = &cfg.disabled_modules {
         // Warn about deprecated option (only once)
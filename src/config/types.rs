@@ -1,7 +1,10 @@
 //! Configuration type definitions.
 
+use crate::files::IgnoreRules;
+use crate::files::ignore::DEFAULT_IGNORE_PATTERNS;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 /// Configuration for fuzzy matching algorithm.
 ///
@@ -38,6 +41,47 @@ pub struct FuzzyMatchConfig {
     /// it will be promoted to the top of the list.
     /// Default: true
     pub show_best_match: bool,
+    /// Alias table for application matching.
+    /// Keys are compared case-insensitively against the whole query, values
+    /// are desktop entry ids (e.g. "code") or application names.
+    /// Default: none
+    pub aliases: Option<HashMap<String, String>>,
+    /// Bonus score for an application targeted by a matching alias.
+    /// Default: 200000
+    pub alias_bonus: i64,
+    /// Weight of the fuzzy match score in the final ranking.
+    /// Default: 1.0
+    pub score_weight: f64,
+    /// Points added per unit of frecency (launch count weighted by recency).
+    /// Default: 0.0 (frecency is ignored)
+    pub frecency_weight: f64,
+    /// Days after which the frecency of an unused item halves.
+    /// 0 disables the decay.
+    /// Default: 30.0
+    pub frecency_half_life_days: f64,
+    /// Exponent applied to the launch count in the frecency score.
+    /// 0 ranks by recency alone, 1 scales linearly with the count.
+    /// Default: 1.0
+    pub frecency_count_weight: f64,
+    /// Maximum number of results of one type (applications, windows, ...).
+    /// Calculator, AI and search items are not counted. 0 disables the cap.
+    /// Default: 1000
+    pub max_results_per_type: usize,
+    /// Maximum total number of results, excluding calculator, AI and search
+    /// items. 0 disables the cap.
+    /// Default: 2000
+    pub max_results: usize,
+    /// How letter case is compared when fuzzy matching.
+    /// Default: smart
+    pub case_matching: CaseMatching,
+    /// Minimum raw fuzzy score per query character; weaker matches are
+    /// dropped. Scales with the query length since short queries score lower.
+    /// Default: 0 (keep every match)
+    pub min_score_per_char: i64,
+    /// Keep the selected item selected while typing if it still matches,
+    /// instead of jumping back to the first result.
+    /// Default: false
+    pub keep_selection: bool,
 }
 
 impl FuzzyMatchConfig {
@@ -52,6 +96,17 @@ impl FuzzyMatchConfig {
             action_score_multiplier: 0.8,
             submenu_score_multiplier: 0.9,
             show_best_match: true,
+            aliases: None,
+            alias_bonus: 200_000,
+            score_weight: 1.0,
+            frecency_weight: 0.0,
+            frecency_half_life_days: 30.0,
+            frecency_count_weight: 1.0,
+            max_results_per_type: 1000,
+            max_results: 2000,
+            case_matching: CaseMatching::Smart,
+            min_score_per_char: 0,
+            keep_selection: false,
         }
     }
 }
@@ -62,6 +117,62 @@ impl Default for FuzzyMatchConfig {
     }
 }
 
+/// Configuration for the file index used by file search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileIndexConfig {
+    /// Index files and show matching ones in the main list.
+    /// Default: false
+    pub enabled: bool,
+    /// Directories to index.
+    /// Default: the home directory
+    pub roots: Option<Vec<PathBuf>>,
+    /// Glob patterns for file and directory names to skip.
+    /// Default: common build and dependency directories
+    pub ignore_patterns: Option<Vec<String>>,
+    /// Index hidden files and directories.
+    /// Default: false
+    pub include_hidden: bool,
+    /// Maximum number of indexed paths.
+    /// Default: 200000
+    pub max_entries: usize,
+}
+
+impl FileIndexConfig {
+    /// Const default for static initialization.
+    pub const fn default_const() -> Self {
+        Self {
+            enabled: false,
+            roots: None,
+            ignore_patterns: None,
+            include_hidden: false,
+            max_entries: 200_000,
+        }
+    }
+
+    /// Get the roots to index, falling back to the home directory.
+    pub fn resolved_roots(&self) -> Vec<PathBuf> {
+        match &self.roots {
+            Some(roots) => roots.clone(),
+            None => dirs::home_dir().into_iter().collect(),
+        }
+    }
+
+    /// Build the ignore rules for this configuration.
+    pub fn ignore_rules(&self) -> IgnoreRules {
+        match &self.ignore_patterns {
+            Some(patterns) => IgnoreRules::new(patterns.iter().cloned(), self.include_hidden),
+            None => IgnoreRules::new(DEFAULT_IGNORE_PATTERNS.iter().copied(), self.include_hidden),
+        }
+    }
+}
+
+impl Default for FileIndexConfig {
+    fn default() -> Self {
+        Self::default_const()
+    }
+}
+
 /// Application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -94,6 +205,101 @@ pub struct AppConfig {
     pub combined_modules: Option<Vec<ConfigModule>>,
     /// Fuzzy matching configuration for search scoring.
     pub fuzzy_match: FuzzyMatchConfig,
+    /// When the calculator activates.
+    /// Default: auto
+    pub calculator_trigger: CalculatorTrigger,
+    /// Show one entry per application instead of one per window.
+    /// Default: false
+    pub group_windows: bool,
+    /// Maximum number of characters shown per clipboard entry in the list.
+    /// Default: 30
+    pub clipboard_preview_length: usize,
+    /// Number of lines shown when a multi-line clipboard entry is selected.
+    /// Set to 1 to disable expansion. Default: 5
+    pub clipboard_preview_lines: usize,
+    /// Also record the primary selection (highlighted text) in clipboard
+    /// history. Default: false
+    pub clipboard_capture_primary: bool,
+    /// File index settings for file search.
+    pub file_index: FileIndexConfig,
+    /// What secondary confirm (shift+enter) copies in the emoji picker.
+    /// Default: shortcode
+    pub emoji_secondary_copy: EmojiCopyFormat,
+    /// What secondary confirm (shift+enter) copies for a window.
+    /// Default: title
+    pub window_secondary_copy: WindowCopyFormat,
+    /// Keep the last confirmed calculator result as `ans` across sessions.
+    /// Set to false to forget it. Default: true
+    pub calculator_remember_answer: bool,
+    /// How digits of calculator results are grouped for display.
+    /// Default: western
+    pub calculator_grouping: DigitGrouping,
+    /// How the action indicator on the selected row is shown.
+    /// Default: key
+    pub action_indicator: ActionIndicatorStyle,
+    /// Close the launcher after copying a calculator result, color, emoji,
+    /// icon or clipboard entry. Default: true
+    pub close_after_copy: bool,
+    /// Include desktop entries marked NoDisplay or Hidden, shown with a badge.
+    /// Default: false
+    pub show_hidden_entries: bool,
+    /// Show the size and age of opened file paths in their description.
+    /// Default: false
+    pub show_file_metadata: bool,
+    /// Which desktop entry field is shown as an application's description.
+    /// Default: comment
+    pub app_description: DescriptionSource,
+    /// Which monitor the launcher opens on.
+    /// Default: focused
+    pub launcher_placement: LauncherPlacement,
+    /// Switch to an open window of an application instead of starting
+    /// another instance. Default: false
+    pub focus_running_apps: bool,
+    /// Desktop entry ids of applications launched on the discrete GPU.
+    /// Default: none
+    pub discrete_gpu_apps: Vec<String>,
+    /// Shortly after the daemon starts, query the compositor and load the
+    /// emoji, icon and calculator data in the background so the first open
+    /// is as fast as later ones. Default: false
+    pub prewarm: bool,
+    /// Hide the launcher when its window loses focus. Default: true
+    pub close_on_focus_loss: bool,
+    /// Offer AI answers when an LLM is configured. Set to false to turn
+    /// the AI item and the `!ai` trigger off entirely. Default: true
+    pub ai_enabled: bool,
+    /// When section headers are shown in the combined view.
+    /// Default: adaptive
+    pub section_headers: SectionHeaders,
+    /// Largest side, in pixels, of downloaded icons. Larger ones are
+    /// downscaled. Default: 128
+    pub max_icon_size: u32,
+    /// Skip animations. None follows the desktop's reduced motion setting.
+    /// Default: none
+    pub reduce_motion: Option<bool>,
+    /// What a search trigger without a query shows.
+    /// Default: hide
+    pub empty_search_trigger: EmptySearchTrigger,
+    /// Terminal to use per desktop entry id for applications that run in a
+    /// terminal, e.g. `{ htop = "kitty" }`. Others use `$TERMINAL`.
+    /// Default: none
+    pub terminal_overrides: Option<HashMap<String, String>>,
+    /// How results are grouped. Default: sections
+    pub grouping: ResultGrouping,
+    /// Characters a query needs before the AI item and search fallbacks
+    /// are offered. Default: 1
+    pub detector_min_query_length: usize,
+    /// Milliseconds the AI item and search fallbacks wait for typing to
+    /// pause. Local detectors run right away. Default: 0
+    pub network_detector_debounce_ms: u64,
+    /// Seconds the results of expensive providers (file search) are reused
+    /// for. Reset on SIGHUP and when indexed files change. Default: 60
+    pub provider_cache_ttl_secs: u64,
+    /// Custom submenus of commands, which may contain further submenus.
+    /// Default: none
+    pub submenus: Vec<ConfigMenuEntry>,
+    /// Remember confirmed queries and suggest them while typing (needs the
+    /// search module). Default: true
+    pub query_history: bool,
 }
 
 impl AppConfig {
@@ -111,6 +317,38 @@ impl AppConfig {
             default_modes: None,
             combined_modules: None,
             fuzzy_match: FuzzyMatchConfig::default_const(),
+            calculator_trigger: CalculatorTrigger::Auto,
+            group_windows: false,
+            clipboard_preview_length: 30,
+            clipboard_preview_lines: 5,
+            clipboard_capture_primary: false,
+            file_index: FileIndexConfig::default_const(),
+            emoji_secondary_copy: EmojiCopyFormat::Shortcode,
+            window_secondary_copy: WindowCopyFormat::Title,
+            close_after_copy: true,
+            show_hidden_entries: false,
+            show_file_metadata: false,
+            app_description: DescriptionSource::Comment,
+            launcher_placement: LauncherPlacement::Focused,
+            focus_running_apps: false,
+            discrete_gpu_apps: Vec::new(),
+            prewarm: false,
+            close_on_focus_loss: true,
+            ai_enabled: true,
+            section_headers: SectionHeaders::Adaptive,
+            calculator_remember_answer: true,
+            calculator_grouping: DigitGrouping::Western,
+            action_indicator: ActionIndicatorStyle::Key,
+            max_icon_size: 128,
+            reduce_motion: None,
+            empty_search_trigger: EmptySearchTrigger::Hide,
+            terminal_overrides: None,
+            grouping: ResultGrouping::Sections,
+            detector_min_query_length: 1,
+            network_detector_debounce_ms: 0,
+            provider_cache_ttl_secs: 60,
+            submenus: Vec::new(),
+            query_history: true,
         }
     }
 
@@ -136,33 +374,209 @@ impl Default for AppConfig {
                     trigger: "!g".to_string(),
                     url: "https://www.google.com/search?q={query}".to_string(),
                     icon: "magnifying-glass".to_string(),
+                    browser: None,
+                    favicon: false,
                 },
                 ConfigSearchProvider {
                     name: "DuckDuckGo".to_string(),
                     trigger: "!d".to_string(),
                     url: "https://duckduckgo.com/?q={query}".to_string(),
                     icon: "globe".to_string(),
+                    browser: None,
+                    favicon: false,
                 },
                 ConfigSearchProvider {
                     name: "Wikipedia".to_string(),
                     trigger: "!wiki".to_string(),
                     url: "https://en.wikipedia.org/wiki/Special:Search?search={query}".to_string(),
                     icon: "book-open".to_string(),
+                    browser: None,
+                    favicon: false,
                 },
                 ConfigSearchProvider {
                     name: "YouTube".to_string(),
                     trigger: "!yt".to_string(),
                     url: "https://www.youtube.com/results?search_query={query}".to_string(),
                     icon: "youtube-logo".to_string(),
+                    browser: None,
+                    favicon: false,
                 },
             ]),
             default_modes: None,
             combined_modules: None,
             fuzzy_match: FuzzyMatchConfig::default(),
+            calculator_trigger: CalculatorTrigger::default(),
+            group_windows: false,
+            clipboard_preview_length: 30,
+            clipboard_preview_lines: 5,
+            clipboard_capture_primary: false,
+            file_index: FileIndexConfig::default_const(),
+            emoji_secondary_copy: EmojiCopyFormat::Shortcode,
+            window_secondary_copy: WindowCopyFormat::Title,
+            close_after_copy: true,
+            show_hidden_entries: false,
+            show_file_metadata: false,
+            app_description: DescriptionSource::Comment,
+            launcher_placement: LauncherPlacement::Focused,
+            focus_running_apps: false,
+            discrete_gpu_apps: Vec::new(),
+            prewarm: false,
+            close_on_focus_loss: true,
+            ai_enabled: true,
+            section_headers: SectionHeaders::Adaptive,
+            calculator_remember_answer: true,
+            calculator_grouping: DigitGrouping::Western,
+            action_indicator: ActionIndicatorStyle::Key,
+            max_icon_size: 128,
+            reduce_motion: None,
+            empty_search_trigger: EmptySearchTrigger::Hide,
+            terminal_overrides: None,
+            grouping: ResultGrouping::Sections,
+            detector_min_query_length: 1,
+            network_detector_debounce_ms: 0,
+            provider_cache_ttl_secs: 60,
+            submenus: Vec::new(),
+            query_history: true,
         }
     }
 }
 
+/// When the calculator evaluates a query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalculatorTrigger {
+    /// Evaluate any query that contains a digit.
+    #[default]
+    Auto,
+    /// Only evaluate queries starting with `=` (e.g. `=2+2`).
+    Explicit,
+}
+
+/// How the action indicator on the selected row is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionIndicatorStyle {
+    /// The action label followed by the Enter key box.
+    #[default]
+    Key,
+    /// Only the action label.
+    Label,
+    /// No indicator.
+    Hidden,
+}
+
+/// When the combined view shows section headers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionHeaders {
+    /// Show headers only while results span more than one section.
+    #[default]
+    Adaptive,
+    /// Always show headers, except for the calculator and search/AI sections.
+    Always,
+    /// Never show headers.
+    Never,
+}
+
+/// How the combined view arranges results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultGrouping {
+    /// Group results by type in `combined_modules` order.
+    #[default]
+    Sections,
+    /// One headerless list ranked by score alone, below the calculator,
+    /// AI and search items.
+    Flat,
+}
+
+/// What a search provider trigger typed without a query (`!g`) shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmptySearchTrigger {
+    /// Show nothing until a query is typed.
+    #[default]
+    Hide,
+    /// Show an item opening the provider's homepage.
+    Homepage,
+}
+
+/// Case sensitivity of the fuzzy matcher.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseMatching {
+    /// Case-insensitive unless the query contains an uppercase letter.
+    #[default]
+    Smart,
+    /// Always case-insensitive.
+    Ignore,
+    /// Always case-sensitive.
+    Respect,
+}
+
+/// Which monitor the launcher opens on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LauncherPlacement {
+    /// The focused monitor.
+    #[default]
+    Focused,
+    /// The monitor under the mouse cursor.
+    Cursor,
+    /// A fixed monitor by index, e.g. `{ monitor = 1 }`.
+    Monitor(usize),
+}
+
+/// Text copied for an emoji by secondary confirm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmojiCopyFormat {
+    /// The `:shortcode:`, falling back to the name.
+    #[default]
+    Shortcode,
+    /// The emoji name, e.g. "thumbs up".
+    Name,
+}
+
+/// Desktop entry field used as an application's description.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DescriptionSource {
+    /// `Comment`, e.g. "Browse the World Wide Web".
+    #[default]
+    Comment,
+    /// `GenericName`, e.g. "Web Browser".
+    GenericName,
+    /// `GenericName`, falling back to `Comment`.
+    GenericNameThenComment,
+}
+
+/// Digit grouping of displayed calculator results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigitGrouping {
+    /// Groups of three, e.g. 1,234,567.
+    #[default]
+    Western,
+    /// Three, then groups of two (lakh/crore), e.g. 12,34,567.
+    Indian,
+    /// Groups of four, e.g. 123,4567.
+    Myriad,
+    /// No separators.
+    None,
+}
+
+/// Text copied for a window by secondary confirm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowCopyFormat {
+    /// The window title.
+    #[default]
+    Title,
+    /// The application class / app-id, e.g. "firefox".
+    Class,
+}
+
 /// Modules enum - configurable components of the launcher.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -170,6 +584,7 @@ pub enum ConfigModule {
     Applications,
     Ai,
     Emojis,
+    Icons,
     Calculator,
     Clipboard,
     Actions,
@@ -185,6 +600,7 @@ impl ConfigModule {
             ConfigModule::Calculator,
             ConfigModule::Windows,
             ConfigModule::Emojis,
+            ConfigModule::Icons,
             ConfigModule::Clipboard,
             ConfigModule::Actions,
             ConfigModule::Themes,
@@ -206,6 +622,8 @@ pub enum LauncherMode {
     Ai,
     #[value(alias = "emoji")]
     Emojis,
+    #[value(alias = "icon", alias = "glyphs")]
+    Icons,
     #[value(alias = "calc")]
     Calculator,
     Clipboard,
@@ -216,6 +634,9 @@ pub enum LauncherMode {
     Themes,
     #[value(alias = "window")]
     Windows,
+    /// Alt-Tab style window switcher, most recent windows first.
+    #[value(alias = "alt-tab")]
+    Switcher,
 }
 
 impl LauncherMode {
@@ -226,12 +647,14 @@ impl LauncherMode {
             "applications" | "apps" | "app" => Some(Self::Applications),
             "ai" => Some(Self::Ai),
             "emojis" | "emoji" => Some(Self::Emojis),
+            "icons" | "icon" | "glyphs" => Some(Self::Icons),
             "calculator" | "calc" => Some(Self::Calculator),
             "clipboard" => Some(Self::Clipboard),
             "actions" | "action" => Some(Self::Actions),
             "search" => Some(Self::Search),
             "themes" | "theme" => Some(Self::Themes),
             "windows" | "window" => Some(Self::Windows),
+            "switcher" | "alt-tab" => Some(Self::Switcher),
             _ => None,
         }
     }
@@ -243,12 +666,14 @@ impl LauncherMode {
             Self::Applications => "Applications",
             Self::Ai => "AI",
             Self::Emojis => "Emojis",
+            Self::Icons => "Icons",
             Self::Calculator => "Calculator",
             Self::Clipboard => "Clipboard",
             Self::Actions => "Actions",
             Self::Search => "Search",
             Self::Themes => "Themes",
             Self::Windows => "Windows",
+            Self::Switcher => "Switcher",
         }
     }
 
@@ -258,6 +683,7 @@ impl LauncherMode {
             ConfigModule::Applications => Self::Applications,
             ConfigModule::Ai => Self::Ai,
             ConfigModule::Emojis => Self::Emojis,
+            ConfigModule::Icons => Self::Icons,
             ConfigModule::Calculator => Self::Calculator,
             ConfigModule::Clipboard => Self::Clipboard,
             ConfigModule::Actions => Self::Actions,
@@ -274,12 +700,13 @@ impl LauncherMode {
             Self::Applications => Some(ConfigModule::Applications),
             Self::Ai => Some(ConfigModule::Ai),
             Self::Emojis => Some(ConfigModule::Emojis),
+            Self::Icons => Some(ConfigModule::Icons),
             Self::Calculator => Some(ConfigModule::Calculator),
             Self::Clipboard => Some(ConfigModule::Clipboard),
             Self::Actions => Some(ConfigModule::Actions),
             Self::Search => Some(ConfigModule::Search),
             Self::Themes => Some(ConfigModule::Themes),
-            Self::Windows => Some(ConfigModule::Windows),
+            Self::Windows | Self::Switcher => Some(ConfigModule::Windows),
         }
     }
 }
@@ -296,6 +723,36 @@ pub struct ConfigSearchProvider {
     /// Optional icon name (defaults to MagnifyingGlass).
     #[serde(default)]
     pub icon: String,
+    /// Optional browser command, with {url} for the search URL
+    /// (e.g. "firefox --private-window {url}"). Defaults to xdg-open.
+    #[serde(default)]
+    pub browser: Option<String>,
+    /// Show the site's favicon instead of the icon, once downloaded.
+    #[serde(default)]
+    pub favicon: bool,
+}
+
+/// An entry of a custom submenu: a command, or a nested submenu when it
+/// has `items`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigMenuEntry {
+    /// Entry name.
+    pub name: String,
+    /// Optional description shown under the name.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Optional icon name.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Command to run. Ignored when `items` is set.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Run `command` through `sh -c`.
+    #[serde(default)]
+    pub shell: bool,
+    /// Entries of the submenu.
+    #[serde(default)]
+    pub items: Vec<ConfigMenuEntry>,
 }
 
 #[cfg(test)]
@@ -305,7 +762,7 @@ mod tests {
     #[test]
     fn test_config_module_all() {
         let all = ConfigModule::all();
-        assert_eq!(all.len(), 9);
+        assert_eq!(all.len(), 10);
         assert!(all.contains(&ConfigModule::Applications));
         assert!(all.contains(&ConfigModule::Calculator));
         assert!(all.contains(&ConfigModule::Ai));
@@ -323,6 +780,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_launcher_mode_parse_switcher() {
+        assert_eq!(
+            LauncherMode::parse_str("switcher"),
+            Some(LauncherMode::Switcher)
+        );
+        assert_eq!(
+            LauncherMode::parse_str("alt-tab"),
+            Some(LauncherMode::Switcher)
+        );
+        assert_eq!(
+            LauncherMode::Switcher.to_module(),
+            Some(ConfigModule::Windows)
+        );
+    }
+
     #[test]
     fn test_launcher_mode_parse_applications() {
         assert_eq!(
@@ -348,6 +821,12 @@ mod tests {
         assert_eq!(LauncherMode::parse_str("emoji"), Some(LauncherMode::Emojis));
     }
 
+    #[test]
+    fn test_launcher_mode_parse_icons() {
+        assert_eq!(LauncherMode::parse_str("icons"), Some(LauncherMode::Icons));
+        assert_eq!(LauncherMode::parse_str("glyphs"), Some(LauncherMode::Icons));
+    }
+
     #[test]
     fn test_launcher_mode_parse_calculator() {
         assert_eq!(
@@ -551,6 +1030,25 @@ mod tests {
         assert!((config.description_penalty - 0.3).abs() < f64::EPSILON);
         assert!((config.action_score_multiplier - 0.8).abs() < f64::EPSILON);
         assert!((config.submenu_score_multiplier - 0.9).abs() < f64::EPSILON);
+        assert!(config.aliases.is_none());
+        assert_eq!(config.alias_bonus, 200_000);
+    }
+
+    #[test]
+    fn test_fuzzy_match_aliases_deserialization() {
+        let toml_str = r#"
+            [fuzzy_match.aliases]
+            vsc = "code"
+            Discord = "discord-canary"
+        "#;
+
+        let config: AppConfig = toml::from_str(toml_str).expect("Failed to deserialize");
+        let aliases = config.fuzzy_match.aliases.expect("aliases should be set");
+        assert_eq!(aliases.get("vsc").map(String::as_str), Some("code"));
+        assert_eq!(
+            aliases.get("Discord").map(String::as_str),
+            Some("discord-canary")
+        );
     }
 
     #[test]
@@ -598,4 +1096,35 @@ mod tests {
         assert_eq!(config.fuzzy_match.exact_match_bonus, 100_000);
         assert_eq!(config.fuzzy_match.prefix_match_bonus, 50_000);
     }
+
+    #[test]
+    fn test_calculator_trigger_deserialization() {
+        let config: AppConfig = toml::from_str("").expect("Failed to deserialize");
+        assert_eq!(config.calculator_trigger, CalculatorTrigger::Auto);
+
+        let config: AppConfig =
+            toml::from_str(r#"calculator_trigger = "explicit""#).expect("Failed to deserialize");
+        assert_eq!(config.calculator_trigger, CalculatorTrigger::Explicit);
+    }
+
+    #[test]
+    fn test_submenus_deserialization() {
+        let toml_str = r#"
+            [[submenus]]
+            name = "Tools"
+
+            [[submenus.items]]
+            name = "Network"
+
+            [[submenus.items.items]]
+            name = "Ping"
+            command = "ping 1.1.1.1"
+        "#;
+
+        let config: AppConfig = toml::from_str(toml_str).expect("Failed to deserialize");
+        let network = &config.submenus[0].items[0];
+        assert_eq!(network.name, "Network");
+        assert_eq!(network.items[0].command.as_deref(), Some("ping 1.1.1.1"));
+        assert!(!network.items[0].shell);
+    }
 }
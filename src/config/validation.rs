@@ -230,6 +230,8 @@ mod tests {
                 trigger: "!bad".to_string(),
                 url: "https://example.com/search".to_string(), // Missing {query}
                 icon: "magnifying-glass".to_string(),
+                browser: None,
+                favicon: false,
             }]),
             ..AppConfig::default()
         };
@@ -249,6 +251,8 @@ mod tests {
                 trigger: "!np".to_string(),
                 url: "example.com/search?q={query}".to_string(), // Missing protocol
                 icon: "magnifying-glass".to_string(),
+                browser: None,
+                favicon: false,
             }]),
             ..AppConfig::default()
         };
@@ -268,6 +272,8 @@ mod tests {
                 trigger: "search".to_string(), // Doesn't start with ! or :
                 url: "https://example.com/search?q={query}".to_string(),
                 icon: "magnifying-glass".to_string(),
+                browser: None,
+                favicon: false,
             }]),
             ..AppConfig::default()
         };
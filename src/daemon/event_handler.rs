@@ -11,7 +11,9 @@ use crate::compositor::Compositor;
 use crate::config::get_default_modes;
 use crate::error::IpcError;
 use crate::items::ApplicationItem;
+use crate::ui::motion;
 
+use super::prewarm::PrewarmedItems;
 use super::reload::set_reload_requested;
 use super::theme::handle_set_theme;
 
@@ -33,11 +35,25 @@ impl WindowState {
     }
 
     /// Close the window if it exists.
+    ///
+    /// The launcher fades out first unless motion is reduced. It counts as
+    /// closed right away, so it can be opened again during the fade.
     pub fn close(&mut self, cx: &mut gpui::App) {
-        if let Some(ref lw) = self.launcher_window {
-            window::close_window(&lw.handle, cx);
+        if let Some(lw) = self.launcher_window.take() {
+            let fade = motion::duration(motion::CLOSE_DURATION);
+            if fade.is_zero() {
+                window::close_window(&lw.handle, cx);
+            } else {
+                lw.launcher_view
+                    .update(cx, |launcher, cx| launcher.start_closing(cx));
+                let timer = cx.background_executor().timer(fade);
+                cx.spawn(async move |cx: &mut gpui::AsyncApp| {
+                    timer.await;
+                    let _ = cx.update(|cx| window::close_window(&lw.handle, cx));
+                })
+                .detach();
+            }
         }
-        self.launcher_window = None;
         self.visible = false;
     }
 }
@@ -60,6 +76,7 @@ pub async fn run_event_loop(
 ) {
     let mut window_state = WindowState::new();
     let mut applications = initial_applications;
+    let mut prewarmed = PrewarmedItems::default();
 
     while let Ok(event) = event_rx.recv_async().await {
         match event {
@@ -74,6 +91,7 @@ pub async fn run_event_loop(
                     &mut window_state,
                     modes,
                     &applications,
+                    &mut prewarmed,
                     &compositor,
                     &event_tx,
                     cx,
@@ -106,6 +124,7 @@ pub async fn run_event_loop(
                         &mut window_state,
                         modes,
                         &applications,
+                        &mut prewarmed,
                         &compositor,
                         &event_tx,
                         cx,
@@ -170,6 +189,7 @@ pub async fn run_event_loop(
             } => {
                 debug!("Applications updated, {} entries", new_apps.len());
                 applications = new_apps;
+                prewarmed.invalidate();
 
                 // If window visible, refresh the view
                 if window_state.visible {
@@ -187,6 +207,42 @@ pub async fn run_event_loop(
                 }
             }
 
+            DaemonEvent::Refreshed {
+                applications: new_apps,
+                config_reloaded,
+            } => {
+                debug!(
+                    config_reloaded,
+                    "Refreshed after SIGHUP, {} entries",
+                    new_apps.len()
+                );
+                applications = new_apps;
+                prewarmed.invalidate();
+
+                if config_reloaded {
+                    crate::ui::theme::sync_theme_from_config();
+                }
+
+                // If window visible, swap items in place and keep the query
+                if window_state.visible
+                    && let Some(ref lw) = window_state.launcher_window
+                {
+                    let view = lw.launcher_view.clone();
+                    let apps = applications.clone();
+                    let _ = cx.update(|cx| {
+                        view.update(cx, |launcher, cx| {
+                            launcher.refresh_theme(cx);
+                            launcher.reload_items(apps, cx);
+                        });
+                    });
+                }
+            }
+
+            DaemonEvent::Prewarmed { items, generation } => {
+                debug!("Prewarmed {} launcher items", items.len());
+                prewarmed.store(items, generation);
+            }
+
             _ => {}
         }
     }
@@ -197,6 +253,7 @@ fn handle_show(
     window_state: &mut WindowState,
     modes: Option<Vec<crate::config::LauncherMode>>,
     applications: &[ApplicationItem],
+    prewarmed: &mut PrewarmedItems,
     compositor: &Arc<dyn Compositor>,
     event_tx: &flume::Sender<DaemonEvent>,
     cx: &mut gpui::AsyncApp,
@@ -211,6 +268,7 @@ fn handle_show(
     cx.update(|cx| {
         match window::create_and_show_window(
             applications.to_vec(),
+            prewarmed.take(),
             compositor.clone(),
             effective_modes,
             event_tx.clone(),
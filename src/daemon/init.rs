@@ -4,11 +4,15 @@
 
 use anyhow::Result;
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
 use crate::compositor::{Compositor, detect_compositor};
 use crate::config::{ConfigModule, get_combined_modules};
-use crate::desktop::cache::load_applications;
+use crate::desktop::DesktopEntry;
+use crate::desktop::cache::{load_all_applications, load_all_applications_without_icons};
+use crate::desktop::mime::{MimeApps, MimeIndex, set_mime_index};
+use crate::desktop::scanner::needs_icon;
+use crate::files::{FileIndexHandle, run_indexer, set_file_index};
 use crate::ipc::{IpcServerHandle, client, prepare_socket, start_server};
 use crate::items::ApplicationItem;
 
@@ -68,6 +72,33 @@ pub fn init_clipboard_if_enabled() {
     }
 }
 
+/// Download favicons for search providers that want one.
+pub fn init_favicons(cx: &gpui::App) {
+    if let Some(providers) = &crate::config::config().search_providers {
+        crate::search::favicon::fetch_missing_favicons(providers, cx);
+    }
+}
+
+/// Start the file indexer on the shared tokio runtime if enabled in config.
+pub fn start_file_indexer(cx: &gpui::App) {
+    let config = crate::config::config().file_index.clone();
+    if !config.enabled {
+        return;
+    }
+    let handle = FileIndexHandle::new();
+    if !set_file_index(handle.clone()) {
+        return;
+    }
+
+    let (progress_tx, progress_rx) = flume::unbounded();
+    crate::tokio_runtime::spawn(cx, run_indexer(handle, config, progress_tx));
+    crate::tokio_runtime::spawn(cx, async move {
+        while let Ok(progress) = progress_rx.recv_async().await {
+            debug!(?progress, "File index progress");
+        }
+    });
+}
+
 /// Detect and return the compositor.
 pub fn init_compositor() -> Arc<dyn Compositor> {
     Arc::from(detect_compositor())
@@ -86,8 +117,29 @@ pub fn apply_compositor_config() {
 
 /// Load applications and convert to ApplicationItems.
 pub fn load_application_items() -> Vec<ApplicationItem> {
-    let entries = load_applications();
-    let applications: Vec<ApplicationItem> = entries.into_iter().map(Into::into).collect();
+    application_items(load_all_applications())
+}
+
+/// Like [`load_application_items`], but icons that aren't cached yet are
+/// left out so the first scan doesn't wait for them. Returns whether any
+/// are missing; [`load_application_items`] resolves them afterwards.
+pub fn load_application_items_without_icons() -> (Vec<ApplicationItem>, bool) {
+    let entries = load_all_applications_without_icons();
+    let icons_missing = entries.iter().any(needs_icon);
+    (application_items(entries), icons_missing)
+}
+
+/// Convert scanned entries to ApplicationItems and index their MIME types.
+fn application_items(entries: Vec<DesktopEntry>) -> Vec<ApplicationItem> {
+    // Scheme handlers are usually NoDisplay, so index every entry
+    set_mime_index(MimeIndex::build(&entries, &MimeApps::load()));
+
+    let include_hidden = crate::config::config().show_hidden_entries;
+    let applications: Vec<ApplicationItem> = entries
+        .into_iter()
+        .filter(|entry| include_hidden || !entry.hidden)
+        .map(Into::into)
+        .collect();
     info!(count = applications.len(), "Loaded applications");
     applications
 }
@@ -5,20 +5,33 @@
 
 mod event_handler;
 mod init;
+mod prewarm;
 mod reload;
+mod signal;
 mod theme;
 mod watcher;
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use anyhow::Result;
 use gpui::{Application, QuitMode};
 use gpui_component::theme::{Theme, ThemeMode};
 use tracing::info;
 
-use crate::app::create_daemon_channel;
+use crate::app::{DaemonEvent, create_daemon_channel};
 use crate::assets::CombinedAssets;
 use crate::ui::init_launcher;
 
 pub use init::init_logging;
+pub use signal::request_reload;
+
+/// Set once the daemon starts.
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Check if this process is the zlaunch daemon.
+pub fn is_running() -> bool {
+    RUNNING.load(Ordering::SeqCst)
+}
 
 /// Run the launcher daemon.
 ///
@@ -26,6 +39,7 @@ pub use init::init_logging;
 /// It initializes services, starts the GPUI application, and runs the event loop.
 pub fn run() -> Result<()> {
     init::init_logging();
+    RUNNING.store(true, Ordering::SeqCst);
     info!(
         version = env!("CARGO_PKG_VERSION"),
         "Starting zlaunch daemon"
@@ -52,8 +66,8 @@ pub fn run() -> Result<()> {
     // Apply compositor-specific configuration
     init::apply_compositor_config();
 
-    // Load applications
-    let applications = init::load_application_items();
+    // Load applications; icons missing from the cache are resolved later
+    let (applications, icons_missing) = init::load_application_items_without_icons();
 
     // Run GPUI application
     Application::new()
@@ -80,6 +94,39 @@ pub fn run() -> Result<()> {
             // Configure theme for transparent background
             theme::configure_theme(cx);
 
+            // Build or load the file index for file search
+            init::start_file_indexer(cx);
+
+            // Fetch search provider favicons in the background
+            init::init_favicons(cx);
+
+            // Resolve the icons the first scan skipped
+            if icons_missing {
+                let event_tx = event_tx.clone();
+                cx.background_spawn(async move {
+                    let applications = init::load_application_items();
+                    let _ = event_tx.send(DaemonEvent::ApplicationsChanged { applications });
+                })
+                .detach();
+            }
+
+            // Build lazily initialized state once, before the first open
+            if crate::config::config().prewarm {
+                let applications = applications.clone();
+                let compositor = compositor.clone();
+                let executor = cx.background_executor().clone();
+                let event_tx = event_tx.clone();
+                cx.background_spawn(async move {
+                    executor.timer(prewarm::PREWARM_DELAY).await;
+                    let items = prewarm::prewarm(&applications, compositor.as_ref());
+                    let _ = event_tx.send(DaemonEvent::Prewarmed {
+                        items,
+                        generation: prewarm::PrewarmedItems::FIRST_GENERATION,
+                    });
+                })
+                .detach();
+            }
+
             // Clone for move into async block
             let applications = applications.clone();
             let compositor = compositor.clone();
@@ -89,6 +136,10 @@ pub fn run() -> Result<()> {
             let event_tx_for_watcher = event_tx.clone();
             crate::tokio_runtime::spawn(cx, watcher::run_watcher_loop(event_tx_for_watcher));
 
+            // Reload config and applications on SIGHUP
+            let event_tx_for_signal = event_tx.clone();
+            crate::tokio_runtime::spawn(cx, signal::run_sighup_loop(event_tx_for_signal));
+
             // Main event loop (runs on GPUI executor)
             // Move ipc_handle into the async block to keep it alive for the daemon's lifetime
             cx.spawn(async move |cx: &mut gpui::AsyncApp| {
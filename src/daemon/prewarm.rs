@@ -0,0 +1,142 @@
+//! Preparing lazily built state before the launcher is first opened.
+
+use std::time::Duration;
+
+use tracing::debug;
+
+use crate::app::window::fetch_windows;
+use crate::compositor::Compositor;
+use crate::config::{ConfigModule, get_combined_modules};
+use crate::items::{ApplicationItem, ListItem};
+use crate::ui::delegates::ItemListDelegate;
+
+/// How long after startup prewarming starts, so it doesn't compete with
+/// the rest of the daemon's initialization.
+pub const PREWARM_DELAY: Duration = Duration::from_secs(2);
+
+/// Items prewarmed for the next launcher open.
+///
+/// Every change of the applications starts a new generation. Items are
+/// tagged with the generation they were built from, so a prewarm that
+/// finishes after a reload is dropped instead of undoing it.
+#[derive(Debug, Default)]
+pub struct PrewarmedItems {
+    items: Option<Vec<ListItem>>,
+    generation: u64,
+}
+
+impl PrewarmedItems {
+    /// Generation of the applications the daemon starts with.
+    pub const FIRST_GENERATION: u64 = 0;
+
+    /// Keep items built from `generation` until the launcher is opened.
+    /// Items of an older generation are dropped.
+    pub fn store(&mut self, items: Vec<ListItem>, generation: u64) {
+        if generation == self.generation {
+            self.items = Some(items);
+        } else {
+            debug!(
+                generation,
+                current = self.generation,
+                "Dropped stale prewarmed items"
+            );
+        }
+    }
+
+    /// Drop stored and pending items that no longer match the applications.
+    pub fn invalidate(&mut self) {
+        self.items = None;
+        self.generation += 1;
+    }
+
+    /// Take the stored items for an open. Only the first open gets them.
+    pub fn take(&mut self) -> Option<Vec<ListItem>> {
+        self.items.take()
+    }
+}
+
+/// Build everything the first open would otherwise pay for.
+///
+/// Queries the compositor (resolving window icons on the way), loads the
+/// emoji and icon datasets and the calculator, and runs the item list
+/// through a delegate once. Returns the items the launcher would show.
+pub fn prewarm(applications: &[ApplicationItem], compositor: &dyn Compositor) -> Vec<ListItem> {
+    let modules = get_combined_modules();
+
+    let windows = if modules.contains(&ConfigModule::Windows) {
+        fetch_windows(compositor, applications)
+    } else {
+        Vec::new()
+    };
+    if modules.contains(&ConfigModule::Emojis) {
+        crate::emoji::all_emojis();
+    }
+    if modules.contains(&ConfigModule::Icons) {
+        crate::glyphs::all_glyphs();
+    }
+    if modules.contains(&ConfigModule::Calculator) {
+        let _ = crate::calculator::evaluate_expression("1+1");
+    }
+
+    let mut items: Vec<ListItem> = Vec::with_capacity(windows.len() + applications.len());
+    items.extend(windows.into_iter().map(ListItem::Window));
+    items.extend(applications.iter().cloned().map(ListItem::Application));
+
+    let delegate = ItemListDelegate::new(items.clone(), modules);
+    debug!(
+        count = delegate.filtered_count(),
+        "Prewarmed launcher items"
+    );
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositor::NoopCompositor;
+    use crate::test_utils::mock_applications;
+
+    #[test]
+    fn test_prewarm_without_window() {
+        let applications = mock_applications();
+        let items = prewarm(&applications, &NoopCompositor);
+
+        assert_eq!(items.len(), applications.len());
+        assert!(
+            items
+                .iter()
+                .zip(&applications)
+                .all(|(item, app)| item.id() == app.id)
+        );
+    }
+
+    #[test]
+    fn test_prewarmed_items_are_taken_once() {
+        let first = PrewarmedItems::FIRST_GENERATION;
+        let mut prewarmed = PrewarmedItems::default();
+        prewarmed.store(prewarm(&mock_applications(), &NoopCompositor), first);
+        assert_eq!(
+            prewarmed.take().map(|items| items.len()),
+            Some(mock_applications().len())
+        );
+        assert!(prewarmed.take().is_none());
+
+        prewarmed.store(prewarm(&mock_applications(), &NoopCompositor), first);
+        prewarmed.invalidate();
+        assert!(prewarmed.take().is_none());
+    }
+
+    #[test]
+    fn test_prewarm_finishing_after_a_reload_is_dropped() {
+        let mut prewarmed = PrewarmedItems::default();
+        let items = prewarm(&mock_applications(), &NoopCompositor);
+
+        // The applications change while the first prewarm is still running
+        prewarmed.invalidate();
+        prewarmed.store(items.clone(), PrewarmedItems::FIRST_GENERATION);
+        assert!(prewarmed.take().is_none());
+
+        prewarmed.store(items, PrewarmedItems::FIRST_GENERATION + 1);
+        assert!(prewarmed.take().is_some());
+    }
+}
@@ -0,0 +1,74 @@
+//! SIGHUP handler for reloading without restarting.
+//!
+//! On SIGHUP the config is reloaded, desktop entries are rescanned and
+//! cached item providers are invalidated, then a `Refreshed` event is sent
+//! to the daemon event loop (`kill -HUP $(pidof zlaunch)`). The same reload can be requested from
+//! within the daemon with [`request_reload`].
+
+use lazy_static::lazy_static;
+use tokio::signal::unix::{SignalKind, signal};
+use tokio::sync::Notify;
+use tracing::{debug, error, info};
+
+use crate::app::DaemonEvent;
+
+use super::init::load_application_items;
+
+lazy_static! {
+    /// Wakes the reload loop without a signal.
+    static ref RELOAD_REQUEST: Notify = Notify::new();
+}
+
+/// Reload the config and applications as if the daemon received SIGHUP.
+pub fn request_reload() {
+    // Stores a permit if the loop is busy, so the request isn't lost
+    RELOAD_REQUEST.notify_one();
+}
+
+/// Run the SIGHUP loop as an async task.
+///
+/// Also handles reloads requested with [`request_reload`].
+///
+/// This should be spawned on the shared tokio runtime via `tokio_runtime::spawn()`.
+pub async fn run_sighup_loop(event_tx: flume::Sender<DaemonEvent>) {
+    let mut sighup = match signal(SignalKind::hangup()) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to install SIGHUP handler: {}", e);
+            return;
+        }
+    };
+
+    debug!("SIGHUP handler installed");
+
+    loop {
+        tokio::select! {
+            received = sighup.recv() => {
+                if received.is_none() {
+                    return;
+                }
+                info!("Received SIGHUP, reloading config and applications");
+            }
+            _ = RELOAD_REQUEST.notified() => {
+                info!("Reload requested, reloading config and applications");
+            }
+        }
+
+        // Config first, since the scan may depend on it
+        let config_reloaded = crate::config::reload_config();
+        let applications = load_application_items();
+        crate::items::invalidate_provider_caches();
+        info!("Reloaded {} applications", applications.len());
+
+        if event_tx
+            .send(DaemonEvent::Refreshed {
+                applications,
+                config_reloaded,
+            })
+            .is_err()
+        {
+            debug!("Event channel closed, SIGHUP handler exiting");
+            return;
+        }
+    }
+}
@@ -2,24 +2,26 @@
 //!
 //! Provides caching of parsed desktop entries to speed up daemon startup.
 //! The cache is stored in XDG cache directory and invalidated when source
-//! directories are modified.
+//! directories are modified. A stale cache still supplies the entries (and
+//! resolved icons) of files that haven't changed, so only those are reparsed.
+//! Icons of new entries can be resolved after the scan (see
+//! [`load_all_applications_without_icons`]) so they don't delay startup.
 
 mod validation;
 
 use crate::desktop::entry::DesktopEntry;
-use crate::desktop::scanner::scan_applications;
-use crate::ui::icon::resolve_icon_path;
+use crate::desktop::scanner::{application_dirs, needs_icon, resolve_icons, scan_dirs_reusing};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use tracing::{debug, info, warn};
 
 pub use validation::get_directory_mtimes;
 
 /// Current cache format version.
-const CACHE_VERSION: u32 = 1;
+const CACHE_VERSION: u32 = 7;
 
 /// Cached representation of a desktop entry.
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -32,6 +34,16 @@ pub struct CachedEntry {
     pub comment: Option<String>,
     pub categories: Vec<String>,
     pub terminal: bool,
+    #[serde(default)]
+    pub startup_notify: bool,
+    #[serde(default)]
+    pub mime_types: Vec<String>,
+    #[serde(default)]
+    pub startup_wm_class: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub generic_name: Option<String>,
     pub source_path: PathBuf,
     #[serde(with = "system_time_serde")]
     pub mtime: SystemTime,
@@ -50,6 +62,11 @@ impl From<CachedEntry> for DesktopEntry {
             cached.terminal,
             cached.source_path,
         )
+        .with_startup_notify(cached.startup_notify)
+        .with_mime_types(cached.mime_types)
+        .with_startup_wm_class(cached.startup_wm_class)
+        .with_hidden(cached.hidden)
+        .with_generic_name(cached.generic_name)
     }
 }
 
@@ -68,6 +85,11 @@ impl From<&DesktopEntry> for CachedEntry {
             comment: entry.comment.clone(),
             categories: entry.categories.clone(),
             terminal: entry.terminal,
+            startup_notify: entry.startup_notify,
+            mime_types: entry.mime_types.clone(),
+            startup_wm_class: entry.startup_wm_class.clone(),
+            hidden: entry.hidden,
+            generic_name: entry.generic_name.clone(),
             source_path: entry.path.clone(),
             mtime,
         }
@@ -84,6 +106,9 @@ pub struct DesktopEntryCache {
     /// Modification times of scanned directories.
     #[serde(with = "hashmap_system_time_serde")]
     pub dir_mtimes: HashMap<PathBuf, SystemTime>,
+    /// Whether NoDisplay/Hidden entries were included in the scan.
+    #[serde(default)]
+    pub include_hidden: bool,
 }
 
 impl DesktopEntryCache {
@@ -119,8 +144,11 @@ impl DesktopEntryCache {
 
     /// Check if the cache is still valid (no directories have been modified).
     pub fn is_valid(&self) -> bool {
-        let current_mtimes = validation::get_directory_mtimes();
+        self.is_valid_for(&validation::get_directory_mtimes())
+    }
 
+    /// Check the cached directory mtimes against the current ones.
+    fn is_valid_for(&self, current_mtimes: &HashMap<PathBuf, SystemTime>) -> bool {
         // Check if all directories match
         for (path, cached_mtime) in &self.dir_mtimes {
             match current_mtimes.get(path) {
@@ -154,50 +182,87 @@ impl DesktopEntryCache {
     }
 }
 
-/// Resolve icon paths for all entries.
-fn resolve_all_icon_paths(entries: &mut [DesktopEntry]) {
-    for entry in entries.iter_mut() {
-        if entry.icon_path.is_none() {
-            entry.icon_path = entry.icon.as_ref().and_then(|name| resolve_icon_path(name));
-        }
-    }
+/// Get the cached entry for a file, if the file hasn't changed since.
+fn reusable_entry(cached: &HashMap<PathBuf, CachedEntry>, path: &Path) -> Option<DesktopEntry> {
+    let entry = cached.get(path)?;
+    let mtime = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    (mtime == entry.mtime).then(|| entry.clone().into())
 }
 
 /// Load applications with caching.
 ///
+/// NoDisplay/Hidden entries are only included with `show_hidden_entries`.
+pub fn load_applications() -> Vec<DesktopEntry> {
+    let include_hidden = crate::config::config().show_hidden_entries;
+    load_all_applications()
+        .into_iter()
+        .filter(|entry| include_hidden || !entry.hidden)
+        .collect()
+}
+
+/// Load all applications, including NoDisplay/Hidden entries (e.g. URL
+/// scheme handlers), with caching.
+///
 /// Attempts to load from cache first. If the cache is invalid or missing,
 /// performs a full scan and saves the result to cache.
-pub fn load_applications() -> Vec<DesktopEntry> {
-    // Try to load from cache
+pub fn load_all_applications() -> Vec<DesktopEntry> {
+    let mut entries = load_all_applications_without_icons();
+    resolve_missing_icons(&mut entries);
+    entries
+}
+
+/// Like [`load_all_applications`], but icons that aren't cached yet stay
+/// unresolved. Pass the entries to [`resolve_missing_icons`] later.
+pub fn load_all_applications_without_icons() -> Vec<DesktopEntry> {
+    // Try to load from cache; older caches only hold the shown entries
+    let mut previous: HashMap<PathBuf, CachedEntry> = HashMap::new();
     if let Some(cache) = DesktopEntryCache::load() {
-        if cache.is_valid() {
+        if cache.is_valid() && cache.include_hidden {
             info!("Loaded {} applications from cache", cache.entries.len());
             return cache.entries.into_iter().map(DesktopEntry::from).collect();
         }
-        debug!("Cache is stale, rescanning");
+        debug!("Cache is stale, rescanning changed files");
+        previous = cache
+            .entries
+            .into_iter()
+            .map(|entry| (entry.source_path.clone(), entry))
+            .collect();
     }
 
-    // Full scan required
     info!("Scanning for desktop applications...");
-    let mut entries = scan_applications();
-    resolve_all_icon_paths(&mut entries);
+    let entries = scan_dirs_reusing(&application_dirs(), true, &|path| {
+        reusable_entry(&previous, path)
+    });
     info!("Found {} applications", entries.len());
+    save_entries(&entries);
+    entries
+}
 
-    // Save to cache
-    let cached_entries: Vec<CachedEntry> = entries.iter().map(CachedEntry::from).collect();
-    let dir_mtimes = validation::get_directory_mtimes();
+/// Resolve the icons the entries are missing and update the cache.
+/// Returns whether any icon was found.
+pub fn resolve_missing_icons(entries: &mut [DesktopEntry]) -> bool {
+    if !entries.iter().any(needs_icon) {
+        return false;
+    }
+    let found = resolve_icons(entries);
+    if found {
+        save_entries(entries);
+    }
+    found
+}
 
+/// Save a full scan to the cache.
+fn save_entries(entries: &[DesktopEntry]) {
     let cache = DesktopEntryCache {
         version: CACHE_VERSION,
-        entries: cached_entries,
-        dir_mtimes,
+        entries: entries.iter().map(CachedEntry::from).collect(),
+        dir_mtimes: validation::get_directory_mtimes(),
+        include_hidden: true,
     };
 
     if let Err(e) = cache.save() {
         warn!("Failed to save application cache: {}", e);
     }
-
-    entries
 }
 
 /// Serde support for SystemTime.
@@ -260,3 +325,68 @@ mod hashmap_system_time_serde {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::time::Duration;
+
+    fn cache_with_mtimes(dir_mtimes: HashMap<PathBuf, SystemTime>) -> DesktopEntryCache {
+        DesktopEntryCache {
+            version: CACHE_VERSION,
+            entries: Vec::new(),
+            dir_mtimes,
+            include_hidden: false,
+        }
+    }
+
+    #[test]
+    fn test_mtime_change_invalidates_cache() {
+        let dir = PathBuf::from("/usr/share/applications");
+        let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let cache = cache_with_mtimes(HashMap::from([(dir.clone(), mtime)]));
+
+        assert!(cache.is_valid_for(&HashMap::from([(dir.clone(), mtime)])));
+        assert!(!cache.is_valid_for(&HashMap::from([(
+            dir.clone(),
+            mtime + Duration::from_secs(1)
+        )])));
+        // Removed and added directories invalidate too
+        assert!(!cache.is_valid_for(&HashMap::new()));
+        assert!(!cache.is_valid_for(&HashMap::from([
+            (dir, mtime),
+            (PathBuf::from("/opt/share/applications"), mtime),
+        ])));
+    }
+
+    #[test]
+    fn test_reusable_entry_requires_unchanged_file() {
+        let dir = TempDir::new("cache");
+        let path = dir.join("editor.desktop");
+        fs::write(&path, "[Desktop Entry]\nName=Editor\nExec=true\n").unwrap();
+
+        let entry = DesktopEntry::new(
+            "editor".to_string(),
+            "Editor".to_string(),
+            "true".to_string(),
+            Some("editor".to_string()),
+            Some(PathBuf::from("/icons/editor.svg")),
+            None,
+            vec![],
+            false,
+            path.clone(),
+        );
+        let mut cached = HashMap::from([(path.clone(), CachedEntry::from(&entry))]);
+        let unchanged = reusable_entry(&cached, &path);
+
+        cached.get_mut(&path).unwrap().mtime = SystemTime::UNIX_EPOCH;
+        let changed = reusable_entry(&cached, &path);
+
+        assert_eq!(
+            unchanged.and_then(|e| e.icon_path),
+            Some(PathBuf::from("/icons/editor.svg"))
+        );
+        assert!(changed.is_none());
+    }
+}
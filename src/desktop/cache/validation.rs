@@ -3,6 +3,7 @@
 //! Provides functions for checking directory modification times
 //! to determine cache validity.
 
+use crate::desktop::scanner::application_dirs;
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
@@ -15,7 +16,7 @@ use std::time::SystemTime;
 pub fn get_directory_mtimes() -> HashMap<PathBuf, SystemTime> {
     let mut mtimes = HashMap::new();
 
-    for dir in get_xdg_application_dirs() {
+    for dir in application_dirs() {
         if let Ok(metadata) = fs::metadata(&dir) {
             if let Ok(mtime) = metadata.modified() {
                 mtimes.insert(dir, mtime);
@@ -26,26 +27,6 @@ pub fn get_directory_mtimes() -> HashMap<PathBuf, SystemTime> {
     mtimes
 }
 
-/// Get the list of XDG application directories to scan.
-fn get_xdg_application_dirs() -> Vec<PathBuf> {
-    let mut dirs = Vec::new();
-
-    if let Some(data_home) = dirs::data_local_dir() {
-        dirs.push(data_home.join("applications"));
-    }
-
-    if let Ok(xdg_dirs) = std::env::var("XDG_DATA_DIRS") {
-        for dir in xdg_dirs.split(':') {
-            dirs.push(PathBuf::from(dir).join("applications"));
-        }
-    } else {
-        dirs.push(PathBuf::from("/usr/local/share/applications"));
-        dirs.push(PathBuf::from("/usr/share/applications"));
-    }
-
-    dirs
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
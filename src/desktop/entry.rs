@@ -1,3 +1,4 @@
+use crate::config::DescriptionSource;
 use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
@@ -9,9 +10,19 @@ pub struct DesktopEntry {
     /// Pre-resolved icon path for fast rendering
     pub icon_path: Option<PathBuf>,
     pub comment: Option<String>,
+    /// Generic description declared via `GenericName=`, e.g. "Web Browser"
+    pub generic_name: Option<String>,
     pub categories: Vec<String>,
     pub terminal: bool,
     pub path: PathBuf,
+    /// Whether the entry declares `StartupNotify=true`
+    pub startup_notify: bool,
+    /// MIME types declared via `MimeType=`
+    pub mime_types: Vec<String>,
+    /// Window class declared via `StartupWMClass=`
+    pub startup_wm_class: Option<String>,
+    /// Whether the entry sets `NoDisplay=true` or `Hidden=true`
+    pub hidden: bool,
 }
 
 impl DesktopEntry {
@@ -34,9 +45,111 @@ impl DesktopEntry {
             icon,
             icon_path,
             comment,
+            generic_name: None,
             categories,
             terminal,
             path,
+            startup_notify: false,
+            mime_types: Vec::new(),
+            startup_wm_class: None,
+            hidden: false,
         }
     }
+
+    /// Set whether the application supports startup notification (builder pattern).
+    pub fn with_startup_notify(mut self, startup_notify: bool) -> Self {
+        self.startup_notify = startup_notify;
+        self
+    }
+
+    /// Set the declared MIME types (builder pattern).
+    pub fn with_mime_types(mut self, mime_types: Vec<String>) -> Self {
+        self.mime_types = mime_types;
+        self
+    }
+
+    /// Set the window class the application's windows use (builder pattern).
+    pub fn with_startup_wm_class(mut self, startup_wm_class: Option<String>) -> Self {
+        self.startup_wm_class = startup_wm_class;
+        self
+    }
+
+    /// Mark the entry as hidden from menus (builder pattern).
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Set the generic name (builder pattern).
+    pub fn with_generic_name(mut self, generic_name: Option<String>) -> Self {
+        self.generic_name = generic_name;
+        self
+    }
+
+    /// The description to show, taken from the preferred field.
+    pub fn description(&self, source: DescriptionSource) -> Option<String> {
+        match source {
+            DescriptionSource::Comment => self.comment.clone(),
+            DescriptionSource::GenericName => self.generic_name.clone(),
+            DescriptionSource::GenericNameThenComment => {
+                self.generic_name.clone().or_else(|| self.comment.clone())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(comment: Option<&str>, generic_name: Option<&str>) -> DesktopEntry {
+        DesktopEntry::new(
+            "firefox".to_string(),
+            "Firefox".to_string(),
+            "firefox %u".to_string(),
+            None,
+            None,
+            comment.map(String::from),
+            vec![],
+            false,
+            "/usr/share/applications/firefox.desktop".into(),
+        )
+        .with_generic_name(generic_name.map(String::from))
+    }
+
+    #[test]
+    fn test_description_preference() {
+        let both = entry(Some("Browse the World Wide Web"), Some("Web Browser"));
+        assert_eq!(
+            both.description(DescriptionSource::Comment).as_deref(),
+            Some("Browse the World Wide Web")
+        );
+        assert_eq!(
+            both.description(DescriptionSource::GenericName).as_deref(),
+            Some("Web Browser")
+        );
+        assert_eq!(
+            both.description(DescriptionSource::GenericNameThenComment)
+                .as_deref(),
+            Some("Web Browser")
+        );
+    }
+
+    #[test]
+    fn test_description_fallback() {
+        let comment_only = entry(Some("Browse the World Wide Web"), None);
+        assert_eq!(
+            comment_only.description(DescriptionSource::GenericName),
+            None
+        );
+        assert_eq!(
+            comment_only
+                .description(DescriptionSource::GenericNameThenComment)
+                .as_deref(),
+            Some("Browse the World Wide Web")
+        );
+
+        let generic_only = entry(None, Some("Web Browser"));
+        assert_eq!(generic_only.description(DescriptionSource::Comment), None);
+    }
 }
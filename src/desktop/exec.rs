@@ -1,26 +1,477 @@
 use crate::desktop::entry::DesktopEntry;
+use crate::desktop::mime::default_app_for_mime;
+use crate::desktop::startup::startup_environment;
+use crate::error::LaunchError;
 use crate::process;
+use std::path::Path;
 
-pub fn launch_application(entry: &DesktopEntry) -> anyhow::Result<()> {
-    let exec = clean_exec_string(&entry.exec);
+/// Field codes that are dropped when expanding an Exec value.
+const FIELD_CODES: [char; 11] = ['f', 'F', 'u', 'U', 'd', 'D', 'n', 'N', 'i', 'c', 'k'];
 
-    if entry.terminal {
-        process::launch_in_terminal(&exec)?;
+/// Field codes that take a file or URL argument.
+const TARGET_CODES: [char; 4] = ['f', 'F', 'u', 'U'];
+
+/// Environment that offloads rendering to the discrete GPU (Mesa PRIME).
+pub const DISCRETE_GPU_ENV: [(&str, &str); 1] = [("DRI_PRIME", "1")];
+
+/// Additional environment for NVIDIA PRIME render offload, only set while
+/// the NVIDIA driver is loaded.
+pub const NVIDIA_PRIME_ENV: [(&str, &str); 2] = [
+    ("__NV_PRIME_RENDER_OFFLOAD", "1"),
+    ("__GLX_VENDOR_LIBRARY_NAME", "nvidia"),
+];
+
+/// Launch an application, on the discrete GPU if it is listed in
+/// `discrete_gpu_apps`.
+pub fn launch_application(entry: &DesktopEntry) -> Result<(), LaunchError> {
+    launch_application_on_gpu(entry, wants_discrete_gpu(entry))
+}
+
+/// Launch an application, on the discrete GPU if `discrete_gpu` is set.
+pub fn launch_application_on_gpu(
+    entry: &DesktopEntry,
+    discrete_gpu: bool,
+) -> Result<(), LaunchError> {
+    launch_with_args(entry, parse_exec(&entry.exec), discrete_gpu)
+}
+
+/// Launch an application, passing a file or URL in place of its `%f`/`%u` codes.
+pub fn launch_application_with(entry: &DesktopEntry, target: &str) -> Result<(), LaunchError> {
+    launch_with_args(
+        entry,
+        parse_exec_with_target(&entry.exec, Some(target)),
+        wants_discrete_gpu(entry),
+    )
+}
+
+/// Whether the config wants an application on the discrete GPU.
+fn wants_discrete_gpu(entry: &DesktopEntry) -> bool {
+    crate::config::config()
+        .discrete_gpu_apps
+        .contains(&entry.id)
+}
+
+/// Whether the proprietary NVIDIA driver is loaded.
+fn nvidia_driver_loaded() -> bool {
+    Path::new("/proc/driver/nvidia/version").exists()
+}
+
+/// Environment for the discrete GPU, with the NVIDIA variables when
+/// `nvidia` is set. They would break rendering on other drivers.
+fn discrete_gpu_env(nvidia: bool) -> Vec<(String, String)> {
+    let nvidia_env: &[(&str, &str)] = if nvidia { &NVIDIA_PRIME_ENV } else { &[] };
+    DISCRETE_GPU_ENV
+        .iter()
+        .chain(nvidia_env)
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Open a URL with the default handler for its scheme.
+///
+/// Falls back to `xdg-open` when no handler is registered.
+pub fn open_url(url: &str) -> anyhow::Result<()> {
+    let handler = url
+        .split_once(':')
+        .and_then(|(scheme, _)| default_app_for_mime(&format!("x-scheme-handler/{scheme}")));
+
+    match handler {
+        Some(entry) => Ok(launch_application_with(&entry, url)?),
+        None => Ok(process::open_url(url)?),
+    }
+}
+
+/// Open a desktop file in `$VISUAL` or `$EDITOR`, run in a terminal.
+///
+/// Without an editor the file's directory is opened in the file manager.
+pub fn edit_desktop_file(path: &Path) -> Result<(), LaunchError> {
+    let editor = editor_command(
+        std::env::var("VISUAL").ok().as_deref(),
+        std::env::var("EDITOR").ok().as_deref(),
+    );
+
+    match editor {
+        Some(mut args) => {
+            let app = args[0].clone();
+            args.push(path.to_string_lossy().into_owned());
+            process::launch_args_in_terminal(&args, &[], None)
+                .map_err(|source| LaunchError { app, source })
+        }
+        None => {
+            let dir = path.parent().unwrap_or(path);
+            process::open_url(&dir.to_string_lossy()).map_err(|source| LaunchError {
+                app: "file manager".to_string(),
+                source,
+            })
+        }
+    }
+}
+
+/// Split the editor command, preferring `$VISUAL` over `$EDITOR`.
+fn editor_command(visual: Option<&str>, editor: Option<&str>) -> Option<Vec<String>> {
+    [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(tokenize)
+        .find(|args| !args.is_empty())
+}
+
+fn launch_with_args(
+    entry: &DesktopEntry,
+    args: Vec<String>,
+    discrete_gpu: bool,
+) -> Result<(), LaunchError> {
+    let env = launch_environment(entry, discrete_gpu);
+
+    let result = if entry.terminal {
+        let terminal = terminal_override(&entry.id);
+        process::launch_args_in_terminal(&args, &env, terminal.as_deref())
+    } else {
+        process::launch_args(&args, &env)
+    };
+
+    result.map_err(|source| LaunchError {
+        app: entry.name.clone(),
+        source,
+    })
+}
+
+/// The terminal configured for an application in `terminal_overrides`.
+fn terminal_override(id: &str) -> Option<String> {
+    crate::config::config()
+        .terminal_overrides
+        .as_ref()?
+        .get(id)
+        .cloned()
+}
+
+/// Extra environment for launching an application.
+fn launch_environment(entry: &DesktopEntry, discrete_gpu: bool) -> Vec<(String, String)> {
+    let mut env = if entry.startup_notify {
+        startup_environment(&entry.id)
     } else {
-        process::launch_exec(&exec)?;
+        Vec::new()
+    };
+    if discrete_gpu {
+        env.extend(discrete_gpu_env(nvidia_driver_loaded()));
     }
+    env
+}
+
+/// Split an Exec value into arguments and expand its field codes.
+///
+/// Follows the desktop entry spec: string escapes are undone first, then
+/// double-quoted arguments may contain spaces and the reserved characters
+/// `"`, `` ` ``, `$` and `\` escaped with a backslash. `%%` is a literal `%`
+/// and field codes count inside quotes too. Arguments consisting only of
+/// field codes are removed entirely.
+pub fn parse_exec(exec: &str) -> Vec<String> {
+    parse_exec_with_target(exec, None)
+}
+
+/// Like [`parse_exec`], but substitutes `target` for the file and URL codes.
+pub fn parse_exec_with_target(exec: &str, target: Option<&str>) -> Vec<String> {
+    tokenize(&unescape_value(exec))
+        .into_iter()
+        .filter_map(|token| expand_field_codes(&token, target))
+        .collect()
+}
 
-    Ok(())
+/// Split a command line into arguments, handling quotes and escapes.
+pub fn split_command(command: &str) -> Vec<String> {
+    tokenize(command)
 }
 
-fn clean_exec_string(exec: &str) -> String {
-    let mut result = exec.to_string();
+/// Undo the escapes of desktop entry string values (`\s`, `\n`, `\t`, `\r`
+/// and `\\`), which apply before the Exec quoting rules.
+///
+/// Other backslashes are kept for the quoting rules, so `\"` and `\$`
+/// still escape inside quotes.
+fn unescape_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
 
-    for placeholder in [
-        "%f", "%F", "%u", "%U", "%d", "%D", "%n", "%N", "%i", "%c", "%k",
-    ] {
-        result = result.replace(placeholder, "");
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => result.push(' '),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
     }
 
-    result.trim().to_string()
+    result
+}
+
+/// Split an Exec value on unquoted whitespace, handling quotes and escapes.
+fn tokenize(exec: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut in_quotes = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_token = true;
+            }
+            '\\' => {
+                in_token = true;
+                match chars.next() {
+                    // Inside quotes only these characters are escapable
+                    Some(next) if !in_quotes || matches!(next, '"' | '`' | '$' | '\\') => {
+                        current.push(next)
+                    }
+                    Some(next) => {
+                        current.push('\\');
+                        current.push(next);
+                    }
+                    None => current.push('\\'),
+                }
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_token {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Expand field codes in a single argument.
+/// Returns None if the argument consisted only of dropped field codes.
+fn expand_field_codes(token: &str, target: Option<&str>) -> Option<String> {
+    let mut result = String::with_capacity(token.len());
+    let mut only_codes = true;
+    let mut chars = token.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            only_codes = false;
+            continue;
+        }
+        match chars.next() {
+            Some('%') => {
+                result.push('%');
+                only_codes = false;
+            }
+            Some(code) if FIELD_CODES.contains(&code) => {
+                if let Some(target) = target.filter(|_| TARGET_CODES.contains(&code)) {
+                    result.push_str(target);
+                    only_codes = false;
+                }
+            }
+            // Unknown or deprecated field codes are kept verbatim
+            Some(other) => {
+                result.push('%');
+                result.push(other);
+                only_codes = false;
+            }
+            None => {
+                result.push('%');
+                only_codes = false;
+            }
+        }
+    }
+
+    if only_codes && !token.is_empty() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_exec_strips_field_codes() {
+        assert_eq!(parse_exec("firefox %u"), vec!["firefox"]);
+        assert_eq!(
+            parse_exec("code --new-window %F"),
+            vec!["code", "--new-window"]
+        );
+    }
+
+    #[test]
+    fn test_parse_exec_escaped_percent() {
+        assert_eq!(parse_exec("date +%%Y-%%m-%%d"), vec!["date", "+%Y-%m-%d"]);
+    }
+
+    #[test]
+    fn test_parse_exec_quoted_path_with_spaces() {
+        assert_eq!(
+            parse_exec(r#""/opt/My App/bin/app" --flag"#),
+            vec!["/opt/My App/bin/app", "--flag"]
+        );
+        assert_eq!(
+            parse_exec(r#"sh -c "echo \"hi\" \\ done""#),
+            vec!["sh", "-c", r#"echo "hi" \ done"#]
+        );
+    }
+
+    #[test]
+    fn test_launch_missing_binary_reports_app_and_os_error() {
+        let entry = DesktopEntry::new(
+            "missing".to_string(),
+            "Missing App".to_string(),
+            "/nonexistent/zlaunch-missing-binary --flag".to_string(),
+            None,
+            None,
+            None,
+            vec![],
+            false,
+            std::path::PathBuf::from("/tmp/missing.desktop"),
+        );
+
+        let err = launch_application(&entry).unwrap_err();
+        assert_eq!(err.app, "Missing App");
+        assert!(matches!(
+            &err.source,
+            crate::error::ProcessError::SpawnFailed(e) if e.kind() == std::io::ErrorKind::NotFound
+        ));
+        assert!(
+            err.to_string()
+                .starts_with("Failed to launch Missing App: ")
+        );
+    }
+
+    #[test]
+    fn test_editor_command() {
+        assert_eq!(
+            editor_command(Some("code --wait"), Some("vim")),
+            Some(vec!["code".to_string(), "--wait".to_string()])
+        );
+        assert_eq!(
+            editor_command(Some(" "), Some("nvim")),
+            Some(vec!["nvim".to_string()])
+        );
+        assert_eq!(editor_command(None, None), None);
+    }
+
+    #[test]
+    fn test_discrete_gpu_environment() {
+        let entry = DesktopEntry::new(
+            "blender".to_string(),
+            "Blender".to_string(),
+            "blender %f".to_string(),
+            None,
+            None,
+            None,
+            vec![],
+            false,
+            "/usr/share/applications/blender.desktop".into(),
+        );
+
+        assert!(launch_environment(&entry, false).is_empty());
+
+        let env = launch_environment(&entry, true);
+        for (key, value) in DISCRETE_GPU_ENV {
+            assert!(
+                env.contains(&(key.to_string(), value.to_string())),
+                "missing {key}"
+            );
+        }
+
+        // Startup notification is kept alongside the GPU variables
+        let env = launch_environment(&entry.with_startup_notify(true), true);
+        assert!(env.iter().any(|(key, _)| key == "DESKTOP_STARTUP_ID"));
+        assert!(env.iter().any(|(key, _)| key == "DRI_PRIME"));
+    }
+
+    #[test]
+    fn test_nvidia_variables_need_the_driver() {
+        let keys = |nvidia| -> Vec<String> {
+            discrete_gpu_env(nvidia)
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect()
+        };
+        assert_eq!(keys(false), vec!["DRI_PRIME"]);
+        assert_eq!(
+            keys(true),
+            vec![
+                "DRI_PRIME",
+                "__NV_PRIME_RENDER_OFFLOAD",
+                "__GLX_VENDOR_LIBRARY_NAME"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_exec_with_target() {
+        assert_eq!(
+            parse_exec_with_target("firefox --new-tab %u", Some("https://example.com")),
+            vec!["firefox", "--new-tab", "https://example.com"]
+        );
+        assert_eq!(
+            parse_exec_with_target("app %i %F", Some("/tmp/a b.txt")),
+            vec!["app", "/tmp/a b.txt"]
+        );
+    }
+
+    #[test]
+    fn test_parse_exec_spec_quoting() {
+        // Values as written in the .desktop file, string escapes included
+        assert_eq!(parse_exec(r#"app "\\\\""#), vec!["app", r"\"]);
+        assert_eq!(parse_exec(r#"echo "\\$HOME""#), vec!["echo", "$HOME"]);
+        assert_eq!(parse_exec(r#"echo "\\`date\\`""#), vec!["echo", "`date`"]);
+        assert_eq!(
+            parse_exec(r#"app "say \"hi\"" "a > b; c | d" ~ "#),
+            vec!["app", r#"say "hi""#, "a > b; c | d", "~"]
+        );
+        assert_eq!(parse_exec(r#"app "" --flag"#), vec!["app", "", "--flag"]);
+    }
+
+    #[test]
+    fn test_unescape_value() {
+        assert_eq!(unescape_value(r"a\sb\tc"), "a b\tc");
+        assert_eq!(unescape_value(r"\\ \$ \q"), r"\ \$ \q");
+        assert_eq!(unescape_value("trailing\\"), "trailing\\");
+    }
+
+    #[test]
+    fn test_parse_exec_unquotes_before_field_codes() {
+        assert_eq!(
+            parse_exec_with_target(r#""/opt/My App/app" "--file=%f""#, Some("/tmp/x$1.txt")),
+            vec!["/opt/My App/app", "--file=/tmp/x$1.txt"]
+        );
+        assert_eq!(
+            parse_exec_with_target(r#"app "100%%" %u"#, Some("a b")),
+            vec!["app", "100%", "a b"]
+        );
+    }
+
+    #[test]
+    fn test_parse_exec_field_code_inside_quotes() {
+        assert_eq!(
+            parse_exec(r#"app "--open=%u" "%F""#),
+            vec!["app", "--open="]
+        );
+    }
 }
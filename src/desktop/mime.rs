@@ -0,0 +1,290 @@
+//! MIME type to default application associations.
+//!
+//! Combines `mimeapps.list` files with the `MimeType=` keys of installed
+//! desktop entries, following the XDG MIME applications spec: entries in
+//! `[Default Applications]` win, then `[Added Associations]`, then apps that
+//! declare the type themselves. `[Removed Associations]` hides declarations.
+
+use crate::desktop::entry::DesktopEntry;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+lazy_static::lazy_static! {
+    static ref MIME_INDEX: RwLock<MimeIndex> = RwLock::new(MimeIndex::default());
+}
+
+/// Associations parsed from a single `mimeapps.list` file.
+#[derive(Clone, Debug, Default)]
+pub struct MimeApps {
+    defaults: HashMap<String, Vec<String>>,
+    added: HashMap<String, Vec<String>>,
+    removed: HashMap<String, Vec<String>>,
+}
+
+impl MimeApps {
+    /// Parse the contents of a `mimeapps.list` file.
+    pub fn parse(content: &str) -> Self {
+        let mut apps = Self::default();
+        let mut section = Section::Other;
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = match name {
+                    "Default Applications" => Section::Defaults,
+                    "Added Associations" => Section::Added,
+                    "Removed Associations" => Section::Removed,
+                    _ => Section::Other,
+                };
+                continue;
+            }
+            let map = match section {
+                Section::Defaults => &mut apps.defaults,
+                Section::Added => &mut apps.added,
+                Section::Removed => &mut apps.removed,
+                Section::Other => continue,
+            };
+            let Some((mime, ids)) = line.split_once('=') else {
+                continue;
+            };
+            map.entry(mime.trim().to_string()).or_default().extend(
+                ids.split(';')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(normalize_id),
+            );
+        }
+
+        apps
+    }
+
+    /// Load all `mimeapps.list` files, highest precedence first.
+    pub fn load() -> Vec<Self> {
+        mimeapps_paths()
+            .into_iter()
+            .filter_map(|path| std::fs::read_to_string(path).ok())
+            .map(|content| Self::parse(&content))
+            .collect()
+    }
+}
+
+/// Sections of a `mimeapps.list` file.
+#[derive(Clone, Copy)]
+enum Section {
+    Defaults,
+    Added,
+    Removed,
+    Other,
+}
+
+/// Lookup table from MIME type to the applications that handle it.
+#[derive(Clone, Debug, Default)]
+pub struct MimeIndex {
+    associations: HashMap<String, Vec<String>>,
+    apps: HashMap<String, DesktopEntry>,
+}
+
+impl MimeIndex {
+    /// Build the index from installed entries and `mimeapps.list` files
+    /// ordered by precedence.
+    pub fn build(entries: &[DesktopEntry], lists: &[MimeApps]) -> Self {
+        let installed: HashSet<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+
+        let mut mime_types: HashSet<&str> = entries
+            .iter()
+            .flat_map(|e| e.mime_types.iter().map(String::as_str))
+            .collect();
+        for list in lists {
+            mime_types.extend(list.defaults.keys().map(String::as_str));
+            mime_types.extend(list.added.keys().map(String::as_str));
+        }
+
+        let mut associations = HashMap::new();
+        for mime in mime_types {
+            let mut candidates: Vec<String> = Vec::new();
+            let mut push = |id: &str| {
+                if installed.contains(id) && !candidates.iter().any(|c| c == id) {
+                    candidates.push(id.to_string());
+                }
+            };
+
+            for list in lists {
+                list.defaults
+                    .get(mime)
+                    .into_iter()
+                    .flatten()
+                    .for_each(|id| push(id));
+            }
+
+            // Removals only affect associations of lower precedence
+            let mut removed: HashSet<&str> = HashSet::new();
+            for list in lists {
+                for id in list.added.get(mime).into_iter().flatten() {
+                    if !removed.contains(id.as_str()) {
+                        push(id);
+                    }
+                }
+                removed.extend(
+                    list.removed
+                        .get(mime)
+                        .into_iter()
+                        .flatten()
+                        .map(String::as_str),
+                );
+            }
+
+            for entry in entries {
+                if entry.mime_types.iter().any(|m| m == mime)
+                    && !removed.contains(entry.id.as_str())
+                {
+                    push(&entry.id);
+                }
+            }
+
+            if !candidates.is_empty() {
+                associations.insert(mime.to_string(), candidates);
+            }
+        }
+
+        let apps = entries
+            .iter()
+            .filter(|e| associations.values().flatten().any(|id| *id == e.id))
+            .map(|e| (e.id.clone(), e.clone()))
+            .collect();
+
+        Self { associations, apps }
+    }
+
+    /// Get the ids of all applications handling a MIME type, preferred first.
+    pub fn apps_for(&self, mime: &str) -> &[String] {
+        self.associations.get(mime).map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the default application for a MIME type.
+    pub fn default_app(&self, mime: &str) -> Option<&DesktopEntry> {
+        self.apps_for(mime).first().and_then(|id| self.apps.get(id))
+    }
+}
+
+/// Replace the global MIME index.
+pub fn set_mime_index(index: MimeIndex) {
+    if let Ok(mut guard) = MIME_INDEX.write() {
+        *guard = index;
+    }
+}
+
+/// Get the default application for a MIME type from the global index.
+pub fn default_app_for_mime(mime: &str) -> Option<DesktopEntry> {
+    MIME_INDEX.read().ok()?.default_app(mime).cloned()
+}
+
+/// Strip the `.desktop` suffix so ids match `DesktopEntry::id`.
+fn normalize_id(id: &str) -> String {
+    id.strip_suffix(".desktop").unwrap_or(id).to_string()
+}
+
+/// `mimeapps.list` locations in order of precedence.
+fn mimeapps_paths() -> Vec<PathBuf> {
+    let desktops: Vec<String> = std::env::var("XDG_CURRENT_DESKTOP")
+        .map(|d| d.split(':').map(str::to_lowercase).collect())
+        .unwrap_or_default();
+    let mut dirs = Vec::new();
+
+    if let Some(config) = dirs::config_dir() {
+        dirs.push(config);
+    }
+    match std::env::var("XDG_CONFIG_DIRS") {
+        Ok(config_dirs) => dirs.extend(config_dirs.split(':').map(PathBuf::from)),
+        Err(_) => dirs.push(PathBuf::from("/etc/xdg")),
+    }
+    if let Some(data) = dirs::data_local_dir() {
+        dirs.push(data.join("applications"));
+    }
+    match std::env::var("XDG_DATA_DIRS") {
+        Ok(data_dirs) => dirs.extend(
+            data_dirs
+                .split(':')
+                .map(|dir| PathBuf::from(dir).join("applications")),
+        ),
+        Err(_) => {
+            dirs.push(PathBuf::from("/usr/local/share/applications"));
+            dirs.push(PathBuf::from("/usr/share/applications"));
+        }
+    }
+
+    dirs.iter()
+        .flat_map(|dir| {
+            desktops
+                .iter()
+                .map(|desktop| dir.join(format!("{desktop}-mimeapps.list")))
+                .chain(std::iter::once(dir.join("mimeapps.list")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, mime_types: &[&str]) -> DesktopEntry {
+        DesktopEntry::new(
+            id.to_string(),
+            id.to_string(),
+            id.to_string(),
+            None,
+            None,
+            None,
+            vec![],
+            false,
+            PathBuf::from(format!("/usr/share/applications/{id}.desktop")),
+        )
+        .with_mime_types(mime_types.iter().map(|m| m.to_string()).collect())
+    }
+
+    #[test]
+    fn test_declared_mime_type() {
+        let entries = vec![entry("evince", &["application/pdf"])];
+        let index = MimeIndex::build(&entries, &[]);
+        assert_eq!(index.default_app("application/pdf").unwrap().id, "evince");
+        assert!(index.default_app("image/png").is_none());
+    }
+
+    #[test]
+    fn test_hidden_scheme_handler() {
+        let mut handler = entry("steam-handler", &["x-scheme-handler/steam"]);
+        handler.hidden = true;
+        let index = MimeIndex::build(&[handler], &[]);
+        assert_eq!(
+            index.default_app("x-scheme-handler/steam").unwrap().id,
+            "steam-handler"
+        );
+    }
+
+    #[test]
+    fn test_mimeapps_default_takes_precedence() {
+        let entries = vec![
+            entry("evince", &["application/pdf"]),
+            entry("zathura", &["application/pdf"]),
+        ];
+        let user = MimeApps::parse(
+            "[Default Applications]\napplication/pdf=missing.desktop;zathura.desktop;\n",
+        );
+        let index = MimeIndex::build(&entries, &[user]);
+        assert_eq!(index.apps_for("application/pdf"), ["zathura", "evince"]);
+        assert_eq!(index.default_app("application/pdf").unwrap().id, "zathura");
+    }
+
+    #[test]
+    fn test_added_and_removed_associations() {
+        let entries = vec![entry("evince", &["application/pdf"]), entry("okular", &[])];
+        let user = MimeApps::parse(
+            "[Added Associations]\napplication/pdf=okular.desktop\n\
+             [Removed Associations]\napplication/pdf=evince.desktop\n",
+        );
+        let index = MimeIndex::build(&entries, &[user]);
+        assert_eq!(index.apps_for("application/pdf"), ["okular"]);
+    }
+}
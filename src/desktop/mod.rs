@@ -2,12 +2,21 @@ pub mod cache;
 pub mod entry;
 pub mod env;
 pub mod exec;
+pub mod mime;
 pub mod parser;
 pub mod scanner;
+pub mod startup;
 pub mod watcher;
 
-pub use cache::load_applications;
+pub use cache::{
+    load_all_applications, load_all_applications_without_icons, load_applications,
+    resolve_missing_icons,
+};
 pub use entry::DesktopEntry;
 pub use env::{capture_session_environment, get_session_environment};
-pub use exec::launch_application;
-pub use scanner::scan_applications;
+pub use exec::{
+    DISCRETE_GPU_ENV, NVIDIA_PRIME_ENV, edit_desktop_file, launch_application,
+    launch_application_on_gpu, launch_application_with, open_url,
+};
+pub use mime::{MimeApps, MimeIndex, default_app_for_mime};
+pub use scanner::{find_application, scan_applications};
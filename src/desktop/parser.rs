@@ -10,10 +10,6 @@ pub fn parse_desktop_file(path: &Path) -> Option<DesktopEntry> {
     let name = fd_entry.name(locales)?.to_string();
     let exec = fd_entry.exec()?.to_string();
 
-    if fd_entry.no_display() {
-        return None;
-    }
-
     let id = path
         .file_stem()
         .and_then(|s| s.to_str())
@@ -22,6 +18,7 @@ pub fn parse_desktop_file(path: &Path) -> Option<DesktopEntry> {
 
     let icon = fd_entry.icon().map(|s| s.to_string());
     let comment = fd_entry.comment(locales).map(|s| s.to_string());
+    let generic_name = fd_entry.generic_name(locales).map(|s| s.to_string());
 
     let categories: Vec<String> = fd_entry
         .categories()
@@ -29,17 +26,32 @@ pub fn parse_desktop_file(path: &Path) -> Option<DesktopEntry> {
         .unwrap_or_default();
 
     let terminal = fd_entry.terminal();
+    let startup_notify = fd_entry.startup_notify();
+    let mime_types: Vec<String> = fd_entry
+        .mime_type()
+        .map(|types| types.into_iter().map(|t| t.to_string()).collect())
+        .unwrap_or_default();
+    let startup_wm_class = fd_entry.startup_wm_class().map(|s| s.to_string());
+    // Kept so they can be shown on request; the scanner filters them by default
+    let hidden = fd_entry.no_display() || fd_entry.hidden();
 
     // icon_path is resolved later in cache.rs after all entries are loaded
-    Some(DesktopEntry::new(
-        id,
-        name,
-        exec,
-        icon,
-        None,
-        comment,
-        categories,
-        terminal,
-        path.to_path_buf(),
-    ))
+    Some(
+        DesktopEntry::new(
+            id,
+            name,
+            exec,
+            icon,
+            None,
+            comment,
+            categories,
+            terminal,
+            path.to_path_buf(),
+        )
+        .with_startup_notify(startup_notify)
+        .with_mime_types(mime_types)
+        .with_startup_wm_class(startup_wm_class)
+        .with_hidden(hidden)
+        .with_generic_name(generic_name),
+    )
 }
@@ -1,58 +1,413 @@
 use crate::desktop::entry::DesktopEntry;
 use crate::desktop::parser::parse_desktop_file;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use crate::error::UnknownApplicationError;
+use crate::ui::icon::resolve_icon_path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 pub fn scan_applications() -> Vec<DesktopEntry> {
-    let dirs = get_xdg_application_dirs();
-    let mut entries: HashMap<String, DesktopEntry> = HashMap::new();
+    let mut entries = scan_dirs(
+        &application_dirs(),
+        crate::config::config().show_hidden_entries,
+    );
+    resolve_icons(&mut entries);
+    entries
+}
+
+/// Upper bound on threads used to parse desktop files.
+const MAX_SCAN_THREADS: usize = 8;
+
+/// Scan application directories in precedence order.
+///
+/// Entries are deduplicated by desktop file id; the first directory that
+/// contains an id wins, even if that file is hidden or fails to parse.
+/// NoDisplay/Hidden entries are only returned with `include_hidden`.
+pub fn scan_dirs(dirs: &[PathBuf], include_hidden: bool) -> Vec<DesktopEntry> {
+    scan_dirs_reusing(dirs, include_hidden, &|_| None)
+}
+
+/// Like [`scan_dirs`], but takes entries from `reuse` instead of parsing
+/// the file when it returns one (e.g. unchanged files from the cache).
+///
+/// Files are parsed on several threads. Icons are left to
+/// [`resolve_icons`], except for reused entries that already have one.
+pub fn scan_dirs_reusing(
+    dirs: &[PathBuf],
+    include_hidden: bool,
+    reuse: &(dyn Fn(&Path) -> Option<DesktopEntry> + Sync),
+) -> Vec<DesktopEntry> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut candidates: Vec<(String, PathBuf)> = Vec::new();
 
     for dir in dirs {
-        scan_directory(&dir, &mut entries);
+        collect_candidates(dir, "", &mut seen, &mut candidates);
     }
 
-    let mut result: Vec<DesktopEntry> = entries.into_values().collect();
+    let mut result: Vec<DesktopEntry> = parse_candidates(&candidates, scan_threads(), reuse)
+        .into_iter()
+        .filter(|entry| include_hidden || !entry.hidden)
+        .collect();
     result.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
     result
 }
 
-fn get_xdg_application_dirs() -> Vec<PathBuf> {
-    let mut dirs = Vec::new();
+/// Parse candidates on up to `threads` threads.
+///
+/// Each thread takes a contiguous chunk and results are joined in chunk
+/// order, so the output matches a sequential parse.
+fn parse_candidates(
+    candidates: &[(String, PathBuf)],
+    threads: usize,
+    reuse: &(dyn Fn(&Path) -> Option<DesktopEntry> + Sync),
+) -> Vec<DesktopEntry> {
+    let chunk_size = candidates.len().div_ceil(threads.max(1)).max(1);
 
-    if let Some(data_home) = dirs::data_local_dir() {
-        dirs.push(data_home.join("applications"));
-    }
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = candidates
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter_map(|(id, path)| parse_candidate(id, path, reuse))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+/// Parse one desktop file (or reuse it).
+fn parse_candidate(
+    id: &str,
+    path: &Path,
+    reuse: &(dyn Fn(&Path) -> Option<DesktopEntry> + Sync),
+) -> Option<DesktopEntry> {
+    let mut entry = reuse(path).or_else(|| parse_desktop_file(path))?;
+    entry.id = id.to_string();
+    Some(entry)
+}
+
+/// Whether an entry names an icon that hasn't been resolved to a file.
+pub fn needs_icon(entry: &DesktopEntry) -> bool {
+    entry.icon.is_some() && entry.icon_path.is_none()
+}
+
+/// Resolve the icons of entries that [need one](needs_icon), on several
+/// threads. Returns whether any icon was found.
+pub fn resolve_icons(entries: &mut [DesktopEntry]) -> bool {
+    let chunk_size = entries.len().div_ceil(scan_threads()).max(1);
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = entries
+            .chunks_mut(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut found = false;
+                    for entry in chunk.iter_mut().filter(|entry| needs_icon(entry)) {
+                        entry.icon_path = entry.icon.as_deref().and_then(resolve_icon_path);
+                        found |= entry.icon_path.is_some();
+                    }
+                    found
+                })
+            })
+            .collect();
+
+        workers.into_iter().fold(false, |found, worker| {
+            worker.join().unwrap_or(false) || found
+        })
+    })
+}
 
-    if let Ok(xdg_dirs) = std::env::var("XDG_DATA_DIRS") {
-        for dir in xdg_dirs.split(':') {
-            dirs.push(PathBuf::from(dir).join("applications"));
+/// Threads used to parse desktop files and resolve icons.
+fn scan_threads() -> usize {
+    std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_SCAN_THREADS)
+}
+
+/// Get the XDG application directories, highest precedence first.
+///
+/// `$XDG_DATA_HOME/applications` comes first, followed by `applications/`
+/// under every `$XDG_DATA_DIRS` entry.
+pub fn application_dirs() -> Vec<PathBuf> {
+    application_dirs_from(
+        dirs::data_local_dir(),
+        std::env::var("XDG_DATA_DIRS").ok().as_deref(),
+    )
+}
+
+/// Find an entry by desktop file id, with or without the `.desktop` suffix.
+pub fn find_application<'a>(
+    entries: &'a [DesktopEntry],
+    id: &str,
+) -> Result<&'a DesktopEntry, UnknownApplicationError> {
+    let id = id.strip_suffix(".desktop").unwrap_or(id);
+    entries
+        .iter()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| UnknownApplicationError(id.to_string()))
+}
+
+fn application_dirs_from(data_home: Option<PathBuf>, data_dirs: Option<&str>) -> Vec<PathBuf> {
+    // An unset or empty XDG_DATA_DIRS falls back to the spec default
+    let data_dirs = match data_dirs {
+        Some(dirs) if !dirs.trim().is_empty() => dirs,
+        _ => "/usr/local/share:/usr/share",
+    };
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    let candidates = data_home.into_iter().chain(
+        data_dirs
+            .split(':')
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from),
+    );
+    for dir in candidates {
+        let dir = dir.join("applications");
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
         }
-    } else {
-        dirs.push(PathBuf::from("/usr/local/share/applications"));
-        dirs.push(PathBuf::from("/usr/share/applications"));
     }
-
     dirs
 }
 
-fn scan_directory(dir: &PathBuf, entries: &mut HashMap<String, DesktopEntry>) {
+/// Collect desktop files of a directory recursively, skipping ids already
+/// seen. `prefix` is the desktop file id prefix for subdirectories
+/// (`kde/foo.desktop` has the id `kde-foo`).
+fn collect_candidates(
+    dir: &Path,
+    prefix: &str,
+    seen: &mut HashSet<String>,
+    candidates: &mut Vec<(String, PathBuf)>,
+) {
     let Ok(read_dir) = std::fs::read_dir(dir) else {
         return;
     };
 
     for entry in read_dir.flatten() {
         let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
 
         if path.is_dir() {
-            scan_directory(&path, entries);
+            let prefix = format!("{prefix}{file_name}-");
+            collect_candidates(&path, &prefix, seen, candidates);
             continue;
         }
 
-        if path.extension().is_some_and(|ext| ext == "desktop")
-            && let Some(desktop_entry) = parse_desktop_file(&path)
-            && !entries.contains_key(&desktop_entry.id)
-        {
-            entries.insert(desktop_entry.id.clone(), desktop_entry);
+        let Some(stem) = file_name.strip_suffix(".desktop") else {
+            continue;
+        };
+        let id = format!("{prefix}{stem}");
+        if seen.insert(id.clone()) {
+            candidates.push((id, path));
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    fn write_entry(dir: &Path, file: &str, name: &str, extra: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            path,
+            format!("[Desktop Entry]\nType=Application\nName={name}\nExec=true\n{extra}"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_application_dirs_from() {
+        let dirs = application_dirs_from(
+            Some(PathBuf::from("/home/u/.local/share")),
+            Some("/nix/profile/share::/usr/share:/usr/share"),
+        );
+        assert_eq!(
+            dirs,
+            vec![
+                PathBuf::from("/home/u/.local/share/applications"),
+                PathBuf::from("/nix/profile/share/applications"),
+                PathBuf::from("/usr/share/applications"),
+            ]
+        );
+
+        let dirs = application_dirs_from(None, Some(""));
+        assert_eq!(
+            dirs,
+            vec![
+                PathBuf::from("/usr/local/share/applications"),
+                PathBuf::from("/usr/share/applications"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_dirs_dedups_by_id_with_precedence() {
+        let root = TempDir::new("scanner");
+        let first = root.join("first");
+        let second = root.join("second");
+        write_entry(&first, "editor.desktop", "Editor (user)", "");
+        write_entry(&second, "editor.desktop", "Editor (system)", "");
+        write_entry(&first, "hidden.desktop", "Hidden", "NoDisplay=true\n");
+        write_entry(&second, "hidden.desktop", "Not Hidden", "");
+        write_entry(&second, "kde/viewer.desktop", "Viewer", "");
+
+        let entries = scan_dirs(&[first, second], false);
+
+        let names: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|e| (e.id.as_str(), e.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("editor", "Editor (user)"), ("kde-viewer", "Viewer")]
+        );
+    }
+
+    #[test]
+    fn test_find_application() {
+        let dir = TempDir::new("scanner-find");
+        write_entry(&dir, "editor.desktop", "Editor", "");
+        write_entry(&dir, "kde/viewer.desktop", "Viewer", "");
+
+        let entries = scan_dirs(std::slice::from_ref(&*dir), false);
+
+        assert_eq!(find_application(&entries, "editor").unwrap().name, "Editor");
+        assert_eq!(
+            find_application(&entries, "kde-viewer.desktop")
+                .unwrap()
+                .name,
+            "Viewer"
+        );
+        let err = find_application(&entries, "missing").unwrap_err();
+        assert_eq!(err.to_string(), "No application with id 'missing'");
+    }
+
+    #[test]
+    fn test_application_item_keeps_desktop_path() {
+        let dir = TempDir::new("scanner-path");
+        write_entry(&dir, "kde/editor.desktop", "Editor", "");
+
+        let entries = scan_dirs(std::slice::from_ref(&*dir), false);
+
+        let app = crate::items::ApplicationItem::from(&entries[0]);
+        assert_eq!(app.desktop_path, dir.join("kde/editor.desktop"));
+    }
+
+    #[test]
+    fn test_scan_dirs_includes_hidden_only_when_requested() {
+        let dir = TempDir::new("scanner-hidden");
+        write_entry(&dir, "shown.desktop", "Shown", "");
+        write_entry(&dir, "nodisplay.desktop", "No Display", "NoDisplay=true\n");
+        write_entry(&dir, "hidden.desktop", "Hidden", "Hidden=true\n");
+
+        let visible = scan_dirs(std::slice::from_ref(&*dir), false);
+        let all = scan_dirs(std::slice::from_ref(&*dir), true);
+
+        let ids = |entries: &[DesktopEntry]| -> Vec<String> {
+            entries.iter().map(|e| e.id.clone()).collect()
+        };
+        assert_eq!(ids(&visible), vec!["shown"]);
+        assert_eq!(ids(&all), vec!["hidden", "nodisplay", "shown"]);
+        assert!(all.iter().filter(|e| e.id != "shown").all(|e| e.hidden));
+    }
+
+    #[test]
+    fn test_parallel_parse_matches_sequential() {
+        let dir = TempDir::new("scanner-parallel");
+        let mut candidates = Vec::new();
+        for i in 0..37 {
+            let file = format!("app{i}.desktop");
+            write_entry(&dir, &file, &format!("App {i}"), "");
+            candidates.push((format!("app{i}"), dir.join(file)));
+        }
+        // A file that fails to parse is skipped without shifting the rest
+        candidates.insert(5, ("missing".to_string(), dir.join("missing.desktop")));
+
+        let ids = |threads| -> Vec<String> {
+            parse_candidates(&candidates, threads, &|_| None)
+                .into_iter()
+                .map(|entry| entry.id)
+                .collect()
+        };
+        let sequential = ids(1);
+        let parallel = ids(4);
+        let many = ids(64);
+
+        assert_eq!(sequential.len(), 37);
+        assert_eq!(
+            sequential[..6],
+            ["app0", "app1", "app2", "app3", "app4", "app5"]
+        );
+        assert_eq!(parallel, sequential);
+        assert_eq!(many, sequential);
+    }
+
+    #[test]
+    fn test_scan_reuses_entries() {
+        let dir = TempDir::new("scanner-reuse");
+        write_entry(&dir, "editor.desktop", "Editor", "");
+        write_entry(&dir, "viewer.desktop", "Viewer", "");
+
+        let reused = dir.join("editor.desktop");
+        let entries = scan_dirs_reusing(std::slice::from_ref(&*dir), false, &|path| {
+            (path == reused).then(|| {
+                DesktopEntry::new(
+                    "cached".to_string(),
+                    "Editor (cached)".to_string(),
+                    "true".to_string(),
+                    None,
+                    None,
+                    None,
+                    vec![],
+                    false,
+                    path.to_path_buf(),
+                )
+            })
+        });
+
+        let names: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|e| (e.id.as_str(), e.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("editor", "Editor (cached)"), ("viewer", "Viewer")]
+        );
+    }
+
+    #[test]
+    fn test_icons_resolved_after_scan() {
+        let dir = TempDir::new("scanner-icons");
+        let icon = dir.join("editor.svg");
+        write_entry(
+            &dir,
+            "editor.desktop",
+            "Editor",
+            &format!("Icon={}\n", icon.display()),
+        );
+        write_entry(&dir, "plain.desktop", "Plain", "");
+        std::fs::write(&icon, "<svg/>").unwrap();
+
+        let mut entries = scan_dirs(std::slice::from_ref(&*dir), false);
+        let pending: Vec<bool> = entries.iter().map(needs_icon).collect();
+        let found = resolve_icons(&mut entries);
+
+        // The scan leaves icons alone; entries without one never need it
+        assert_eq!(pending, vec![true, false]);
+        assert!(found);
+        assert_eq!(entries[0].icon_path.as_deref(), Some(icon.as_path()));
+        assert!(!entries.iter().any(needs_icon));
+    }
+}
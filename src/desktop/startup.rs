@@ -0,0 +1,93 @@
+//! Startup notification support for launched applications.
+//!
+//! Applications declaring `StartupNotify=true` receive a launch id in
+//! `DESKTOP_STARTUP_ID` (X11 startup-notification). Desktops that recognise
+//! it show launch feedback; others simply ignore it.
+//!
+//! `XDG_ACTIVATION_TOKEN` is left unset: Wayland compositors only accept
+//! tokens they issued through xdg-activation, and a made-up one can keep the
+//! new window from being focused.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Prefix identifying tokens generated by the launcher.
+const TOKEN_PREFIX: &str = "zlaunch";
+
+/// Sequence number keeping tokens unique within a single process.
+static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+
+/// Generate a startup notification id for the given application.
+///
+/// Follows the startup-notification spec format
+/// `<launcher>-<pid>-<app>-<sequence>_TIME<timestamp>`, with the app id
+/// reduced to characters that are safe in the token.
+pub fn generate_startup_id(app_id: &str) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u32)
+        .unwrap_or(0);
+    format_startup_id(
+        app_id,
+        std::process::id(),
+        SEQUENCE.fetch_add(1, Ordering::Relaxed),
+        timestamp,
+    )
+}
+
+fn format_startup_id(app_id: &str, pid: u32, sequence: u32, timestamp: u32) -> String {
+    let app: String = app_id
+        .trim_end_matches(".desktop")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{TOKEN_PREFIX}-{pid}-{app}-{sequence}_TIME{timestamp}")
+}
+
+/// Environment variables announcing a new launch to the desktop.
+pub fn startup_environment(app_id: &str) -> Vec<(String, String)> {
+    vec![(
+        "DESKTOP_STARTUP_ID".to_string(),
+        generate_startup_id(app_id),
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_startup_id() {
+        assert_eq!(
+            format_startup_id("org.gnome.Nautilus.desktop", 42, 3, 1000),
+            "zlaunch-42-org.gnome.Nautilus-3_TIME1000"
+        );
+        assert_eq!(
+            format_startup_id("my app/v2", 1, 0, 0),
+            "zlaunch-1-my_app_v2-0_TIME0"
+        );
+    }
+
+    #[test]
+    fn test_generate_startup_id_unique() {
+        let first = generate_startup_id("firefox");
+        let second = generate_startup_id("firefox");
+        assert_ne!(first, second);
+        assert!(first.starts_with(&format!("zlaunch-{}-firefox-", std::process::id())));
+        assert!(first.contains("_TIME"));
+        assert!(!first.contains(char::is_whitespace));
+    }
+
+    #[test]
+    fn test_startup_environment_has_no_activation_token() {
+        let env = startup_environment("firefox");
+        let keys: Vec<&str> = env.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["DESKTOP_STARTUP_ID"]);
+    }
+}
@@ -3,6 +3,7 @@
 //! Watches XDG application directories for changes and emits events
 //! when applications are added, removed, or modified.
 
+use crate::desktop::scanner::application_dirs;
 use flume::{Receiver, TryRecvError};
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::path::PathBuf;
@@ -50,7 +51,7 @@ impl ApplicationWatcher {
         })?;
 
         // Watch all XDG application directories
-        for dir in get_xdg_application_dirs() {
+        for dir in application_dirs() {
             if dir.exists() {
                 match watcher.watch(&dir, RecursiveMode::Recursive) {
                     Ok(()) => {
@@ -154,33 +155,13 @@ impl ApplicationWatcher {
     }
 }
 
-/// Get the list of XDG application directories to watch.
-fn get_xdg_application_dirs() -> Vec<PathBuf> {
-    let mut dirs = Vec::new();
-
-    if let Some(data_home) = dirs::data_local_dir() {
-        dirs.push(data_home.join("applications"));
-    }
-
-    if let Ok(xdg_dirs) = std::env::var("XDG_DATA_DIRS") {
-        for dir in xdg_dirs.split(':') {
-            dirs.push(PathBuf::from(dir).join("applications"));
-        }
-    } else {
-        dirs.push(PathBuf::from("/usr/local/share/applications"));
-        dirs.push(PathBuf::from("/usr/share/applications"));
-    }
-
-    dirs
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_get_xdg_dirs() {
-        let dirs = get_xdg_application_dirs();
+        let dirs = application_dirs();
         // Should have at least the local dir and system dirs
         assert!(!dirs.is_empty());
     }
@@ -1,4 +1,5 @@
 use crate::emoji::EmojiItem;
+use crate::emoji::usage::{EmojiUsage, emoji_usage};
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use lazy_static::lazy_static;
@@ -11,24 +12,140 @@ lazy_static! {
 /// Load all emojis from the emojis crate.
 fn load_all_emojis() -> Vec<EmojiItem> {
     emojis::iter()
-        .map(|emoji| EmojiItem::new(emoji.as_str(), emoji.name()))
+        .map(|emoji| {
+            EmojiItem::new(emoji.as_str(), emoji.name())
+                .with_shortcode(emoji.shortcode())
+                .with_variation_selector(needs_variation_selector(emoji.as_str()))
+        })
         .collect()
 }
 
+/// Code point ranges with `Emoji_Presentation=Yes` (Unicode emoji-data.txt).
+/// These render as emoji on their own and must not get VS16.
+const EMOJI_PRESENTATION: &[(u32, u32)] = &[
+    (0x231A, 0x231B),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F1E6, 0x1F1FF),
+    (0x1F201, 0x1F201),
+    (0x1F21A, 0x1F21A),
+    (0x1F22F, 0x1F22F),
+    (0x1F232, 0x1F236),
+    (0x1F238, 0x1F23A),
+    (0x1F250, 0x1F251),
+    (0x1F300, 0x1F320),
+    (0x1F32D, 0x1F335),
+    (0x1F337, 0x1F37C),
+    (0x1F37E, 0x1F393),
+    (0x1F3A0, 0x1F3CA),
+    (0x1F3CF, 0x1F3D3),
+    (0x1F3E0, 0x1F3F0),
+    (0x1F3F4, 0x1F3F4),
+    (0x1F3F8, 0x1F43E),
+    (0x1F440, 0x1F440),
+    (0x1F442, 0x1F4FC),
+    (0x1F4FF, 0x1F53D),
+    (0x1F54B, 0x1F54E),
+    (0x1F550, 0x1F567),
+    (0x1F57A, 0x1F57A),
+    (0x1F595, 0x1F596),
+    (0x1F5A4, 0x1F5A4),
+    (0x1F5FB, 0x1F64F),
+    (0x1F680, 0x1F6C5),
+    (0x1F6CC, 0x1F6CC),
+    (0x1F6D0, 0x1F6D2),
+    (0x1F6D5, 0x1F6D7),
+    (0x1F6DC, 0x1F6DF),
+    (0x1F6EB, 0x1F6EC),
+    (0x1F6F4, 0x1F6FC),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F7F0, 0x1F7F0),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1F9FF),
+    (0x1FA70, 0x1FA7C),
+    (0x1FA80, 0x1FA88),
+    (0x1FA90, 0x1FABD),
+    (0x1FABF, 0x1FAC5),
+    (0x1FACE, 0x1FADB),
+    (0x1FAE0, 0x1FAE8),
+    (0x1FAF0, 0x1FAF8),
+];
+
+/// Whether an emoji defaults to text presentation without VS16.
+///
+/// Single emoji without `Emoji_Presentation` (e.g. ❤, ☀, 🌡) render as
+/// monochrome text in many fonts unless the selector follows them.
+fn needs_variation_selector(emoji: &str) -> bool {
+    let mut chars = emoji.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => !has_emoji_presentation(c),
+        _ => false,
+    }
+}
+
+/// Whether a character renders as emoji by default.
+fn has_emoji_presentation(c: char) -> bool {
+    let c = c as u32;
+    EMOJI_PRESENTATION
+        .iter()
+        .any(|&(start, end)| (start..=end).contains(&c))
+}
+
 /// Get all emojis.
 pub fn all_emojis() -> &'static [EmojiItem] {
     &ALL_EMOJIS
 }
 
 /// Search emojis by name using fuzzy matching.
-/// Returns indices into the all_emojis() slice, sorted by match score.
+/// Returns indices into the all_emojis() slice, most used first, then by
+/// match score.
 pub fn search_emojis(query: &str) -> Vec<usize> {
+    search_emojis_with(query, &emoji_usage())
+}
+
+/// Search emojis, ranking by the given usage statistics.
+pub fn search_emojis_with(query: &str, usage: &EmojiUsage) -> Vec<usize> {
     if query.is_empty() {
         return (0..ALL_EMOJIS.len()).collect();
     }
 
     let matcher = SkimMatcherV2::default();
-    let mut scored: Vec<(usize, i64)> = ALL_EMOJIS
+    let scored: Vec<(usize, i64)> = ALL_EMOJIS
         .iter()
         .enumerate()
         .filter_map(|(idx, item)| {
@@ -38,7 +155,82 @@ pub fn search_emojis(query: &str) -> Vec<usize> {
         })
         .collect();
 
-    // Sort by score descending
-    scored.sort_by(|a, b| b.1.cmp(&a.1));
+    rank_matches(&ALL_EMOJIS, scored, usage)
+}
+
+/// Order matches by usage count, then match score, then dataset order.
+/// Takes `(index, score)` pairs and returns the sorted indices.
+pub fn rank_matches(
+    items: &[EmojiItem],
+    mut scored: Vec<(usize, i64)>,
+    usage: &EmojiUsage,
+) -> Vec<usize> {
+    scored.sort_by(|a, b| {
+        let count = |idx: usize| usage.count(&items[idx].emoji);
+        count(b.0)
+            .cmp(&count(a.0))
+            .then_with(|| b.1.cmp(&a.1))
+            .then_with(|| a.0.cmp(&b.0))
+    });
     scored.into_iter().map(|(idx, _)| idx).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_variation_selector() {
+        assert!(needs_variation_selector("\u{2764}"));
+        assert!(needs_variation_selector("\u{2600}"));
+        assert!(!needs_variation_selector("\u{2764}\u{FE0F}"));
+        assert!(!needs_variation_selector("👍"));
+        assert!(!needs_variation_selector("🇯🇵"));
+    }
+
+    #[test]
+    fn test_variation_selector_only_for_text_default() {
+        // ⭐ and ⚡ are emoji-presentation already
+        assert!(!needs_variation_selector("\u{2B50}"));
+        assert!(!needs_variation_selector("\u{26A1}"));
+        // ❤ and 🌡 default to text
+        assert!(needs_variation_selector("\u{2764}"));
+        assert!(needs_variation_selector("\u{1F321}"));
+    }
+
+    #[test]
+    fn test_rank_matches_by_frequency() {
+        let items = vec![
+            EmojiItem::new("😺", "grinning cat"),
+            EmojiItem::new("🐈", "cat"),
+            EmojiItem::new("🐱", "cat face"),
+        ];
+        let scored = vec![(0, 50), (1, 90), (2, 70)];
+
+        // Without usage, match quality decides
+        let ranked = rank_matches(&items, scored.clone(), &EmojiUsage::default());
+        assert_eq!(ranked, vec![1, 2, 0]);
+
+        let mut usage = EmojiUsage::default();
+        usage.record("😺");
+        usage.record("😺");
+        usage.record("🐱");
+        assert_eq!(rank_matches(&items, scored, &usage), vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_sequences_are_loaded_whole() {
+        let flag = "\u{1F1EF}\u{1F1F5}";
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+        for sequence in [flag, family] {
+            assert!(all_emojis().iter().any(|item| item.emoji == sequence));
+        }
+    }
+
+    #[test]
+    fn test_rank_matches_deterministic_tiebreak() {
+        let items = vec![EmojiItem::new("🍎", "apple"), EmojiItem::new("🍏", "apple")];
+        let ranked = rank_matches(&items, vec![(1, 10), (0, 10)], &EmojiUsage::default());
+        assert_eq!(ranked, vec![0, 1]);
+    }
+}
@@ -1,3 +1,8 @@
+use crate::config::EmojiCopyFormat;
+
+/// Variation selector-16, requesting emoji (rather than text) presentation.
+pub const EMOJI_VARIATION_SELECTOR: char = '\u{FE0F}';
+
 /// An emoji item for display in the emoji picker grid.
 #[derive(Clone, Debug)]
 pub struct EmojiItem {
@@ -5,6 +10,10 @@ pub struct EmojiItem {
     pub emoji: String,
     /// The display name of the emoji.
     pub name: String,
+    /// The canonical `:shortcode:` (without colons), if the emoji has one.
+    pub shortcode: Option<String>,
+    /// Whether the glyph needs a trailing VS16 to render as emoji.
+    pub variation_selector: bool,
 }
 
 impl EmojiItem {
@@ -13,6 +22,93 @@ impl EmojiItem {
         Self {
             emoji: emoji.into(),
             name: name.into(),
+            shortcode: None,
+            variation_selector: false,
+        }
+    }
+
+    /// Set the canonical shortcode.
+    pub fn with_shortcode(mut self, shortcode: Option<impl Into<String>>) -> Self {
+        self.shortcode = shortcode.map(Into::into);
+        self
+    }
+
+    /// Set whether the glyph needs the emoji variation selector.
+    pub fn with_variation_selector(mut self, variation_selector: bool) -> Self {
+        self.variation_selector = variation_selector;
+        self
+    }
+
+    /// The glyph as shown and copied, with VS16 appended if needed.
+    pub fn glyph(&self) -> String {
+        if self.variation_selector && !self.emoji.ends_with(EMOJI_VARIATION_SELECTOR) {
+            format!("{}{}", self.emoji, EMOJI_VARIATION_SELECTOR)
+        } else {
+            self.emoji.clone()
+        }
+    }
+
+    /// Get the text to copy to clipboard.
+    ///
+    /// Primary confirm copies the glyph, secondary confirm copies the
+    /// configured text. Emojis without a shortcode fall back to their name.
+    pub fn text_for_clipboard(&self, secondary: bool, format: EmojiCopyFormat) -> String {
+        match (secondary, format, &self.shortcode) {
+            (false, _, _) => self.glyph(),
+            (true, EmojiCopyFormat::Shortcode, Some(code)) => format!(":{}:", code),
+            (true, _, _) => self.name.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_for_clipboard() {
+        let item = EmojiItem::new("👍", "thumbs up").with_shortcode(Some("+1"));
+        assert_eq!(
+            item.text_for_clipboard(false, EmojiCopyFormat::Shortcode),
+            "👍"
+        );
+        assert_eq!(
+            item.text_for_clipboard(true, EmojiCopyFormat::Shortcode),
+            ":+1:"
+        );
+        assert_eq!(
+            item.text_for_clipboard(true, EmojiCopyFormat::Name),
+            "thumbs up"
+        );
+    }
+
+    #[test]
+    fn test_variation_selector_is_appended() {
+        let heart = EmojiItem::new("\u{2764}", "red heart").with_variation_selector(true);
+        assert_eq!(
+            heart.text_for_clipboard(false, EmojiCopyFormat::Shortcode),
+            "\u{2764}\u{FE0F}"
+        );
+        assert_eq!(heart.glyph(), "\u{2764}\u{FE0F}");
+
+        // Not doubled if the dataset already has it
+        let qualified =
+            EmojiItem::new("\u{2764}\u{FE0F}", "red heart").with_variation_selector(true);
+        assert_eq!(qualified.glyph(), "\u{2764}\u{FE0F}");
+
+        let thumbs = EmojiItem::new("👍", "thumbs up");
+        assert_eq!(
+            thumbs.text_for_clipboard(false, EmojiCopyFormat::Shortcode),
+            "👍"
+        );
+    }
+
+    #[test]
+    fn test_text_for_clipboard_without_shortcode() {
+        let item = EmojiItem::new("🫠", "melting face");
+        assert_eq!(
+            item.text_for_clipboard(true, EmojiCopyFormat::Shortcode),
+            "melting face"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 mod data;
 mod item;
+pub mod usage;
 
-pub use data::{all_emojis, search_emojis};
-pub use item::EmojiItem;
+pub use data::{all_emojis, rank_matches, search_emojis, search_emojis_with};
+pub use item::{EMOJI_VARIATION_SELECTOR, EmojiItem};
+pub use usage::{EmojiUsage, emoji_usage, record_emoji_usage};
@@ -0,0 +1,70 @@
+//! Persistent emoji usage store.
+//!
+//! Records how often and when each emoji was copied. Search ranking uses
+//! the counts and the timestamps allow listing recently used emoji.
+
+use crate::store::PersistedStore;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+lazy_static! {
+    /// Usage store shared by the emoji picker.
+    static ref USAGE: PersistedStore<EmojiUsage> = PersistedStore::open("emoji_usage.json");
+}
+
+/// Usage statistics for a single emoji.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageEntry {
+    /// How often the emoji was copied.
+    pub count: u32,
+    /// When the emoji was last copied (seconds since the epoch).
+    pub last_used: u64,
+}
+
+/// Usage statistics keyed by emoji glyph.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EmojiUsage {
+    entries: HashMap<String, UsageEntry>,
+}
+
+impl EmojiUsage {
+    /// Record one use of an emoji.
+    pub fn record(&mut self, emoji: &str) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let entry = self.entries.entry(emoji.to_string()).or_default();
+        entry.count = entry.count.saturating_add(1);
+        entry.last_used = now;
+    }
+
+    /// How often an emoji was used.
+    pub fn count(&self, emoji: &str) -> u32 {
+        self.entries.get(emoji).map_or(0, |e| e.count)
+    }
+
+    /// The most recently used emoji, newest first.
+    pub fn recent(&self, limit: usize) -> Vec<&str> {
+        let mut recent: Vec<(&String, &UsageEntry)> = self.entries.iter().collect();
+        recent.sort_by(|a, b| b.1.last_used.cmp(&a.1.last_used).then_with(|| a.0.cmp(b.0)));
+        recent
+            .into_iter()
+            .take(limit)
+            .map(|(emoji, _)| emoji.as_str())
+            .collect()
+    }
+}
+
+/// Get a snapshot of the shared usage store.
+pub fn emoji_usage() -> Arc<EmojiUsage> {
+    USAGE.snapshot()
+}
+
+/// Record one use of an emoji in the shared store and persist it.
+pub fn record_emoji_usage(emoji: &str) {
+    USAGE.update(|usage| usage.record(emoji));
+}
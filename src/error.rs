@@ -44,6 +44,54 @@ pub enum ProcessError {
     SpawnFailed(#[source] std::io::Error),
 }
 
+/// Application launch errors.
+#[derive(Error, Debug)]
+#[error("Failed to launch {app}: {source}")]
+pub struct LaunchError {
+    /// Display name of the application.
+    pub app: String,
+    /// The underlying process error.
+    #[source]
+    pub source: ProcessError,
+}
+
+/// No desktop entry has the requested id.
+#[derive(Error, Debug)]
+#[error("No application with id '{0}'")]
+pub struct UnknownApplicationError(pub String);
+
+/// Errors shown in the launcher's error banner after confirming an item.
+#[derive(Error, Debug)]
+pub enum ConfirmError {
+    /// An application failed to start.
+    #[error(transparent)]
+    Launch(#[from] LaunchError),
+
+    /// Copying to the clipboard failed.
+    #[error(transparent)]
+    Copy(#[from] ClipboardError),
+}
+
+impl ConfirmError {
+    /// Short summary for the banner.
+    pub fn title(&self) -> String {
+        match self {
+            Self::Launch(e) => format!("Failed to launch {}", e.app),
+            Self::Copy(_) => "Failed to copy to clipboard".to_string(),
+        }
+    }
+
+    /// Underlying cause for the banner.
+    pub fn detail(&self) -> String {
+        match self {
+            Self::Launch(e) => e.source.to_string(),
+            Self::Copy(ClipboardError::AccessFailed(msg) | ClipboardError::CopyFailed(msg)) => {
+                msg.clone()
+            }
+        }
+    }
+}
+
 /// Configuration errors.
 #[derive(Error, Debug)]
 pub enum ConfigError {
@@ -80,6 +128,30 @@ pub enum ClipboardError {
     CopyFailed(String),
 }
 
+/// Clipboard history export and import errors.
+#[derive(Error, Debug)]
+pub enum ClipboardHistoryError {
+    /// Clipboard history isn't enabled in the daemon.
+    #[error("Clipboard history is disabled")]
+    Disabled,
+
+    /// Failed to read or write the history file.
+    #[error("Failed to access history file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The history file isn't a valid export.
+    #[error("Invalid history file: {0}")]
+    Malformed(#[from] serde_json::Error),
+
+    /// The history file was written by an unknown version.
+    #[error("Unsupported history file version {0}")]
+    UnsupportedVersion(u32),
+
+    /// An image entry couldn't be decoded.
+    #[error("Invalid image in history entry {0}")]
+    InvalidImage(usize),
+}
+
 // Conversion from ClipboardError to String for backwards compatibility
 impl From<ClipboardError> for String {
     fn from(e: ClipboardError) -> Self {
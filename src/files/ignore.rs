@@ -0,0 +1,114 @@
+//! Ignore pattern matching for the file indexer.
+
+/// Patterns excluded from the index unless configured otherwise.
+pub const DEFAULT_IGNORE_PATTERNS: &[&str] = &[
+    "node_modules",
+    "target",
+    "__pycache__",
+    "*.pyc",
+    "*.o",
+    "*.swp",
+];
+
+/// Rules deciding which entries are skipped while indexing.
+#[derive(Clone, Debug)]
+pub struct IgnoreRules {
+    /// Glob patterns matched against file and directory names.
+    patterns: Vec<String>,
+    /// Whether entries starting with `.` are indexed.
+    include_hidden: bool,
+}
+
+impl IgnoreRules {
+    /// Create rules from glob patterns (`*` and `?` wildcards).
+    pub fn new(
+        patterns: impl IntoIterator<Item = impl Into<String>>,
+        include_hidden: bool,
+    ) -> Self {
+        Self {
+            patterns: patterns.into_iter().map(Into::into).collect(),
+            include_hidden,
+        }
+    }
+
+    /// Check whether an entry with the given file name should be skipped.
+    /// Ignored directories are not descended into.
+    pub fn is_ignored(&self, name: &str) -> bool {
+        if !self.include_hidden && name.starts_with('.') {
+            return true;
+        }
+        self.patterns
+            .iter()
+            .any(|pattern| glob_match(pattern.as_bytes(), name.as_bytes()))
+    }
+}
+
+impl Default for IgnoreRules {
+    fn default() -> Self {
+        Self::new(DEFAULT_IGNORE_PATTERNS.iter().copied(), false)
+    }
+}
+
+/// Match a name against a glob pattern supporting `*` and `?`.
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` absorb one more character
+                Some((star_p, star_n)) => {
+                    backtrack = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hidden_entries_ignored_by_default() {
+        let rules = IgnoreRules::default();
+        assert!(rules.is_ignored(".git"));
+        assert!(rules.is_ignored(".config"));
+        assert!(!rules.is_ignored("Documents"));
+
+        let rules = IgnoreRules::new(Vec::<String>::new(), true);
+        assert!(!rules.is_ignored(".config"));
+    }
+
+    #[test]
+    fn test_ignore_patterns() {
+        let rules = IgnoreRules::default();
+        assert!(rules.is_ignored("node_modules"));
+        assert!(rules.is_ignored("module.pyc"));
+        assert!(!rules.is_ignored("targets"));
+        assert!(!rules.is_ignored("notes.md"));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match(b"*.log", b"app.log"));
+        assert!(glob_match(b"cache-?", b"cache-1"));
+        assert!(glob_match(b"*build*", b"prebuilt-build-x"));
+        assert!(!glob_match(b"*.log", b"app.log.gz"));
+        assert!(!glob_match(b"cache-?", b"cache-10"));
+    }
+}
@@ -0,0 +1,324 @@
+//! In-memory file index with persistence and fuzzy search.
+
+use super::ignore::IgnoreRules;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::debug;
+
+/// Current index format version.
+const INDEX_VERSION: u32 = 1;
+
+/// How many entries are indexed between progress reports.
+const PROGRESS_INTERVAL: usize = 1000;
+
+/// Index of file and directory paths under a set of roots.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FileIndex {
+    /// Index format version for compatibility checks.
+    version: u32,
+    /// Roots the index was built from.
+    roots: Vec<PathBuf>,
+    /// Indexed paths, sorted so that subtrees can be removed cheaply.
+    paths: BTreeSet<PathBuf>,
+    /// Whether the build stopped at the size cap.
+    truncated: bool,
+}
+
+impl FileIndex {
+    /// Create an empty index for the given roots.
+    pub fn empty(roots: Vec<PathBuf>) -> Self {
+        Self {
+            version: INDEX_VERSION,
+            roots,
+            paths: BTreeSet::new(),
+            truncated: false,
+        }
+    }
+
+    /// Walk the roots and build a new index.
+    ///
+    /// Hidden and ignored entries are skipped, symlinks are not followed and
+    /// at most `max_entries` paths are collected. `on_progress` is called
+    /// periodically with the number of indexed paths. Returns None if
+    /// `cancel` was set during the walk.
+    pub fn build(
+        roots: &[PathBuf],
+        rules: &IgnoreRules,
+        max_entries: usize,
+        cancel: &AtomicBool,
+        mut on_progress: impl FnMut(usize),
+    ) -> Option<Self> {
+        let mut index = Self::empty(roots.to_vec());
+        let mut pending: Vec<PathBuf> = roots.iter().filter(|r| r.is_dir()).cloned().collect();
+
+        while let Some(dir) = pending.pop() {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+
+            let Ok(entries) = fs::read_dir(&dir) else {
+                debug!(?dir, "Skipping unreadable directory");
+                continue;
+            };
+
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                if rules.is_ignored(&name.to_string_lossy()) {
+                    continue;
+                }
+
+                if index.paths.len() >= max_entries {
+                    index.truncated = true;
+                    return Some(index);
+                }
+
+                let path = entry.path();
+                if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    pending.push(path.clone());
+                }
+                index.paths.insert(path);
+
+                if index.paths.len() % PROGRESS_INTERVAL == 0 {
+                    on_progress(index.paths.len());
+                }
+            }
+        }
+
+        Some(index)
+    }
+
+    /// Number of indexed paths.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Check if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Whether the build stopped at the size cap.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Roots the index was built from.
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Check if the index contains a path.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.paths.contains(path)
+    }
+
+    /// Check whether a path belongs in this index: it must be under one of
+    /// the roots and none of its components may be ignored.
+    pub fn accepts(&self, path: &Path, rules: &IgnoreRules) -> bool {
+        self.roots.iter().any(|root| {
+            path.strip_prefix(root).is_ok_and(|rel| {
+                rel.components().next().is_some()
+                    && rel
+                        .components()
+                        .all(|c| !rules.is_ignored(&c.as_os_str().to_string_lossy()))
+            })
+        })
+    }
+
+    /// Add a path to the index. Returns false if it was not added.
+    pub fn insert(&mut self, path: PathBuf, rules: &IgnoreRules, max_entries: usize) -> bool {
+        if self.paths.len() >= max_entries || !self.accepts(&path, rules) {
+            return false;
+        }
+        self.paths.insert(path)
+    }
+
+    /// Remove a path and everything below it. Returns the number removed.
+    pub fn remove(&mut self, path: &Path) -> usize {
+        let removed: Vec<PathBuf> = self
+            .paths
+            .range(path.to_path_buf()..)
+            .take_while(|p| p.starts_with(path))
+            .cloned()
+            .collect();
+        for p in &removed {
+            self.paths.remove(p);
+        }
+        removed.len()
+    }
+
+    /// Fuzzy search the index, best matches first.
+    ///
+    /// File names are matched first; a match on the full path only counts
+    /// for half as much.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<PathBuf> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let matcher = SkimMatcherV2::default();
+        let mut scored: Vec<(&PathBuf, i64)> = self
+            .paths
+            .iter()
+            .filter_map(|path| {
+                let name = path.file_name()?.to_string_lossy();
+                matcher
+                    .fuzzy_match(&name, query)
+                    .or_else(|| {
+                        matcher
+                            .fuzzy_match(&path.to_string_lossy(), query)
+                            .map(|score| score / 2)
+                    })
+                    .map(|score| (path, score))
+            })
+            .collect();
+
+        // Sort by score descending, shorter paths first on ties
+        scored.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| a.0.as_os_str().len().cmp(&b.0.as_os_str().len()))
+        });
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Load an index from a file.
+    /// Returns None if it is missing, unreadable or from another version.
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let data = fs::read_to_string(path).ok()?;
+        let index: Self = serde_json::from_str(&data).ok()?;
+
+        if index.version != INDEX_VERSION {
+            debug!("File index version mismatch, ignoring");
+            return None;
+        }
+
+        Some(index)
+    }
+
+    /// Save the index to a file.
+    pub fn save_to(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let data = serde_json::to_string(self)?;
+        fs::write(path, data)?;
+        debug!("Saved {} paths to file index", self.paths.len());
+
+        Ok(())
+    }
+
+    /// Get the cache file path.
+    pub fn cache_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|d| d.join("zlaunch").join("files.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    /// Create a fresh directory tree for a test.
+    fn test_tree(name: &str) -> TempDir {
+        let root = TempDir::new(&format!("files-{name}"));
+        fs::create_dir_all(root.join("docs/notes")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("docs/report.pdf"), "").unwrap();
+        fs::write(root.join("docs/notes/todo.md"), "").unwrap();
+        fs::write(root.join(".git/config"), "").unwrap();
+        fs::write(root.join("node_modules/pkg/index.js"), "").unwrap();
+        root
+    }
+
+    fn build(root: &Path, max_entries: usize) -> FileIndex {
+        FileIndex::build(
+            &[root.to_path_buf()],
+            &IgnoreRules::default(),
+            max_entries,
+            &AtomicBool::new(false),
+            |_| {},
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_skips_ignored() {
+        let root = test_tree("build");
+        let index = build(&root, 100);
+
+        assert_eq!(index.len(), 4);
+        assert!(index.contains(&root.join("docs/notes/todo.md")));
+        assert!(!index.contains(&root.join(".git")));
+        assert!(!index.contains(&root.join("node_modules/pkg/index.js")));
+        assert!(!index.is_truncated());
+    }
+
+    #[test]
+    fn test_build_respects_cap_and_cancel() {
+        let root = test_tree("cap");
+        let index = build(&root, 2);
+        assert_eq!(index.len(), 2);
+        assert!(index.is_truncated());
+
+        let canceled = FileIndex::build(
+            &[root.to_path_buf()],
+            &IgnoreRules::default(),
+            100,
+            &AtomicBool::new(true),
+            |_| {},
+        );
+        assert!(canceled.is_none());
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let root = test_tree("incremental");
+        let rules = IgnoreRules::default();
+        let mut index = build(&root, 100);
+
+        assert!(index.insert(root.join("docs/new.txt"), &rules, 100));
+        assert!(!index.insert(root.join(".cache/file"), &rules, 100));
+        assert!(!index.insert(PathBuf::from("/elsewhere/file"), &rules, 100));
+
+        // Removing a directory drops its subtree
+        assert_eq!(index.remove(&root.join("docs")), 5);
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_search_prefers_file_names() {
+        let root = test_tree("search");
+        let index = build(&root, 100);
+
+        let results = index.search("todo", 10);
+        assert_eq!(results.first(), Some(&root.join("docs/notes/todo.md")));
+        assert!(index.search("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_load_save_roundtrip() {
+        let root = test_tree("persist");
+        let index = build(&root, 100);
+        let cache = root.join("cache/files.json");
+
+        index.save_to(&cache).unwrap();
+        let loaded = FileIndex::load_from(&cache).unwrap();
+        assert_eq!(loaded.len(), index.len());
+        assert_eq!(loaded.roots(), index.roots());
+        assert!(loaded.contains(&root.join("docs/report.pdf")));
+
+        fs::write(&cache, "not json").unwrap();
+        assert!(FileIndex::load_from(&cache).is_none());
+    }
+}
@@ -0,0 +1,261 @@
+//! Background task that builds, loads and refreshes the file index.
+
+use super::ignore::IgnoreRules;
+use super::index::FileIndex;
+use crate::config::FileIndexConfig;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// How long to wait for more file system events before applying a batch.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Minimum time between two saves of the index while watching.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The daemon's file index, set when it starts the indexer.
+static FILE_INDEX: OnceLock<FileIndexHandle> = OnceLock::new();
+
+/// Get the daemon's file index, if file indexing is enabled.
+pub fn file_index() -> Option<FileIndexHandle> {
+    FILE_INDEX.get().cloned()
+}
+
+/// Share a handle as the daemon's file index.
+/// Returns false if there already is one.
+pub fn set_file_index(handle: FileIndexHandle) -> bool {
+    FILE_INDEX.set(handle).is_ok()
+}
+
+/// Progress reported by the indexer task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndexProgress {
+    /// The index was loaded from the cache with the given number of paths.
+    Loaded(usize),
+    /// A build is running and has indexed this many paths so far.
+    Scanning(usize),
+    /// A build finished with the given number of paths.
+    Ready(usize),
+    /// The indexer was canceled.
+    Canceled,
+}
+
+/// Shared handle to the file index, used to query it and stop the indexer.
+#[derive(Clone, Default)]
+pub struct FileIndexHandle {
+    index: Arc<RwLock<FileIndex>>,
+    canceled: Arc<AtomicBool>,
+}
+
+impl FileIndexHandle {
+    /// Create a handle with an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a handle serving an existing index.
+    pub fn with_index(index: FileIndex) -> Self {
+        Self {
+            index: Arc::new(RwLock::new(index)),
+            ..Self::default()
+        }
+    }
+
+    /// Fuzzy search the current index.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<PathBuf> {
+        self.index.read().unwrap().search(query, limit)
+    }
+
+    /// Number of indexed paths.
+    pub fn len(&self) -> usize {
+        self.index.read().unwrap().len()
+    }
+
+    /// Check if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stop the indexer task. A running build is abandoned.
+    pub fn cancel(&self) {
+        self.canceled.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if the indexer was canceled.
+    pub fn is_canceled(&self) -> bool {
+        self.canceled.load(Ordering::Relaxed)
+    }
+}
+
+/// Run the file indexer as an async task.
+///
+/// Loads the cached index if it was built for the same roots, otherwise
+/// builds it on a blocking thread while reporting progress. Afterwards the
+/// roots are watched and the index is updated incrementally until the
+/// handle is canceled.
+///
+/// This should be spawned on the shared tokio runtime via `tokio_runtime::spawn()`.
+pub async fn run_indexer(
+    handle: FileIndexHandle,
+    config: FileIndexConfig,
+    progress: flume::Sender<IndexProgress>,
+) {
+    let roots = config.resolved_roots();
+    let rules = config.ignore_rules();
+    let cache_path = FileIndex::cache_path();
+
+    let cached = cache_path
+        .as_deref()
+        .and_then(FileIndex::load_from)
+        .filter(|index| index.roots() == roots.as_slice());
+
+    if let Some(index) = cached {
+        info!("Loaded {} paths from file index cache", index.len());
+        let _ = progress.send(IndexProgress::Loaded(index.len()));
+        *handle.index.write().unwrap() = index;
+    } else {
+        info!("Building file index for {:?}", roots);
+        let build = {
+            let (roots, rules, handle, progress) = (
+                roots.clone(),
+                rules.clone(),
+                handle.clone(),
+                progress.clone(),
+            );
+            let max_entries = config.max_entries;
+            tokio::task::spawn_blocking(move || {
+                FileIndex::build(&roots, &rules, max_entries, &handle.canceled, |count| {
+                    let _ = progress.send(IndexProgress::Scanning(count));
+                })
+            })
+        };
+
+        let Ok(Some(index)) = build.await else {
+            debug!("File index build canceled");
+            let _ = progress.send(IndexProgress::Canceled);
+            return;
+        };
+
+        if index.is_truncated() {
+            warn!(
+                "File index reached the limit of {} entries",
+                config.max_entries
+            );
+        }
+        info!("Indexed {} paths", index.len());
+        let _ = progress.send(IndexProgress::Ready(index.len()));
+        save_index(&index, cache_path.as_deref());
+        *handle.index.write().unwrap() = index;
+    }
+
+    watch_roots(
+        &handle,
+        &roots,
+        &rules,
+        config.max_entries,
+        cache_path.as_deref(),
+    )
+    .await;
+    let _ = progress.send(IndexProgress::Canceled);
+}
+
+/// Apply file system events to the index until the handle is canceled.
+///
+/// Changes are saved at most once per `SAVE_INTERVAL`, and once more when
+/// watching stops.
+async fn watch_roots(
+    handle: &FileIndexHandle,
+    roots: &[PathBuf],
+    rules: &IgnoreRules,
+    max_entries: usize,
+    cache_path: Option<&std::path::Path>,
+) {
+    let (tx, rx) = flume::unbounded();
+    let mut watcher = match notify::recommended_watcher(move |res: Result<Event, _>| match res {
+        Ok(event) => {
+            let _ = tx.send(event);
+        }
+        Err(e) => warn!("File index watcher error: {}", e),
+    }) {
+        Ok(w) => w,
+        Err(e) => {
+            warn!("Failed to create file index watcher: {}", e);
+            return;
+        }
+    };
+
+    for root in roots {
+        if let Err(e) = watcher.watch(root, RecursiveMode::Recursive) {
+            warn!("Failed to watch {:?}: {}", root, e);
+        }
+    }
+
+    let mut unsaved = false;
+    let mut last_save = Instant::now();
+    while !handle.is_canceled() {
+        if unsaved && last_save.elapsed() >= SAVE_INTERVAL {
+            save_snapshot(handle, cache_path);
+            unsaved = false;
+            last_save = Instant::now();
+        }
+
+        // Wake up periodically to notice cancellation
+        let Ok(Ok(first)) = tokio::time::timeout(Duration::from_secs(1), rx.recv_async()).await
+        else {
+            continue;
+        };
+
+        // Debounce: wait for rapid changes to settle
+        tokio::time::sleep(DEBOUNCE).await;
+        let events = std::iter::once(first).chain(rx.drain());
+
+        let changed = {
+            let mut index = handle.index.write().unwrap();
+            let mut changed = false;
+            for event in events {
+                let creates = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_));
+                let removes = matches!(event.kind, EventKind::Remove(_) | EventKind::Modify(_));
+                for path in event.paths {
+                    // Renames arrive as modify events, so check what is on disk
+                    if path.exists() {
+                        if creates && !index.contains(&path) {
+                            changed |= index.insert(path, rules, max_entries);
+                        }
+                    } else if removes {
+                        changed |= index.remove(&path) > 0;
+                    }
+                }
+            }
+            changed
+        };
+
+        if changed {
+            debug!("File index updated, {} paths", handle.len());
+            crate::items::invalidate_provider_caches();
+            unsaved = true;
+        }
+    }
+
+    if unsaved {
+        save_snapshot(handle, cache_path);
+    }
+}
+
+/// Persist a copy of the index, so the lock is not held while writing.
+fn save_snapshot(handle: &FileIndexHandle, cache_path: Option<&std::path::Path>) {
+    let index = handle.index.read().unwrap().clone();
+    save_index(&index, cache_path);
+}
+
+/// Persist the index, logging failures.
+fn save_index(index: &FileIndex, cache_path: Option<&std::path::Path>) {
+    let Some(path) = cache_path else {
+        return;
+    };
+    if let Err(e) = index.save_to(path) {
+        warn!("Failed to save file index: {}", e);
+    }
+}
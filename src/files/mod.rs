@@ -0,0 +1,19 @@
+//! File indexing for file search.
+//!
+//! Builds an index of paths under the configured roots in the background,
+//! persists it to the cache directory and keeps it up to date from file
+//! system events. Queries are served from the in-memory index.
+//!
+//! # Modules
+//!
+//! - `ignore` - Ignore pattern matching for hidden and excluded entries
+//! - `index` - The index itself, building, persistence and search
+//! - `indexer` - Background task that builds, loads and refreshes the index
+
+pub mod ignore;
+pub mod index;
+pub mod indexer;
+
+pub use ignore::IgnoreRules;
+pub use index::FileIndex;
+pub use indexer::{FileIndexHandle, IndexProgress, file_index, run_indexer, set_file_index};
@@ -0,0 +1,167 @@
+//! Launch history for frecency ranking.
+//!
+//! Records how often and how recently items from the main list were
+//! confirmed, keyed by item id. The ranking combines both into a single
+//! frecency score that favors items used often and recently: the launch
+//! count raised to a configurable weight, decayed exponentially by the time
+//! since the last launch with a configurable half-life.
+
+use crate::store::PersistedStore;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+lazy_static! {
+    /// Launch history shared by the launcher.
+    static ref HISTORY: PersistedStore<LaunchHistory> = PersistedStore::open("history.json");
+}
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// Usage of a single item.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchEntry {
+    /// How often the item was confirmed.
+    pub count: u32,
+    /// When the item was last confirmed (seconds since the epoch).
+    pub last_used: u64,
+}
+
+/// Tuning of the frecency score.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrecencyDecay {
+    /// Days after which the score of an unused item halves.
+    pub half_life_days: f64,
+    /// Exponent applied to the launch count (0 ignores the count).
+    pub count_weight: f64,
+}
+
+impl Default for FrecencyDecay {
+    fn default() -> Self {
+        Self {
+            half_life_days: 30.0,
+            count_weight: 1.0,
+        }
+    }
+}
+
+/// Frecency score of a launch entry at time `now`.
+///
+/// A non-positive half-life disables the decay.
+pub fn frecency_score(entry: &LaunchEntry, now: u64, decay: &FrecencyDecay) -> f64 {
+    let count = (entry.count as f64).powf(decay.count_weight);
+    if decay.half_life_days <= 0.0 {
+        return count;
+    }
+    let age_days = now.saturating_sub(entry.last_used) as f64 / DAY as f64;
+    count * 0.5f64.powf(age_days / decay.half_life_days)
+}
+
+/// Launch history keyed by item id.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LaunchHistory {
+    entries: HashMap<String, LaunchEntry>,
+}
+
+impl LaunchHistory {
+    /// Record a launch of an item at the given time.
+    pub fn record_at(&mut self, id: &str, now: u64) {
+        let entry = self.entries.entry(id.to_string()).or_default();
+        entry.count = entry.count.saturating_add(1);
+        entry.last_used = now;
+    }
+
+    /// Get the usage of an item.
+    pub fn get(&self, id: &str) -> Option<&LaunchEntry> {
+        self.entries.get(id)
+    }
+
+    /// Frecency score of an item at time `now` (see [`frecency_score`]).
+    /// Unknown items score 0.
+    pub fn score_at(&self, id: &str, now: u64, decay: &FrecencyDecay) -> f64 {
+        self.entries
+            .get(id)
+            .map_or(0.0, |entry| frecency_score(entry, now, decay))
+    }
+}
+
+/// Current time in seconds since the epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Get a snapshot of the shared launch history.
+pub fn launch_history() -> Arc<LaunchHistory> {
+    HISTORY.snapshot()
+}
+
+/// Record a launch in the shared history and persist it.
+pub fn record_launch(id: &str) {
+    HISTORY.update(|history| history.record_at(id, now()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_counts_and_recency() {
+        let now = 1_000 * DAY;
+        let decay = FrecencyDecay::default();
+        let mut history = LaunchHistory::default();
+        history.record_at("recent", now);
+        history.record_at("old", now - 60 * DAY);
+        history.record_at("old", now - 60 * DAY);
+
+        assert_eq!(history.score_at("recent", now, &decay), 1.0);
+        // Two launches, two half-lives ago
+        assert!((history.score_at("old", now, &decay) - 0.5).abs() < 1e-9);
+        assert_eq!(history.score_at("unknown", now, &decay), 0.0);
+    }
+
+    #[test]
+    fn test_half_life_decides_recent_vs_frequent() {
+        let now = 1_000 * DAY;
+        let recent = LaunchEntry {
+            count: 1,
+            last_used: now - 3600,
+        };
+        let frequent = LaunchEntry {
+            count: 50,
+            last_used: now - 30 * DAY,
+        };
+        let score = |entry, half_life_days| {
+            frecency_score(
+                &entry,
+                now,
+                &FrecencyDecay {
+                    half_life_days,
+                    count_weight: 1.0,
+                },
+            )
+        };
+
+        assert!(score(recent, 1.0) > score(frequent, 1.0));
+        assert!(score(recent, 365.0) < score(frequent, 365.0));
+    }
+
+    #[test]
+    fn test_count_weight() {
+        let entry = LaunchEntry {
+            count: 9,
+            last_used: 0,
+        };
+        let no_decay = |count_weight| FrecencyDecay {
+            half_life_days: 0.0,
+            count_weight,
+        };
+        assert_eq!(frecency_score(&entry, 0, &no_decay(1.0)), 9.0);
+        assert!((frecency_score(&entry, 0, &no_decay(0.5)) - 3.0).abs() < 1e-9);
+        assert_eq!(frecency_score(&entry, 0, &no_decay(0.0)), 1.0);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::glyphs::GlyphItem;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use lazy_static::lazy_static;
+
+/// Embedded Nerd Font glyph dataset: (codepoint, name, tags).
+const GLYPH_DATA: &[(u32, &str, &[&str])] = &[
+    // Font Awesome
+    (0xF015, "nf-fa-home", &["house", "home"]),
+    (0xF07B, "nf-fa-folder", &["directory", "folder"]),
+    (
+        0xF07C,
+        "nf-fa-folder_open",
+        &["directory", "folder", "open"],
+    ),
+    (0xF15B, "nf-fa-file", &["document", "file"]),
+    (0xF002, "nf-fa-search", &["find", "magnifier", "search"]),
+    (0xF013, "nf-fa-cog", &["settings", "gear", "config"]),
+    (0xF120, "nf-fa-terminal", &["shell", "console", "prompt"]),
+    (0xF121, "nf-fa-code", &["source", "brackets", "dev"]),
+    (0xF09B, "nf-fa-github", &["git", "repo", "octocat"]),
+    (0xF1D3, "nf-fa-git", &["vcs", "version control"]),
+    (0xF004, "nf-fa-heart", &["love", "like", "favorite"]),
+    (0xF005, "nf-fa-star", &["favorite", "bookmark", "rating"]),
+    (0xF00C, "nf-fa-check", &["ok", "done", "tick"]),
+    (0xF00D, "nf-fa-times", &["close", "cross", "x"]),
+    (0xF1F8, "nf-fa-trash", &["delete", "remove", "bin"]),
+    (0xF023, "nf-fa-lock", &["secure", "password", "locked"]),
+    (0xF09C, "nf-fa-unlock", &["unlocked", "open"]),
+    (0xF007, "nf-fa-user", &["person", "account", "profile"]),
+    (0xF0E0, "nf-fa-envelope", &["mail", "email", "message"]),
+    (0xF001, "nf-fa-music", &["audio", "note", "song"]),
+    (0xF017, "nf-fa-clock_o", &["time", "clock"]),
+    (0xF073, "nf-fa-calendar", &["date", "schedule"]),
+    (0xF1EB, "nf-fa-wifi", &["wireless", "network"]),
+    (0xF293, "nf-fa-bluetooth", &["wireless"]),
+    (0xF240, "nf-fa-battery_full", &["power", "charge"]),
+    (0xF028, "nf-fa-volume_up", &["audio", "sound", "speaker"]),
+    (0xF026, "nf-fa-volume_off", &["mute", "audio", "sound"]),
+    (0xF019, "nf-fa-download", &["save", "arrow"]),
+    (0xF093, "nf-fa-upload", &["arrow", "send"]),
+    (0xF188, "nf-fa-bug", &["debug", "error", "insect"]),
+    (0xF1C0, "nf-fa-database", &["db", "storage", "sql"]),
+    (0xF0C2, "nf-fa-cloud", &["weather", "sync"]),
+    (0xF011, "nf-fa-power_off", &["shutdown", "power"]),
+    (0xF0F3, "nf-fa-bell", &["notification", "alert"]),
+    (0xF17C, "nf-fa-linux", &["os", "tux", "penguin"]),
+    (0xF17A, "nf-fa-windows", &["os", "microsoft"]),
+    (0xF179, "nf-fa-apple", &["os", "mac", "macos"]),
+    (0xF269, "nf-fa-firefox", &["browser", "mozilla"]),
+    (0xF268, "nf-fa-chrome", &["browser", "google"]),
+    // Devicons
+    (0xE7A8, "nf-dev-rust", &["language", "cargo", "ferris"]),
+    (0xE73C, "nf-dev-python", &["language", "py"]),
+    (0xE74E, "nf-dev-javascript", &["language", "js"]),
+    (0xE725, "nf-dev-git_branch", &["vcs", "branch"]),
+    (0xE7B0, "nf-dev-docker", &["container", "whale"]),
+    (0xE7C5, "nf-dev-vim", &["editor", "neovim"]),
+    // Linux distributions
+    (0xF303, "nf-linux-archlinux", &["arch", "distro", "os"]),
+    (0xF31B, "nf-linux-ubuntu", &["distro", "os"]),
+    (0xF306, "nf-linux-debian", &["distro", "os"]),
+    (0xF30A, "nf-linux-fedora", &["distro", "os", "redhat"]),
+    (0xF313, "nf-linux-nixos", &["nix", "distro", "os"]),
+    (0xF31A, "nf-linux-tux", &["linux", "penguin"]),
+    // Octicons
+    (0xF418, "nf-oct-git_branch", &["vcs", "branch"]),
+    // Powerline
+    (
+        0xE0B0,
+        "nf-pl-left_hard_divider",
+        &["powerline", "arrow", "separator"],
+    ),
+    (
+        0xE0B2,
+        "nf-pl-right_hard_divider",
+        &["powerline", "arrow", "separator"],
+    ),
+];
+
+lazy_static! {
+    /// All glyphs loaded from the embedded dataset.
+    static ref ALL_GLYPHS: Vec<GlyphItem> = load_all_glyphs();
+}
+
+/// Load all glyphs from the embedded dataset.
+fn load_all_glyphs() -> Vec<GlyphItem> {
+    GLYPH_DATA
+        .iter()
+        .filter_map(|(codepoint, name, tags)| GlyphItem::new(*codepoint, *name, tags))
+        .collect()
+}
+
+/// Get all glyphs.
+pub fn all_glyphs() -> &'static [GlyphItem] {
+    &ALL_GLYPHS
+}
+
+/// Search glyphs by name or tag using fuzzy matching.
+/// Returns indices into the all_glyphs() slice, sorted by match score.
+pub fn search_glyphs(query: &str) -> Vec<usize> {
+    search_glyphs_in(&ALL_GLYPHS, query)
+}
+
+/// Search `glyphs` like [`search_glyphs`], returning indices into `glyphs`.
+///
+/// Matching ignores case. Pasting a glyph itself ranks it first.
+pub fn search_glyphs_in(glyphs: &[GlyphItem], query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return (0..glyphs.len()).collect();
+    }
+
+    let matcher = SkimMatcherV2::default();
+    let query_lower = query.to_lowercase();
+    let mut scored: Vec<(usize, i64)> = glyphs
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| {
+            if item.glyph == query {
+                return Some((idx, i64::MAX));
+            }
+            std::iter::once(&item.name)
+                .chain(item.tags.iter())
+                .filter_map(|text| matcher.fuzzy_match(text, &query_lower))
+                .max()
+                .map(|score| (idx, score))
+        })
+        .collect();
+
+    // Sort by score descending
+    scored.sort_by(|a, b| b.1.cmp(&a.1));
+    scored.into_iter().map(|(idx, _)| idx).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_glyphs_loaded() {
+        assert_eq!(all_glyphs().len(), GLYPH_DATA.len());
+    }
+
+    #[test]
+    fn test_search_glyphs_by_name() {
+        let results = search_glyphs("rust");
+        assert!(!results.is_empty());
+        assert_eq!(all_glyphs()[results[0]].name, "nf-dev-rust");
+    }
+
+    #[test]
+    fn test_search_glyphs_by_tag() {
+        let results = search_glyphs("penguin");
+        let names: Vec<&str> = results
+            .iter()
+            .map(|&idx| all_glyphs()[idx].name.as_str())
+            .collect();
+        assert!(names.contains(&"nf-linux-tux"));
+        assert!(names.contains(&"nf-fa-linux"));
+    }
+
+    #[test]
+    fn test_search_glyphs_empty_query() {
+        assert_eq!(search_glyphs("").len(), all_glyphs().len());
+    }
+}
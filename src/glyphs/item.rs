@@ -0,0 +1,48 @@
+/// An icon glyph for display in the icon picker grid.
+#[derive(Clone, Debug)]
+pub struct GlyphItem {
+    /// The glyph character.
+    pub glyph: String,
+    /// The display name of the glyph (e.g. "nf-dev-rust").
+    pub name: String,
+    /// The Unicode codepoint of the glyph.
+    pub codepoint: u32,
+    /// Additional search keywords.
+    pub tags: Vec<String>,
+}
+
+impl GlyphItem {
+    /// Create a new glyph item from a codepoint.
+    /// Returns None if the codepoint is not a valid Unicode scalar value.
+    pub fn new(codepoint: u32, name: impl Into<String>, tags: &[&str]) -> Option<Self> {
+        let glyph = char::from_u32(codepoint)?;
+        Some(Self {
+            glyph: glyph.to_string(),
+            name: name.into(),
+            codepoint,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        })
+    }
+
+    /// Codepoint label shown as a fallback when no Nerd Font is installed.
+    pub fn codepoint_label(&self) -> String {
+        format!("U+{:04X}", self.codepoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codepoint_label() {
+        let item = GlyphItem::new(0xF303, "nf-linux-archlinux", &["arch"]).unwrap();
+        assert_eq!(item.codepoint_label(), "U+F303");
+        assert_eq!(item.glyph, "\u{f303}");
+    }
+
+    #[test]
+    fn test_invalid_codepoint() {
+        assert!(GlyphItem::new(0xD800, "surrogate", &[]).is_none());
+    }
+}
@@ -0,0 +1,5 @@
+mod data;
+mod item;
+
+pub use data::{all_glyphs, search_glyphs, search_glyphs_in};
+pub use item::GlyphItem;
@@ -3,6 +3,7 @@
 use crate::config::LauncherMode;
 use crate::ipc::commands::{ThemeInfo, ZlaunchServiceClient};
 use crate::ipc::server::get_socket_path;
+use std::path::Path;
 use tarpc::client;
 use tarpc::context;
 use tarpc::tokio_serde::formats::Json;
@@ -92,6 +93,27 @@ pub fn set_theme(name: &str) -> anyhow::Result<()> {
     })
 }
 
+/// Export the clipboard history to a JSON file.
+pub fn export_clipboard(path: &Path, include_images: bool) -> anyhow::Result<usize> {
+    // The daemon resolves relative paths against its own working directory
+    let path = std::path::absolute(path)?;
+    run_async(async {
+        let client = connect().await?;
+        Ok(client
+            .export_clipboard(context::current(), path, include_images)
+            .await??)
+    })
+}
+
+/// Import an exported clipboard history file.
+pub fn import_clipboard(path: &Path) -> anyhow::Result<usize> {
+    let path = std::path::absolute(path)?;
+    run_async(async {
+        let client = connect().await?;
+        Ok(client.import_clipboard(context::current(), path).await??)
+    })
+}
+
 /// Run an async operation synchronously using a temporary tokio runtime.
 fn run_async<F, T>(future: F) -> anyhow::Result<T>
 where
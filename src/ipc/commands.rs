@@ -3,6 +3,7 @@
 use crate::config::LauncherMode;
 use crate::error::IpcError;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Theme information returned by the IPC service.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,4 +41,12 @@ pub trait ZlaunchService {
     /// Set the active theme by name.
     /// Returns Ok(()) if successful, Err with IpcError if theme not found.
     async fn set_theme(name: String) -> Result<(), IpcError>;
+
+    /// Write the clipboard history to a JSON file.
+    /// Returns the number of exported entries.
+    async fn export_clipboard(path: PathBuf, include_images: bool) -> Result<usize, IpcError>;
+
+    /// Merge the entries of an exported history file into the clipboard history.
+    /// Returns the number of entries added.
+    async fn import_clipboard(path: PathBuf) -> Result<usize, IpcError>;
 }
@@ -115,6 +115,22 @@ impl ZlaunchService for ZlaunchServer {
             .map_err(|_| IpcError::ChannelClosed)?;
         response_rx.await.unwrap_or(Err(IpcError::ResponseClosed))
     }
+
+    async fn export_clipboard(
+        self,
+        _: Context,
+        path: PathBuf,
+        include_images: bool,
+    ) -> Result<usize, IpcError> {
+        // The history is shared state - no need to go through the UI thread
+        crate::clipboard::export::export_history(&path, include_images)
+            .map_err(|e| IpcError::Internal(e.to_string()))
+    }
+
+    async fn import_clipboard(self, _: Context, path: PathBuf) -> Result<usize, IpcError> {
+        crate::clipboard::export::import_history(&path)
+            .map_err(|e| IpcError::Internal(e.to_string()))
+    }
 }
 
 /// Prepare the IPC socket, checking for existing instances.
@@ -1,4 +1,5 @@
 use std::process::Command;
+use std::thread;
 
 use super::traits::{Categorizable, DisplayItem, Executable, IconProvider};
 use crate::process;
@@ -16,8 +17,16 @@ pub enum ActionKind {
     Lock,
     /// Log out of the session
     Logout,
-    /// Custom command execution
-    Command(String),
+    /// Quit the zlaunch daemon
+    QuitDaemon,
+    /// Restart the zlaunch daemon
+    RestartDaemon,
+    /// Reload the config and rescan applications
+    ReloadConfig,
+    /// Custom command execution, through `sh -c` only if `shell` is set
+    Command { command: String, shell: bool },
+    /// Copy text to the clipboard
+    Copy { text: String },
 }
 
 /// An action item representing a functional command (shutdown, reboot, etc.).
@@ -60,15 +69,42 @@ impl ActionItem {
             ActionKind::Suspend => ("action-suspend", "Suspend", "Suspend to RAM", "moon"),
             ActionKind::Lock => ("action-lock", "Lock Screen", "Lock the session", "lock"),
             ActionKind::Logout => ("action-logout", "Log Out", "End the session", "sign-out"),
-            ActionKind::Command(cmd) => {
+            ActionKind::QuitDaemon => (
+                "action-quit-daemon",
+                "Quit zlaunch daemon",
+                "Stop the launcher daemon",
+                "power",
+            ),
+            ActionKind::RestartDaemon => (
+                "action-restart-daemon",
+                "Restart zlaunch",
+                "Restart the launcher daemon",
+                "reboot",
+            ),
+            ActionKind::ReloadConfig => (
+                "action-reload-config",
+                "Reload config",
+                "Reload the config and rescan applications",
+                "file-text",
+            ),
+            ActionKind::Command { command, .. } => {
                 return Self {
-                    id: format!("action-cmd-{}", cmd.len()),
+                    id: format!("action-cmd-{}", command.len()),
                     name: "Custom Command".to_string(),
-                    description: Some(cmd.clone()),
+                    description: Some(command.clone()),
                     icon_name: Some("terminal".to_string()),
                     kind,
                 };
             }
+            ActionKind::Copy { text } => {
+                return Self {
+                    id: "action-copy".to_string(),
+                    name: "Copy".to_string(),
+                    description: Some(text.clone()),
+                    icon_name: Some("clipboard".to_string()),
+                    kind,
+                };
+            }
         };
 
         Self {
@@ -88,10 +124,23 @@ impl ActionItem {
             Self::builtin(ActionKind::Suspend),
             Self::builtin(ActionKind::Lock),
             Self::builtin(ActionKind::Logout),
+            Self::builtin(ActionKind::QuitDaemon),
+            Self::builtin(ActionKind::RestartDaemon),
+            Self::builtin(ActionKind::ReloadConfig),
         ]
     }
 }
 
+impl ActionKind {
+    /// Whether the action manages the zlaunch daemon itself.
+    pub fn requires_daemon(&self) -> bool {
+        matches!(
+            self,
+            Self::QuitDaemon | Self::RestartDaemon | Self::ReloadConfig
+        )
+    }
+}
+
 impl DisplayItem for ActionItem {
     fn id(&self) -> &str {
         &self.id
@@ -106,7 +155,10 @@ impl DisplayItem for ActionItem {
     }
 
     fn action_label(&self) -> &'static str {
-        "Run"
+        match self.kind {
+            ActionKind::Copy { .. } => "Copy",
+            _ => "Run",
+        }
     }
 }
 
@@ -136,9 +188,32 @@ impl Executable for ActionItem {
                     .args(["terminate-session", "self"])
                     .spawn()?;
             }
-            ActionKind::Command(cmd) => {
+            ActionKind::QuitDaemon => {
+                // Go through IPC like `zlaunch quit`, off the UI thread so the
+                // daemon's event loop can handle the request
+                thread::spawn(|| {
+                    if let Err(e) = crate::ipc::client::quit() {
+                        tracing::warn!("Failed to quit daemon: {:#}", e);
+                    }
+                });
+            }
+            ActionKind::RestartDaemon => {
+                thread::spawn(|| {
+                    if let Err(e) = crate::ipc::client::reload() {
+                        tracing::warn!("Failed to restart daemon: {:#}", e);
+                    }
+                });
+            }
+            ActionKind::ReloadConfig => {
+                // Same as `kill -HUP`, handled by the daemon's SIGHUP loop
+                crate::daemon::request_reload();
+            }
+            ActionKind::Command { command, shell } => {
                 // Custom commands should be disowned from daemon
-                process::run_shell_command(cmd)?;
+                process::run_command(command, *shell)?;
+            }
+            ActionKind::Copy { text } => {
+                crate::clipboard::copy_to_clipboard(text)?;
             }
         }
         Ok(())
@@ -160,3 +235,43 @@ impl From<ActionItem> for super::ListItem {
         Self::Action(item)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtins_include_daemon_actions() {
+        let builtins = ActionItem::builtins();
+        let kind_of = |id: &str| {
+            builtins
+                .iter()
+                .find(|action| action.id == id)
+                .map(|action| action.kind.clone())
+        };
+
+        assert!(matches!(
+            kind_of("action-quit-daemon"),
+            Some(ActionKind::QuitDaemon)
+        ));
+        assert!(matches!(
+            kind_of("action-restart-daemon"),
+            Some(ActionKind::RestartDaemon)
+        ));
+        assert!(matches!(
+            kind_of("action-reload-config"),
+            Some(ActionKind::ReloadConfig)
+        ));
+
+        let daemon_only: Vec<&str> = builtins
+            .iter()
+            .filter(|action| action.kind.requires_daemon())
+            .map(|action| action.name.as_str())
+            .collect();
+        assert_eq!(
+            daemon_only,
+            vec!["Quit zlaunch daemon", "Restart zlaunch", "Reload config"]
+        );
+        assert!(builtins.iter().all(|action| action.icon_name.is_some()));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::config;
 use crate::desktop::DesktopEntry;
 use std::path::PathBuf;
 
@@ -13,6 +14,12 @@ pub struct ApplicationItem {
     pub description: Option<String>,
     pub terminal: bool,
     pub desktop_path: PathBuf,
+    /// Whether the application supports startup notification
+    pub startup_notify: bool,
+    /// Window class of the application's windows, if declared
+    pub startup_wm_class: Option<String>,
+    /// Whether the desktop entry is marked NoDisplay or Hidden
+    pub hidden: bool,
 }
 
 impl ApplicationItem {
@@ -33,20 +40,27 @@ impl ApplicationItem {
             description,
             terminal,
             desktop_path,
+            startup_notify: false,
+            startup_wm_class: None,
+            hidden: false,
         }
     }
 }
 
 impl From<DesktopEntry> for ApplicationItem {
     fn from(entry: DesktopEntry) -> Self {
+        let description = entry.description(config().app_description);
         Self {
             id: entry.id,
             name: entry.name,
             exec: entry.exec,
             icon_path: entry.icon_path,
-            description: entry.comment,
+            description,
             terminal: entry.terminal,
             desktop_path: entry.path,
+            startup_notify: entry.startup_notify,
+            startup_wm_class: entry.startup_wm_class,
+            hidden: entry.hidden,
         }
     }
 }
@@ -58,9 +72,12 @@ impl From<&DesktopEntry> for ApplicationItem {
             name: entry.name.clone(),
             exec: entry.exec.clone(),
             icon_path: entry.icon_path.clone(),
-            description: entry.comment.clone(),
+            description: entry.description(config().app_description),
             terminal: entry.terminal,
             desktop_path: entry.path.clone(),
+            startup_notify: entry.startup_notify,
+            startup_wm_class: entry.startup_wm_class.clone(),
+            hidden: entry.hidden,
         }
     }
 }
@@ -81,6 +98,10 @@ impl DisplayItem for ApplicationItem {
     fn action_label(&self) -> &'static str {
         "Open"
     }
+
+    fn secondary_action_label(&self) -> Option<&'static str> {
+        Some("Edit entry")
+    }
 }
 
 impl IconProvider for ApplicationItem {
@@ -1,4 +1,5 @@
 use super::traits::{Categorizable, DisplayItem, Executable, IconProvider, Previewable};
+use super::{ActionItem, ActionKind, ListItem, SubmenuItem};
 
 /// A calculator item representing a calculation result.
 #[derive(Clone, Debug)]
@@ -14,6 +15,12 @@ pub struct CalculatorItem {
     pub clipboard_result: Option<String>,
     /// Whether this is an error result.
     pub is_error: bool,
+    /// Signed amount added or removed by an `A + B%` expression
+    /// (e.g. "+15.3"), shown next to the result.
+    pub delta: Option<String>,
+    /// Variables assigned by a multi-statement expression (`a = 2; a * 3`)
+    /// with their display values, in assignment order.
+    pub bindings: Vec<(String, String)>,
 }
 
 impl CalculatorItem {
@@ -23,6 +30,53 @@ impl CalculatorItem {
             .as_deref()
             .unwrap_or(&self.display_result)
     }
+
+    /// The result in each representation that applies, as (label, text):
+    /// the plain number, with digit grouping, hex and binary for integers,
+    /// and scientific notation. Empty for errors.
+    pub fn copy_formats(&self) -> Vec<(&'static str, String)> {
+        let Some(plain) = self.clipboard_result.as_deref().filter(|_| !self.is_error) else {
+            return Vec::new();
+        };
+
+        let mut formats = vec![("Number", plain.to_string())];
+        if self.display_result != plain {
+            formats.push(("Grouped", self.display_result.clone()));
+        }
+        if let Ok(int) = plain.parse::<i128>() {
+            let sign = if int < 0 { "-" } else { "" };
+            formats.push(("Hex", format!("{sign}{:#x}", int.unsigned_abs())));
+            formats.push(("Binary", format!("{sign}{:#b}", int.unsigned_abs())));
+        }
+        if let Ok(number) = plain.parse::<f64>()
+            && number.is_finite()
+            && number != 0.0
+        {
+            formats.push(("Scientific", format!("{number:e}")));
+        }
+        formats
+    }
+
+    /// A submenu with one entry per [`copy_formats`](Self::copy_formats)
+    /// representation, each copying it on confirm.
+    pub fn copy_formats_submenu(&self) -> SubmenuItem {
+        let children = self
+            .copy_formats()
+            .into_iter()
+            .map(|(label, text)| {
+                ListItem::Action(ActionItem::new(
+                    format!("calculator-copy-{}", label.to_lowercase()),
+                    label.to_string(),
+                    Some(text.clone()),
+                    Some("clipboard".to_string()),
+                    ActionKind::Copy { text },
+                ))
+            })
+            .collect();
+        SubmenuItem::list("calculator-copy-formats", "Copy result as")
+            .with_description(self.display_result.clone())
+            .with_children(children)
+    }
 }
 
 impl DisplayItem for CalculatorItem {
@@ -41,6 +95,10 @@ impl DisplayItem for CalculatorItem {
     fn action_label(&self) -> &'static str {
         "Copy"
     }
+
+    fn secondary_action_label(&self) -> Option<&'static str> {
+        (!self.is_error).then_some("Copy as…")
+    }
 }
 
 impl IconProvider for CalculatorItem {
@@ -77,3 +135,83 @@ impl From<CalculatorItem> for super::ListItem {
         Self::Calculator(item)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(clipboard: &str, display: &str) -> CalculatorItem {
+        CalculatorItem {
+            id: "calculator-result".to_string(),
+            expression: "x".to_string(),
+            display_result: display.to_string(),
+            clipboard_result: Some(clipboard.to_string()),
+            is_error: false,
+            delta: None,
+            bindings: Vec::new(),
+        }
+    }
+
+    fn labels(item: &CalculatorItem) -> Vec<&'static str> {
+        item.copy_formats()
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect()
+    }
+
+    #[test]
+    fn test_copy_formats_for_integer() {
+        let item = result("1234", "1,234");
+        assert_eq!(
+            item.copy_formats(),
+            vec![
+                ("Number", "1234".to_string()),
+                ("Grouped", "1,234".to_string()),
+                ("Hex", "0x4d2".to_string()),
+                ("Binary", "0b10011010010".to_string()),
+                ("Scientific", "1.234e3".to_string()),
+            ]
+        );
+        assert_eq!(
+            result("-10", "-10").copy_formats()[1],
+            ("Hex", "-0xa".to_string())
+        );
+    }
+
+    #[test]
+    fn test_copy_formats_for_decimal() {
+        assert_eq!(
+            labels(&result("1234.5", "1,234.5")),
+            ["Number", "Grouped", "Scientific"]
+        );
+        assert_eq!(labels(&result("0.5", "0.5")), ["Number", "Scientific"]);
+        // Non-numeric results can only be copied as they are
+        assert_eq!(labels(&result("true", "true")), ["Number"]);
+
+        let error = CalculatorItem {
+            clipboard_result: None,
+            is_error: true,
+            ..result("1", "Infinity")
+        };
+        assert!(error.copy_formats().is_empty());
+    }
+
+    #[test]
+    fn test_copy_formats_submenu() {
+        let submenu = result("255", "255").copy_formats_submenu();
+        let children: Vec<(&str, Option<&str>)> = submenu
+            .children
+            .iter()
+            .map(|child| (child.name(), child.description()))
+            .collect();
+        assert_eq!(
+            children,
+            [
+                ("Number", Some("255")),
+                ("Hex", Some("0xff")),
+                ("Binary", Some("0b11111111")),
+                ("Scientific", Some("2.55e2")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use super::traits::{Categorizable, DisplayItem, Executable, IconProvider, Previewable};
+use crate::ui::utils::color::{Color, parse_color};
+
+/// Output format for copying a color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorFormat {
+    /// `#RRGGBB` (or `#RRGGBBAA` with alpha)
+    Hex,
+    /// `rgb(r, g, b)` (or `rgba(...)` with alpha)
+    Rgb,
+    /// `hsl(h, s%, l%)`
+    Hsl,
+}
+
+impl ColorFormat {
+    /// Detect the format a color string was typed in.
+    fn detect(input: &str) -> Self {
+        let input = input.trim_start();
+        if input.starts_with("rgb") {
+            Self::Rgb
+        } else if input.starts_with("hsl") {
+            Self::Hsl
+        } else {
+            Self::Hex
+        }
+    }
+}
+
+/// A color item shown when the query is a color literal.
+#[derive(Clone, Debug)]
+pub struct ColorItem {
+    /// Unique identifier for this item.
+    pub id: String,
+    /// The color string as typed by the user.
+    pub input: String,
+    /// The parsed color.
+    pub color: Color,
+    /// Display string with all formats.
+    pub display: String,
+}
+
+impl ColorItem {
+    /// Try to create a color item from a query.
+    /// Returns None if the query is not a valid color.
+    pub fn parse(query: &str) -> Option<Self> {
+        let color = parse_color(query)?;
+        let display = format!(
+            "{}  ·  {}  ·  {}",
+            Self::format_color(&color, ColorFormat::Hex),
+            Self::format_color(&color, ColorFormat::Rgb),
+            Self::format_color(&color, ColorFormat::Hsl)
+        );
+        Some(Self {
+            id: format!("color-{}", color.to_hex()),
+            input: query.trim().to_string(),
+            color,
+            display,
+        })
+    }
+
+    /// Format a color in the given format.
+    pub fn format_color(color: &Color, format: ColorFormat) -> String {
+        match format {
+            ColorFormat::Hex => color.to_hex(),
+            ColorFormat::Rgb if color.a == 255 => color.to_rgb_string(),
+            ColorFormat::Rgb => color.to_rgba_string(),
+            ColorFormat::Hsl => color.to_hsl_string(),
+        }
+    }
+
+    /// Get the format used for a confirm.
+    ///
+    /// Both confirms convert away from the typed format: primary copies
+    /// hex (or rgb when hex was typed), secondary copies the remaining one.
+    pub fn confirm_format(&self, secondary: bool) -> ColorFormat {
+        match (ColorFormat::detect(&self.input), secondary) {
+            (ColorFormat::Hex, false) => ColorFormat::Rgb,
+            (_, false) => ColorFormat::Hex,
+            (ColorFormat::Hsl, true) => ColorFormat::Rgb,
+            (_, true) => ColorFormat::Hsl,
+        }
+    }
+
+    /// Get the text to copy to clipboard.
+    pub fn text_for_clipboard(&self, secondary: bool) -> String {
+        Self::format_color(&self.color, self.confirm_format(secondary))
+    }
+}
+
+impl DisplayItem for ColorItem {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.input
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some(&self.display)
+    }
+
+    fn action_label(&self) -> &'static str {
+        "Copy"
+    }
+
+    fn secondary_action_label(&self) -> Option<&'static str> {
+        Some(match self.confirm_format(true) {
+            ColorFormat::Hex => "Copy hex",
+            ColorFormat::Rgb => "Copy RGB",
+            ColorFormat::Hsl => "Copy HSL",
+        })
+    }
+}
+
+impl IconProvider for ColorItem {
+    // Color items render a swatch instead of an icon
+}
+
+impl Executable for ColorItem {
+    fn execute(&self) -> anyhow::Result<()> {
+        crate::clipboard::copy_to_clipboard(&self.text_for_clipboard(false))
+            .map_err(|e| anyhow::anyhow!("Failed to copy to clipboard: {}", e))?;
+        Ok(())
+    }
+}
+
+impl Previewable for ColorItem {
+    fn has_preview(&self) -> bool {
+        false
+    }
+}
+
+impl Categorizable for ColorItem {
+    fn section_name(&self) -> &'static str {
+        "Color"
+    }
+
+    fn sort_priority(&self) -> u8 {
+        0
+    }
+}
+
+impl From<ColorItem> for super::ListItem {
+    fn from(item: ColorItem) -> Self {
+        Self::Color(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_input() {
+        let item = ColorItem::parse("#3366ff").unwrap();
+        assert_eq!(item.text_for_clipboard(false), "rgb(51, 102, 255)");
+        assert_eq!(item.text_for_clipboard(true), "hsl(225, 100%, 60%)");
+    }
+
+    #[test]
+    fn test_parse_rgb_input() {
+        let item = ColorItem::parse("rgb(51, 102, 255)").unwrap();
+        assert_eq!(item.text_for_clipboard(false), "#3366FF");
+        assert_eq!(item.text_for_clipboard(true), "hsl(225, 100%, 60%)");
+    }
+
+    #[test]
+    fn test_parse_hsl_input() {
+        let item = ColorItem::parse("hsl(220,100%,60%)").unwrap();
+        assert_eq!(item.text_for_clipboard(false), "#3377FF");
+        assert_eq!(item.text_for_clipboard(true), "rgb(51, 119, 255)");
+    }
+
+    #[test]
+    fn test_parse_invalid_color() {
+        assert!(ColorItem::parse("#zzzzzz").is_none());
+        assert!(ColorItem::parse("rgb(1, 2)").is_none());
+        assert!(ColorItem::parse("hello").is_none());
+    }
+}
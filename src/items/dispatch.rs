@@ -18,8 +18,11 @@ macro_rules! dispatch_item {
             Self::Action(item) => item.$method($($arg),*),
             Self::Submenu(item) => item.$method($($arg),*),
             Self::Calculator(item) => item.$method($($arg),*),
+            Self::Color(item) => item.$method($($arg),*),
+            Self::Path(item) => item.$method($($arg),*),
             Self::Search(item) => item.$method($($arg),*),
             Self::Ai(item) => item.$method($($arg),*),
+            Self::Suggestion(item) => item.$method($($arg),*),
             Self::Theme(item) => item.$method($($arg),*),
         }
     };
@@ -11,8 +11,10 @@
 //! - [`WindowItem`] - Open windows for window switching
 //! - [`ActionItem`] - System actions (shutdown, reboot, logout)
 //! - [`CalculatorItem`] - Mathematical calculation results
+//! - [`ColorItem`] - Color previews with format conversion
 //! - [`SearchItem`] - Web search queries
 //! - [`AiItem`] - AI/LLM query interface
+//! - [`SuggestionItem`] - Previously confirmed queries
 //! - [`ThemeItem`] - Theme selection entries
 //! - [`SubmenuItem`] - Nested submenus
 //!
@@ -33,6 +35,12 @@
 //! - [`Categorizable`] - Section grouping and sort priority
 //! - [`Previewable`] - Preview content for preview panels
 //!
+//! # Item Providers
+//!
+//! Sources of items (the loaded applications and windows, built-in
+//! submenus, actions) implement [`ItemProvider`] and are collected through a
+//! [`ProviderRegistry`].
+//!
 //! # Design Decisions
 //!
 //! The dispatch macro approach was chosen over trait objects because:
@@ -44,9 +52,13 @@ mod action;
 mod ai;
 mod application;
 mod calculator;
+mod color;
 mod dispatch;
+mod path;
+mod provider;
 mod search;
 mod submenu;
+mod suggestion;
 mod theme;
 mod traits;
 mod window;
@@ -57,11 +69,21 @@ pub use action::{ActionItem, ActionKind};
 pub use ai::AiItem;
 pub use application::ApplicationItem;
 pub use calculator::CalculatorItem;
+pub use color::{ColorFormat, ColorItem};
+pub use path::PathItem;
+pub use provider::{
+    ActionProvider, ApplicationProvider, CacheInvalidator, CachedProvider, ConfigSubmenuProvider,
+    FileSearchProvider, ItemProvider, ProviderRegistry, SubmenuProvider, WindowProvider,
+    invalidate_provider_caches,
+};
 pub use search::SearchItem;
-pub use submenu::{SubmenuItem, SubmenuLayout};
+pub use submenu::{SubmenuItem, SubmenuLayout, SubmenuSource};
+pub use suggestion::SuggestionItem;
 pub use theme::{ThemeItem, ThemeSource};
 pub use traits::{Categorizable, DisplayItem, Executable, IconProvider, Previewable};
-pub use window::WindowItem;
+pub use window::{
+    WindowItem, application_for_class, group_windows, icon_for_class, window_for_application,
+};
 
 use crate::config::ConfigModule;
 use std::path::PathBuf;
@@ -80,10 +102,16 @@ pub enum ListItem {
     Submenu(SubmenuItem),
     /// A calculator result
     Calculator(CalculatorItem),
+    /// A color preview
+    Color(ColorItem),
+    /// An existing file or directory typed as the query
+    Path(PathItem),
     /// A web search item
     Search(SearchItem),
     /// An AI query item
     Ai(AiItem),
+    /// A previously confirmed query
+    Suggestion(SuggestionItem),
     /// A theme item (boxed due to large size)
     Theme(Box<ThemeItem>),
 }
@@ -144,6 +172,11 @@ impl ListItem {
         dispatch_item!(self, action_label)
     }
 
+    /// Get the label of the secondary (shift+enter) action, if any.
+    pub fn secondary_action_label(&self) -> Option<&'static str> {
+        dispatch_item!(self, secondary_action_label)
+    }
+
     /// Get the sort priority for this item type.
     /// Lower values appear first in the list.
     pub fn sort_priority(&self) -> u8 {
@@ -155,26 +188,166 @@ impl ListItem {
         dispatch_item!(self, section_name)
     }
 
+    /// Text copied by the "copy info" shortcut, if the item has any.
+    ///
+    /// An app's command line, a window's title, a command's text, a
+    /// calculator or color result, a typed path, a search URL, or a
+    /// suggested query.
+    pub fn copy_payload(&self) -> Option<String> {
+        match self {
+            Self::Application(app) => Some(app.exec.clone()),
+            Self::Window(win) => Some(win.title.clone()),
+            Self::Action(action) => match &action.kind {
+                ActionKind::Command { command, .. } => Some(command.clone()),
+                _ => None,
+            },
+            Self::Calculator(calc) => calc.clipboard_result.clone(),
+            Self::Color(color) => Some(color.text_for_clipboard(false)),
+            Self::Path(path) => Some(path.description.clone()),
+            Self::Search(search) => Some(search.url.clone()),
+            Self::Suggestion(suggestion) => Some(suggestion.query.clone()),
+            Self::Submenu(_) | Self::Ai(_) | Self::Theme(_) => None,
+        }
+    }
+
     /// Get the ConfigModule this item belongs to.
     /// This method has custom logic per variant and cannot use dispatch_item!.
     pub fn config_module(&self) -> ConfigModule {
         match self {
-            Self::Application(_) => ConfigModule::Applications,
+            Self::Application(_) | Self::Path(_) => ConfigModule::Applications,
             Self::Window(_) => ConfigModule::Windows,
             Self::Action(_) => ConfigModule::Actions,
-            Self::Submenu(item) => {
-                // Map submenu IDs to their modules
-                match item.id.as_str() {
-                    "submenu-emojis" => ConfigModule::Emojis,
-                    "submenu-clipboard" => ConfigModule::Clipboard,
-                    "submenu-themes" => ConfigModule::Themes,
-                    _ => ConfigModule::Actions, // Default fallback
-                }
-            }
-            Self::Calculator(_) => ConfigModule::Calculator,
-            Self::Search(_) => ConfigModule::Search,
+            Self::Submenu(item) => item
+                .source
+                .map(SubmenuSource::config_module)
+                .unwrap_or(ConfigModule::Actions),
+            Self::Calculator(_) | Self::Color(_) => ConfigModule::Calculator,
+            Self::Search(_) | Self::Suggestion(_) => ConfigModule::Search,
             Self::Ai(_) => ConfigModule::Ai,
             Self::Theme(_) => ConfigModule::Themes,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{mock_application, mock_list_item_variants, mock_window};
+
+    #[test]
+    fn test_every_variant_has_metadata() {
+        let items = mock_list_item_variants();
+        assert_eq!(items.len(), 11, "one item per ListItem variant");
+
+        for item in &items {
+            assert!(!item.id().is_empty(), "{:?}", item);
+            assert!(!item.name().is_empty(), "{}", item.id());
+            assert!(!item.action_label().is_empty(), "{}", item.id());
+            assert!(!item.section_name().is_empty(), "{}", item.id());
+        }
+
+        let metadata = |item: &ListItem| {
+            (
+                item.section_name(),
+                item.sort_priority(),
+                item.config_module(),
+                item.action_label(),
+            )
+        };
+        let search = items.iter().find(|i| matches!(i, ListItem::Search(_)));
+        let ai = items.iter().find(|i| matches!(i, ListItem::Ai(_)));
+        assert_eq!(
+            search.map(metadata),
+            Some(("Search", 1, ConfigModule::Search, "Open"))
+        );
+        assert_eq!(ai.map(metadata), Some(("AI", 1, ConfigModule::Ai, "Ask")));
+        assert_eq!(search.unwrap().name(), "Search on Google");
+    }
+
+    #[test]
+    fn test_copy_payload() {
+        let payloads: Vec<(&str, Option<String>)> = mock_list_item_variants()
+            .iter()
+            .map(|item| (item.section_name(), item.copy_payload()))
+            .collect();
+        let color = ColorItem::parse("#3366ff")
+            .unwrap()
+            .text_for_clipboard(false);
+
+        assert_eq!(
+            payloads,
+            vec![
+                ("Applications", Some("/usr/bin/firefox".to_string())),
+                ("Windows", Some("Terminal".to_string())),
+                ("Commands", None),
+                ("Commands", None),
+                ("Calculator", Some("4".to_string())),
+                ("Color", Some(color)),
+                ("Path", Some("/".to_string())),
+                (
+                    "Search",
+                    Some("https://www.google.com/search?q=rust".to_string())
+                ),
+                ("AI", None),
+                ("Recent Searches", Some("rust book".to_string())),
+                ("Themes", None),
+            ]
+        );
+
+        let command = ListItem::Action(ActionItem::builtin(ActionKind::Command {
+            command: "notify-send hi".to_string(),
+            shell: false,
+        }));
+        assert_eq!(command.copy_payload().as_deref(), Some("notify-send hi"));
+    }
+
+    #[test]
+    fn test_secondary_action_labels() {
+        let calculator = CalculatorItem {
+            id: "calc".to_string(),
+            expression: "1000*2".to_string(),
+            display_result: "2,000".to_string(),
+            clipboard_result: Some("2000".to_string()),
+            is_error: false,
+            delta: None,
+            bindings: Vec::new(),
+        };
+        let error = CalculatorItem {
+            clipboard_result: None,
+            is_error: true,
+            ..calculator.clone()
+        };
+        let group = group_windows(vec![
+            mock_window("One", "kitty"),
+            mock_window("Two", "kitty"),
+        ]);
+
+        let cases = [
+            (
+                ListItem::Application(mock_application("Firefox")),
+                Some("Edit entry"),
+            ),
+            (
+                ListItem::Window(mock_window("Firefox", "firefox")),
+                Some("Copy"),
+            ),
+            (ListItem::Window(group[0].clone()), Some("Expand")),
+            (ListItem::Calculator(calculator), Some("Copy as…")),
+            (ListItem::Calculator(error), None),
+            (
+                ListItem::Color(ColorItem::parse("#ff0000").unwrap()),
+                Some("Copy HSL"),
+            ),
+            (
+                ListItem::Path(PathItem::detect_with_home("/", None).unwrap()),
+                Some("Reveal"),
+            ),
+            (ListItem::Ai(AiItem::new("hello".to_string())), None),
+            (ListItem::Submenu(SubmenuItem::list("sub", "Sub")), None),
+        ];
+
+        for (item, expected) in cases {
+            assert_eq!(item.secondary_action_label(), expected, "{}", item.name());
+        }
+    }
+}
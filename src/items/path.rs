@@ -0,0 +1,239 @@
+use super::traits::{Categorizable, DisplayItem, Executable, IconProvider, Previewable};
+use crate::error::LaunchError;
+use crate::process;
+use crate::ui::utils::{format_age, format_size};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// An existing file or directory whose path was typed or pasted as the query.
+#[derive(Clone, Debug)]
+pub struct PathItem {
+    /// Unique identifier for this item.
+    pub id: String,
+    /// Display name (e.g. "Open report.pdf").
+    pub name: String,
+    /// The expanded path shown as the description.
+    pub description: String,
+    /// The expanded path.
+    pub path: PathBuf,
+    /// Whether the path is a directory.
+    pub is_dir: bool,
+    /// Size in bytes, for files.
+    pub size: Option<u64>,
+    /// Last modification time, if the file system reports it.
+    pub modified: Option<SystemTime>,
+}
+
+impl PathItem {
+    /// Create an item if the query is an absolute or `~` path that exists.
+    pub fn detect(query: &str) -> Option<Self> {
+        Self::detect_with_home(query, dirs::home_dir().as_deref())
+    }
+
+    /// Like [`detect`](Self::detect), expanding `~` to `home`.
+    pub fn detect_with_home(query: &str, home: Option<&Path>) -> Option<Self> {
+        Self::from_path(expand_path(query, home)?)
+    }
+
+    /// Create an item for a path if it exists, e.g. a file index result.
+    pub fn from_path(path: PathBuf) -> Option<Self> {
+        let metadata = path.metadata().ok()?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Some(Self {
+            id: format!("path-{}", path.display()),
+            name: format!("Open {file_name}"),
+            description: path.display().to_string(),
+            is_dir: metadata.is_dir(),
+            size: metadata.is_file().then_some(metadata.len()),
+            modified: metadata.modified().ok(),
+            path,
+        })
+    }
+
+    /// The description with the size and age appended where known,
+    /// e.g. "/tmp/x.png · 1.2 MB · 3 hours ago".
+    pub fn description_with_metadata(&self, now: SystemTime) -> String {
+        let size = self.size.map(format_size);
+        let age = self.modified.map(|modified| format_age(modified, now));
+        std::iter::once(self.description.clone())
+            .chain(size)
+            .chain(age)
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+
+    /// The directory shown by the reveal action: the parent of the path.
+    pub fn reveal_dir(&self) -> &Path {
+        self.path.parent().unwrap_or(&self.path)
+    }
+
+    /// Open the path with its default application.
+    pub fn open(&self) -> Result<(), LaunchError> {
+        process::open_url(&self.path.to_string_lossy()).map_err(|source| LaunchError {
+            app: self.description.clone(),
+            source,
+        })
+    }
+
+    /// Open the containing directory in the file manager.
+    pub fn reveal(&self) -> Result<(), LaunchError> {
+        process::open_url(&self.reveal_dir().to_string_lossy()).map_err(|source| LaunchError {
+            app: "file manager".to_string(),
+            source,
+        })
+    }
+}
+
+/// Expand a query that looks like a path (`/...`, `~` or `~/...`).
+///
+/// Surrounding quotes, as added by some file managers when copying, are
+/// removed. Returns None for anything else.
+fn expand_path(query: &str, home: Option<&Path>) -> Option<PathBuf> {
+    let query = query.trim();
+    let query = ['\'', '"']
+        .iter()
+        .find_map(|quote| {
+            query
+                .strip_prefix(*quote)
+                .and_then(|rest| rest.strip_suffix(*quote))
+        })
+        .unwrap_or(query);
+    if query.contains('\n') {
+        return None;
+    }
+
+    if query.starts_with('/') {
+        return Some(PathBuf::from(query));
+    }
+    match query.strip_prefix('~')? {
+        "" => home.map(Path::to_path_buf),
+        rest => Some(home?.join(rest.strip_prefix('/')?)),
+    }
+}
+
+impl DisplayItem for PathItem {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some(&self.description)
+    }
+
+    fn action_label(&self) -> &'static str {
+        "Open"
+    }
+
+    fn secondary_action_label(&self) -> Option<&'static str> {
+        Some("Reveal")
+    }
+}
+
+impl IconProvider for PathItem {
+    fn icon_name(&self) -> Option<&str> {
+        Some(if self.is_dir { "folder" } else { "file" })
+    }
+}
+
+impl Executable for PathItem {
+    fn execute(&self) -> anyhow::Result<()> {
+        Ok(self.open()?)
+    }
+}
+
+impl Previewable for PathItem {}
+
+impl Categorizable for PathItem {
+    fn section_name(&self) -> &'static str {
+        "Path"
+    }
+
+    fn sort_priority(&self) -> u8 {
+        0
+    }
+}
+
+impl From<PathItem> for super::ListItem {
+    fn from(item: PathItem) -> Self {
+        Self::Path(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    fn temp_home(name: &str) -> TempDir {
+        let home = TempDir::new(&format!("path-item-{name}"));
+        std::fs::create_dir_all(home.join("Downloads")).unwrap();
+        std::fs::write(home.join("Downloads").join("x.png"), b"png").unwrap();
+        home
+    }
+
+    #[test]
+    fn test_expand_path() {
+        let home = Path::new("/home/me");
+        assert_eq!(
+            expand_path("~/doc.pdf", Some(home)),
+            Some(PathBuf::from("/home/me/doc.pdf"))
+        );
+        assert_eq!(expand_path("~", Some(home)), Some(home.to_path_buf()));
+        assert_eq!(
+            expand_path(" '/tmp/a b.txt' ", Some(home)),
+            Some(PathBuf::from("/tmp/a b.txt"))
+        );
+        assert_eq!(expand_path("~/doc.pdf", None), None);
+        assert_eq!(expand_path("~other/doc.pdf", Some(home)), None);
+        assert_eq!(expand_path("doc.pdf", Some(home)), None);
+        assert_eq!(expand_path("2/3", Some(home)), None);
+    }
+
+    #[test]
+    fn test_detect_existing_paths_only() {
+        let home = temp_home("detect");
+
+        let file = PathItem::detect_with_home("~/Downloads/x.png", Some(home.as_path())).unwrap();
+        assert_eq!(file.path, home.join("Downloads").join("x.png"));
+        assert_eq!(file.name, "Open x.png");
+        assert!(!file.is_dir);
+        assert_eq!(file.reveal_dir(), home.join("Downloads"));
+
+        let absolute = home.join("Downloads");
+        let dir = PathItem::detect_with_home(&absolute.to_string_lossy(), None).unwrap();
+        assert!(dir.is_dir);
+        assert_eq!(dir.icon_name(), Some("folder"));
+
+        assert!(
+            PathItem::detect_with_home("~/Downloads/missing.png", Some(home.as_path())).is_none()
+        );
+        assert!(
+            PathItem::detect_with_home("/nonexistent/zlaunch/file", Some(home.as_path())).is_none()
+        );
+    }
+
+    #[test]
+    fn test_description_with_metadata() {
+        let home = temp_home("metadata");
+        let mut file =
+            PathItem::detect_with_home("~/Downloads/x.png", Some(home.as_path())).unwrap();
+        assert_eq!(file.size, Some(3));
+        let now = file.modified.unwrap() + std::time::Duration::from_secs(2 * 3600);
+        assert_eq!(
+            file.description_with_metadata(now),
+            format!("{} · 3 B · 2 hours ago", file.description)
+        );
+
+        // Without stat results only the path is shown
+        file.size = None;
+        file.modified = None;
+        assert_eq!(file.description_with_metadata(now), file.description);
+    }
+}
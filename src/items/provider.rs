@@ -0,0 +1,766 @@
+//! Item providers: sources of list items for the main launcher list.
+//!
+//! The applications and windows loaded by the daemon, built-in submenus and
+//! actions are providers, and new sources are added by registering another
+//! [`ItemProvider`] instead of special-casing them in the delegate. All
+//! provided items go through the same fuzzy filtering.
+//!
+//! Providers that are expensive to query (file search, bookmarks) can be
+//! wrapped in a [`CachedProvider`].
+
+use super::{ActionItem, ActionKind, ListItem, PathItem, SubmenuItem, SubmenuSource};
+use crate::config::{AppConfig, ConfigMenuEntry, ConfigModule};
+use crate::files::FileIndexHandle;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Most queries a query-dependent provider keeps items cached for.
+const MAX_CACHED_QUERIES: usize = 64;
+
+/// Bumped to invalidate every provider cache at once (e.g. on SIGHUP).
+static CACHE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Make all cached providers refresh on their next use.
+pub fn invalidate_provider_caches() {
+    CACHE_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A source of list items.
+pub trait ItemProvider: Send + Sync {
+    /// Stable identifier of the provider (e.g. "actions").
+    fn id(&self) -> &str;
+
+    /// Whether the provider contributes items with the given combined modules.
+    fn is_available(&self, _combined_modules: &[ConfigModule]) -> bool {
+        true
+    }
+
+    /// Items for a query.
+    ///
+    /// Providers with a fixed set of items can ignore the query; the items
+    /// are fuzzy filtered anyway.
+    fn provide(&self, query: &str) -> Vec<ListItem>;
+
+    /// Whether the items depend on the query (e.g. file search), so they
+    /// are provided again whenever the query changes.
+    fn depends_on_query(&self) -> bool {
+        false
+    }
+}
+
+/// An ordered set of item providers.
+#[derive(Clone, Default)]
+pub struct ProviderRegistry {
+    providers: Vec<Arc<dyn ItemProvider>>,
+}
+
+impl ProviderRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the built-in submenus and actions.
+    pub fn builtin() -> Self {
+        Self::new()
+            .with_provider(SubmenuProvider(SubmenuSource::Emojis))
+            .with_provider(SubmenuProvider(SubmenuSource::Icons))
+            .with_provider(SubmenuProvider(SubmenuSource::Clipboard))
+            .with_provider(SubmenuProvider(SubmenuSource::Themes))
+            .with_provider(SubmenuProvider(SubmenuSource::Calculator))
+            .with_provider(ActionProvider)
+    }
+
+    /// Create a registry with the built-in providers and the custom
+    /// submenus of `cfg`.
+    pub fn for_config(cfg: &AppConfig) -> Self {
+        Self::builtin().with_provider(ConfigSubmenuProvider::new(&cfg.submenus))
+    }
+
+    /// Add a provider after the registered ones (builder pattern).
+    pub fn with_provider(mut self, provider: impl ItemProvider + 'static) -> Self {
+        self.register(provider);
+        self
+    }
+
+    /// Add a provider whose items are cached for `ttl` (builder pattern).
+    pub fn with_cached_provider(
+        self,
+        provider: impl ItemProvider + 'static,
+        ttl: Duration,
+    ) -> Self {
+        self.with_provider(CachedProvider::new(provider, ttl))
+    }
+
+    /// Provide the applications and windows among `items`, replacing the
+    /// ones provided before (builder pattern).
+    ///
+    /// Other kinds of items are left to their own providers.
+    pub fn with_loaded(mut self, items: &[ListItem]) -> Self {
+        self.replace(ApplicationProvider::new(items));
+        self.replace(WindowProvider::new(items));
+        self
+    }
+
+    /// Add a provider after the registered ones.
+    pub fn register(&mut self, provider: impl ItemProvider + 'static) {
+        self.providers.push(Arc::new(provider));
+    }
+
+    /// Replace the provider with the same id, or add it after the
+    /// registered ones.
+    pub fn replace(&mut self, provider: impl ItemProvider + 'static) {
+        let provider: Arc<dyn ItemProvider> = Arc::new(provider);
+        match self.providers.iter_mut().find(|p| p.id() == provider.id()) {
+            Some(existing) => *existing = provider,
+            None => self.providers.push(provider),
+        }
+    }
+
+    /// Ids of the registered providers, in order.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.providers.iter().map(|provider| provider.id())
+    }
+
+    /// Whether any available provider's items depend on the query.
+    pub fn depends_on_query(&self, combined_modules: &[ConfigModule]) -> bool {
+        self.providers
+            .iter()
+            .any(|provider| provider.is_available(combined_modules) && provider.depends_on_query())
+    }
+
+    /// Collect the items of all available providers in registration order.
+    ///
+    /// When several providers offer an item with the same id, the first
+    /// one wins.
+    pub fn provide(&self, query: &str, combined_modules: &[ConfigModule]) -> Vec<ListItem> {
+        let mut seen = HashSet::new();
+        self.providers
+            .iter()
+            .filter(|provider| provider.is_available(combined_modules))
+            .flat_map(|provider| provider.provide(query))
+            .filter(|item| seen.insert(item.id().to_string()))
+            .collect()
+    }
+}
+
+/// Items fetched at one time.
+struct CacheEntry {
+    fetched: Instant,
+    items: Vec<ListItem>,
+}
+
+/// Cached items per query, valid for one cache generation.
+#[derive(Default)]
+struct Cache {
+    generation: u64,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Serves a provider's items from a cache, refreshing them once `ttl` has
+/// passed or after an invalidation.
+///
+/// Items of providers that depend on the query are cached per query, the
+/// others once for all queries.
+pub struct CachedProvider<P> {
+    inner: P,
+    ttl: Duration,
+    invalidated: Arc<AtomicBool>,
+    cache: Mutex<Cache>,
+}
+
+impl<P: ItemProvider> CachedProvider<P> {
+    /// Cache the items of `inner` for `ttl`.
+    pub fn new(inner: P, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            invalidated: Arc::new(AtomicBool::new(false)),
+            cache: Mutex::new(Cache::default()),
+        }
+    }
+
+    /// Handle for invalidating this cache, e.g. from a file watcher.
+    pub fn invalidator(&self) -> CacheInvalidator {
+        CacheInvalidator(self.invalidated.clone())
+    }
+}
+
+impl<P: ItemProvider> ItemProvider for CachedProvider<P> {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn is_available(&self, combined_modules: &[ConfigModule]) -> bool {
+        self.inner.is_available(combined_modules)
+    }
+
+    fn provide(&self, query: &str) -> Vec<ListItem> {
+        let key = if self.inner.depends_on_query() {
+            query
+        } else {
+            ""
+        };
+
+        let generation = CACHE_GENERATION.load(Ordering::Relaxed);
+        let mut cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        if self.invalidated.swap(false, Ordering::Relaxed) || cache.generation != generation {
+            cache.entries.clear();
+            cache.generation = generation;
+        }
+        cache
+            .entries
+            .retain(|_, entry| entry.fetched.elapsed() < self.ttl);
+        if let Some(entry) = cache.entries.get(key) {
+            return entry.items.clone();
+        }
+
+        let items = self.inner.provide(query);
+        if cache.entries.len() >= MAX_CACHED_QUERIES {
+            cache.entries.clear();
+        }
+        cache.entries.insert(
+            key.to_string(),
+            CacheEntry {
+                fetched: Instant::now(),
+                items: items.clone(),
+            },
+        );
+        items
+    }
+
+    fn depends_on_query(&self) -> bool {
+        self.inner.depends_on_query()
+    }
+}
+
+/// Invalidates a [`CachedProvider`] from elsewhere.
+#[derive(Clone)]
+pub struct CacheInvalidator(Arc<AtomicBool>);
+
+impl CacheInvalidator {
+    /// Make the provider refresh on its next use.
+    pub fn invalidate(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Applications loaded by the daemon.
+pub struct ApplicationProvider {
+    items: Vec<ListItem>,
+}
+
+impl ApplicationProvider {
+    /// Provide the applications among `items`.
+    pub fn new(items: &[ListItem]) -> Self {
+        Self {
+            items: items
+                .iter()
+                .filter(|item| item.is_application())
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl ItemProvider for ApplicationProvider {
+    fn id(&self) -> &str {
+        "applications"
+    }
+
+    fn is_available(&self, combined_modules: &[ConfigModule]) -> bool {
+        combined_modules.contains(&ConfigModule::Applications)
+    }
+
+    fn provide(&self, _query: &str) -> Vec<ListItem> {
+        self.items.clone()
+    }
+}
+
+/// Open windows listed by the compositor.
+pub struct WindowProvider {
+    items: Vec<ListItem>,
+}
+
+impl WindowProvider {
+    /// Provide the windows among `items`.
+    pub fn new(items: &[ListItem]) -> Self {
+        Self {
+            items: items
+                .iter()
+                .filter(|item| matches!(item, ListItem::Window(_)))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl ItemProvider for WindowProvider {
+    fn id(&self) -> &str {
+        "windows"
+    }
+
+    fn is_available(&self, combined_modules: &[ConfigModule]) -> bool {
+        combined_modules.contains(&ConfigModule::Windows)
+    }
+
+    fn provide(&self, _query: &str) -> Vec<ListItem> {
+        self.items.clone()
+    }
+}
+
+/// A built-in submenu (emojis, icons, clipboard history, themes,
+/// calculator), available while its module is combined.
+pub struct SubmenuProvider(pub SubmenuSource);
+
+impl SubmenuProvider {
+    fn submenu(&self) -> SubmenuItem {
+        let submenu = match self.0 {
+            SubmenuSource::Emojis => SubmenuItem::grid("submenu-emojis", "Emojis", 8)
+                .with_description("Search and copy emojis")
+                .with_icon("smiley"),
+            SubmenuSource::Icons => SubmenuItem::grid("submenu-icons", "Icons", 8)
+                .with_description("Search and copy Nerd Font icons")
+                .with_icon("terminal"),
+            SubmenuSource::Clipboard => SubmenuItem::list("submenu-clipboard", "Clipboard History")
+                .with_description("View and paste clipboard history")
+                .with_icon("clipboard"),
+            SubmenuSource::Themes => SubmenuItem::list("submenu-themes", "Themes")
+                .with_description("Browse and apply themes")
+                .with_icon("palette"),
+            SubmenuSource::Calculator => SubmenuItem::list("submenu-calculator", "Calculator")
+                .with_description("Evaluate expressions in a scratchpad")
+                .with_icon("calculator"),
+        };
+        submenu.with_source(self.0)
+    }
+}
+
+impl ItemProvider for SubmenuProvider {
+    fn id(&self) -> &str {
+        match self.0 {
+            SubmenuSource::Emojis => "submenu-emojis",
+            SubmenuSource::Icons => "submenu-icons",
+            SubmenuSource::Clipboard => "submenu-clipboard",
+            SubmenuSource::Themes => "submenu-themes",
+            SubmenuSource::Calculator => "submenu-calculator",
+        }
+    }
+
+    fn is_available(&self, combined_modules: &[ConfigModule]) -> bool {
+        combined_modules.contains(&self.0.config_module())
+    }
+
+    fn provide(&self, _query: &str) -> Vec<ListItem> {
+        vec![ListItem::Submenu(self.submenu())]
+    }
+}
+
+/// Built-in actions (shutdown, reboot, etc.).
+pub struct ActionProvider;
+
+impl ItemProvider for ActionProvider {
+    fn id(&self) -> &str {
+        "actions"
+    }
+
+    fn is_available(&self, combined_modules: &[ConfigModule]) -> bool {
+        combined_modules.contains(&ConfigModule::Actions)
+    }
+
+    fn provide(&self, _query: &str) -> Vec<ListItem> {
+        // Daemon management only makes sense inside the daemon
+        let daemon = crate::daemon::is_running();
+        ActionItem::builtins()
+            .into_iter()
+            .filter(|action| daemon || !action.kind.requires_daemon())
+            .map(ListItem::Action)
+            .collect()
+    }
+}
+
+/// Custom submenus from the config (`[[submenus]]`), whose entries run
+/// commands or open further submenus.
+pub struct ConfigSubmenuProvider {
+    items: Vec<ListItem>,
+}
+
+impl ConfigSubmenuProvider {
+    /// Build the submenus of the configured entries.
+    pub fn new(entries: &[ConfigMenuEntry]) -> Self {
+        Self {
+            items: menu_items(entries, "submenu-custom"),
+        }
+    }
+}
+
+impl ItemProvider for ConfigSubmenuProvider {
+    fn id(&self) -> &str {
+        "config-submenus"
+    }
+
+    fn is_available(&self, combined_modules: &[ConfigModule]) -> bool {
+        combined_modules.contains(&ConfigModule::Actions)
+    }
+
+    fn provide(&self, _query: &str) -> Vec<ListItem> {
+        self.items.clone()
+    }
+}
+
+/// Files of the file index matching the query.
+///
+/// Searching walks the whole index, so it is usually wrapped in a
+/// [`CachedProvider`]. Indexed files deleted since are left out.
+pub struct FileSearchProvider {
+    index: FileIndexHandle,
+    limit: usize,
+}
+
+impl FileSearchProvider {
+    /// Search `index` for at most `limit` files per query.
+    pub fn new(index: FileIndexHandle, limit: usize) -> Self {
+        Self { index, limit }
+    }
+}
+
+impl ItemProvider for FileSearchProvider {
+    fn id(&self) -> &str {
+        "files"
+    }
+
+    fn provide(&self, query: &str) -> Vec<ListItem> {
+        self.index
+            .search(query, self.limit)
+            .into_iter()
+            .filter_map(PathItem::from_path)
+            .map(ListItem::Path)
+            .collect()
+    }
+
+    fn depends_on_query(&self) -> bool {
+        true
+    }
+}
+
+/// Convert menu entries to items with ids below `parent_id`.
+///
+/// Entries with `items` become submenus, entries with a `command` actions;
+/// entries with neither are skipped.
+fn menu_items(entries: &[ConfigMenuEntry], parent_id: &str) -> Vec<ListItem> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let id = format!(
+                "{parent_id}-{}",
+                entry.name.to_lowercase().replace(char::is_whitespace, "-")
+            );
+            if !entry.items.is_empty() {
+                let mut submenu = SubmenuItem::list(id.clone(), entry.name.clone())
+                    .with_children(menu_items(&entry.items, &id));
+                submenu.description = entry.description.clone();
+                submenu.icon_name = entry.icon.clone();
+                return Some(ListItem::Submenu(submenu));
+            }
+            let command = entry.command.clone()?;
+            Some(ListItem::Action(ActionItem::new(
+                id,
+                entry.name.clone(),
+                entry.description.clone(),
+                entry.icon.clone(),
+                ActionKind::Command {
+                    command,
+                    shell: entry.shell,
+                },
+            )))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{mock_application, mock_window};
+
+    /// Provider echoing the query as an application.
+    struct EchoProvider(&'static str);
+
+    impl ItemProvider for EchoProvider {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn provide(&self, query: &str) -> Vec<ListItem> {
+            vec![ListItem::Application(mock_application(query))]
+        }
+
+        fn depends_on_query(&self) -> bool {
+            true
+        }
+    }
+
+    /// Provider offering fixed applications.
+    struct FixedProvider(Vec<&'static str>);
+
+    impl ItemProvider for FixedProvider {
+        fn id(&self) -> &str {
+            "fixed"
+        }
+
+        fn is_available(&self, combined_modules: &[ConfigModule]) -> bool {
+            combined_modules.contains(&ConfigModule::Applications)
+        }
+
+        fn provide(&self, _query: &str) -> Vec<ListItem> {
+            self.0
+                .iter()
+                .map(|name| ListItem::Application(mock_application(name)))
+                .collect()
+        }
+    }
+
+    /// Provider counting how often it is asked for items.
+    struct CountingProvider {
+        calls: Arc<AtomicU64>,
+        query_dependent: bool,
+    }
+
+    impl CountingProvider {
+        fn new(query_dependent: bool) -> (Self, Arc<AtomicU64>) {
+            let calls = Arc::new(AtomicU64::new(0));
+            let provider = Self {
+                calls: calls.clone(),
+                query_dependent,
+            };
+            (provider, calls)
+        }
+    }
+
+    impl ItemProvider for CountingProvider {
+        fn id(&self) -> &str {
+            "counting"
+        }
+
+        fn provide(&self, _query: &str) -> Vec<ListItem> {
+            let calls = self.calls.fetch_add(1, Ordering::Relaxed) + 1;
+            vec![ListItem::Application(mock_application(&format!(
+                "Call {calls}"
+            )))]
+        }
+
+        fn depends_on_query(&self) -> bool {
+            self.query_dependent
+        }
+    }
+
+    fn names(items: &[ListItem]) -> Vec<&str> {
+        items.iter().map(ListItem::name).collect()
+    }
+
+    #[test]
+    fn test_registry_merges_providers_in_order() {
+        let registry = ProviderRegistry::new()
+            .with_provider(EchoProvider("echo"))
+            .with_provider(FixedProvider(vec!["Firefox", "Kitty"]));
+        assert_eq!(registry.ids().collect::<Vec<_>>(), vec!["echo", "fixed"]);
+
+        let items = registry.provide("Zed", &[ConfigModule::Applications]);
+        assert_eq!(names(&items), vec!["Zed", "Firefox", "Kitty"]);
+
+        // Unavailable providers are skipped
+        let items = registry.provide("Zed", &[ConfigModule::Windows]);
+        assert_eq!(names(&items), vec!["Zed"]);
+
+        // Duplicate ids keep the first provider's item
+        let items = registry.provide("Kitty", &[ConfigModule::Applications]);
+        assert_eq!(names(&items), vec!["Kitty", "Firefox"]);
+    }
+
+    #[test]
+    fn test_cached_provider_within_ttl() {
+        let (provider, calls) = CountingProvider::new(false);
+        let cached = CachedProvider::new(provider, Duration::from_secs(3600));
+
+        assert_eq!(names(&cached.provide("a")), vec!["Call 1"]);
+        assert_eq!(names(&cached.provide("ab")), vec!["Call 1"]);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // An expired TTL refreshes every time
+        let (provider, calls) = CountingProvider::new(false);
+        let cached = CachedProvider::new(provider, Duration::ZERO);
+        cached.provide("");
+        cached.provide("");
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_cached_provider_invalidation() {
+        let (provider, calls) = CountingProvider::new(false);
+        let cached = CachedProvider::new(provider, Duration::from_secs(3600));
+        cached.provide("");
+
+        cached.invalidator().invalidate();
+        assert_eq!(names(&cached.provide("")), vec!["Call 2"]);
+        assert_eq!(names(&cached.provide("")), vec!["Call 2"]);
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_query_dependent_provider_is_cached_per_query() {
+        let (provider, calls) = CountingProvider::new(true);
+        let registry =
+            ProviderRegistry::new().with_cached_provider(provider, Duration::from_secs(3600));
+        assert_eq!(names(&registry.provide("a", &[])), vec!["Call 1"]);
+        assert_eq!(names(&registry.provide("ab", &[])), vec!["Call 2"]);
+        assert_eq!(names(&registry.provide("a", &[])), vec!["Call 1"]);
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_file_search_provider() {
+        let root = crate::test_utils::TempDir::new("provider-files");
+        let report = root.join("quarterly-report.pdf");
+        std::fs::write(&report, b"pdf").unwrap();
+        let mut index = crate::files::FileIndex::empty(vec![root.to_path_buf()]);
+        let rules = crate::files::IgnoreRules::default();
+        assert!(index.insert(report.clone(), &rules, 100));
+        // Indexed but deleted since: left out
+        assert!(index.insert(root.join("quarterly-gone.pdf"), &rules, 100));
+
+        let provider = FileSearchProvider::new(FileIndexHandle::with_index(index), 5);
+        let items = provider.provide("quarterly");
+        assert_eq!(items.len(), 1);
+        assert!(matches!(&items[0], ListItem::Path(item) if item.path == report));
+    }
+
+    #[test]
+    fn test_registry_depends_on_query() {
+        let registry = ProviderRegistry::builtin();
+        assert!(!registry.depends_on_query(&[ConfigModule::Actions]));
+
+        let registry = registry.with_provider(EchoProvider("echo"));
+        assert!(registry.depends_on_query(&[ConfigModule::Actions]));
+    }
+
+    #[test]
+    fn test_builtin_providers() {
+        let registry = ProviderRegistry::builtin();
+        assert_eq!(
+            registry.ids().collect::<Vec<_>>(),
+            vec![
+                "submenu-emojis",
+                "submenu-icons",
+                "submenu-clipboard",
+                "submenu-themes",
+                "submenu-calculator",
+                "actions"
+            ]
+        );
+
+        // Only the submenus of combined modules are available
+        let items = registry.provide("", &[ConfigModule::Emojis]);
+        assert_eq!(
+            items.iter().map(ListItem::id).collect::<Vec<_>>(),
+            vec!["submenu-emojis"]
+        );
+        assert!(
+            registry
+                .provide("", &[ConfigModule::Actions])
+                .iter()
+                .any(ListItem::is_action)
+        );
+    }
+
+    #[test]
+    fn test_loaded_items_are_providers() {
+        let items = vec![
+            ListItem::Application(mock_application("Firefox")),
+            ListItem::Window(mock_window("Terminal", "kitty")),
+        ];
+        let mut registry = ProviderRegistry::builtin().with_loaded(&items);
+
+        let ids = |registry: &ProviderRegistry, modules: &[ConfigModule]| {
+            registry
+                .provide("", modules)
+                .iter()
+                .map(|item| item.id().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(&registry, &[ConfigModule::Applications]),
+            vec!["app-firefox"]
+        );
+        assert_eq!(
+            ids(&registry, &[ConfigModule::Windows]),
+            vec!["window-terminal"]
+        );
+
+        // Loading again replaces the loaded items
+        registry = registry.with_loaded(&[ListItem::Application(mock_application("Kitty"))]);
+        assert_eq!(
+            ids(
+                &registry,
+                &[ConfigModule::Applications, ConfigModule::Windows]
+            ),
+            vec!["app-kitty"]
+        );
+        assert_eq!(registry.ids().filter(|id| *id == "applications").count(), 1);
+    }
+
+    #[test]
+    fn test_config_submenus_nest() {
+        let entry = |name: &str, command: Option<&str>, items| ConfigMenuEntry {
+            name: name.to_string(),
+            description: None,
+            icon: None,
+            command: command.map(str::to_string),
+            shell: false,
+            items,
+        };
+        let cfg = AppConfig {
+            submenus: vec![entry(
+                "Tools",
+                None,
+                vec![
+                    entry("Top", Some("htop"), Vec::new()),
+                    entry(
+                        "Network",
+                        None,
+                        vec![entry("Ping", Some("ping 1.1.1.1"), Vec::new())],
+                    ),
+                    entry("Nothing", None, Vec::new()),
+                ],
+            )],
+            ..AppConfig::default()
+        };
+        let registry = ProviderRegistry::for_config(&cfg);
+        assert!(
+            !registry
+                .provide("", &[ConfigModule::Emojis])
+                .iter()
+                .any(|item| item.id() == "submenu-custom-tools")
+        );
+
+        let items = registry.provide("", &[ConfigModule::Actions]);
+        let Some(ListItem::Submenu(tools)) = items
+            .iter()
+            .find(|item| item.id() == "submenu-custom-tools")
+        else {
+            panic!("custom submenu missing");
+        };
+        assert_eq!(names(&tools.children), vec!["Top", "Network"]);
+        let ListItem::Submenu(network) = &tools.children[1] else {
+            panic!("nested submenu missing");
+        };
+        assert_eq!(network.id, "submenu-custom-tools-network");
+        assert!(matches!(
+            &network.children[..],
+            [ListItem::Action(ActionItem {
+                kind: ActionKind::Command { command, .. },
+                ..
+            })] if command == "ping 1.1.1.1"
+        ));
+    }
+}
@@ -1,6 +1,8 @@
 use crate::assets::PhosphorIcon;
+use crate::desktop;
 use crate::process;
 use crate::search::SearchProvider;
+use std::path::PathBuf;
 
 use super::traits::{Categorizable, DisplayItem, Executable, IconProvider};
 
@@ -17,6 +19,8 @@ pub struct SearchItem {
     pub query: String,
     /// The generated search URL
     pub url: String,
+    /// Providers the query can be cycled through, including the current one
+    pub variants: Vec<SearchProvider>,
 }
 
 impl SearchItem {
@@ -31,13 +35,56 @@ impl SearchItem {
             provider,
             query,
             url,
+            variants: Vec::new(),
         }
     }
 
+    /// Create an item opening the provider's homepage, for a trigger typed
+    /// without a query. Returns None if the homepage can't be derived.
+    pub fn homepage(provider: SearchProvider) -> Option<Self> {
+        let url = provider.homepage_url()?;
+        Some(Self {
+            id: format!("search-{}-homepage", provider.name.to_lowercase()),
+            name: format!("Open {}", provider.name),
+            provider,
+            query: String::new(),
+            url,
+            variants: Vec::new(),
+        })
+    }
+
+    /// Offer the same query on other providers (builder pattern).
+    pub fn with_variants(mut self, providers: &[SearchProvider]) -> Self {
+        self.variants = providers.to_vec();
+        self
+    }
+
+    /// Switch to the next provider variant, rebuilding the URL for the same
+    /// query. Returns false if there is no other provider to switch to.
+    pub fn cycle_provider(&mut self) -> bool {
+        if self.variants.len() < 2 {
+            return false;
+        }
+        let next = self
+            .variants
+            .iter()
+            .position(|provider| *provider == self.provider)
+            .map_or(0, |index| (index + 1) % self.variants.len());
+        let variants = std::mem::take(&mut self.variants);
+        *self = Self::new(variants[next].clone(), std::mem::take(&mut self.query));
+        self.variants = variants;
+        true
+    }
+
     /// Get the icon for this search item.
     pub fn icon(&self) -> PhosphorIcon {
         self.provider.icon
     }
+
+    /// Get the provider's favicon, if one is cached.
+    pub fn icon_path(&self) -> Option<&PathBuf> {
+        self.provider.icon_path.as_ref()
+    }
 }
 
 impl DisplayItem for SearchItem {
@@ -64,9 +111,11 @@ impl IconProvider for SearchItem {
 
 impl Executable for SearchItem {
     fn execute(&self) -> anyhow::Result<()> {
-        // Open URL in browser, disowned from daemon
-        process::open_url(&self.url)?;
-        Ok(())
+        // Open URL in the provider's browser or the default handler, disowned from daemon
+        match self.provider.browser_args(&self.url) {
+            Some(args) => Ok(process::launch_args(&args, &[])?),
+            None => desktop::open_url(&self.url),
+        }
     }
 }
 
@@ -1,4 +1,5 @@
 use super::traits::{Categorizable, DisplayItem, IconProvider};
+use crate::config::ConfigModule;
 
 /// The layout style for a submenu.
 #[derive(Clone, Debug, Default)]
@@ -12,6 +13,29 @@ pub enum SubmenuLayout {
     Custom(String),
 }
 
+/// The data a submenu shows when opened.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmenuSource {
+    Emojis,
+    Icons,
+    Clipboard,
+    Themes,
+    Calculator,
+}
+
+impl SubmenuSource {
+    /// The module that has to be combined for the submenu to show.
+    pub fn config_module(self) -> ConfigModule {
+        match self {
+            Self::Emojis => ConfigModule::Emojis,
+            Self::Icons => ConfigModule::Icons,
+            Self::Clipboard => ConfigModule::Clipboard,
+            Self::Themes => ConfigModule::Themes,
+            Self::Calculator => ConfigModule::Calculator,
+        }
+    }
+}
+
 /// A submenu item that opens a nested list or custom UI.
 #[derive(Clone, Debug)]
 pub struct SubmenuItem {
@@ -20,6 +44,9 @@ pub struct SubmenuItem {
     pub description: Option<String>,
     pub icon_name: Option<String>,
     pub layout: SubmenuLayout,
+    pub source: Option<SubmenuSource>,
+    /// Child items shown when the submenu has no dedicated view
+    pub children: Vec<super::ListItem>,
 }
 
 impl SubmenuItem {
@@ -36,6 +63,8 @@ impl SubmenuItem {
             description,
             icon_name,
             layout,
+            source: None,
+            children: Vec::new(),
         }
     }
 
@@ -47,6 +76,8 @@ impl SubmenuItem {
             description: None,
             icon_name: None,
             layout: SubmenuLayout::List,
+            source: None,
+            children: Vec::new(),
         }
     }
 
@@ -58,6 +89,8 @@ impl SubmenuItem {
             description: None,
             icon_name: None,
             layout: SubmenuLayout::Grid { columns },
+            source: None,
+            children: Vec::new(),
         }
     }
 
@@ -73,6 +106,8 @@ impl SubmenuItem {
             description: None,
             icon_name: None,
             layout: SubmenuLayout::Custom(layout_name.into()),
+            source: None,
+            children: Vec::new(),
         }
     }
 
@@ -82,6 +117,18 @@ impl SubmenuItem {
         self
     }
 
+    /// Builder method to set the data source.
+    pub fn with_source(mut self, source: SubmenuSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Builder method to set the child items (which may include submenus).
+    pub fn with_children(mut self, children: Vec<super::ListItem>) -> Self {
+        self.children = children;
+        self
+    }
+
     /// Builder method to set an icon name.
     pub fn with_icon(mut self, icon_name: impl Into<String>) -> Self {
         self.icon_name = Some(icon_name.into());
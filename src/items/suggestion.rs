@@ -0,0 +1,62 @@
+use super::traits::{Categorizable, DisplayItem, IconProvider};
+use crate::assets::PhosphorIcon;
+
+/// A previously confirmed query that starts with the current query.
+#[derive(Clone, Debug)]
+pub struct SuggestionItem {
+    /// Unique identifier for this item
+    pub id: String,
+    /// The remembered query, set as the query on confirm
+    pub query: String,
+}
+
+impl SuggestionItem {
+    /// Create a suggestion for a remembered query.
+    pub fn new(query: String) -> Self {
+        let id = format!("suggestion-{}", query.replace(' ', "-").to_lowercase());
+        Self { id, query }
+    }
+
+    /// Get the icon for this suggestion.
+    pub fn icon(&self) -> PhosphorIcon {
+        PhosphorIcon::MagnifyingGlass
+    }
+}
+
+impl DisplayItem for SuggestionItem {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.query
+    }
+
+    fn description(&self) -> Option<&str> {
+        None
+    }
+
+    fn action_label(&self) -> &'static str {
+        "Use"
+    }
+}
+
+impl IconProvider for SuggestionItem {
+    // Uses Phosphor icons via icon() method
+}
+
+impl Categorizable for SuggestionItem {
+    fn section_name(&self) -> &'static str {
+        "Recent Searches"
+    }
+
+    fn sort_priority(&self) -> u8 {
+        2
+    }
+}
+
+impl From<SuggestionItem> for super::ListItem {
+    fn from(item: SuggestionItem) -> Self {
+        Self::Suggestion(item)
+    }
+}
@@ -13,6 +13,11 @@ pub trait DisplayItem {
 
     /// Get the action label (e.g., "Open", "Switch", "Run")
     fn action_label(&self) -> &'static str;
+
+    /// Get the label of the secondary (shift+enter) action, if the item has one
+    fn secondary_action_label(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 /// Trait for items that have icons
@@ -1,5 +1,7 @@
+use super::application::ApplicationItem;
 use super::traits::{Categorizable, DisplayItem, Executable, IconProvider};
-use crate::compositor::WindowInfo;
+use crate::compositor::{CompositorCapabilities, WindowInfo};
+use crate::config::WindowCopyFormat;
 use std::path::PathBuf;
 
 /// A window item representing an open window for window switching.
@@ -23,6 +25,11 @@ pub struct WindowItem {
     pub workspace: i32,
     /// Whether this window is currently focused
     pub focused: bool,
+    /// Whether this window is kept above others
+    pub keep_above: bool,
+    /// Windows collapsed into this entry when grouped by application
+    /// (empty for a regular window).
+    pub group: Vec<WindowItem>,
 }
 
 impl WindowItem {
@@ -51,13 +58,18 @@ impl WindowItem {
             icon_path,
             workspace,
             focused,
+            keep_above: false,
+            group: Vec::new(),
         }
     }
 
     /// Create a WindowItem from compositor WindowInfo.
     pub fn from_window_info(info: WindowInfo, icon_path: Option<PathBuf>) -> Self {
         let app_name = titlecase_app_name(&info.class);
-        let description = format!("{} - Workspace {}", app_name, info.workspace);
+        let mut description = format!("{} - Workspace {}", app_name, info.workspace);
+        if info.keep_above {
+            description.push_str(" - Always on top");
+        }
         Self {
             id: format!("window-{}", info.address),
             address: info.address,
@@ -68,10 +80,157 @@ impl WindowItem {
             icon_path,
             workspace: info.workspace,
             focused: info.focused,
+            keep_above: info.keep_above,
+            group: Vec::new(),
+        }
+    }
+
+    /// Get the text secondary confirm copies for this window.
+    pub fn text_for_clipboard(&self, format: WindowCopyFormat) -> &str {
+        match format {
+            WindowCopyFormat::Title => &self.title,
+            WindowCopyFormat::Class => &self.app_id,
+        }
+    }
+
+    /// Hand the text for `format` to a clipboard sink such as `copy_to_clipboard`.
+    pub fn copy_with<R>(&self, format: WindowCopyFormat, copy: impl FnOnce(&str) -> R) -> R {
+        copy(self.text_for_clipboard(format))
+    }
+
+    /// The keep-above state toggling this window switches to, or None if
+    /// the compositor can't keep windows above others.
+    pub fn keep_above_toggle(&self, capabilities: &CompositorCapabilities) -> Option<bool> {
+        capabilities.keep_above.then_some(!self.keep_above)
+    }
+
+    /// Check if this entry stands for a group of windows.
+    pub fn is_group(&self) -> bool {
+        !self.group.is_empty()
+    }
+
+    /// Collapse a group of windows sharing an app class, most recently used
+    /// first, into one entry that focuses the most recent window.
+    fn collapse(windows: Vec<WindowItem>) -> Self {
+        let representative = &windows[0];
+
+        Self {
+            id: format!("window-group-{}", representative.app_id),
+            address: representative.address.clone(),
+            title: representative.app_name.clone(),
+            app_id: representative.app_id.clone(),
+            app_name: representative.app_name.clone(),
+            description: format!("{} windows - {}", windows.len(), representative.title),
+            icon_path: representative.icon_path.clone(),
+            workspace: representative.workspace,
+            focused: representative.focused,
+            keep_above: representative.keep_above,
+            group: windows,
         }
     }
 }
 
+/// Find the icon of the application owning windows of the given class.
+///
+/// Uses the same matching as [`application_for_class`]. Applications
+/// without a resolved icon are skipped.
+pub fn icon_for_class(class: &str, applications: &[ApplicationItem]) -> Option<PathBuf> {
+    find_application(
+        class,
+        applications.iter().filter(|app| app.icon_path.is_some()),
+    )
+    .and_then(|app| app.icon_path.clone())
+}
+
+/// Find the application owning windows of the given class.
+///
+/// Matches `StartupWMClass` first, then the desktop entry id and icon name,
+/// all case-insensitively.
+pub fn application_for_class<'a>(
+    class: &str,
+    applications: &'a [ApplicationItem],
+) -> Option<&'a ApplicationItem> {
+    find_application(class, applications.iter())
+}
+
+/// Find an open window of the given application, most recent first.
+///
+/// A window belongs to the application that [`application_for_class`]
+/// picks among all `applications`, so a class claimed by another
+/// application's `StartupWMClass` never matches by name or id.
+pub fn window_for_application<'a>(
+    app: &ApplicationItem,
+    applications: &[ApplicationItem],
+    windows: &'a [WindowInfo],
+) -> Option<&'a WindowInfo> {
+    let app = std::slice::from_ref(app);
+    windows.iter().find(|window| {
+        let owner = application_for_class(&window.class, applications)
+            .or_else(|| application_for_class(&window.class, app));
+        owner.is_some_and(|owner| owner.id == app[0].id)
+    })
+}
+
+fn find_application<'a>(
+    class: &str,
+    applications: impl Iterator<Item = &'a ApplicationItem> + Clone,
+) -> Option<&'a ApplicationItem> {
+    let icon_name = |app: &ApplicationItem| {
+        app.icon_path
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .and_then(|s| s.to_str())
+            .map(str::to_string)
+    };
+
+    applications
+        .clone()
+        .find(|app| {
+            app.startup_wm_class
+                .as_ref()
+                .is_some_and(|wm_class| wm_class.eq_ignore_ascii_case(class))
+        })
+        .or_else(|| {
+            applications
+                .clone()
+                .find(|app| app.id.eq_ignore_ascii_case(class))
+        })
+        .or_else(|| {
+            applications
+                .clone()
+                .find(|app| icon_name(app).is_some_and(|name| name.eq_ignore_ascii_case(class)))
+        })
+}
+
+/// Group windows by app class, one entry per app.
+///
+/// Windows are ordered by recency: the focused one first, then the
+/// compositor's order, which is the focus history where it is tracked.
+/// Groups follow their most recent window. Apps with a single window keep
+/// their regular entry.
+pub fn group_windows(mut windows: Vec<WindowItem>) -> Vec<WindowItem> {
+    windows.sort_by_key(|window| !window.focused);
+
+    let mut groups: Vec<Vec<WindowItem>> = Vec::new();
+    for window in windows {
+        match groups.iter_mut().find(|g| g[0].app_id == window.app_id) {
+            Some(group) => group.push(window),
+            None => groups.push(vec![window]),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|mut group| {
+            if group.len() == 1 {
+                group.remove(0)
+            } else {
+                WindowItem::collapse(group)
+            }
+        })
+        .collect()
+}
+
 impl DisplayItem for WindowItem {
     fn id(&self) -> &str {
         &self.id
@@ -88,6 +247,10 @@ impl DisplayItem for WindowItem {
     fn action_label(&self) -> &'static str {
         "Switch"
     }
+
+    fn secondary_action_label(&self) -> Option<&'static str> {
+        Some(if self.is_group() { "Expand" } else { "Copy" })
+    }
 }
 
 impl IconProvider for WindowItem {
@@ -129,3 +292,225 @@ fn titlecase_app_name(class: &str) -> String {
         Some(first) => first.to_uppercase().chain(chars).collect(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{mock_application, mock_window};
+
+    fn window(title: &str, app_id: &str, address: &str) -> WindowItem {
+        WindowItem {
+            address: address.to_string(),
+            ..mock_window(title, app_id)
+        }
+    }
+
+    #[test]
+    fn test_group_windows_collapses_duplicates() {
+        let windows = vec![
+            window("GitHub", "firefox", "0x1"),
+            window("Terminal", "kitty", "0x2"),
+            window("Docs", "firefox", "0x3"),
+            window("Mail", "firefox", "0x4"),
+        ];
+
+        let grouped = group_windows(windows);
+        assert_eq!(grouped.len(), 2);
+
+        let firefox = &grouped[0];
+        assert!(firefox.is_group());
+        assert_eq!(firefox.group.len(), 3);
+        assert_eq!(firefox.title, "firefox");
+        assert_eq!(firefox.description, "3 windows - GitHub");
+        assert_eq!(firefox.address, "0x1");
+
+        // Single windows are kept as-is
+        assert!(!grouped[1].is_group());
+        assert_eq!(grouped[1].title, "Terminal");
+    }
+
+    #[test]
+    fn test_group_focuses_most_recent_window() {
+        let mut focused = window("Docs", "firefox", "0x3");
+        focused.focused = true;
+        let windows = vec![
+            window("GitHub", "firefox", "0x1"),
+            window("Terminal", "kitty", "0x2"),
+            window("Shell", "kitty", "0x4"),
+            focused,
+        ];
+
+        let grouped = group_windows(windows);
+        assert_eq!(grouped.len(), 2);
+        // The group of the focused window comes first
+        assert_eq!(grouped[0].address, "0x3");
+        assert_eq!(grouped[0].description, "2 windows - Docs");
+        let addresses: Vec<&str> = grouped[0]
+            .group
+            .iter()
+            .map(|w| w.address.as_str())
+            .collect();
+        assert_eq!(addresses, vec!["0x3", "0x1"]);
+        // Without a focused window the compositor's order decides
+        assert_eq!(grouped[1].address, "0x2");
+    }
+
+    #[test]
+    fn test_icon_for_class_uses_startup_wm_class() {
+        let mut app = mock_application("Visual Studio Code");
+        app.icon_path = Some(PathBuf::from(
+            "/usr/share/icons/hicolor/48x48/apps/vscode.png",
+        ));
+        app.startup_wm_class = Some("Code".to_string());
+        let applications = vec![mock_application("Firefox"), app];
+
+        assert_eq!(
+            icon_for_class("code", &applications),
+            Some(PathBuf::from(
+                "/usr/share/icons/hicolor/48x48/apps/vscode.png"
+            ))
+        );
+        assert_eq!(
+            icon_for_class("vscode", &applications),
+            Some(PathBuf::from(
+                "/usr/share/icons/hicolor/48x48/apps/vscode.png"
+            ))
+        );
+        assert_eq!(icon_for_class("kitty", &applications), None);
+    }
+
+    #[test]
+    fn test_application_for_class() {
+        let mut code = mock_application("Visual Studio Code");
+        code.startup_wm_class = Some("Code".to_string());
+        let mut kitty = mock_application("Kitty");
+        kitty.id = "kitty".to_string();
+        let applications = vec![mock_application("Firefox"), code, kitty];
+
+        let owner =
+            |class: &str| application_for_class(class, &applications).map(|app| app.name.as_str());
+        // StartupWMClass, then the desktop entry id
+        assert_eq!(owner("code"), Some("Visual Studio Code"));
+        assert_eq!(owner("Kitty"), Some("Kitty"));
+        // Icons aren't required to find the owner
+        assert_eq!(owner("alacritty"), None);
+    }
+
+    #[test]
+    fn test_window_for_application() {
+        let info = |address: &str, class: &str| WindowInfo {
+            address: address.to_string(),
+            title: class.to_string(),
+            class: class.to_string(),
+            workspace: 1,
+            focused: false,
+            keep_above: false,
+        };
+        let windows = vec![
+            info("0x1", "kitty"),
+            info("0x2", "Code"),
+            info("0x3", "Code"),
+        ];
+
+        let mut code = mock_application("Visual Studio Code");
+        code.startup_wm_class = Some("Code".to_string());
+        let applications = vec![code.clone()];
+        // Switch to the most recent matching window
+        assert_eq!(
+            window_for_application(&code, &applications, &windows).map(|w| w.address.as_str()),
+            Some("0x2")
+        );
+        // Launch normally without an open window
+        let firefox = mock_application("Firefox");
+        assert!(window_for_application(&firefox, &applications, &windows).is_none());
+        assert!(window_for_application(&code, &applications, &[]).is_none());
+    }
+
+    #[test]
+    fn test_window_for_application_respects_startup_wm_class() {
+        let window = WindowInfo {
+            address: "0x1".to_string(),
+            title: "Terminal".to_string(),
+            class: "kitty".to_string(),
+            workspace: 1,
+            focused: false,
+            keep_above: false,
+        };
+        let windows = vec![window];
+
+        // An app whose id equals the class, and one claiming it via StartupWMClass
+        let mut by_id = mock_application("Kitty Launcher");
+        by_id.id = "kitty".to_string();
+        let mut by_wm_class = mock_application("Kitty");
+        by_wm_class.id = "kitty-terminal".to_string();
+        by_wm_class.startup_wm_class = Some("kitty".to_string());
+        let applications = vec![by_id.clone(), by_wm_class.clone()];
+
+        assert!(window_for_application(&by_id, &applications, &windows).is_none());
+        assert_eq!(
+            window_for_application(&by_wm_class, &applications, &windows)
+                .map(|w| w.address.as_str()),
+            Some("0x1")
+        );
+        // Without a competing claim the id still matches
+        assert!(window_for_application(&by_id, &[by_id.clone()], &windows).is_some());
+    }
+
+    #[test]
+    fn test_keep_above_toggle() {
+        let mut window = window("Notes", "kate", "0x1");
+        let kwin = CompositorCapabilities {
+            keep_above: true,
+            ..CompositorCapabilities::limited()
+        };
+        assert_eq!(window.keep_above_toggle(&kwin), Some(true));
+        window.keep_above = true;
+        assert_eq!(window.keep_above_toggle(&kwin), Some(false));
+
+        // Not offered where the compositor can't do it
+        assert_eq!(
+            window.keep_above_toggle(&CompositorCapabilities::full()),
+            None
+        );
+        assert_eq!(
+            window.keep_above_toggle(&CompositorCapabilities::limited()),
+            None
+        );
+        assert_eq!(
+            window.keep_above_toggle(&CompositorCapabilities::none()),
+            None
+        );
+
+        let info = WindowInfo {
+            address: "0x1".to_string(),
+            title: "Notes".to_string(),
+            class: "kate".to_string(),
+            workspace: 1,
+            focused: false,
+            keep_above: true,
+        };
+        let window = WindowItem::from_window_info(info, None);
+        assert!(window.keep_above);
+        assert!(window.description.ends_with("Always on top"));
+    }
+
+    #[test]
+    fn test_secondary_copy_uses_title_or_class() {
+        let window = window("Bug #42 - Mozilla Firefox", "org.mozilla.firefox", "0x1");
+
+        let mut copied = String::new();
+        window.copy_with(WindowCopyFormat::Title, |text| copied = text.to_string());
+        assert_eq!(copied, "Bug #42 - Mozilla Firefox");
+
+        window.copy_with(WindowCopyFormat::Class, |text| copied = text.to_string());
+        assert_eq!(copied, "org.mozilla.firefox");
+    }
+
+    #[test]
+    fn test_group_windows_without_duplicates() {
+        let windows = vec![window("A", "a", "0x1"), window("B", "b", "0x2")];
+        let grouped = group_windows(windows);
+        assert!(grouped.iter().all(|w| !w.is_group()));
+        assert_eq!(grouped.len(), 2);
+    }
+}
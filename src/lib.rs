@@ -10,11 +10,17 @@ pub mod daemon;
 pub mod desktop;
 pub mod emoji;
 pub mod error;
+pub mod files;
+pub mod frecency;
+pub mod glyphs;
 pub mod ipc;
 pub mod items;
 pub mod process;
+pub mod query_history;
 pub mod search;
+pub mod store;
 pub mod tokio_runtime;
+pub mod triggers;
 pub mod ui;
 
 #[cfg(test)]
@@ -5,8 +5,9 @@
 //! to create a new session, preventing them from being killed when the daemon exits.
 
 use crate::desktop::env::get_session_environment;
+use crate::desktop::exec::split_command;
 use crate::error::ProcessError;
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
 use std::os::unix::process::CommandExt;
 use std::process::{Command, Stdio};
 
@@ -27,6 +28,7 @@ pub struct DetachedProcess {
     command: Command,
     use_session_env: bool,
     shell_command: Option<String>,
+    extra_env: Vec<(OsString, OsString)>,
 }
 
 impl DetachedProcess {
@@ -36,6 +38,7 @@ impl DetachedProcess {
             command: Command::new(program),
             use_session_env: false,
             shell_command: None,
+            extra_env: Vec::new(),
         }
     }
 
@@ -48,6 +51,7 @@ impl DetachedProcess {
             command: Command::new("sh"),
             use_session_env: false,
             shell_command: Some(cmd),
+            extra_env: Vec::new(),
         }
     }
 
@@ -77,6 +81,15 @@ impl DetachedProcess {
         self
     }
 
+    /// Set an environment variable for the spawned process.
+    ///
+    /// Applied after the session environment, so it overrides captured values.
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(mut self, key: K, value: V) -> Self {
+        self.extra_env
+            .push((key.as_ref().to_os_string(), value.as_ref().to_os_string()));
+        self
+    }
+
     /// Spawn the detached process.
     ///
     /// The spawned process:
@@ -98,6 +111,8 @@ impl DetachedProcess {
             self.command.env_clear();
             self.command.envs(get_session_environment().iter());
         }
+        self.command
+            .envs(self.extra_env.iter().map(|(k, v)| (k, v)));
 
         // Redirect stdio to null
         self.command
@@ -140,6 +155,48 @@ pub fn launch_exec(exec: &str) -> Result<(), ProcessError> {
         .spawn()
 }
 
+/// Launch an application from an already split argument list.
+///
+/// The first argument is the program and `env` holds extra environment
+/// variables. Empty lists return an error.
+pub fn launch_args(args: &[String], env: &[(String, String)]) -> Result<(), ProcessError> {
+    let Some((program, rest)) = args.split_first() else {
+        return Err(ProcessError::EmptyCommand);
+    };
+
+    env.iter()
+        .fold(DetachedProcess::new(program), |process, (key, value)| {
+            process.env(key, value)
+        })
+        .args(rest)
+        .with_session_env()
+        .spawn()
+}
+
+/// Launch an already split argument list in a terminal emulator.
+///
+/// `terminal` overrides the default terminal if it is installed.
+pub fn launch_args_in_terminal(
+    args: &[String],
+    env: &[(String, String)],
+    terminal: Option<&str>,
+) -> Result<(), ProcessError> {
+    if args.is_empty() {
+        return Err(ProcessError::EmptyCommand);
+    }
+
+    let terminal = choose_terminal(terminal, is_installed, get_terminal)?;
+
+    env.iter()
+        .fold(DetachedProcess::new(&terminal), |process, (key, value)| {
+            process.env(key, value)
+        })
+        .arg("-e")
+        .args(args)
+        .with_session_env()
+        .spawn()
+}
+
 /// Launch an application in a terminal emulator.
 ///
 /// Uses the `$TERMINAL` environment variable, falling back to `xterm`.
@@ -158,9 +215,41 @@ pub fn open_url(url: &str) -> Result<(), ProcessError> {
     DetachedProcess::new("xdg-open").arg(url).spawn()
 }
 
-/// Execute a shell command in a detached process.
-pub fn run_shell_command(command: &str) -> Result<(), ProcessError> {
-    DetachedProcess::shell(command).spawn()
+/// Run a user-configured command in a detached process.
+///
+/// Uses the session environment like application launches. See
+/// [`command_args`] for how the command line is interpreted.
+pub fn run_command(command: &str, shell: bool) -> Result<(), ProcessError> {
+    launch_args(&command_args(command, shell), &[])
+}
+
+/// Build the argument list for a configured command.
+///
+/// Without `shell`, the command is split into arguments (honoring quotes)
+/// and run directly, so `|`, `;` or `$(...)` have no special meaning.
+/// With `shell`, it is passed to `sh -c` to allow pipelines and expansions.
+pub fn command_args(command: &str, shell: bool) -> Vec<String> {
+    if shell {
+        vec!["sh".to_string(), "-c".to_string(), command.to_string()]
+    } else {
+        split_command(command)
+    }
+}
+
+/// Pick the override terminal if it is installed, otherwise the default.
+fn choose_terminal(
+    terminal: Option<&str>,
+    is_installed: impl Fn(&str) -> bool,
+    default: impl FnOnce() -> Result<String, ProcessError>,
+) -> Result<String, ProcessError> {
+    match terminal {
+        Some(terminal) if is_installed(terminal) => Ok(terminal.to_string()),
+        Some(terminal) => {
+            tracing::warn!(terminal, "Terminal override not found, using the default");
+            default()
+        }
+        None => default(),
+    }
 }
 
 /// Get the terminal emulator to use.
@@ -169,18 +258,21 @@ fn get_terminal() -> Result<String, ProcessError> {
         return Ok(terminal);
     }
 
-    // Check for xterm
-    if std::process::Command::new("which")
-        .arg("xterm")
-        .output()
-        .is_ok_and(|o| o.status.success())
-    {
+    if is_installed("xterm") {
         return Ok("xterm".to_string());
     }
 
     Err(ProcessError::NoTerminal)
 }
 
+/// Check with `which` whether a program is on the `PATH`.
+fn is_installed(program: &str) -> bool {
+    std::process::Command::new("which")
+        .arg(program)
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,4 +288,59 @@ mod tests {
         let result = launch_exec("   ");
         assert!(matches!(result, Err(ProcessError::EmptyCommand)));
     }
+
+    #[test]
+    fn test_command_args_without_shell() {
+        assert_eq!(
+            command_args(r#"notify-send "Hello world" done"#, false),
+            vec!["notify-send", "Hello world", "done"]
+        );
+        // Shell syntax is passed through as plain arguments
+        assert_eq!(
+            command_args("echo hi; rm -rf ~", false),
+            vec!["echo", "hi;", "rm", "-rf", "~"]
+        );
+        assert!(command_args("  ", false).is_empty());
+    }
+
+    #[test]
+    fn test_command_args_with_shell() {
+        assert_eq!(
+            command_args("ps aux | grep -c firefox", true),
+            vec!["sh", "-c", "ps aux | grep -c firefox"]
+        );
+    }
+
+    #[test]
+    fn test_run_command_empty() {
+        let result = run_command("", false);
+        assert!(matches!(result, Err(ProcessError::EmptyCommand)));
+    }
+
+    #[test]
+    fn test_launch_args_empty() {
+        let result = launch_args(&[], &[]);
+        assert!(matches!(result, Err(ProcessError::EmptyCommand)));
+    }
+
+    #[test]
+    fn test_choose_terminal_override() {
+        let default = || Ok("foot".to_string());
+        let installed = |program: &str| program == "kitty";
+
+        assert_eq!(
+            choose_terminal(Some("kitty"), installed, default).unwrap(),
+            "kitty"
+        );
+        assert_eq!(choose_terminal(None, installed, default).unwrap(), "foot");
+        // A missing override falls back to the default
+        assert_eq!(
+            choose_terminal(Some("alacritty"), installed, default).unwrap(),
+            "foot"
+        );
+        assert!(matches!(
+            choose_terminal(None, installed, || Err(ProcessError::NoTerminal)),
+            Err(ProcessError::NoTerminal)
+        ));
+    }
 }
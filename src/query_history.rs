@@ -0,0 +1,130 @@
+//! History of confirmed queries for query suggestions.
+//!
+//! Queries that were confirmed into a web search in the main list are
+//! remembered, most recent first. Typing a prefix of a remembered query
+//! offers it as a suggestion that fills in the rest of the query.
+
+use crate::store::PersistedStore;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+lazy_static! {
+    /// Query history shared by the launcher.
+    static ref QUERIES: PersistedStore<QueryHistory> = PersistedStore::open("queries.json");
+}
+
+/// Maximum number of remembered queries.
+pub const MAX_QUERIES: usize = 100;
+
+/// Confirmed queries, most recent first, without case-insensitive duplicates.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryHistory {
+    queries: Vec<String>,
+}
+
+impl QueryHistory {
+    /// Remember a query, moving it to the front if it is already known.
+    ///
+    /// Surrounding whitespace is trimmed and empty queries are ignored. The
+    /// oldest queries are dropped beyond [`MAX_QUERIES`].
+    pub fn record(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        let lowercase = query.to_lowercase();
+        self.queries
+            .retain(|known| known.to_lowercase() != lowercase);
+        self.queries.insert(0, query.to_string());
+        self.queries.truncate(MAX_QUERIES);
+    }
+
+    /// All remembered queries, most recent first.
+    pub fn queries(&self) -> &[String] {
+        &self.queries
+    }
+
+    /// Up to `limit` remembered queries that start with `prefix`, ignoring
+    /// case, most recent first.
+    ///
+    /// A query equal to the prefix is left out, as there is nothing to
+    /// complete.
+    pub fn suggestions(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        let prefix = prefix.trim().to_lowercase();
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+        self.queries
+            .iter()
+            .filter(|query| {
+                let query = query.to_lowercase();
+                query.starts_with(&prefix) && query != prefix
+            })
+            .map(String::as_str)
+            .take(limit)
+            .collect()
+    }
+}
+
+/// Get a snapshot of the shared query history.
+pub fn query_history() -> Arc<QueryHistory> {
+    QUERIES.snapshot()
+}
+
+/// Record a confirmed query in the shared history and persist it.
+///
+/// Nothing is recorded with `query_history = false`.
+pub fn record_query(query: &str) {
+    if !crate::config::config().query_history || query.trim().is_empty() {
+        return;
+    }
+    QUERIES.update(|history| history.record(query));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(queries: &[&str]) -> QueryHistory {
+        let mut history = QueryHistory::default();
+        // Record oldest first so the first query ends up most recent
+        for query in queries.iter().rev() {
+            history.record(query);
+        }
+        history
+    }
+
+    #[test]
+    fn test_suggestions_match_prefix() {
+        let history = history(&["firefox private", "Files", "fish", "terminal"]);
+
+        assert_eq!(
+            history.suggestions("fi", 5),
+            ["firefox private", "Files", "fish"]
+        );
+        assert_eq!(history.suggestions("FIR", 5), ["firefox private"]);
+        assert_eq!(history.suggestions("fi", 2), ["firefox private", "Files"]);
+        assert!(history.suggestions("rust", 5).is_empty());
+        assert!(history.suggestions("  ", 5).is_empty());
+        // Nothing to complete for the full query
+        assert!(history.suggestions("fish", 5).is_empty());
+    }
+
+    #[test]
+    fn test_record_dedupes_and_caps() {
+        let mut history = QueryHistory::default();
+        history.record("firefox");
+        history.record("  ");
+        history.record("terminal");
+        history.record(" Firefox ");
+        assert_eq!(history.queries(), ["Firefox", "terminal"]);
+
+        for i in 0..MAX_QUERIES + 10 {
+            history.record(&format!("query {i}"));
+        }
+        assert_eq!(history.queries().len(), MAX_QUERIES);
+        assert_eq!(history.queries()[0], format!("query {}", MAX_QUERIES + 9));
+        assert!(!history.queries().iter().any(|q| q == "Firefox"));
+    }
+}
@@ -3,7 +3,9 @@
 //! This module provides functionality to detect if user input contains a search trigger
 //! (e.g., "!g rust async") and parse out the provider and query.
 
-use super::providers::{SearchProvider, get_providers};
+use super::providers::SearchProvider;
+use crate::config::config;
+use crate::triggers::{TriggerRegistry, TriggerTarget};
 
 /// The result of parsing a search query.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,6 +28,15 @@ pub enum SearchDetection {
 /// - `SearchDetection::Fallback` if input should show all providers as fallback
 /// - `SearchDetection::None` if this is not a search query
 pub fn detect_search(input: &str) -> SearchDetection {
+    detect_search_with(input, &TriggerRegistry::for_config(&config()))
+}
+
+/// Detect a search trigger using the given triggers.
+///
+/// Pass the registry the launcher resolves queries with, so a query that
+/// belongs to another feature (`!ai`, `=`) is never taken for a search.
+/// The longest trigger wins and a trigger must be followed by whitespace.
+pub fn detect_search_with(input: &str, triggers: &TriggerRegistry) -> SearchDetection {
     let trimmed = input.trim();
 
     if trimmed.is_empty() {
@@ -33,21 +44,18 @@ pub fn detect_search(input: &str) -> SearchDetection {
     }
 
     // Check if input starts with a trigger
-    for provider in get_providers() {
-        if let Some(stripped) = trimmed.strip_prefix(provider.trigger.as_str()) {
-            // Extract the query after the trigger
-            let query = stripped.trim();
-
-            if query.is_empty() {
-                // Just the trigger, no query yet - don't show anything
-                return SearchDetection::None;
-            }
-
-            return SearchDetection::Triggered {
-                provider,
-                query: query.to_string(),
-            };
+    if let Some(matched) = triggers.resolve(trimmed)
+        && let TriggerTarget::Search(provider) = matched.target
+    {
+        if matched.rest.is_empty() {
+            // Just the trigger, no query yet - don't show anything
+            return SearchDetection::None;
         }
+
+        return SearchDetection::Triggered {
+            provider,
+            query: matched.rest.to_string(),
+        };
     }
 
     // No trigger found - this could be a fallback candidate
@@ -120,6 +128,29 @@ mod tests {
         }
     }
 
-    // Note: Tests for specific triggers (!g, !d, etc.) are skipped because they depend
-    // on config being initialized. These are tested through integration tests instead.
+    #[test]
+    fn test_detect_with_launcher_triggers() {
+        let amazon = SearchProvider {
+            name: "Amazon".to_string(),
+            trigger: "!a".to_string(),
+            url_template: "https://amazon.com/s?k={query}".to_string(),
+            icon: crate::assets::PhosphorIcon::MagnifyingGlass,
+            icon_path: None,
+            browser: None,
+        };
+        let triggers = TriggerRegistry::for_features(true, true, &[amazon.clone()]);
+
+        assert_eq!(
+            detect_search_with("!a headphones", &triggers),
+            SearchDetection::Triggered {
+                provider: amazon,
+                query: "headphones".to_string(),
+            }
+        );
+        // Owned by the AI, not a search
+        assert!(matches!(
+            detect_search_with("!ai headphones", &triggers),
+            SearchDetection::Fallback { .. }
+        ));
+    }
 }
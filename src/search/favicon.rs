@@ -0,0 +1,312 @@
+//! Favicons for search providers.
+//!
+//! Providers with `favicon = true` show their site's favicon instead of a
+//! Phosphor icon. Favicons are downloaded in the background at startup and
+//! cached as PNG files keyed by host, so lookups while typing only touch the
+//! disk cache. Providers without a cached favicon keep their Phosphor icon.
+//! Oversized favicons are downscaled to `max_icon_size` before caching.
+//!
+//! The icons a site's homepage declares (`<link rel="icon">`) are tried
+//! first, then `/favicon.ico`.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, bail};
+use gpui::App;
+use image::DynamicImage;
+use image::imageops::FilterType;
+use regex::Regex;
+use tokio::runtime::Handle;
+use tracing::{debug, warn};
+
+use crate::config::{ConfigSearchProvider, config};
+
+/// How long to wait for a favicon or homepage download.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Downloads larger than this are rejected.
+const MAX_DOWNLOAD_BYTES: usize = 1024 * 1024;
+
+lazy_static::lazy_static! {
+    /// `<link>` tags of an HTML page.
+    static ref LINK_TAG: Regex = Regex::new(r"(?is)<link\b[^>]*>").unwrap();
+    /// `rel` and `href` attributes of a tag.
+    static ref REL_ATTR: Regex = Regex::new(r#"(?i)\brel\s*=\s*["']([^"']*)["']"#).unwrap();
+    static ref HREF_ATTR: Regex = Regex::new(r#"(?i)\bhref\s*=\s*["']([^"']*)["']"#).unwrap();
+}
+
+/// Icons with a side larger than this are rejected without being decoded.
+const MAX_ICON_DIMENSION: u32 = 1024;
+
+/// Directory holding the cached favicons.
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|d| d.join("zlaunch").join("favicons"))
+}
+
+/// Cache file name for a provider URL, derived from its host.
+///
+/// Returns None for URLs without a usable host.
+pub fn cache_key(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    let host = host.to_ascii_lowercase();
+    let valid = !host.is_empty()
+        && !host.starts_with('.')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-'));
+    valid.then(|| format!("{host}.png"))
+}
+
+/// The cached favicon for a provider URL, if one was downloaded.
+pub fn cached_favicon(url: &str) -> Option<PathBuf> {
+    cached_favicon_in(&cache_dir()?, url)
+}
+
+/// Look up the cached favicon for a provider URL in `dir`.
+pub fn cached_favicon_in(dir: &Path, url: &str) -> Option<PathBuf> {
+    let path = dir.join(cache_key(url)?);
+    let non_empty = path.metadata().is_ok_and(|m| m.len() > 0);
+    non_empty.then_some(path)
+}
+
+/// Download the missing favicons of the providers that want one.
+///
+/// Runs on the background executor, with the requests on the shared tokio
+/// runtime; failures are logged and the provider keeps its Phosphor icon.
+pub fn fetch_missing_favicons(providers: &[ConfigSearchProvider], cx: &App) {
+    let Some(dir) = cache_dir() else {
+        return;
+    };
+    let urls: Vec<String> = providers
+        .iter()
+        .filter(|p| p.favicon && cached_favicon_in(&dir, &p.url).is_none())
+        .map(|p| p.url.clone())
+        .collect();
+    if urls.is_empty() {
+        return;
+    }
+
+    let client = match reqwest::Client::builder().timeout(FETCH_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("Failed to create HTTP client for favicons: {}", e);
+            return;
+        }
+    };
+    let runtime = crate::tokio_runtime::handle(cx);
+    cx.background_spawn(async move {
+        for url in urls {
+            match fetch_favicon(&client, &runtime, &dir, &url).await {
+                Ok(path) => debug!(path = %path.display(), "Cached favicon"),
+                Err(e) => warn!("Failed to fetch favicon for {}: {:#}", url, e),
+            }
+        }
+    })
+    .detach();
+}
+
+/// Download the favicon of the site behind `url` and store it as PNG in `dir`.
+async fn fetch_favicon(
+    client: &reqwest::Client,
+    runtime: &Handle,
+    dir: &Path,
+    url: &str,
+) -> anyhow::Result<PathBuf> {
+    let key = cache_key(url).context("URL has no host")?;
+    let host = key.trim_end_matches(".png");
+    let homepage = format!("https://{host}/");
+
+    // A homepage that fails to load still leaves /favicon.ico
+    let mut candidates = match download(client, runtime, &homepage).await {
+        Ok(html) => icon_links(&String::from_utf8_lossy(&html), host),
+        Err(e) => {
+            debug!("Failed to load {}: {:#}", homepage, e);
+            Vec::new()
+        }
+    };
+    candidates.push(format!("https://{host}/favicon.ico"));
+
+    let mut last_error = None;
+    for candidate in candidates {
+        let image = download(client, runtime, &candidate)
+            .await
+            .and_then(|data| decode_icon(&data, config().max_icon_size));
+        match image {
+            Ok(image) => {
+                std::fs::create_dir_all(dir)?;
+                let path = dir.join(&key);
+                image.save_with_format(&path, image::ImageFormat::Png)?;
+                return Ok(path);
+            }
+            Err(e) => {
+                debug!("No usable favicon at {}: {:#}", candidate, e);
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("no favicon found")))
+}
+
+/// Download `url` on the tokio runtime.
+async fn download(
+    client: &reqwest::Client,
+    runtime: &Handle,
+    url: &str,
+) -> anyhow::Result<Vec<u8>> {
+    let request = client.get(url);
+    runtime
+        .spawn(async move {
+            let response = request.send().await?.error_for_status()?;
+            if response
+                .content_length()
+                .is_some_and(|len| len > MAX_DOWNLOAD_BYTES as u64)
+            {
+                bail!("download too large");
+            }
+            let data = response.bytes().await?;
+            if data.is_empty() || data.len() > MAX_DOWNLOAD_BYTES {
+                bail!("empty or too large download");
+            }
+            Ok(data.to_vec())
+        })
+        .await?
+}
+
+/// URLs of the icons declared by `<link rel="icon">` style tags in `html`,
+/// resolved against `https://{host}/`.
+fn icon_links(html: &str, host: &str) -> Vec<String> {
+    LINK_TAG
+        .find_iter(html)
+        .filter(|tag| {
+            REL_ATTR.captures(tag.as_str()).is_some_and(|rel| {
+                rel[1]
+                    .split_ascii_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case("icon") || rel.ends_with("-icon"))
+            })
+        })
+        .filter_map(|tag| {
+            let href = HREF_ATTR.captures(tag.as_str())?[1].trim().to_string();
+            if href.starts_with("https://") || href.starts_with("http://") {
+                Some(href)
+            } else if let Some(rest) = href.strip_prefix("//") {
+                Some(format!("https://{rest}"))
+            } else if href.is_empty() || href.starts_with("data:") {
+                None
+            } else {
+                Some(format!("https://{host}/{}", href.trim_start_matches('/')))
+            }
+        })
+        .collect()
+}
+
+/// Decode icon data, downscaling it to fit within `max_size` pixels.
+///
+/// The dimensions are checked before decoding, so absurdly large icons
+/// (over [`MAX_ICON_DIMENSION`]) fail without a huge allocation.
+fn decode_icon(data: &[u8], max_size: u32) -> anyhow::Result<DynamicImage> {
+    let (width, height) = image::ImageReader::new(Cursor::new(data))
+        .with_guessed_format()?
+        .into_dimensions()
+        .context("not an image")?;
+    if width > MAX_ICON_DIMENSION || height > MAX_ICON_DIMENSION {
+        bail!("icon too large ({width}x{height})");
+    }
+
+    let image = image::load_from_memory(data).context("not an image")?;
+    let max_size = max_size.max(1);
+    if width > max_size || height > max_size {
+        Ok(image.resize(max_size, max_size, FilterType::Lanczos3))
+    } else {
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Cursor::new(Vec::new());
+        DynamicImage::new_luma8(width, height)
+            .write_to(&mut data, image::ImageFormat::Png)
+            .unwrap();
+        data.into_inner()
+    }
+
+    #[test]
+    fn test_decode_icon_downscales_large_icons() {
+        let icon = decode_icon(&png(512, 512), 128).unwrap();
+        assert_eq!((icon.width(), icon.height()), (128, 128));
+
+        let icon = decode_icon(&png(512, 256), 128).unwrap();
+        assert_eq!((icon.width(), icon.height()), (128, 64));
+
+        let icon = decode_icon(&png(32, 32), 128).unwrap();
+        assert_eq!((icon.width(), icon.height()), (32, 32));
+    }
+
+    #[test]
+    fn test_decode_icon_rejects_huge_or_invalid_data() {
+        assert!(decode_icon(&png(4096, 16), 128).is_err());
+        assert!(decode_icon(b"<html>not found</html>", 128).is_err());
+    }
+
+    #[test]
+    fn test_icon_links() {
+        let html = r#"<html><head>
+            <link rel="stylesheet" href="/style.css">
+            <link rel="icon" type="image/png" href="/static/icon.png">
+            <LINK REL="shortcut icon" HREF="favicon-32.ico">
+            <link href="//cdn.example.net/touch.png" rel="apple-touch-icon">
+            <link rel="icon" href="data:,">
+        </head></html>"#;
+
+        assert_eq!(
+            icon_links(html, "example.com"),
+            vec![
+                "https://example.com/static/icon.png",
+                "https://example.com/favicon-32.ico",
+                "https://cdn.example.net/touch.png",
+            ]
+        );
+        assert!(icon_links("<p>no icons</p>", "example.com").is_empty());
+    }
+
+    #[test]
+    fn test_cache_key() {
+        assert_eq!(
+            cache_key("https://www.Google.com/search?q={query}").as_deref(),
+            Some("www.google.com.png")
+        );
+        assert_eq!(
+            cache_key("https://user@example.com:8080?q={query}").as_deref(),
+            Some("example.com.png")
+        );
+        assert_eq!(cache_key("example.com/?q={query}"), None);
+        assert_eq!(cache_key("file:///etc/passwd"), None);
+        assert_eq!(cache_key("https://../x"), None);
+    }
+
+    #[test]
+    fn test_cached_favicon_lookup() {
+        let dir = TempDir::new("favicons");
+        let url = "https://duckduckgo.com/?q={query}";
+
+        // Nothing cached yet: the provider keeps its Phosphor icon
+        assert_eq!(cached_favicon_in(&dir, url), None);
+
+        std::fs::write(dir.join("duckduckgo.com.png"), b"").unwrap();
+        assert_eq!(cached_favicon_in(&dir, url), None);
+
+        std::fs::write(dir.join("duckduckgo.com.png"), b"png").unwrap();
+        assert_eq!(
+            cached_favicon_in(&dir, url),
+            Some(dir.join("duckduckgo.com.png"))
+        );
+    }
+}
@@ -4,9 +4,13 @@
 //! - Detect search triggers (e.g., !g, !wiki, !d, !yt)
 //! - Parse search queries
 //! - Generate search URLs for various providers
+//! - Cache provider favicons
 
 mod detection;
+pub mod favicon;
 mod providers;
 
-pub use detection::{SearchDetection, detect_search};
-pub use providers::{SearchProvider, find_provider_by_trigger, get_providers};
+pub use detection::{SearchDetection, detect_search, detect_search_with};
+pub use providers::{
+    SearchProvider, find_provider_by_trigger, get_providers, providers_from_config,
+};
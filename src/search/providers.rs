@@ -4,7 +4,10 @@
 //! with their triggers, URL templates, and icons.
 
 use crate::assets::PhosphorIcon;
-use crate::config::config;
+use crate::config::{AppConfig, config};
+use crate::desktop::exec::split_command;
+use crate::search::favicon::cached_favicon;
+use std::path::PathBuf;
 use tracing::warn;
 
 /// A search provider configuration.
@@ -18,6 +21,10 @@ pub struct SearchProvider {
     pub url_template: String,
     /// The Phosphor icon to use
     pub icon: PhosphorIcon,
+    /// Cached favicon shown instead of the Phosphor icon
+    pub icon_path: Option<PathBuf>,
+    /// Browser command with {url} placeholder, or None for xdg-open
+    pub browser: Option<String>,
 }
 
 impl SearchProvider {
@@ -26,6 +33,44 @@ impl SearchProvider {
         let encoded_query = urlencoding::encode(query);
         self.url_template.replace("{query}", &encoded_query)
     }
+
+    /// The provider's homepage: the scheme and host of its URL template.
+    pub fn homepage_url(&self) -> Option<String> {
+        let (scheme, rest) = self.url_template.split_once("://")?;
+        let authority = rest
+            .split(['/', '?', '#'])
+            .next()
+            .filter(|authority| !authority.is_empty() && !authority.contains("{query}"))?;
+        Some(format!("{scheme}://{authority}/"))
+    }
+
+    /// Build the argument list opening a URL in the configured browser.
+    ///
+    /// Returns None if no browser is configured, so xdg-open is used.
+    pub fn browser_args(&self, url: &str) -> Option<Vec<String>> {
+        let command = self.browser.as_deref()?;
+        let args = browser_argv(command, url);
+        (!args.is_empty()).then_some(args)
+    }
+}
+
+/// Split a browser command and substitute {url}.
+///
+/// The URL is appended when the command has no placeholder.
+fn browser_argv(command: &str, url: &str) -> Vec<String> {
+    let mut args = split_command(command);
+    if args.is_empty() {
+        return args;
+    }
+
+    if args.iter().any(|arg| arg.contains("{url}")) {
+        for arg in &mut args {
+            *arg = arg.replace("{url}", url);
+        }
+    } else {
+        args.push(url.to_string());
+    }
+    args
 }
 
 fn provider_icon(provider_name: &str, icon_name: Option<&String>) -> PhosphorIcon {
@@ -49,17 +94,27 @@ fn provider_icon(provider_name: &str, icon_name: Option<&String>) -> PhosphorIco
 
 /// Get all available search providers
 pub fn get_providers() -> Vec<SearchProvider> {
+    providers_from_config(&config())
+}
+
+/// Get the search providers defined in the given config.
+pub fn providers_from_config(cfg: &AppConfig) -> Vec<SearchProvider> {
     let mut providers = vec![];
 
-    if let Some(custom) = config().search_providers {
+    if let Some(custom) = &cfg.search_providers {
         for provider in custom {
             let icon = provider_icon(&provider.name, Some(&provider.icon));
 
             providers.push(SearchProvider {
-                name: provider.name,
-                trigger: provider.trigger,
-                url_template: provider.url,
+                name: provider.name.clone(),
+                trigger: provider.trigger.clone(),
+                url_template: provider.url.clone(),
                 icon,
+                icon_path: provider
+                    .favicon
+                    .then(|| cached_favicon(&provider.url))
+                    .flatten(),
+                browser: provider.browser.clone(),
             });
         }
     }
@@ -71,3 +126,68 @@ pub fn get_providers() -> Vec<SearchProvider> {
 pub fn find_provider_by_trigger(trigger: &str) -> Option<SearchProvider> {
     get_providers().into_iter().find(|p| p.trigger == trigger)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(browser: Option<&str>) -> SearchProvider {
+        SearchProvider {
+            name: "Work".to_string(),
+            trigger: "!w".to_string(),
+            url_template: "https://example.com/?q={query}".to_string(),
+            icon: PhosphorIcon::MagnifyingGlass,
+            icon_path: None,
+            browser: browser.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_browser_args_substitutes_url() {
+        let provider = provider(Some("firefox --private-window {url}"));
+        let url = provider.build_url("rust lang");
+        assert_eq!(
+            provider.browser_args(&url),
+            Some(vec![
+                "firefox".to_string(),
+                "--private-window".to_string(),
+                "https://example.com/?q=rust%20lang".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_browser_args_appends_url_without_placeholder() {
+        let provider = provider(Some(r#"chromium "--profile-directory=Profile 1""#));
+        assert_eq!(
+            provider.browser_args("https://example.com"),
+            Some(vec![
+                "chromium".to_string(),
+                "--profile-directory=Profile 1".to_string(),
+                "https://example.com".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_homepage_url() {
+        assert_eq!(
+            provider(None).homepage_url().as_deref(),
+            Some("https://example.com/")
+        );
+        let mut custom = provider(None);
+        custom.url_template = "https://{query}.example.com".to_string();
+        assert_eq!(custom.homepage_url(), None);
+        custom.url_template = "not a url {query}".to_string();
+        assert_eq!(custom.homepage_url(), None);
+    }
+
+    #[test]
+    fn test_browser_args_unset_or_empty() {
+        assert_eq!(provider(None).browser_args("https://example.com"), None);
+        assert_eq!(
+            provider(Some("  ")).browser_args("https://example.com"),
+            None
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Shared state persisted as JSON in the data directory.
+//!
+//! Launch history, query history, emoji usage and calculator answers are
+//! each kept in a [`PersistedStore`]: loaded once on first use, read through
+//! cheap snapshots and written back to disk on every update.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tracing::{debug, warn};
+
+/// A value backed by a JSON file.
+pub struct PersistedStore<T> {
+    /// File the value is saved to, or None to keep it in memory only.
+    path: Option<PathBuf>,
+    /// Current value; snapshots share it until the next update.
+    value: RwLock<Arc<T>>,
+}
+
+impl<T: Clone + Default + Serialize + DeserializeOwned> PersistedStore<T> {
+    /// Open the store kept in `file_name` in the zlaunch data directory.
+    ///
+    /// Tests get an empty in-memory store instead, so they neither depend on
+    /// nor change the user's data.
+    pub fn open(file_name: &str) -> Self {
+        if cfg!(test) {
+            return Self::at(None);
+        }
+        Self::at(dirs::data_local_dir().map(|d| d.join("zlaunch").join(file_name)))
+    }
+
+    /// Open a store backed by `path`, starting from its saved value.
+    ///
+    /// A missing or unreadable file starts from the default value.
+    pub fn at(path: Option<PathBuf>) -> Self {
+        let value = path.as_deref().and_then(load_from).unwrap_or_default();
+        Self {
+            path,
+            value: RwLock::new(Arc::new(value)),
+        }
+    }
+
+    /// Get a snapshot of the current value without copying it.
+    pub fn snapshot(&self) -> Arc<T> {
+        self.value.read().unwrap().clone()
+    }
+
+    /// Update the value and persist it.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut value = self.value.write().unwrap();
+        // Only copies the value while snapshots of it are still alive
+        f(Arc::make_mut(&mut value));
+        if let Some(path) = &self.path
+            && let Err(e) = save_to(&**value, path)
+        {
+            warn!("Failed to save {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Load a value from a JSON file.
+pub fn load_from<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let data = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// Save a value to a JSON file, creating its directory.
+pub fn save_to<T: Serialize>(value: &T, path: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(value)?)?;
+    debug!("Saved {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::collections::HashMap;
+
+    type Counts = HashMap<String, u32>;
+
+    #[test]
+    fn test_updates_are_saved_and_loaded() {
+        let dir = TempDir::new("store");
+        let path = dir.join("counts.json");
+        let store: PersistedStore<Counts> = PersistedStore::at(Some(path.clone()));
+        store.update(|counts| *counts.entry("firefox".to_string()).or_default() += 1);
+
+        let reopened: PersistedStore<Counts> = PersistedStore::at(Some(path));
+        assert_eq!(reopened.snapshot().get("firefox"), Some(&1));
+    }
+
+    #[test]
+    fn test_shared_stores_stay_in_memory_in_tests() {
+        let store: PersistedStore<Counts> = PersistedStore::open("queries.json");
+        assert!(store.path.is_none());
+    }
+
+    #[test]
+    fn test_snapshots_are_not_changed_by_updates() {
+        let store: PersistedStore<Counts> = PersistedStore::at(None);
+        let before = store.snapshot();
+        store.update(|counts| {
+            counts.insert("firefox".to_string(), 1);
+        });
+
+        assert!(before.is_empty());
+        assert_eq!(store.snapshot().len(), 1);
+    }
+}
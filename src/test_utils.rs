@@ -3,9 +3,16 @@
 //! This module provides common testing utilities including mock object factories
 //! and test helpers. Only compiled in test builds.
 
+use crate::assets::PhosphorIcon;
 use crate::config::{AppConfig, ConfigModule, ConfigSearchProvider};
-use crate::items::{ApplicationItem, ListItem, WindowItem};
-use std::path::PathBuf;
+use crate::items::{
+    ActionItem, ActionKind, AiItem, ApplicationItem, CalculatorItem, ColorItem, ListItem, PathItem,
+    SearchItem, SubmenuItem, SuggestionItem, ThemeItem, ThemeSource, WindowItem,
+};
+use crate::search::SearchProvider;
+use crate::ui::theme::LauncherTheme;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
 
 /// Create a mock AppConfig with default values.
 pub fn mock_config() -> AppConfig {
@@ -85,6 +92,43 @@ pub fn mock_list_items() -> Vec<ListItem> {
         .collect()
 }
 
+/// Create one ListItem of every variant.
+pub fn mock_list_item_variants() -> Vec<ListItem> {
+    let provider = SearchProvider {
+        name: "Google".to_string(),
+        trigger: "!g".to_string(),
+        url_template: "https://www.google.com/search?q={query}".to_string(),
+        icon: PhosphorIcon::MagnifyingGlass,
+        icon_path: None,
+        browser: None,
+    };
+    vec![
+        ListItem::Application(mock_application("Firefox")),
+        ListItem::Window(mock_window("Terminal", "kitty")),
+        ListItem::Action(ActionItem::builtin(ActionKind::Lock)),
+        ListItem::Submenu(SubmenuItem::list("submenu-clipboard", "Clipboard History")),
+        ListItem::Calculator(CalculatorItem {
+            id: "calculator-result".to_string(),
+            expression: "2+2".to_string(),
+            display_result: "4".to_string(),
+            clipboard_result: Some("4".to_string()),
+            is_error: false,
+            delta: None,
+            bindings: Vec::new(),
+        }),
+        ListItem::Color(ColorItem::parse("#3366ff").unwrap()),
+        ListItem::Path(PathItem::detect_with_home("~", Some(Path::new("/"))).unwrap()),
+        ListItem::Search(SearchItem::new(provider, "rust".to_string())),
+        ListItem::Ai(AiItem::new("what is rust".to_string())),
+        ListItem::Suggestion(SuggestionItem::new("rust book".to_string())),
+        ListItem::Theme(Box::new(ThemeItem::new(
+            "Default".to_string(),
+            ThemeSource::Bundled,
+            LauncherTheme::default(),
+        ))),
+    ]
+}
+
 /// Create a mock SearchProvider configuration.
 pub fn mock_search_provider(name: &str, trigger: &str) -> ConfigSearchProvider {
     ConfigSearchProvider {
@@ -95,6 +139,41 @@ pub fn mock_search_provider(name: &str, trigger: &str) -> ConfigSearchProvider {
             name.to_lowercase()
         ),
         icon: "magnifying-glass".to_string(),
+        browser: None,
+        favicon: false,
+    }
+}
+
+/// A scratch directory under the system temp dir, removed when dropped.
+///
+/// Derefs to its path.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Create an empty directory named after `name` and this test process.
+    ///
+    /// Names must be unique among the tests, which run in parallel.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("zlaunch-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+}
+
+impl Deref for TempDir {
+    type Target = PathBuf;
+
+    fn deref(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
     }
 }
 
@@ -144,6 +223,17 @@ mod tests {
         assert_eq!(config.combined_modules.unwrap().len(), 2);
     }
 
+    #[test]
+    fn test_temp_dir_is_removed_on_drop() {
+        let dir = TempDir::new("test-utils");
+        std::fs::write(dir.join("file"), "").unwrap();
+        let path = dir.to_path_buf();
+        assert!(path.is_dir());
+
+        drop(dir);
+        assert!(!path.exists());
+    }
+
     #[test]
     fn test_mock_search_provider() {
         let provider = mock_search_provider("Google", "!g");
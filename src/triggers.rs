@@ -0,0 +1,223 @@
+//! Query trigger registry.
+//!
+//! Features reached through a query prefix (`!ai`, search providers such as
+//! `!g`, the explicit calculator `=`) are registered here, so a single ordered
+//! lookup decides which feature owns a query instead of each feature checking
+//! prefixes on its own.
+//!
+//! Precedence:
+//! - A trigger only matches when followed by whitespace or the end of the
+//!   query, unless it is attached (`=2+2`). So `!a` never claims `!ai`.
+//! - The longest matching prefix wins.
+//! - Ties go to the trigger registered first; a prefix can only be registered once.
+
+use crate::config::AppConfig;
+use crate::search::{SearchProvider, providers_from_config};
+
+/// Feature a trigger dispatches to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriggerTarget {
+    /// Ask the AI (`!ai`).
+    Ai,
+    /// Evaluate an expression (`=`).
+    Calculator,
+    /// Search with a provider (`!g`, `!wiki`, ...).
+    Search(SearchProvider),
+}
+
+/// A query claimed by a trigger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerMatch<'q> {
+    /// The feature that owns the query.
+    pub target: TriggerTarget,
+    /// The query after the trigger, trimmed.
+    pub rest: &'q str,
+}
+
+#[derive(Debug, Clone)]
+struct Trigger {
+    prefix: String,
+    target: TriggerTarget,
+    attached: bool,
+}
+
+/// Ordered set of query triggers.
+#[derive(Debug, Clone, Default)]
+pub struct TriggerRegistry {
+    triggers: Vec<Trigger>,
+}
+
+impl TriggerRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry for the enabled features: `!ai`, then `=`, then the search providers.
+    pub fn for_features(
+        ai_enabled: bool,
+        calculator_enabled: bool,
+        search_providers: &[SearchProvider],
+    ) -> Self {
+        let mut registry = Self::new();
+        if ai_enabled {
+            registry.register("!ai", TriggerTarget::Ai);
+        }
+        if calculator_enabled {
+            registry.register_attached("=", TriggerTarget::Calculator);
+        }
+        for provider in search_providers {
+            registry.register(&provider.trigger, TriggerTarget::Search(provider.clone()));
+        }
+        registry
+    }
+
+    /// Registry for everything the config offers: `!ai` unless AI is turned
+    /// off, `=` and the configured search providers.
+    ///
+    /// Built once when the config is loaded; views that leave out a module
+    /// skip its triggers with [`Self::resolve_where`].
+    pub fn for_config(cfg: &AppConfig) -> Self {
+        Self::for_features(cfg.ai_enabled, true, &providers_from_config(cfg))
+    }
+
+    /// Register a trigger that must be followed by whitespace.
+    ///
+    /// Returns false if the prefix is empty or already taken.
+    pub fn register(&mut self, prefix: &str, target: TriggerTarget) -> bool {
+        self.insert(prefix, target, false)
+    }
+
+    /// Register a trigger that may be directly followed by its argument.
+    ///
+    /// Returns false if the prefix is empty or already taken.
+    pub fn register_attached(&mut self, prefix: &str, target: TriggerTarget) -> bool {
+        self.insert(prefix, target, true)
+    }
+
+    fn insert(&mut self, prefix: &str, target: TriggerTarget, attached: bool) -> bool {
+        if prefix.is_empty() || self.triggers.iter().any(|t| t.prefix == prefix) {
+            return false;
+        }
+        self.triggers.push(Trigger {
+            prefix: prefix.to_string(),
+            target,
+            attached,
+        });
+        true
+    }
+
+    /// Find the trigger that owns a query, if any.
+    ///
+    /// Returns None for queries that should fall through to fuzzy matching.
+    pub fn resolve<'q>(&self, query: &'q str) -> Option<TriggerMatch<'q>> {
+        self.resolve_where(query, |_| true)
+    }
+
+    /// Find the trigger that owns a query among those whose target is
+    /// `enabled`, as if the others were not registered.
+    pub fn resolve_where<'q>(
+        &self,
+        query: &'q str,
+        enabled: impl Fn(&TriggerTarget) -> bool,
+    ) -> Option<TriggerMatch<'q>> {
+        let query = query.trim_start();
+        let mut best: Option<(&Trigger, &'q str)> = None;
+
+        for trigger in self.triggers.iter().filter(|t| enabled(&t.target)) {
+            let Some(rest) = query.strip_prefix(trigger.prefix.as_str()) else {
+                continue;
+            };
+            let separated = rest.is_empty() || rest.starts_with(char::is_whitespace);
+            if !trigger.attached && !separated {
+                continue;
+            }
+            if best.is_none_or(|(b, _)| trigger.prefix.len() > b.prefix.len()) {
+                best = Some((trigger, rest));
+            }
+        }
+
+        best.map(|(trigger, rest)| TriggerMatch {
+            target: trigger.target.clone(),
+            rest: rest.trim(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::PhosphorIcon;
+
+    fn provider(name: &str, trigger: &str) -> SearchProvider {
+        SearchProvider {
+            name: name.to_string(),
+            trigger: trigger.to_string(),
+            url_template: "https://example.com/?q={query}".to_string(),
+            icon: PhosphorIcon::MagnifyingGlass,
+            icon_path: None,
+            browser: None,
+        }
+    }
+
+    #[test]
+    fn test_ai_wins_over_shorter_provider_prefix() {
+        let amazon = provider("Amazon", "!a");
+        let registry = TriggerRegistry::for_features(true, true, &[amazon.clone()]);
+
+        let matched = registry.resolve("!ai what is rust").unwrap();
+        assert_eq!(matched.target, TriggerTarget::Ai);
+        assert_eq!(matched.rest, "what is rust");
+
+        let matched = registry.resolve("!a headphones").unwrap();
+        assert_eq!(matched.target, TriggerTarget::Search(amazon));
+        assert_eq!(matched.rest, "headphones");
+
+        // Registration order doesn't matter for overlapping prefixes
+        let mut registry = TriggerRegistry::new();
+        registry.register("!a", TriggerTarget::Search(provider("Amazon", "!a")));
+        registry.register("!ai", TriggerTarget::Ai);
+        assert_eq!(
+            registry.resolve("!ai hello").unwrap().target,
+            TriggerTarget::Ai
+        );
+    }
+
+    #[test]
+    fn test_non_trigger_query_falls_through() {
+        let registry = TriggerRegistry::for_features(true, true, &[provider("Google", "!g")]);
+        assert_eq!(registry.resolve("firefox"), None);
+        assert_eq!(registry.resolve("!grust"), None);
+        assert_eq!(registry.resolve("2+2"), None);
+    }
+
+    #[test]
+    fn test_attached_trigger() {
+        let registry = TriggerRegistry::for_features(false, true, &[]);
+        let matched = registry.resolve("=2+2").unwrap();
+        assert_eq!(matched.target, TriggerTarget::Calculator);
+        assert_eq!(matched.rest, "2+2");
+    }
+
+    #[test]
+    fn test_disabled_triggers_are_skipped() {
+        let registry = TriggerRegistry::for_features(true, true, &[provider("Amazon", "!a")]);
+        let no_ai = |target: &TriggerTarget| *target != TriggerTarget::Ai;
+
+        assert_eq!(registry.resolve_where("!ai hello", no_ai), None);
+        assert_eq!(registry.resolve_where("=2+2", no_ai).unwrap().rest, "2+2");
+        assert_eq!(
+            registry.resolve_where("!a headphones", no_ai).unwrap().rest,
+            "headphones"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_prefix_is_rejected() {
+        let mut registry = TriggerRegistry::new();
+        assert!(registry.register("!ai", TriggerTarget::Ai));
+        assert!(!registry.register("!ai", TriggerTarget::Search(provider("AI", "!ai"))));
+        assert!(!registry.register("", TriggerTarget::Ai));
+        assert_eq!(registry.resolve("!ai x").unwrap().target, TriggerTarget::Ai);
+    }
+}
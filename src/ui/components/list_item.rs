@@ -1,3 +1,4 @@
+use crate::config::{ActionIndicatorStyle, config};
 use crate::ui::theme::theme;
 use gpui::{Div, ElementId, SharedString, Stateful, div, img, prelude::*, px};
 use std::path::PathBuf;
@@ -123,6 +124,7 @@ impl ListItemComponent {
 
         // Add action indicator if selected and label provided
         if self.selected
+            && config().action_indicator != ActionIndicatorStyle::Hidden
             && let Some(label) = self.action_label
         {
             container = container.child(render_action_indicator(&label));
@@ -191,7 +193,10 @@ fn render_text_content(name: &str, description: Option<&str>, selected: bool) ->
         .text_ellipsis()
         .child(SharedString::from(name.to_string()));
 
-    let max_width = theme.max_text_width(px(crate::config::launcher_size().0), selected);
+    let max_width = theme.max_text_width(
+        px(crate::config::launcher_size().0),
+        selected && config().action_indicator != ActionIndicatorStyle::Hidden,
+    );
 
     let mut content = div()
         .h(theme.item_content_height)
@@ -223,7 +228,7 @@ fn render_text_content(name: &str, description: Option<&str>, selected: bool) ->
 fn render_action_indicator(label: &str) -> Div {
     let theme = theme();
 
-    div()
+    let indicator = div()
         .absolute()
         .right(theme.action_indicator.right_position)
         .top_0()
@@ -237,19 +242,24 @@ fn render_action_indicator(label: &str) -> Div {
                 .text_xs()
                 .text_color(theme.action_indicator.label_color)
                 .child(SharedString::from(label.to_string())),
-        )
-        .child(
-            div()
-                .px(theme.action_indicator.key_padding_x)
-                .pt(theme.action_indicator.key_padding_top)
-                .pb(theme.action_indicator.key_padding_bottom)
-                .bg(theme.action_indicator.key_background)
-                .border_1()
-                .border_color(theme.action_indicator.key_border)
-                .rounded(theme.action_indicator.key_border_radius)
-                .text_size(theme.action_indicator.key_font_size)
-                .line_height(theme.action_indicator.key_line_height)
-                .text_color(theme.action_indicator.key_color)
-                .child(SharedString::from("↵")),
-        )
+        );
+
+    if config().action_indicator != ActionIndicatorStyle::Key {
+        return indicator;
+    }
+
+    indicator.child(
+        div()
+            .px(theme.action_indicator.key_padding_x)
+            .pt(theme.action_indicator.key_padding_top)
+            .pb(theme.action_indicator.key_padding_bottom)
+            .bg(theme.action_indicator.key_background)
+            .border_1()
+            .border_color(theme.action_indicator.key_border)
+            .rounded(theme.action_indicator.key_border_radius)
+            .text_size(theme.action_indicator.key_font_size)
+            .line_height(theme.action_indicator.key_line_height)
+            .text_color(theme.action_indicator.key_color)
+            .child(SharedString::from("↵")),
+    )
 }
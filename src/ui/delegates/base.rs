@@ -45,6 +45,12 @@ impl<T: Clone> BaseDelegate<T> {
         }
     }
 
+    /// Replace all items, keeping callbacks and the query (caller should re-filter)
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        self.reset_filter();
+    }
+
     /// Set the confirm callback
     pub fn set_on_confirm(&mut self, callback: impl Fn(&T) + Send + Sync + 'static) {
         self.on_confirm = Some(Arc::new(callback));
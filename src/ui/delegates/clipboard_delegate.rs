@@ -1,10 +1,14 @@
-use crate::clipboard::ClipboardItem;
+use crate::clipboard::{ClipboardContent, ClipboardItem, ClipboardQuery};
 use crate::ui::delegates::BaseDelegate;
 use crate::ui::theme::theme;
 use crate::ui::views::render_clipboard_item;
 use gpui::{App, Context, SharedString, Task, Window, div, prelude::*};
 use gpui_component::IndexPath;
 use gpui_component::list::{ListDelegate, ListItem as GpuiListItem, ListState};
+use std::sync::Arc;
+
+/// Callback receiving the joined text of marked entries.
+type ConfirmMarkedCallback = Arc<dyn Fn(&str) + Send + Sync>;
 
 /// Delegate for the clipboard history list.
 ///
@@ -12,16 +16,39 @@ use gpui_component::list::{ListDelegate, ListItem as GpuiListItem, ListState};
 pub struct ClipboardListDelegate {
     /// Base delegate handling common behavior
     base: BaseDelegate<ClipboardItem>,
+    /// Maximum characters per preview line
+    preview_length: usize,
+    /// Lines shown for the selected multi-line entry
+    preview_lines: usize,
+    /// Ids of the items marked for batch copy, in marking order.
+    /// Ids rather than indices, so marks stay on their entries when the
+    /// items are replaced.
+    marked: Vec<u64>,
+    /// Callback for confirming with marked entries
+    on_confirm_marked: Option<ConfirmMarkedCallback>,
+    /// Whether the query is a `/pattern/` that failed to compile
+    invalid_pattern: bool,
 }
 
 impl ClipboardListDelegate {
     /// Create a new clipboard list delegate
     pub fn new(items: Vec<ClipboardItem>) -> Self {
+        let cfg = crate::config::config();
         Self {
             base: BaseDelegate::new(items),
+            preview_length: cfg.clipboard_preview_length,
+            preview_lines: cfg.clipboard_preview_lines,
+            marked: Vec::new(),
+            on_confirm_marked: None,
+            invalid_pattern: false,
         }
     }
 
+    /// Set the callback for confirming with marked entries (copy joined text)
+    pub fn set_on_confirm_marked(&mut self, callback: impl Fn(&str) + Send + Sync + 'static) {
+        self.on_confirm_marked = Some(Arc::new(callback));
+    }
+
     /// Set the confirm callback (paste clipboard item)
     pub fn set_on_confirm(&mut self, callback: impl Fn(&ClipboardItem) + Send + Sync + 'static) {
         self.base.set_on_confirm(callback);
@@ -47,6 +74,11 @@ impl ClipboardListDelegate {
         self.base.filtered_count()
     }
 
+    /// Get the total count of items, ignoring the query
+    pub fn item_count(&self) -> usize {
+        self.base.items().len()
+    }
+
     /// Get the current query
     pub fn query(&self) -> &str {
         self.base.query()
@@ -55,6 +87,21 @@ impl ClipboardListDelegate {
     /// Clear the query
     pub fn clear_query(&mut self) {
         self.base.clear_query();
+        self.invalid_pattern = false;
+    }
+
+    /// Whether the query is a regex pattern that failed to compile
+    pub fn has_invalid_pattern(&self) -> bool {
+        self.invalid_pattern
+    }
+
+    /// Replace the items (e.g. after the history changed) and re-run the
+    /// current query. Marks of entries that are gone are dropped.
+    pub fn set_items(&mut self, items: Vec<ClipboardItem>) {
+        self.marked
+            .retain(|id| items.iter().any(|item| item.id() == *id));
+        self.base.set_items(items);
+        self.filter_items();
     }
 
     /// Set the query and filter
@@ -63,26 +110,37 @@ impl ClipboardListDelegate {
         self.filter_items();
     }
 
-    /// Filter items based on the current query
+    /// Filter items based on the current query.
+    /// A `/pattern/` query matches entries against a regex.
     fn filter_items(&mut self) {
         let query = self.base.query();
+        self.invalid_pattern = false;
         if query.is_empty() {
             self.base.reset_filter();
-        } else {
-            let items = self.base.items();
-            let query_lower = query.to_lowercase();
-            let filtered_indices: Vec<usize> = items
-                .iter()
-                .enumerate()
-                .filter(|(_, item)| {
-                    // Search in text content (preview and full_content)
-                    item.preview().to_lowercase().contains(&query_lower)
-                        || item.full_content().to_lowercase().contains(&query_lower)
-                })
-                .map(|(idx, _)| idx)
-                .collect();
-            self.base.apply_filtered_indices(filtered_indices);
+            return;
         }
+
+        let query = match ClipboardQuery::parse(query) {
+            ClipboardQuery::Text(text) => ClipboardQuery::Text(text.to_lowercase()),
+            query => query,
+        };
+        self.invalid_pattern = query.is_invalid();
+        let items = self.base.items();
+        let filtered_indices: Vec<usize> = items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| match &query {
+                // Search in text content (preview and full_content)
+                ClipboardQuery::Text(query_lower) => {
+                    item.preview().to_lowercase().contains(query_lower)
+                        || item.full_content().to_lowercase().contains(query_lower)
+                }
+                ClipboardQuery::Regex(regex) => regex.is_match(&item.full_content()),
+                ClipboardQuery::InvalidRegex => false,
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        self.base.apply_filtered_indices(filtered_indices);
     }
 
     /// Get an item at a filtered index
@@ -95,8 +153,67 @@ impl ClipboardListDelegate {
         self.base.selected_item()
     }
 
-    /// Execute confirm callback
+    /// Toggle the batch-copy mark of the item at a filtered index.
+    pub fn toggle_marked_at(&mut self, filtered_index: usize) {
+        let Some(id) = self.get_item_at(filtered_index).map(ClipboardItem::id) else {
+            return;
+        };
+        match self.marked.iter().position(|&marked| marked == id) {
+            Some(pos) => {
+                self.marked.remove(pos);
+            }
+            None => self.marked.push(id),
+        }
+    }
+
+    /// Toggle the batch-copy mark of the selected item.
+    pub fn toggle_selected_marked(&mut self) {
+        if let Some(index) = self.selected_index() {
+            self.toggle_marked_at(index);
+        }
+    }
+
+    /// Get the 1-based marking order of the item at a filtered index.
+    pub fn mark_position_at(&self, filtered_index: usize) -> Option<usize> {
+        let id = self.get_item_at(filtered_index)?.id();
+        self.marked
+            .iter()
+            .position(|&marked| marked == id)
+            .map(|pos| pos + 1)
+    }
+
+    /// Get the number of marked items
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    /// Join the marked entries in marking order, one per line.
+    ///
+    /// Images have no text and are skipped. Returns None if nothing is marked.
+    pub fn marked_text(&self) -> Option<String> {
+        if self.marked.is_empty() {
+            return None;
+        }
+        let items = self.base.items();
+        let texts: Vec<String> = self
+            .marked
+            .iter()
+            .filter_map(|&id| items.iter().find(|item| item.id() == id))
+            .filter(|item| !matches!(item.content, ClipboardContent::Image { .. }))
+            .map(|item| item.full_content())
+            .collect();
+        Some(texts.join("\n"))
+    }
+
+    /// Execute confirm callback.
+    /// With marked entries, their joined text is confirmed instead.
     pub fn do_confirm(&self) {
+        if let Some(text) = self.marked_text()
+            && let Some(ref callback) = self.on_confirm_marked
+        {
+            callback(&text);
+            return;
+        }
         self.base.do_confirm();
     }
 
@@ -143,12 +260,21 @@ impl ListDelegate for ClipboardListDelegate {
         let is_selected = self.base.selected_index() == Some(ix.row);
         let row = ix.row;
 
-        let element = render_clipboard_item(item, is_selected, row).on_click(cx.listener(
-            move |state, _, _window, _cx| {
+        let preview = item.list_preview(is_selected, self.preview_length, self.preview_lines);
+        let mark = self.mark_position_at(row);
+
+        let element = render_clipboard_item(item, &preview, is_selected, mark, row).on_click(
+            cx.listener(move |state, event: &gpui::ClickEvent, _window, cx| {
                 state.delegate_mut().set_selected(row);
-                state.delegate().do_confirm();
-            },
-        ));
+                // Ctrl+click toggles the batch-copy mark
+                if event.modifiers().control {
+                    state.delegate_mut().toggle_marked_at(row);
+                    cx.notify();
+                } else {
+                    state.delegate().do_confirm();
+                }
+            }),
+        );
 
         Some(GpuiListItem::new(("clipboard-item", row)).child(element))
     }
@@ -201,7 +327,116 @@ impl ListDelegate for ClipboardListDelegate {
                 div()
                     .text_sm()
                     .text_color(theme.empty_state_color)
-                    .child(SharedString::from("No clipboard history")),
+                    .child(SharedString::from(if self.invalid_pattern {
+                        "Invalid pattern"
+                    } else {
+                        "No clipboard history"
+                    })),
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delegate(texts: &[&str]) -> ClipboardListDelegate {
+        ClipboardListDelegate::new(
+            texts
+                .iter()
+                .map(|t| ClipboardItem::new(ClipboardContent::Text(t.to_string())))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_toggle_marked() {
+        let mut delegate = delegate(&["a", "b", "c"]);
+        assert_eq!(delegate.marked_text(), None);
+
+        delegate.toggle_marked_at(2);
+        delegate.toggle_marked_at(0);
+        assert_eq!(delegate.marked_count(), 2);
+        assert_eq!(delegate.mark_position_at(2), Some(1));
+        assert_eq!(delegate.mark_position_at(0), Some(2));
+        assert_eq!(delegate.mark_position_at(1), None);
+
+        // Toggling again unmarks
+        delegate.toggle_marked_at(2);
+        assert_eq!(delegate.marked_count(), 1);
+        assert_eq!(delegate.mark_position_at(0), Some(1));
+
+        // Out of range indices are ignored
+        delegate.toggle_marked_at(10);
+        assert_eq!(delegate.marked_count(), 1);
+    }
+
+    #[test]
+    fn test_marked_text_in_marking_order() {
+        let mut delegate = delegate(&["first", "second", "third"]);
+        delegate.toggle_marked_at(1);
+        delegate.toggle_marked_at(2);
+        delegate.toggle_marked_at(0);
+        assert_eq!(
+            delegate.marked_text().as_deref(),
+            Some("second\nthird\nfirst")
+        );
+    }
+
+    #[test]
+    fn test_marks_survive_filtering() {
+        let mut delegate = delegate(&["apple", "banana", "apricot"]);
+        delegate.toggle_marked_at(1);
+        delegate.set_query("ap".to_string());
+
+        // "apricot" is at filtered index 1 now
+        delegate.toggle_marked_at(1);
+        assert_eq!(delegate.mark_position_at(1), Some(2));
+        assert_eq!(delegate.marked_text().as_deref(), Some("banana\napricot"));
+    }
+
+    #[test]
+    fn test_marks_follow_entries_when_items_change() {
+        let items: Vec<ClipboardItem> = ["old", "kept", "gone"]
+            .iter()
+            .map(|t| ClipboardItem::new(ClipboardContent::Text(t.to_string())))
+            .collect();
+        let mut delegate = ClipboardListDelegate::new(items.clone());
+        delegate.toggle_marked_at(1);
+        delegate.toggle_marked_at(2);
+
+        // A new entry shifts the others down and "gone" was removed
+        let fresh = ClipboardItem::new(ClipboardContent::Text("new".to_string()));
+        delegate.set_items(vec![fresh, items[0].clone(), items[1].clone()]);
+        assert_eq!(delegate.marked_count(), 1);
+        assert_eq!(delegate.mark_position_at(2), Some(1));
+        assert_eq!(delegate.marked_text().as_deref(), Some("kept"));
+    }
+
+    #[test]
+    fn test_regex_query_filters_entries() {
+        let mut delegate = delegate(&["order 1234", "hello", "ticket 98", "1234 main st"]);
+        delegate.set_query(r"/^\D+\d+$/".to_string());
+        assert!(!delegate.has_invalid_pattern());
+        let matched: Vec<_> = (0..delegate.filtered_count())
+            .map(|i| delegate.get_item_at(i).unwrap().full_content())
+            .collect();
+        assert_eq!(matched, vec!["order 1234", "ticket 98"]);
+
+        // Plain queries still match substrings
+        delegate.set_query("1234".to_string());
+        assert_eq!(delegate.filtered_count(), 2);
+    }
+
+    #[test]
+    fn test_invalid_regex_query() {
+        let mut delegate = delegate(&["a(b", "c"]);
+        delegate.set_query("/a(b/".to_string());
+        assert!(delegate.has_invalid_pattern());
+        assert_eq!(delegate.filtered_count(), 0);
+
+        delegate.set_query("a(b".to_string());
+        assert!(!delegate.has_invalid_pattern());
+        assert_eq!(delegate.filtered_count(), 1);
+    }
+}
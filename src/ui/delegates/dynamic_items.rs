@@ -1,21 +1,97 @@
-//! Dynamic item detection for calculator, AI, and search.
+//! Dynamic item detection for calculator, colors, paths, indexed files, AI,
+//! search, and query suggestions.
 //!
 //! These items are generated on-the-fly based on the user's query,
 //! rather than being static items in the list.
 
-use crate::calculator::evaluate_expression;
-use crate::items::{AiItem, CalculatorItem, SearchItem};
-use crate::search::{SearchDetection, detect_search, get_providers};
+use crate::calculator::{calculator_expression, evaluate_expression_with_answers};
+use crate::config::{CalculatorTrigger, EmptySearchTrigger};
+use crate::files::FileIndexHandle;
+use crate::items::{
+    AiItem, CachedProvider, CalculatorItem, ColorItem, FileSearchProvider, ItemProvider, ListItem,
+    PathItem, SearchItem, SuggestionItem,
+};
+use crate::query_history::QueryHistory;
+use crate::search::SearchProvider;
+use crate::triggers::{TriggerMatch, TriggerRegistry, TriggerTarget};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Maximum number of query suggestions shown.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Maximum number of indexed files shown.
+const MAX_FILE_RESULTS: usize = 5;
+
+/// Characters a query needs before the file index is searched.
+const MIN_FILE_QUERY_LENGTH: usize = 2;
+
+/// Maximum number of query characters used for matching.
+const MAX_MATCH_QUERY_LENGTH: usize = 512;
+
+/// Cut a query to `MAX_MATCH_QUERY_LENGTH` characters.
+///
+/// Very long queries (e.g. accidental pastes) are only matched on their
+/// start to keep typing responsive.
+pub(super) fn truncate_query(query: &str) -> &str {
+    match query.char_indices().nth(MAX_MATCH_QUERY_LENGTH) {
+        Some((end, _)) => &query[..end],
+        None => query,
+    }
+}
+
+/// Network detectors (AI and search fallbacks) waiting for typing to pause.
+#[derive(Clone)]
+struct PendingDetectors {
+    /// The trimmed query they run for.
+    query: String,
+    ai_enabled: bool,
+    search_providers: Vec<SearchProvider>,
+    /// When they run.
+    due: Instant,
+}
 
 /// Container for dynamically generated items based on user query.
 #[derive(Clone, Default)]
 pub struct DynamicItems {
     /// Calculator result (shown at top when query is a math expression).
     pub calculator_item: Option<CalculatorItem>,
+    /// Color preview (shown at top when query is a color literal).
+    pub color_item: Option<ColorItem>,
+    /// Existing file or directory (shown at top when query is a path).
+    pub path_item: Option<PathItem>,
     /// AI query item (shown when query triggers AI).
     pub ai_item: Option<AiItem>,
     /// Search provider items (shown when query triggers search).
     pub search_items: Vec<SearchItem>,
+    /// Indexed files matching the query (shown after the applications).
+    pub file_items: Vec<PathItem>,
+    /// Past queries starting with the query (shown last).
+    pub suggestions: Vec<SuggestionItem>,
+    /// When the calculator evaluates a query.
+    calculator_trigger: CalculatorTrigger,
+    /// Prefix triggers (`!ai`, `=`, `!g`, ...), built from the config on load.
+    triggers: Arc<TriggerRegistry>,
+    /// Recent calculator answers, most recent first, available as `ans`/`ans1`, `ans2`, ...
+    answers: Vec<String>,
+    /// Whether queries that are existing paths produce a path item, and
+    /// the file index is searched.
+    detect_paths: bool,
+    /// Searches the file index for files matching the query.
+    file_search: Option<Arc<dyn ItemProvider>>,
+    /// What a search trigger without a query shows.
+    empty_search_trigger: EmptySearchTrigger,
+    /// Confirmed queries offered as suggestions.
+    query_history: Arc<QueryHistory>,
+    /// Characters a query needs before the network detectors run.
+    min_query_length: usize,
+    /// How long the network detectors wait for the query to settle.
+    network_debounce: Duration,
+    /// Network detectors waiting for `network_debounce` to pass.
+    pending: Option<PendingDetectors>,
+    /// Query the network detectors last ran for; re-processing it (e.g. on
+    /// a refresh) runs them right away.
+    settled_query: Option<String>,
 }
 
 impl DynamicItems {
@@ -24,19 +100,107 @@ impl DynamicItems {
         Self::default()
     }
 
+    /// Create a container using the given calculator trigger mode.
+    pub fn with_calculator_trigger(calculator_trigger: CalculatorTrigger) -> Self {
+        Self {
+            calculator_trigger,
+            ..Self::default()
+        }
+    }
+
+    /// Resolve prefix triggers with the given registry (builder pattern).
+    pub fn with_triggers(mut self, triggers: Arc<TriggerRegistry>) -> Self {
+        self.triggers = triggers;
+        self
+    }
+
+    /// Make previous results, most recent first, available as `ans1`, `ans2`, ...
+    /// (builder pattern).
+    pub fn with_answers(mut self, answers: Vec<String>) -> Self {
+        self.answers = answers;
+        self
+    }
+
+    /// Offer to open queries that are existing paths (builder pattern).
+    pub fn with_path_detection(mut self, detect_paths: bool) -> Self {
+        self.detect_paths = detect_paths;
+        self
+    }
+
+    /// Search the file index for files matching queries, keeping the
+    /// results of a query for `cache_ttl` (builder pattern).
+    ///
+    /// Only used together with path detection.
+    pub fn with_file_index(
+        mut self,
+        file_index: Option<FileIndexHandle>,
+        cache_ttl: Duration,
+    ) -> Self {
+        self.file_search = file_index.map(|index| {
+            let provider = FileSearchProvider::new(index, MAX_FILE_RESULTS);
+            Arc::new(CachedProvider::new(provider, cache_ttl)) as Arc<dyn ItemProvider>
+        });
+        self
+    }
+
+    /// Set what a search trigger without a query shows (builder pattern).
+    pub fn with_empty_search_trigger(mut self, empty_search_trigger: EmptySearchTrigger) -> Self {
+        self.empty_search_trigger = empty_search_trigger;
+        self
+    }
+
+    /// Suggest past queries that start with the query (builder pattern).
+    pub fn with_query_history(mut self, query_history: Arc<QueryHistory>) -> Self {
+        self.query_history = query_history;
+        self
+    }
+
+    /// Only run the network detectors for queries of at least
+    /// `min_query_length` characters (builder pattern).
+    pub fn with_min_query_length(mut self, min_query_length: usize) -> Self {
+        self.min_query_length = min_query_length;
+        self
+    }
+
+    /// Defer the network detectors until the query has not changed for
+    /// `network_debounce` (builder pattern). See [`Self::run_due_detectors`].
+    pub fn with_network_debounce(mut self, network_debounce: Duration) -> Self {
+        self.network_debounce = network_debounce;
+        self
+    }
+
+    /// Find the trigger that owns a query, skipping the disabled features.
+    pub fn resolve_trigger<'q>(
+        &self,
+        query: &'q str,
+        calculator_enabled: bool,
+        ai_enabled: bool,
+        search_enabled: bool,
+    ) -> Option<TriggerMatch<'q>> {
+        self.triggers.resolve_where(query, |target| match target {
+            TriggerTarget::Ai => ai_enabled,
+            TriggerTarget::Calculator => calculator_enabled,
+            TriggerTarget::Search(_) => search_enabled,
+        })
+    }
+
     /// Process a query and detect dynamic items.
     ///
     /// # Arguments
     /// * `query` - The user's search query
     /// * `calculator_enabled` - Whether calculator module is enabled
     /// * `ai_enabled` - Whether AI module is enabled and configured
-    /// * `search_enabled` - Whether search module is enabled
+    /// * `search_providers` - Search providers to offer (empty when search is disabled)
+    ///
+    /// Without a trigger, the AI and search fallbacks (the network detectors)
+    /// are skipped for queries shorter than the minimum length and deferred
+    /// by the network debounce. The local detectors always run right away.
     pub fn process_query(
         &mut self,
         query: &str,
         calculator_enabled: bool,
         ai_enabled: bool,
-        search_enabled: bool,
+        search_providers: &[SearchProvider],
     ) {
         // Clear previous items
         self.clear();
@@ -46,67 +210,188 @@ impl DynamicItems {
             return;
         }
 
-        // Check for calculator expression
-        if calculator_enabled && query.chars().any(|c| c.is_numeric()) {
-            if let Ok(result) = evaluate_expression(query) {
-                self.calculator_item = Some(result);
-            }
+        // Check for a color literal first; "#123" would otherwise be a calculation
+        if calculator_enabled {
+            self.color_item = ColorItem::parse(trimmed);
         }
 
-        // Check for trigger phrases
-        let has_ai_trigger = trimmed.starts_with("!ai");
-        let search_detection = detect_search(query);
-        let has_search_trigger = matches!(search_detection, SearchDetection::Triggered { .. });
-
-        // Logic:
-        // 1. If !ai trigger → only show AI item
-        // 2. Else if search trigger (!g, !ddg, etc.) → only show that search provider
-        // 3. Else if query not empty → show AI item + all search providers at bottom
-
-        if ai_enabled && has_ai_trigger {
-            // Only show AI item when !ai trigger is used
-            let ai_query = trimmed.strip_prefix("!ai").unwrap().trim();
-            if !ai_query.is_empty() {
-                self.ai_item = Some(AiItem::new(ai_query.to_string()));
+        // Prefix triggers (!ai, =, !g, ...) claim the query for one feature
+        match self.resolve_trigger(
+            trimmed,
+            calculator_enabled,
+            ai_enabled,
+            !search_providers.is_empty(),
+        ) {
+            // Just a search trigger: the provider's homepage, if configured
+            Some(TriggerMatch {
+                target: TriggerTarget::Search(provider),
+                rest: "",
+            }) if self.empty_search_trigger == EmptySearchTrigger::Homepage => {
+                self.search_items.extend(SearchItem::homepage(provider));
             }
-        } else if search_enabled && has_search_trigger {
-            // Only show the triggered search provider
-            if let SearchDetection::Triggered { provider, query } = search_detection {
-                self.search_items.push(SearchItem::new(provider, query));
+            // Just the trigger, no query yet - don't show anything
+            Some(TriggerMatch { rest: "", .. }) => {}
+            Some(TriggerMatch {
+                target: TriggerTarget::Ai,
+                rest,
+            }) => {
+                self.ai_item = Some(AiItem::new(rest.to_string()));
             }
-        } else {
-            // Show AI item and all search providers when query is not empty
-            if ai_enabled {
-                self.ai_item = Some(AiItem::new(trimmed.to_string()));
+            Some(TriggerMatch {
+                target: TriggerTarget::Calculator,
+                ..
+            }) => {
+                if let Some(expression) =
+                    calculator_expression(trimmed, CalculatorTrigger::Explicit)
+                {
+                    self.calculate(expression);
+                }
             }
-            if search_enabled {
-                if let SearchDetection::Fallback { query } = search_detection {
-                    for provider in get_providers() {
-                        self.search_items
-                            .push(SearchItem::new(provider, query.clone()));
+            Some(TriggerMatch {
+                target: TriggerTarget::Search(provider),
+                rest,
+            }) => {
+                self.search_items.push(
+                    SearchItem::new(provider, rest.to_string()).with_variants(search_providers),
+                );
+            }
+            None => {
+                // No trigger: falls through to fuzzy matching, with the
+                // calculator, AI and all search providers as extras
+                let matched = truncate_query(trimmed);
+                if self.detect_paths {
+                    self.path_item = PathItem::detect(matched);
+                    self.search_files(matched);
+                }
+                if calculator_enabled
+                    && self.path_item.is_none()
+                    && self.color_item.is_none()
+                    && let Some(expression) =
+                        calculator_expression(matched, self.calculator_trigger)
+                {
+                    self.calculate(expression);
+                }
+                if trimmed.chars().count() >= self.min_query_length {
+                    let pending = PendingDetectors {
+                        query: trimmed.to_string(),
+                        ai_enabled,
+                        search_providers: search_providers.to_vec(),
+                        due: Instant::now() + self.network_debounce,
+                    };
+                    if self.network_debounce.is_zero()
+                        || self.settled_query.as_deref() == Some(trimmed)
+                    {
+                        self.run_network_detectors(pending);
+                    } else {
+                        self.pending = Some(pending);
                     }
                 }
+                self.suggestions = self
+                    .query_history
+                    .suggestions(matched, MAX_SUGGESTIONS)
+                    .into_iter()
+                    .map(|query| SuggestionItem::new(query.to_string()))
+                    .collect();
             }
         }
     }
 
+    /// Fill the file slots from the file index, leaving out the path item.
+    fn search_files(&mut self, query: &str) {
+        let Some(file_search) = &self.file_search else {
+            return;
+        };
+        if query.chars().count() < MIN_FILE_QUERY_LENGTH {
+            return;
+        }
+        let path_item = self.path_item.as_ref().map(|item| &item.path);
+        self.file_items = file_search
+            .provide(query)
+            .into_iter()
+            .filter_map(|item| match item {
+                ListItem::Path(item) => Some(item),
+                _ => None,
+            })
+            .filter(|item| Some(&item.path) != path_item)
+            .collect();
+    }
+
+    /// How long until deferred network detectors are due, if any are waiting.
+    pub fn pending_delay(&self, now: Instant) -> Option<Duration> {
+        self.pending
+            .as_ref()
+            .map(|pending| pending.due.saturating_duration_since(now))
+    }
+
+    /// Run the deferred network detectors if they are due at `now`.
+    /// Returns whether they ran.
+    pub fn run_due_detectors(&mut self, now: Instant) -> bool {
+        match self.pending.take() {
+            Some(pending) if pending.due <= now => {
+                self.run_network_detectors(pending);
+                true
+            }
+            pending => {
+                self.pending = pending;
+                false
+            }
+        }
+    }
+
+    /// Add the AI and search fallback items for a query.
+    fn run_network_detectors(&mut self, pending: PendingDetectors) {
+        if pending.ai_enabled {
+            self.ai_item = Some(AiItem::new(pending.query.clone()));
+        }
+        for provider in pending.search_providers {
+            self.search_items
+                .push(SearchItem::new(provider, pending.query.clone()));
+        }
+        self.settled_query = Some(pending.query);
+    }
+
+    /// Evaluate an expression into the calculator slot.
+    fn calculate(&mut self, expression: &str) {
+        let answers: Vec<&str> = self.answers.iter().map(String::as_str).collect();
+        self.calculator_item = evaluate_expression_with_answers(expression, &answers).ok();
+    }
+
     /// Clear all dynamic items.
     pub fn clear(&mut self) {
         self.calculator_item = None;
+        self.color_item = None;
+        self.path_item = None;
         self.ai_item = None;
         self.search_items.clear();
+        self.file_items.clear();
+        self.suggestions.clear();
+        self.pending = None;
     }
 
     /// Get the total count of dynamic items.
     pub fn count(&self) -> usize {
-        let calc_count = if self.calculator_item.is_some() { 1 } else { 0 };
+        let calc_count = if self.has_calculator() { 1 } else { 0 };
         let ai_count = if self.ai_item.is_some() { 1 } else { 0 };
-        calc_count + ai_count + self.search_items.len()
+        calc_count
+            + ai_count
+            + self.search_items.len()
+            + self.file_items.len()
+            + self.suggestions.len()
     }
 
-    /// Check if there's a calculator item.
+    /// Check if there's a calculator, color or path item (all use the top slot).
     pub fn has_calculator(&self) -> bool {
-        self.calculator_item.is_some()
+        self.calculator_item.is_some() || self.color_item.is_some() || self.path_item.is_some()
+    }
+
+    /// Check if there's a path item.
+    pub fn has_path(&self) -> bool {
+        self.path_item.is_some()
+    }
+
+    /// Check if there's a color item.
+    pub fn has_color(&self) -> bool {
+        self.color_item.is_some()
     }
 
     /// Check if there's an AI item.
@@ -118,11 +403,35 @@ impl DynamicItems {
     pub fn search_count(&self) -> usize {
         self.search_items.len()
     }
+
+    /// Get the indexed files count.
+    pub fn file_count(&self) -> usize {
+        self.file_items.len()
+    }
+
+    /// Get the query suggestions count.
+    pub fn suggestion_count(&self) -> usize {
+        self.suggestions.len()
+    }
+
+    /// Switch the search item `id` to its next provider.
+    /// Returns false if there is no such item or nothing to switch to.
+    pub fn cycle_search_provider(&mut self, id: &str) -> bool {
+        self.search_items
+            .iter_mut()
+            .find(|item| item.id == id)
+            .is_some_and(SearchItem::cycle_provider)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::TempDir;
+
+    fn triggers(providers: &[SearchProvider]) -> Arc<TriggerRegistry> {
+        Arc::new(TriggerRegistry::for_features(true, true, providers))
+    }
 
     #[test]
     fn test_new_is_empty() {
@@ -142,6 +451,8 @@ mod tests {
             display_result: "4".to_string(),
             clipboard_result: Some("4".to_string()),
             is_error: false,
+            delta: None,
+            bindings: Vec::new(),
         });
         assert!(items.has_calculator());
 
@@ -152,7 +463,7 @@ mod tests {
     #[test]
     fn test_process_empty_query() {
         let mut items = DynamicItems::new();
-        items.process_query("", true, true, true);
+        items.process_query("", true, true, &[]);
         assert_eq!(items.count(), 0);
     }
 
@@ -160,8 +471,349 @@ mod tests {
     fn test_calculator_detection() {
         let mut items = DynamicItems::new();
         // Enable calculator, disable AI and search
-        items.process_query("2+2", true, false, false);
+        items.process_query("2+2", true, false, &[]);
+        assert!(items.has_calculator());
+        assert!(!items.has_ai());
+    }
+
+    #[test]
+    fn test_color_detection() {
+        let mut items = DynamicItems::new();
+        items.process_query("#3366ff", true, false, &[]);
+        assert!(items.has_color());
+        assert!(items.calculator_item.is_none());
+        assert_eq!(items.count(), 1);
+
+        items.process_query("hsl(220,100%,60%)", true, false, &[]);
+        assert!(items.has_color());
+
+        items.process_query("#3366fg", true, false, &[]);
+        assert!(!items.has_color());
+    }
+
+    #[test]
+    fn test_path_detection() {
+        let dir = std::env::temp_dir();
+        let query = dir.to_string_lossy();
+
+        let mut items = DynamicItems::new();
+        items.process_query(&query, true, false, &[]);
+        assert!(!items.has_path());
+
+        let mut items = DynamicItems::new().with_path_detection(true);
+        items.process_query(&query, true, false, &[]);
+        assert!(items.has_path());
+        assert!(items.has_calculator());
+        assert_eq!(items.count(), 1);
+
+        items.process_query("/nonexistent/zlaunch", true, false, &[]);
+        assert!(!items.has_path());
+    }
+
+    #[test]
+    fn test_calculator_explicit_trigger() {
+        let mut items = DynamicItems::with_calculator_trigger(CalculatorTrigger::Explicit)
+            .with_triggers(triggers(&[]));
+        items.process_query("=2+2", true, false, &[]);
+        assert_eq!(
+            items
+                .calculator_item
+                .as_ref()
+                .map(|c| c.display_result.as_str()),
+            Some("4")
+        );
+
+        items.process_query("2+2", true, false, &[]);
+        assert!(!items.has_calculator());
+
+        items.process_query("gtk-3-dev", true, false, &[]);
+        assert!(!items.has_calculator());
+    }
+
+    #[test]
+    fn test_calculator_auto_trigger_unchanged() {
+        let mut items = DynamicItems::new();
+        items.process_query("2+2", true, false, &[]);
+        assert_eq!(
+            items
+                .calculator_item
+                .as_ref()
+                .map(|c| c.expression.as_str()),
+            Some("2+2")
+        );
+
+        items.process_query("firefox", true, false, &[]);
+        assert!(!items.has_calculator());
+    }
+
+    #[test]
+    fn test_last_answer_available_on_new_container() {
+        let mut items = DynamicItems::with_calculator_trigger(CalculatorTrigger::Auto)
+            .with_answers(vec!["21".to_string()]);
+        items.process_query("ans * 2", true, false, &[]);
+        assert_eq!(
+            items
+                .calculator_item
+                .as_ref()
+                .map(|c| c.display_result.as_str()),
+            Some("42")
+        );
+    }
+
+    #[test]
+    fn test_numbered_answers() {
+        let mut items = DynamicItems::new().with_answers(vec!["5".to_string(), "8".to_string()]);
+        items.process_query("ans2 - ans1", true, false, &[]);
+        let result = items.calculator_item.as_ref().unwrap();
+        assert_eq!(result.display_result, "3");
+
+        items.process_query("ans3", true, false, &[]);
+        assert!(items.calculator_item.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn test_triggers_claim_query() {
+        let amazon = SearchProvider {
+            name: "Amazon".to_string(),
+            trigger: "!a".to_string(),
+            url_template: "https://amazon.com/s?k={query}".to_string(),
+            icon: crate::assets::PhosphorIcon::MagnifyingGlass,
+            icon_path: None,
+            browser: None,
+        };
+        let providers = [amazon];
+        let mut items = DynamicItems::new().with_triggers(triggers(&providers));
+
+        items.process_query("!ai 2+2", true, true, &providers);
+        assert_eq!(
+            items.ai_item.as_ref().map(|a| a.query.as_str()),
+            Some("2+2")
+        );
+        assert!(!items.has_calculator());
+        assert_eq!(items.search_count(), 0);
+
+        items.process_query("!a 2+2", true, true, &providers);
+        assert!(!items.has_ai());
+        assert!(!items.has_calculator());
+        assert_eq!(items.search_count(), 1);
+
+        // Non-trigger queries keep every fallback
+        items.process_query("2+2", true, true, &providers);
+        assert!(items.has_calculator());
+        assert!(items.has_ai());
+        assert_eq!(items.search_count(), 1);
+    }
+
+    #[test]
+    fn test_cycle_search_provider_rewrites_url() {
+        let provider = |name: &str, trigger: &str, url: &str| SearchProvider {
+            name: name.to_string(),
+            trigger: trigger.to_string(),
+            url_template: url.to_string(),
+            icon: crate::assets::PhosphorIcon::MagnifyingGlass,
+            icon_path: None,
+            browser: None,
+        };
+        let providers = [
+            provider("Google", "!g", "https://google.com/search?q={query}"),
+            provider(
+                "Wikipedia",
+                "!wiki",
+                "https://en.wikipedia.org/w/index.php?search={query}",
+            ),
+            provider("DuckDuckGo", "!d", "https://duckduckgo.com/?q={query}"),
+        ];
+        let mut items = DynamicItems::new().with_triggers(triggers(&providers));
+        items.process_query("!wiki rust lang", false, false, &providers);
+
+        let mut urls = vec![items.search_items[0].url.clone()];
+        for _ in 0..3 {
+            let id = items.search_items[0].id.clone();
+            assert!(items.cycle_search_provider(&id));
+            urls.push(items.search_items[0].url.clone());
+        }
+        assert_eq!(
+            urls,
+            vec![
+                "https://en.wikipedia.org/w/index.php?search=rust%20lang",
+                "https://duckduckgo.com/?q=rust%20lang",
+                "https://google.com/search?q=rust%20lang",
+                "https://en.wikipedia.org/w/index.php?search=rust%20lang",
+            ]
+        );
+        assert_eq!(items.search_items[0].name, "Search on Wikipedia");
+        assert!(!items.cycle_search_provider("missing"));
+
+        // Fallback items already list every provider
+        items.process_query("rust lang", false, false, &providers);
+        let id = items.search_items[0].id.clone();
+        assert!(!items.cycle_search_provider(&id));
+    }
+
+    #[test]
+    fn test_empty_search_trigger() {
+        let providers = [SearchProvider {
+            name: "Google".to_string(),
+            trigger: "!g".to_string(),
+            url_template: "https://google.com/search?q={query}".to_string(),
+            icon: crate::assets::PhosphorIcon::MagnifyingGlass,
+            icon_path: None,
+            browser: None,
+        }];
+
+        // Hidden by default
+        let mut items = DynamicItems::new().with_triggers(triggers(&providers));
+        items.process_query("!g ", false, true, &providers);
+        assert_eq!(items.count(), 0);
+
+        let mut items = DynamicItems::new()
+            .with_triggers(triggers(&providers))
+            .with_empty_search_trigger(EmptySearchTrigger::Homepage);
+        items.process_query("!g", false, true, &providers);
+        assert_eq!(items.search_count(), 1);
+        assert_eq!(items.search_items[0].name, "Open Google");
+        assert_eq!(items.search_items[0].url, "https://google.com/");
+
+        // An AI trigger without a query stays hidden either way
+        items.process_query("!ai", false, true, &providers);
+        assert_eq!(items.count(), 0);
+
+        items.process_query("!g rust", false, true, &providers);
+        assert_eq!(
+            items.search_items[0].url,
+            "https://google.com/search?q=rust"
+        );
+    }
+
+    #[test]
+    fn test_query_suggestions() {
+        let mut history = QueryHistory::default();
+        for query in [
+            "rust book",
+            "rustup",
+            "firefox",
+            "rust analyzer",
+            "rust std",
+        ] {
+            history.record(query);
+        }
+        let mut items = DynamicItems::new()
+            .with_triggers(triggers(&[]))
+            .with_query_history(Arc::new(history));
+
+        items.process_query("rust", false, false, &[]);
+        let suggestions: Vec<&str> = items.suggestions.iter().map(|s| s.query.as_str()).collect();
+        assert_eq!(suggestions, ["rust std", "rust analyzer", "rustup"]);
+        assert_eq!(items.count(), MAX_SUGGESTIONS);
+
+        // Triggers claim the query
+        items.process_query("=rust", true, false, &[]);
+        assert_eq!(items.suggestion_count(), 0);
+    }
+
+    #[test]
+    fn test_file_index_results() {
+        let root = TempDir::new("dynamic-files");
+        let report = root.join("quarterly-report.pdf");
+        std::fs::write(&report, b"pdf").unwrap();
+        let mut index = crate::files::FileIndex::empty(vec![root.to_path_buf()]);
+        let rules = crate::files::IgnoreRules::default();
+        assert!(index.insert(report.clone(), &rules, 100));
+        // Indexed but deleted since: left out
+        assert!(index.insert(root.join("quarterly-gone.pdf"), &rules, 100));
+        let file_index = Some(FileIndexHandle::with_index(index));
+
+        let ttl = Duration::from_secs(60);
+        let mut items = DynamicItems::new().with_file_index(file_index.clone(), ttl);
+        items.process_query("quarterly", false, false, &[]);
+        assert_eq!(items.file_count(), 0, "needs path detection");
+
+        let mut items = DynamicItems::new()
+            .with_path_detection(true)
+            .with_file_index(file_index, ttl);
+        items.process_query("quarterly", false, false, &[]);
+        let paths: Vec<&std::path::Path> = items
+            .file_items
+            .iter()
+            .map(|item| item.path.as_path())
+            .collect();
+        assert_eq!(paths, [report.as_path()]);
+        assert_eq!(items.count(), 1);
+
+        items.process_query("q", false, false, &[]);
+        assert_eq!(items.file_count(), 0);
+    }
+
+    #[test]
+    fn test_network_detectors_gated_by_length_and_debounce() {
+        let providers = [SearchProvider {
+            name: "Google".to_string(),
+            trigger: "!g".to_string(),
+            url_template: "https://google.com/search?q={query}".to_string(),
+            icon: crate::assets::PhosphorIcon::MagnifyingGlass,
+            icon_path: None,
+            browser: None,
+        }];
+        let debounce = Duration::from_millis(150);
+        let mut items = DynamicItems::new()
+            .with_triggers(triggers(&providers))
+            .with_min_query_length(2)
+            .with_network_debounce(debounce);
+
+        // Below the minimum only the local detectors run
+        items.process_query("7", true, true, &providers);
+        assert!(!items.has_ai());
+        assert_eq!(items.search_count(), 0);
+        assert_eq!(items.pending_delay(Instant::now()), None);
+        assert!(!items.run_due_detectors(Instant::now() + debounce));
+
+        // Above it they wait for the debounce, the calculator doesn't
+        items.process_query("7*6", true, true, &providers);
         assert!(items.has_calculator());
         assert!(!items.has_ai());
+        assert!(items.pending_delay(Instant::now()).is_some());
+        assert!(!items.run_due_detectors(Instant::now()));
+        assert!(items.run_due_detectors(Instant::now() + debounce));
+        assert!(items.has_ai());
+        assert_eq!(items.search_count(), 1);
+        assert_eq!(items.count(), 3);
+
+        // Re-processing the settled query doesn't wait again
+        items.process_query("7*6", true, true, &providers);
+        assert!(items.has_ai());
+        assert_eq!(items.pending_delay(Instant::now()), None);
+
+        // Explicit triggers aren't gated
+        items.process_query("!g a", true, true, &providers);
+        assert_eq!(items.search_count(), 1);
+    }
+
+    #[test]
+    fn test_truncate_query() {
+        assert_eq!(truncate_query("firefox"), "firefox");
+        let long = "é".repeat(MAX_MATCH_QUERY_LENGTH + 10);
+        assert_eq!(
+            truncate_query(&long).chars().count(),
+            MAX_MATCH_QUERY_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_long_query_reaches_ai_whole() {
+        let prompt = "word ".repeat(MAX_MATCH_QUERY_LENGTH);
+        let mut items = DynamicItems::new().with_triggers(triggers(&[]));
+
+        items.process_query(&format!("!ai {prompt}"), true, true, &[]);
+        assert_eq!(
+            items.ai_item.as_ref().map(|a| a.query.len()),
+            Some(prompt.trim().len())
+        );
+
+        items.process_query(&prompt, true, true, &[]);
+        assert_eq!(
+            items.ai_item.as_ref().map(|a| a.query.len()),
+            Some(prompt.trim().len())
+        );
+        assert!(!items.has_calculator());
     }
 }
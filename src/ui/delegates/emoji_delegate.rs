@@ -1,271 +1,225 @@
-use crate::emoji::EmojiItem;
-use crate::ui::delegates::BaseDelegate;
+use crate::config::EmojiCopyFormat;
+use crate::emoji::{EMOJI_VARIATION_SELECTOR, EmojiItem, EmojiUsage, rank_matches};
+use crate::ui::delegates::{GridDelegate, GridItem};
 use crate::ui::theme::theme;
-use crate::ui::views::render_emoji_row;
-use gpui::{App, Context, SharedString, Task, Window, div, prelude::*};
-use gpui_component::IndexPath;
-use gpui_component::list::{ListDelegate, ListItem as GpuiListItem, ListState};
+use gpui::{Div, SharedString, div, prelude::*};
+use std::sync::Arc;
 
 /// Delegate for the emoji picker grid.
-///
-/// This is a simplified delegate that composes with BaseDelegate<EmojiItem>
-/// and adds grid-specific navigation logic.
-pub struct EmojiGridDelegate {
-    /// Base delegate handling common behavior
-    base: BaseDelegate<EmojiItem>,
-    /// Number of columns in the grid
-    columns: usize,
-}
-
-impl EmojiGridDelegate {
-    /// Create a new emoji grid delegate
-    pub fn new(items: Vec<EmojiItem>, columns: usize) -> Self {
-        Self {
-            base: BaseDelegate::new(items),
-            columns,
-        }
-    }
-
-    /// Set the confirm callback
-    pub fn set_on_confirm(&mut self, callback: impl Fn(&EmojiItem) + Send + Sync + 'static) {
-        self.base.set_on_confirm(callback);
-    }
-
-    /// Set the cancel callback
-    pub fn set_on_cancel(&mut self, callback: impl Fn() + Send + Sync + 'static) {
-        self.base.set_on_cancel(callback);
-    }
-
-    /// Get the currently selected index
-    pub fn selected_index(&self) -> Option<usize> {
-        self.base.selected_index()
-    }
-
-    /// Get the selected row (for scrolling in grid layout)
-    pub fn selected_row(&self) -> Option<usize> {
-        self.selected_index().map(|idx| idx / self.columns)
-    }
-
-    /// Get the total count of filtered items
-    pub fn filtered_count(&self) -> usize {
-        self.base.filtered_count()
-    }
-
-    /// Get the current query
-    pub fn query(&self) -> &str {
-        self.base.query()
-    }
-
-    /// Clear the query
-    pub fn clear_query(&mut self) {
-        self.base.clear_query();
-    }
-
-    /// Set the query and filter
-    pub fn set_query(&mut self, query: String) {
-        self.base.set_query(query);
-        self.filter_items();
-    }
-
-    /// Filter items based on the current query
-    fn filter_items(&mut self) {
-        let query = self.base.query();
-        if query.is_empty() {
-            self.base.reset_filter();
-        } else {
-            let items = self.base.items();
-            let query_lower = query.to_lowercase();
-            let filtered_indices: Vec<usize> = items
-                .iter()
-                .enumerate()
-                .filter(|(_, item)| {
-                    item.emoji.contains(query) || item.name.to_lowercase().contains(&query_lower)
-                })
-                .map(|(idx, _)| idx)
-                .collect();
-            self.base.apply_filtered_indices(filtered_indices);
-        }
-    }
-
-    /// Get an item at a filtered index
-    pub fn get_item_at(&self, index: usize) -> Option<&EmojiItem> {
-        self.base.get_filtered_item(index)
-    }
-
-    /// Execute confirm callback
-    pub fn do_confirm(&self) {
-        self.base.do_confirm();
-    }
-
-    /// Execute cancel callback
-    pub fn do_cancel(&self) {
-        self.base.do_cancel();
-    }
-
-    /// Move selection right (for grid navigation)
-    pub fn select_right(&mut self) {
-        // In a grid, "right" is the same as "next"
-        self.base.select_down();
-    }
-
-    /// Move selection left (for grid navigation)
-    pub fn select_left(&mut self) {
-        // In a grid, "left" is the same as "previous"
-        self.base.select_up();
-    }
-
-    /// Move selection down (by one row)
-    pub fn select_down(&mut self) {
-        let count = self.filtered_count();
-        if count == 0 {
-            return;
-        }
-
-        if let Some(current) = self.selected_index() {
-            let next = current + self.columns;
-            if next < count {
-                self.base.set_selected(next);
-            } else {
-                // Wrap to first item in same column
-                self.base.set_selected(current % self.columns);
-            }
-        }
-    }
-
-    /// Move selection up (by one row)
-    pub fn select_up(&mut self) {
-        let count = self.filtered_count();
-        if count == 0 {
-            return;
-        }
-
-        if let Some(current) = self.selected_index() {
-            if current >= self.columns {
-                self.base.set_selected(current - self.columns);
-            } else {
-                // Wrap to last row in same column
-                let col = current % self.columns;
-                let last_row = (count - 1) / self.columns;
-                let target = last_row * self.columns + col;
-                if target < count {
-                    self.base.set_selected(target);
-                } else {
-                    // If last row doesn't have this column, go to previous row
-                    let prev_row = last_row - 1;
-                    self.base.set_selected(prev_row * self.columns + col);
+pub type EmojiGridDelegate = GridDelegate<EmojiItem>;
+
+impl GridItem for EmojiItem {
+    type Ranking = Arc<EmojiUsage>;
+
+    const EMPTY_TEXT: &'static str = "No emojis found";
+
+    /// Matches are ordered by usage frequency, then match quality.
+    ///
+    /// Emoji are compared as whole sequences, so a flag or ZWJ family only
+    /// matches itself and not the emoji it is composed of. Variation
+    /// selectors are ignored, so a bare ❤ finds ❤️.
+    fn filter(items: &[Self], query: &str, usage: &Arc<EmojiUsage>) -> Vec<usize> {
+        let query_lower = query.to_lowercase();
+        let scored: Vec<(usize, i64)> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| {
+                if same_emoji(&item.emoji, query.trim()) {
+                    return Some((idx, 2));
                 }
-            }
-        }
-    }
-
-    /// Get the number of rows needed for the grid.
-    fn row_count(&self) -> usize {
-        let count = self.filtered_count();
-        if count == 0 {
-            0
-        } else {
-            count.div_ceil(self.columns)
-        }
-    }
-
-    /// Get emojis for a specific row.
-    fn emojis_for_row(&self, row: usize) -> Vec<EmojiItem> {
-        let start = row * self.columns;
-        let end = (start + self.columns).min(self.filtered_count());
-        (start..end)
-            .filter_map(|i| self.base.get_filtered_item(i).cloned())
-            .collect()
+                let name = item.name.to_lowercase();
+                let name_quality = name.find(&query_lower).map(|pos| {
+                    // Prefer matches at the start of the name or of a word
+                    match pos {
+                        0 => 2,
+                        _ if name.as_bytes()[pos - 1] == b' ' => 1,
+                        _ => 0,
+                    }
+                });
+                // `:jp:` or `jp` finds the flag of Japan
+                let code_query = query_lower.trim_matches(':');
+                let shortcode_quality = item
+                    .shortcode
+                    .as_deref()
+                    .filter(|code| !code_query.is_empty() && code.starts_with(code_query))
+                    .map(|code| if code == code_query { 2 } else { 1 });
+                Some((idx, name_quality.max(shortcode_quality)?))
+            })
+            .collect();
+        rank_matches(items, scored, usage)
+    }
+
+    fn copy_text(&self) -> String {
+        self.glyph()
+    }
+
+    fn render_cell_content(&self) -> Div {
+        div()
+            .text_size(theme().emoji.font_size)
+            .child(SharedString::from(self.glyph()))
     }
 }
 
-/// Implement ListDelegate trait for GPUI integration.
-impl ListDelegate for EmojiGridDelegate {
-    type Item = GpuiListItem;
-
-    fn sections_count(&self, _cx: &App) -> usize {
-        1
-    }
-
-    fn items_count(&self, _section: usize, _cx: &App) -> usize {
-        self.row_count()
+impl EmojiGridDelegate {
+    /// Get the text to copy for the selected emoji.
+    /// Secondary confirm copies the shortcode or name instead of the glyph.
+    pub fn selected_clipboard_text(
+        &self,
+        secondary: bool,
+        format: EmojiCopyFormat,
+    ) -> Option<String> {
+        Some(self.selected_item()?.text_for_clipboard(secondary, format))
     }
+}
 
-    fn render_item(
-        &mut self,
-        ix: IndexPath,
-        _window: &mut Window,
-        _cx: &mut Context<'_, ListState<Self>>,
-    ) -> Option<Self::Item> {
-        let row = ix.row;
-        let emojis = self.emojis_for_row(row);
-        let start_index = row * self.columns;
+/// Whether two emoji are the same sequence, ignoring variation selectors.
+fn same_emoji(a: &str, b: &str) -> bool {
+    a.chars()
+        .filter(|&c| c != EMOJI_VARIATION_SELECTOR)
+        .eq(b.chars().filter(|&c| c != EMOJI_VARIATION_SELECTOR))
+}
 
-        let row_element = render_emoji_row(
-            &emojis,
-            start_index,
-            self.base.selected_index(),
-            self.columns,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selected_clipboard_text() {
+        let items = vec![
+            EmojiItem::new("😀", "grinning face").with_shortcode(Some("grinning")),
+            EmojiItem::new("👍", "thumbs up").with_shortcode(Some("+1")),
+        ];
+        let mut delegate = EmojiGridDelegate::new(items, 8);
+        delegate.select_right();
+
+        assert_eq!(
+            delegate
+                .selected_clipboard_text(false, EmojiCopyFormat::Shortcode)
+                .as_deref(),
+            Some("👍")
+        );
+        assert_eq!(
+            delegate
+                .selected_clipboard_text(true, EmojiCopyFormat::Shortcode)
+                .as_deref(),
+            Some(":+1:")
         );
-
-        Some(
-            GpuiListItem::new(("emoji-row", row))
-                .py_0()
-                .px_0()
-                .child(row_element),
-        )
-    }
-
-    fn set_selected_index(
-        &mut self,
-        ix: Option<IndexPath>,
-        _window: &mut Window,
-        _cx: &mut Context<ListState<Self>>,
-    ) {
-        // Convert row to first item in that row
-        self.base
-            .set_selected(ix.map(|i| i.row * self.columns).unwrap_or(0));
-    }
-
-    fn perform_search(
-        &mut self,
-        query: &str,
-        _window: &mut Window,
-        _cx: &mut Context<ListState<Self>>,
-    ) -> Task<()> {
-        self.set_query(query.to_string());
-        Task::ready(())
-    }
-
-    fn confirm(
-        &mut self,
-        _secondary: bool,
-        _window: &mut Window,
-        _cx: &mut Context<ListState<Self>>,
-    ) {
-        self.do_confirm();
     }
 
-    fn cancel(&mut self, _window: &mut Window, _cx: &mut Context<ListState<Self>>) {
-        self.do_cancel();
-    }
+    #[test]
+    fn test_sequences_select_and_copy_whole() {
+        let flag = "\u{1F1EF}\u{1F1F5}";
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+        let items = vec![
+            EmojiItem::new("\u{1F468}", "man").with_shortcode(Some("man")),
+            EmojiItem::new(flag, "flag: Japan").with_shortcode(Some("jp")),
+            EmojiItem::new(family, "family: man, woman, girl, boy")
+                .with_shortcode(Some("family_man_woman_girl_boy")),
+        ];
+        let mut delegate = EmojiGridDelegate::new(items, 8);
+
+        delegate.set_query(":jp:".to_string());
+        assert_eq!(delegate.filtered_count(), 1);
+        delegate.set_selected(0);
+        assert_eq!(
+            delegate
+                .selected_clipboard_text(false, EmojiCopyFormat::Shortcode)
+                .as_deref(),
+            Some(flag)
+        );
 
-    fn render_empty(
-        &mut self,
-        _window: &mut Window,
-        _cx: &mut Context<'_, ListState<Self>>,
-    ) -> impl IntoElement {
-        let theme = theme();
-        div()
-            .w_full()
-            .h(theme.empty_state_height)
-            .flex()
-            .items_center()
-            .justify_center()
-            .child(
-                div()
-                    .text_sm()
-                    .text_color(theme.empty_state_color)
-                    .child(SharedString::from("No emojis found")),
-            )
+        delegate.set_query("family".to_string());
+        delegate.set_selected(0);
+        let copied = delegate
+            .selected_clipboard_text(false, EmojiCopyFormat::Shortcode)
+            .unwrap();
+        assert_eq!(copied, family);
+        assert_eq!(copied.chars().count(), 7);
+
+        // Pasting a sequence finds the sequence, not its parts
+        delegate.set_query(family.to_string());
+        assert_eq!(delegate.filtered_count(), 1);
+        delegate.set_query("\u{1F468}".to_string());
+        assert_eq!(delegate.filtered_count(), 1);
+        assert_eq!(delegate.get_item_at(0).unwrap().name, "man");
+    }
+
+    #[test]
+    fn test_exact_match_ignores_variation_selector() {
+        let items = vec![
+            EmojiItem::new("\u{2764}\u{FE0F}", "red heart"),
+            EmojiItem::new("\u{2B50}", "star"),
+        ];
+        let mut delegate = EmojiGridDelegate::new(items, 8);
+
+        delegate.set_query("\u{2764}".to_string());
+        assert_eq!(delegate.filtered_count(), 1);
+        assert_eq!(delegate.get_item_at(0).unwrap().name, "red heart");
+
+        delegate.set_query("\u{2B50}\u{FE0F}".to_string());
+        assert_eq!(delegate.filtered_count(), 1);
+        assert_eq!(delegate.get_item_at(0).unwrap().name, "star");
+    }
+
+    fn delegate(emojis: &[&str]) -> EmojiGridDelegate {
+        let items = emojis
+            .iter()
+            .map(|&emoji| EmojiItem::new(emoji, emoji))
+            .collect();
+        EmojiGridDelegate::new(items, 8)
+    }
+
+    #[test]
+    fn test_toggle_marked() {
+        let mut delegate = delegate(&["😀", "👍", "🎉"]);
+        assert_eq!(delegate.marked_text(), None);
+
+        delegate.toggle_marked_at(2);
+        delegate.toggle_marked_at(0);
+        assert_eq!(delegate.marked_count(), 2);
+        assert!(delegate.is_marked_at(2));
+        assert!(!delegate.is_marked_at(1));
+
+        // Toggling again unmarks
+        delegate.toggle_marked_at(2);
+        assert_eq!(delegate.marked_count(), 1);
+        assert!(!delegate.is_marked_at(2));
+
+        // Out of range indices are ignored
+        delegate.toggle_marked_at(10);
+        assert_eq!(delegate.marked_count(), 1);
+    }
+
+    #[test]
+    fn test_marked_text_in_marking_order() {
+        let mut delegate = delegate(&["😀", "👍", "🎉"]);
+        delegate.toggle_marked_at(1);
+        delegate.toggle_marked_at(2);
+        delegate.set_selected(0);
+        delegate.toggle_selected_marked();
+        assert_eq!(delegate.marked_text().as_deref(), Some("👍🎉😀"));
+
+        // Marks are kept while filtering
+        delegate.set_query("🎉".to_string());
+        assert!(delegate.is_marked_at(0));
+        assert_eq!(delegate.marked_text().as_deref(), Some("👍🎉😀"));
+    }
+
+    #[test]
+    fn test_filter_orders_by_usage() {
+        let items = vec![
+            EmojiItem::new("🐈", "cat"),
+            EmojiItem::new("😺", "grinning cat"),
+            EmojiItem::new("🐶", "dog"),
+        ];
+        let mut usage = EmojiUsage::default();
+        usage.record("😺");
+
+        let mut delegate = EmojiGridDelegate::new(items, 8).with_ranking(Arc::new(usage));
+        delegate.set_query("cat".to_string());
+
+        assert_eq!(delegate.filtered_count(), 2);
+        assert_eq!(delegate.get_item_at(0).unwrap().emoji, "😺");
+        assert_eq!(delegate.get_item_at(1).unwrap().emoji, "🐈");
     }
 }
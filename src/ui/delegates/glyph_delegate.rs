@@ -0,0 +1,85 @@
+use crate::glyphs::{GlyphItem, search_glyphs_in};
+use crate::ui::delegates::{GridDelegate, GridItem};
+use crate::ui::theme::theme;
+use gpui::{Div, SharedString, div, prelude::*};
+
+/// Delegate for the icon glyph picker grid.
+pub type GlyphGridDelegate = GridDelegate<GlyphItem>;
+
+impl GridItem for GlyphItem {
+    type Ranking = ();
+
+    const EMPTY_TEXT: &'static str = "No icons found";
+
+    /// Fuzzy filter items by name or tag, best matches first
+    fn filter(items: &[Self], query: &str, _ranking: &()) -> Vec<usize> {
+        search_glyphs_in(items, query)
+    }
+
+    fn copy_text(&self) -> String {
+        self.glyph.clone()
+    }
+
+    /// The glyph only renders correctly with a Nerd Font, so the codepoint
+    /// is always shown underneath as a fallback label.
+    fn render_cell_content(&self) -> Div {
+        let theme = theme();
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .child(
+                div()
+                    .font_family(theme.markdown.code_font_family)
+                    .text_size(theme.emoji.font_size * 0.8)
+                    .text_color(theme.item_title_color)
+                    .child(SharedString::from(self.glyph.clone())),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.item_description_color)
+                    .child(SharedString::from(self.codepoint_label())),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyphs() -> Vec<GlyphItem> {
+        vec![
+            GlyphItem::new(0xE7A8, "nf-dev-rust", &["language", "cargo"]).unwrap(),
+            GlyphItem::new(0xF303, "nf-linux-archlinux", &["arch", "distro"]).unwrap(),
+            GlyphItem::new(0xF31B, "nf-linux-ubuntu", &["distro"]).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_filter_by_name() {
+        let mut delegate = GlyphGridDelegate::new(glyphs(), 8);
+        delegate.set_query("rust".to_string());
+        assert_eq!(delegate.filtered_count(), 1);
+        assert_eq!(delegate.get_item_at(0).unwrap().name, "nf-dev-rust");
+    }
+
+    #[test]
+    fn test_filter_by_tag() {
+        let mut delegate = GlyphGridDelegate::new(glyphs(), 8);
+        delegate.set_query("Distro".to_string());
+        assert_eq!(delegate.filtered_count(), 2);
+    }
+
+    #[test]
+    fn test_filter_is_fuzzy_and_ranked() {
+        let mut delegate = GlyphGridDelegate::new(glyphs(), 8);
+        delegate.set_query("ubnt".to_string());
+        assert_eq!(delegate.filtered_count(), 1);
+        assert_eq!(delegate.get_item_at(0).unwrap().name, "nf-linux-ubuntu");
+
+        // Pasting the glyph finds it
+        delegate.set_query("\u{F303}".to_string());
+        assert_eq!(delegate.get_item_at(0).unwrap().name, "nf-linux-archlinux");
+    }
+}
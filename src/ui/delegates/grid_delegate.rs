@@ -0,0 +1,412 @@
+use crate::ui::delegates::BaseDelegate;
+use crate::ui::theme::theme;
+use crate::ui::views::render_grid_row;
+use gpui::{App, Context, Div, SharedString, Task, Window, div, prelude::*};
+use gpui_component::IndexPath;
+use gpui_component::list::{ListDelegate, ListItem as GpuiListItem, ListState};
+use std::sync::Arc;
+
+/// An item shown as a cell of a picker grid.
+pub trait GridItem: Clone + Send + Sync + 'static {
+    /// State used to rank matches, such as usage statistics.
+    type Ranking: Default;
+
+    /// Text shown when no item matches the query.
+    const EMPTY_TEXT: &'static str;
+
+    /// Indices of the items matching a non-empty query, best matches first.
+    fn filter(items: &[Self], query: &str, ranking: &Self::Ranking) -> Vec<usize>;
+
+    /// The text copied for the item.
+    fn copy_text(&self) -> String;
+
+    /// Render the content of the item's cell.
+    fn render_cell_content(&self) -> Div;
+}
+
+/// Callback receiving the joined text and the marked items.
+type ConfirmMarkedCallback<T> = Arc<dyn Fn(&str, &[&T]) + Send + Sync>;
+
+/// Delegate for the picker grids (emoji and icon glyphs).
+///
+/// Composes with BaseDelegate<T> and adds grid-specific navigation logic
+/// and batch-copy marks.
+pub struct GridDelegate<T: GridItem> {
+    /// Base delegate handling common behavior
+    base: BaseDelegate<T>,
+    /// Number of columns in the grid
+    columns: usize,
+    /// State for ranking matches
+    ranking: T::Ranking,
+    /// Item indices marked for batch copy, in marking order
+    marked: Vec<usize>,
+    /// Callback for confirming with marked items
+    on_confirm_marked: Option<ConfirmMarkedCallback<T>>,
+}
+
+impl<T: GridItem> GridDelegate<T> {
+    /// Create a new grid delegate
+    pub fn new(items: Vec<T>, columns: usize) -> Self {
+        Self {
+            base: BaseDelegate::new(items),
+            columns,
+            ranking: T::Ranking::default(),
+            marked: Vec::new(),
+            on_confirm_marked: None,
+        }
+    }
+
+    /// Use the given state for ranking matches (builder pattern).
+    pub fn with_ranking(mut self, ranking: T::Ranking) -> Self {
+        self.ranking = ranking;
+        self
+    }
+
+    /// Set the callback for confirming with marked items (copy joined text)
+    pub fn set_on_confirm_marked(
+        &mut self,
+        callback: impl Fn(&str, &[&T]) + Send + Sync + 'static,
+    ) {
+        self.on_confirm_marked = Some(Arc::new(callback));
+    }
+
+    /// Set the confirm callback
+    pub fn set_on_confirm(&mut self, callback: impl Fn(&T) + Send + Sync + 'static) {
+        self.base.set_on_confirm(callback);
+    }
+
+    /// Set the cancel callback
+    pub fn set_on_cancel(&mut self, callback: impl Fn() + Send + Sync + 'static) {
+        self.base.set_on_cancel(callback);
+    }
+
+    /// Get the currently selected index
+    pub fn selected_index(&self) -> Option<usize> {
+        self.base.selected_index()
+    }
+
+    /// Get the currently selected item
+    pub fn selected_item(&self) -> Option<&T> {
+        self.base.selected_item()
+    }
+
+    /// Get the selected row (for scrolling in grid layout)
+    pub fn selected_row(&self) -> Option<usize> {
+        self.selected_index().map(|idx| idx / self.columns)
+    }
+
+    /// Set the selected index
+    pub fn set_selected(&mut self, index: usize) {
+        self.base.set_selected(index);
+    }
+
+    /// Get the total count of filtered items
+    pub fn filtered_count(&self) -> usize {
+        self.base.filtered_count()
+    }
+
+    /// Get the total count of items, ignoring the query
+    pub fn item_count(&self) -> usize {
+        self.base.items().len()
+    }
+
+    /// Get the current query
+    pub fn query(&self) -> &str {
+        self.base.query()
+    }
+
+    /// Clear the query
+    pub fn clear_query(&mut self) {
+        self.base.clear_query();
+    }
+
+    /// Set the query and filter
+    pub fn set_query(&mut self, query: String) {
+        self.base.set_query(query);
+        self.filter_items();
+    }
+
+    /// Filter items based on the current query.
+    fn filter_items(&mut self) {
+        let query = self.base.query();
+        if query.is_empty() {
+            self.base.reset_filter();
+        } else {
+            let filtered_indices = T::filter(self.base.items(), query, &self.ranking);
+            self.base.apply_filtered_indices(filtered_indices);
+        }
+    }
+
+    /// Get an item at a filtered index
+    pub fn get_item_at(&self, index: usize) -> Option<&T> {
+        self.base.get_filtered_item(index)
+    }
+
+    /// Toggle the batch-copy mark of the item at a filtered index.
+    pub fn toggle_marked_at(&mut self, filtered_index: usize) {
+        let Some(&index) = self.base.filtered_indices().get(filtered_index) else {
+            return;
+        };
+        match self.marked.iter().position(|&i| i == index) {
+            Some(pos) => {
+                self.marked.remove(pos);
+            }
+            None => self.marked.push(index),
+        }
+    }
+
+    /// Toggle the batch-copy mark of the selected item.
+    pub fn toggle_selected_marked(&mut self) {
+        if let Some(index) = self.selected_index() {
+            self.toggle_marked_at(index);
+        }
+    }
+
+    /// Whether the item at a filtered index is marked.
+    pub fn is_marked_at(&self, filtered_index: usize) -> bool {
+        self.base
+            .filtered_indices()
+            .get(filtered_index)
+            .is_some_and(|index| self.marked.contains(index))
+    }
+
+    /// Get the number of marked items
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    /// Get the marked items in marking order.
+    pub fn marked_items(&self) -> Vec<&T> {
+        let items = self.base.items();
+        self.marked.iter().filter_map(|&i| items.get(i)).collect()
+    }
+
+    /// Concatenate the text of the marked items in marking order.
+    ///
+    /// Returns None if nothing is marked.
+    pub fn marked_text(&self) -> Option<String> {
+        if self.marked.is_empty() {
+            return None;
+        }
+        Some(
+            self.marked_items()
+                .iter()
+                .map(|item| item.copy_text())
+                .collect(),
+        )
+    }
+
+    /// Execute confirm callback.
+    /// With marked items, their joined text is confirmed instead.
+    pub fn do_confirm(&self) {
+        if let Some(text) = self.marked_text()
+            && let Some(ref callback) = self.on_confirm_marked
+        {
+            callback(&text, &self.marked_items());
+            return;
+        }
+        self.base.do_confirm();
+    }
+
+    /// Execute cancel callback
+    pub fn do_cancel(&self) {
+        self.base.do_cancel();
+    }
+
+    /// Move selection right (for grid navigation)
+    pub fn select_right(&mut self) {
+        // In a grid, "right" is the same as "next"
+        self.base.select_down();
+    }
+
+    /// Move selection left (for grid navigation)
+    pub fn select_left(&mut self) {
+        // In a grid, "left" is the same as "previous"
+        self.base.select_up();
+    }
+
+    /// Move selection down (by one row)
+    pub fn select_down(&mut self) {
+        let count = self.filtered_count();
+        if count == 0 {
+            return;
+        }
+
+        if let Some(current) = self.selected_index() {
+            let next = current + self.columns;
+            if next < count {
+                self.base.set_selected(next);
+            } else {
+                // Wrap to first item in same column
+                self.base.set_selected(current % self.columns);
+            }
+        }
+    }
+
+    /// Move selection up (by one row)
+    pub fn select_up(&mut self) {
+        let count = self.filtered_count();
+        if count == 0 {
+            return;
+        }
+
+        if let Some(current) = self.selected_index() {
+            if current >= self.columns {
+                self.base.set_selected(current - self.columns);
+            } else {
+                // Wrap to last row in same column
+                let col = current % self.columns;
+                let last_row = (count - 1) / self.columns;
+                let target = last_row * self.columns + col;
+                if target < count {
+                    self.base.set_selected(target);
+                } else if last_row > 0 {
+                    // If last row doesn't have this column, go to previous row
+                    self.base.set_selected((last_row - 1) * self.columns + col);
+                }
+            }
+        }
+    }
+
+    /// Get the number of rows needed for the grid.
+    fn row_count(&self) -> usize {
+        self.filtered_count().div_ceil(self.columns)
+    }
+
+    /// Get items for a specific row.
+    fn items_for_row(&self, row: usize) -> Vec<&T> {
+        let start = row * self.columns;
+        let end = (start + self.columns).min(self.filtered_count());
+        (start..end)
+            .filter_map(|i| self.base.get_filtered_item(i))
+            .collect()
+    }
+}
+
+/// Implement ListDelegate trait for GPUI integration.
+impl<T: GridItem> ListDelegate for GridDelegate<T> {
+    type Item = GpuiListItem;
+
+    fn sections_count(&self, _cx: &App) -> usize {
+        1
+    }
+
+    fn items_count(&self, _section: usize, _cx: &App) -> usize {
+        self.row_count()
+    }
+
+    fn render_item(
+        &mut self,
+        ix: IndexPath,
+        _window: &mut Window,
+        _cx: &mut Context<'_, ListState<Self>>,
+    ) -> Option<Self::Item> {
+        let row = ix.row;
+        let items = self.items_for_row(row);
+        let start_index = row * self.columns;
+        let marked: Vec<bool> = (start_index..start_index + items.len())
+            .map(|i| self.is_marked_at(i))
+            .collect();
+
+        let row_element = render_grid_row(
+            &items,
+            start_index,
+            self.base.selected_index(),
+            &marked,
+            self.columns,
+        );
+
+        Some(
+            GpuiListItem::new(("grid-row", row))
+                .py_0()
+                .px_0()
+                .child(row_element),
+        )
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: Option<IndexPath>,
+        _window: &mut Window,
+        _cx: &mut Context<ListState<Self>>,
+    ) {
+        // Convert row to first item in that row
+        self.base
+            .set_selected(ix.map(|i| i.row * self.columns).unwrap_or(0));
+    }
+
+    fn perform_search(
+        &mut self,
+        query: &str,
+        _window: &mut Window,
+        _cx: &mut Context<ListState<Self>>,
+    ) -> Task<()> {
+        self.set_query(query.to_string());
+        Task::ready(())
+    }
+
+    fn confirm(
+        &mut self,
+        _secondary: bool,
+        _window: &mut Window,
+        _cx: &mut Context<ListState<Self>>,
+    ) {
+        self.do_confirm();
+    }
+
+    fn cancel(&mut self, _window: &mut Window, _cx: &mut Context<ListState<Self>>) {
+        self.do_cancel();
+    }
+
+    fn render_empty(
+        &mut self,
+        _window: &mut Window,
+        _cx: &mut Context<'_, ListState<Self>>,
+    ) -> impl IntoElement {
+        let theme = theme();
+        div()
+            .w_full()
+            .h(theme.empty_state_height)
+            .flex()
+            .items_center()
+            .justify_center()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(theme.empty_state_color)
+                    .child(SharedString::from(T::EMPTY_TEXT)),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyphs::GlyphItem;
+
+    fn delegate(count: u32, columns: usize) -> GridDelegate<GlyphItem> {
+        let items = (0..count)
+            .filter_map(|i| GlyphItem::new(0xE700 + i, format!("glyph-{i}"), &[]))
+            .collect();
+        GridDelegate::new(items, columns)
+    }
+
+    #[test]
+    fn test_vertical_moves_wrap_within_column() {
+        // Two full rows of three and a last row with one item
+        let mut delegate = delegate(7, 3);
+
+        delegate.set_selected(1);
+        delegate.select_up();
+        // The last row has no second column, so the row above it is used
+        assert_eq!(delegate.selected_index(), Some(4));
+        delegate.select_down();
+        assert_eq!(delegate.selected_index(), Some(1));
+
+        delegate.set_selected(0);
+        delegate.select_up();
+        assert_eq!(delegate.selected_index(), Some(6));
+        delegate.select_down();
+        assert_eq!(delegate.selected_index(), Some(0));
+    }
+}
@@ -4,23 +4,90 @@
 //! and section management.
 
 use crate::ai::LLMClient;
-use crate::config::{ConfigModule, config};
-use crate::items::{ActionItem, ListItem, SubmenuItem};
+use crate::calculator::recent_answers;
+use crate::config::{
+    AppConfig, ConfigModule, ResultGrouping, SectionHeaders, combined_modules_for, config,
+};
+use crate::files::file_index;
+use crate::frecency::{LaunchHistory, launch_history};
+use crate::items::{ItemProvider, ListItem, ProviderRegistry, WindowItem};
+use crate::query_history::{QueryHistory, query_history};
+use crate::search::{SearchProvider, providers_from_config};
+use crate::triggers::{TriggerRegistry, TriggerTarget};
 use crate::ui::delegates::BaseDelegate;
+use crate::ui::motion;
 use crate::ui::theme::theme;
-use crate::ui::views::render_item;
+use crate::ui::views::{render_item, render_loading_row};
 use gpui::{App, Context, SharedString, Task, Window, div, prelude::*};
 use gpui_component::IndexPath;
 use gpui_component::list::{ListDelegate, ListItem as GpuiListItem, ListState};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use super::dynamic_items::DynamicItems;
+use super::dynamic_items::{DynamicItems, truncate_query};
 use super::item_filter::ItemFilter;
 use super::section_manager::{SectionManager, SectionType};
 
 /// Type alias for confirm callback.
 type ConfirmCallback = Arc<dyn Fn(&ListItem) + Send + Sync>;
 
+/// Which detectors fired for a query (see [`ItemListDelegate::explain`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryExplanation {
+    /// Trigger that claimed the query (`!ai`, `=`, a search provider).
+    pub trigger: Option<TriggerTarget>,
+    /// Calculator result shown at the top.
+    pub calculator: Option<String>,
+    /// Whether the query is a color literal.
+    pub color: bool,
+    /// Whether an AI item is offered.
+    pub ai: bool,
+    /// Names of the search providers offered.
+    pub search_providers: Vec<String>,
+    /// Number of regular items matched by fuzzy matching.
+    pub fuzzy_matches: usize,
+}
+
+/// Options for [`ItemListDelegate::with_items`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DelegateOptions {
+    /// Drop items of modules missing from the combined modules and add the
+    /// built-in submenus and actions. Default: true
+    pub builtins: bool,
+}
+
+impl Default for DelegateOptions {
+    fn default() -> Self {
+        Self { builtins: true }
+    }
+}
+
+impl DelegateOptions {
+    /// Show exactly the given items, without built-in submenus or actions.
+    pub fn without_builtins() -> Self {
+        Self { builtins: false }
+    }
+}
+
+/// Launch and query history a delegate ranks and suggests with.
+#[derive(Clone, Default)]
+pub struct DelegateHistory {
+    /// Launches, for frecency ranking.
+    pub launches: Arc<LaunchHistory>,
+    /// Confirmed queries, for suggestions.
+    pub queries: Arc<QueryHistory>,
+}
+
+impl DelegateHistory {
+    /// Snapshots of the shared stores.
+    pub fn shared() -> Self {
+        Self {
+            launches: launch_history(),
+            queries: query_history(),
+        }
+    }
+}
+
 /// Enhanced delegate for the main item list.
 ///
 /// This delegate composes with BaseDelegate<ListItem> and adds:
@@ -41,11 +108,202 @@ pub struct ItemListDelegate {
     on_confirm: Option<ConfirmCallback>,
     /// Modules enabled in combined view (for filtering).
     combined_modules: Vec<ConfigModule>,
+    /// Search providers offered for queries.
+    search_providers: Vec<SearchProvider>,
+    /// Whether the config allows AI answers.
+    ai_enabled: bool,
+    /// Whether an LLM is configured.
+    llm_configured: bool,
+    /// When section headers are shown.
+    section_headers: SectionHeaders,
+    /// Follow the selected item across query changes.
+    keep_selection: bool,
+    /// Alt-Tab style window switcher: start on the previous window.
+    switcher: bool,
+    /// Sources of the items (loaded applications and windows, built-in
+    /// submenus, actions). None when the items are shown as given.
+    providers: Option<ProviderRegistry>,
+    /// History to use instead of the shared stores, e.g. in tests.
+    history: Option<DelegateHistory>,
 }
 
 impl ItemListDelegate {
     /// Create a new item list delegate with specified combined modules.
-    pub fn new(mut items: Vec<ListItem>, combined_modules: Vec<ConfigModule>) -> Self {
+    pub fn new(items: Vec<ListItem>, combined_modules: Vec<ConfigModule>) -> Self {
+        Self::with_items(items, combined_modules, DelegateOptions::default())
+    }
+
+    /// Create a delegate, choosing whether built-in items are injected.
+    ///
+    /// Without built-ins the delegate shows exactly `items`, e.g. for an
+    /// embedded list or a custom frontend.
+    pub fn with_items(
+        items: Vec<ListItem>,
+        combined_modules: Vec<ConfigModule>,
+        options: DelegateOptions,
+    ) -> Self {
+        if options.builtins {
+            Self::with_config(items, combined_modules, &config(), None)
+        } else {
+            Self::from_prepared(items, combined_modules, &config(), None)
+        }
+    }
+
+    /// Create a new item list delegate using the given config for scoring
+    /// and search providers instead of the global one, and the given
+    /// history instead of the shared stores.
+    fn with_config(
+        items: Vec<ListItem>,
+        combined_modules: Vec<ConfigModule>,
+        cfg: &AppConfig,
+        history: Option<DelegateHistory>,
+    ) -> Self {
+        let providers = ProviderRegistry::for_config(cfg).with_loaded(&items);
+        let items = Self::sort_items(providers.provide("", &combined_modules), &combined_modules);
+
+        let mut delegate = Self::from_prepared(items, combined_modules, cfg, history);
+        delegate.providers = Some(providers);
+        delegate
+    }
+
+    /// Add an item provider after the registered ones (builder pattern).
+    ///
+    /// Its items are merged with the others and fuzzy filtered like them.
+    /// A delegate showing its items as given keeps its applications and
+    /// windows.
+    pub fn with_provider(mut self, provider: impl ItemProvider + 'static) -> Self {
+        let items = self.base.items();
+        self.providers
+            .get_or_insert_with(|| ProviderRegistry::new().with_loaded(items))
+            .register(provider);
+        self.rebuild_items();
+        let query = self.base.query().to_string();
+        self.process_query(&query);
+        self
+    }
+
+    /// Create a delegate for the children of a nested submenu.
+    /// Unlike `new`, no built-in submenus or actions are added.
+    pub fn nested(items: Vec<ListItem>, combined_modules: Vec<ConfigModule>) -> Self {
+        Self::with_items(items, combined_modules, DelegateOptions::without_builtins())
+    }
+
+    /// Create a delegate for the Alt-Tab style window switcher.
+    ///
+    /// Only windows are kept (groups are split into their windows), the
+    /// focused one first followed by the compositor's order. The previously
+    /// focused window is selected while the query is empty.
+    pub fn window_switcher(items: Vec<ListItem>) -> Self {
+        let mut windows: Vec<WindowItem> = items
+            .into_iter()
+            .filter_map(|item| match item {
+                ListItem::Window(win) if win.is_group() => Some(win.group),
+                ListItem::Window(win) => Some(vec![win]),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        windows.sort_by_key(|win| !win.focused);
+
+        let mut delegate = Self::with_items(
+            windows.into_iter().map(ListItem::Window).collect(),
+            vec![ConfigModule::Windows],
+            DelegateOptions::without_builtins(),
+        );
+        delegate.switcher = true;
+        delegate.filter_items();
+        delegate
+    }
+
+    fn from_prepared(
+        items: Vec<ListItem>,
+        combined_modules: Vec<ConfigModule>,
+        cfg: &AppConfig,
+        history: Option<DelegateHistory>,
+    ) -> Self {
+        let snapshot = history.clone().unwrap_or_else(DelegateHistory::shared);
+        let mut sections = sections_for(&combined_modules, cfg);
+        let filter = filter_for(cfg, &snapshot);
+
+        // The empty query still applies the result caps
+        let filtered = filter.filter_with_scores(&items, "", &combined_modules);
+        sections.update_with_scores(&items, &filtered, false, false, 0);
+        let mut base = BaseDelegate::new(items);
+        base.apply_filtered_indices(filtered.iter().map(|f| f.index).collect());
+
+        Self {
+            base,
+            filter,
+            dynamic: dynamic_items_for(&combined_modules, cfg, &snapshot),
+            sections,
+            on_confirm: None,
+            combined_modules,
+            search_providers: providers_from_config(cfg),
+            ai_enabled: cfg.ai_enabled,
+            llm_configured: LLMClient::is_configured(),
+            section_headers: section_headers_for(cfg),
+            keep_selection: cfg.fuzzy_match.keep_selection,
+            switcher: false,
+            providers: None,
+            history,
+        }
+    }
+
+    /// Run the full matching pipeline without GPUI and return the items
+    /// in the order they would be shown.
+    ///
+    /// Uses `cfg` for the combined modules, fuzzy scoring and search
+    /// providers. AI items still depend on the configured LLM keys.
+    pub fn query_results(items: &[ListItem], query: &str, cfg: &AppConfig) -> Vec<ListItem> {
+        let mut delegate = Self::with_config(items.to_vec(), combined_modules_for(cfg), cfg, None);
+        delegate.set_settled_query(query);
+
+        (0..delegate.filtered_count())
+            .filter_map(|idx| delegate.get_item_at(idx))
+            .collect()
+    }
+
+    /// Set the query as if typing had paused, without waiting for the
+    /// network detector debounce.
+    fn set_settled_query(&mut self, query: &str) {
+        self.set_query(query.to_string());
+        if let Some(delay) = self.pending_detector_delay() {
+            self.dynamic.run_due_detectors(Instant::now() + delay);
+            self.filter_items();
+        }
+    }
+
+    /// Run the matching pipeline like [`Self::query_results`] and report
+    /// which detectors fired instead of the items, for debugging.
+    pub fn explain(items: &[ListItem], query: &str, cfg: &AppConfig) -> QueryExplanation {
+        let mut delegate = Self::with_config(items.to_vec(), combined_modules_for(cfg), cfg, None);
+        delegate.set_settled_query(query);
+
+        let (calculator_enabled, ai_enabled, search_enabled) = delegate.enabled_features();
+        let dynamic = &delegate.dynamic;
+        let trigger = dynamic
+            .resolve_trigger(query.trim(), calculator_enabled, ai_enabled, search_enabled)
+            .map(|matched| matched.target);
+
+        QueryExplanation {
+            trigger,
+            calculator: dynamic
+                .calculator_item
+                .as_ref()
+                .map(|calc| calc.display_result.clone()),
+            color: dynamic.has_color(),
+            ai: dynamic.has_ai(),
+            search_providers: dynamic
+                .search_items
+                .iter()
+                .map(|item| item.provider.name.clone())
+                .collect(),
+            fuzzy_matches: delegate.base.filtered_count(),
+        }
+    }
+
+    /// Filter given items by combined_modules and sort by module order.
+    fn prepare_items(mut items: Vec<ListItem>, combined_modules: &[ConfigModule]) -> Vec<ListItem> {
         // Filter items based on combined_modules
         items.retain(|item| match item {
             ListItem::Application(_) => combined_modules.contains(&ConfigModule::Applications),
@@ -53,36 +311,22 @@ impl ItemListDelegate {
             _ => true, // Keep other items for now
         });
 
-        // Add built-in submenu items (only if module is in combined_modules)
-        if combined_modules.contains(&ConfigModule::Emojis) {
-            items.push(ListItem::Submenu(
-                SubmenuItem::grid("submenu-emojis", "Emojis", 8)
-                    .with_description("Search and copy emojis")
-                    .with_icon("smiley"),
-            ));
-        }
-        if combined_modules.contains(&ConfigModule::Clipboard) {
-            items.push(ListItem::Submenu(
-                SubmenuItem::list("submenu-clipboard", "Clipboard History")
-                    .with_description("View and paste clipboard history")
-                    .with_icon("clipboard"),
-            ));
-        }
-        if combined_modules.contains(&ConfigModule::Themes) {
-            items.push(ListItem::Submenu(
-                SubmenuItem::list("submenu-themes", "Themes")
-                    .with_description("Browse and apply themes")
-                    .with_icon("palette"),
-            ));
-        }
+        Self::sort_items(items, combined_modules)
+    }
 
-        // Add built-in action items (shutdown, reboot, etc.)
-        if combined_modules.contains(&ConfigModule::Actions) {
-            for action in ActionItem::builtins() {
-                items.push(ListItem::Action(action));
-            }
-        }
+    /// Replace the items with the ones of the providers for the current
+    /// query. Does nothing when the items are shown as given.
+    fn rebuild_items(&mut self) {
+        let Some(providers) = &self.providers else {
+            return;
+        };
+        let items = providers.provide(self.base.query(), &self.combined_modules);
+        self.base
+            .set_items(Self::sort_items(items, &self.combined_modules));
+    }
 
+    /// Sort items by their module's position in combined_modules.
+    fn sort_items(mut items: Vec<ListItem>, combined_modules: &[ConfigModule]) -> Vec<ListItem> {
         // Sort items by their position in combined_modules
         tracing::debug!(?combined_modules, "Sorting items by combined_modules order");
         items.sort_by(|a, b| {
@@ -108,22 +352,7 @@ impl ItemListDelegate {
             tracing::debug!(i, name = item.name(), module = ?item.config_module(), "Sorted item");
         }
 
-        // Get fuzzy match config from application config
-        let fuzzy_config = config().fuzzy_match.clone();
-
-        let mut sections =
-            SectionManager::new(combined_modules.clone(), fuzzy_config.show_best_match);
-        let filtered_indices: Vec<usize> = (0..items.len()).collect();
-        sections.update(&items, &filtered_indices, false, false, 0);
-
-        Self {
-            base: BaseDelegate::new(items),
-            filter: ItemFilter::new(fuzzy_config),
-            dynamic: DynamicItems::new(),
-            sections,
-            on_confirm: None,
-            combined_modules,
-        }
+        items
     }
 
     /// Set the confirm callback.
@@ -148,6 +377,24 @@ impl ItemListDelegate {
         }
     }
 
+    /// Switch the selected search item to its next provider.
+    /// Returns false if the selection isn't a search item with variants.
+    pub fn cycle_search_provider(&mut self) -> bool {
+        match self
+            .selected_index()
+            .and_then(|index| self.get_item_at(index))
+        {
+            Some(ListItem::Search(search)) => self.dynamic.cycle_search_provider(&search.id),
+            _ => false,
+        }
+    }
+
+    /// Mark a section as waiting for background results, showing a
+    /// placeholder row until it is marked as loaded again.
+    pub fn set_section_loading(&mut self, section_type: SectionType, loading: bool) {
+        self.sections.set_loading(section_type, loading);
+    }
+
     /// Get the total count of filtered items (including dynamic items).
     pub fn filtered_count(&self) -> usize {
         self.base.filtered_count() + self.dynamic.count()
@@ -166,22 +413,122 @@ impl ItemListDelegate {
         self.filter_items();
     }
 
+    /// Replace the items (e.g. after a reload) and re-run the current query.
+    ///
+    /// Callbacks and the query are kept; the fuzzy config, search providers,
+    /// calculator trigger and display settings are re-read so reloaded
+    /// settings take effect.
+    pub fn set_items(&mut self, items: Vec<ListItem>) {
+        let cfg = config();
+        let history = self.history.clone().unwrap_or_else(DelegateHistory::shared);
+        self.filter = filter_for(&cfg, &history);
+        self.sections = sections_for(&self.combined_modules, &cfg);
+        self.search_providers = providers_from_config(&cfg);
+        self.ai_enabled = cfg.ai_enabled;
+        self.section_headers = section_headers_for(&cfg);
+        self.keep_selection = cfg.fuzzy_match.keep_selection;
+        self.dynamic = dynamic_items_for(&self.combined_modules, &cfg, &history);
+        match self.providers.take() {
+            Some(providers) => {
+                self.providers = Some(providers.with_loaded(&items));
+                self.rebuild_items();
+            }
+            None => self
+                .base
+                .set_items(Self::prepare_items(items, &self.combined_modules)),
+        }
+
+        let query = self.base.query().to_string();
+        self.process_query(&query);
+    }
+
+    /// Expand a grouped window entry into its individual windows and
+    /// re-run the current query. Returns false if no such group exists.
+    pub fn expand_window_group(&mut self, id: &str) -> bool {
+        let mut items = self.base.items().to_vec();
+        let Some(pos) = items.iter().position(|item| item.id() == id) else {
+            return false;
+        };
+        let ListItem::Window(group) = &mut items[pos] else {
+            return false;
+        };
+        if !group.is_group() {
+            return false;
+        }
+
+        let windows = std::mem::take(&mut group.group);
+        items.splice(pos..=pos, windows.into_iter().map(ListItem::Window));
+        self.base.set_items(items);
+
+        let query = self.base.query().to_string();
+        self.process_query(&query);
+        true
+    }
+
     /// Set the query and trigger filtering.
+    ///
+    /// With `keep_selection`, the previously selected item stays selected
+    /// at its new position if it still matches; otherwise the first result
+    /// is selected.
     pub fn set_query(&mut self, query: String) {
+        let previous = self
+            .keep_selection
+            .then(|| self.selected_item_id())
+            .flatten();
+
         self.base.set_query(query.clone());
         self.process_query(&query);
+
+        if let Some(id) = previous {
+            self.select_item_id(&id);
+        }
+    }
+
+    /// Id of the currently selected item.
+    fn selected_item_id(&self) -> Option<String> {
+        self.selected_index()
+            .and_then(|idx| self.get_item_at(idx))
+            .map(|item| item.id().to_string())
+    }
+
+    /// Select the item with the given id. Returns false if it isn't shown.
+    fn select_item_id(&mut self, id: &str) -> bool {
+        let position = (0..self.filtered_count())
+            .find(|&idx| self.get_item_at(idx).is_some_and(|item| item.id() == id));
+        match position {
+            Some(idx) => {
+                self.set_selected(idx);
+                true
+            }
+            None => false,
+        }
     }
 
     /// Process the query to detect special items.
+    ///
+    /// Dynamic items see the whole query so AI prompts and searches keep
+    /// long pastes; matching works on a truncated copy.
     fn process_query(&mut self, query: &str) {
-        let ai_enabled =
-            self.combined_modules.contains(&ConfigModule::Ai) && LLMClient::is_configured();
-        let calculator_enabled = self.combined_modules.contains(&ConfigModule::Calculator);
-        let search_enabled = self.combined_modules.contains(&ConfigModule::Search);
-
-        // Process dynamic items
+        let (calculator_enabled, ai_enabled, search_enabled) = self.enabled_features();
+        let search_providers: &[SearchProvider] = if search_enabled {
+            &self.search_providers
+        } else {
+            &[]
+        };
+
+        // Process dynamic items, without a `ws:` token
+        let (_, query) = parse_workspace_filter(query);
         self.dynamic
-            .process_query(query, calculator_enabled, ai_enabled, search_enabled);
+            .process_query(&query, calculator_enabled, ai_enabled, search_providers);
+
+        // Providers with query-dependent items provide them again
+        if self
+            .providers
+            .as_ref()
+            .is_some_and(|providers| providers.depends_on_query(&self.combined_modules))
+        {
+            self.rebuild_items();
+        }
 
         // Filter the base items
         self.filter_items();
@@ -192,21 +539,49 @@ impl ItemListDelegate {
         }
     }
 
+    /// How long until network detectors deferred by the debounce are due.
+    pub fn pending_detector_delay(&self) -> Option<Duration> {
+        self.dynamic.pending_delay(Instant::now())
+    }
+
+    /// Run network detectors deferred by the debounce if they are due and
+    /// re-filter. Returns whether anything changed.
+    pub fn run_pending_detectors(&mut self) -> bool {
+        if !self.dynamic.run_due_detectors(Instant::now()) {
+            return false;
+        }
+        self.filter_items();
+        true
+    }
+
+    /// Whether the calculator, AI and search are enabled.
+    fn enabled_features(&self) -> (bool, bool, bool) {
+        let ai_enabled = self.ai_enabled
+            && self.llm_configured
+            && self.combined_modules.contains(&ConfigModule::Ai);
+        let calculator_enabled = self.combined_modules.contains(&ConfigModule::Calculator);
+        let search_enabled = self.combined_modules.contains(&ConfigModule::Search);
+        (calculator_enabled, ai_enabled, search_enabled)
+    }
+
     /// Filter items based on the current query.
     fn filter_items(&mut self) {
-        let query = self.base.query();
+        let (workspace, query) = parse_workspace_filter(truncate_query(self.base.query()));
         let items = self.base.items();
 
         // Get filtered items with scores for best-match detection
-        let filtered = self
-            .filter
-            .filter_with_scores(items, query, &self.combined_modules);
+        let filtered =
+            self.filter
+                .filter_in_workspace(items, &query, workspace, &self.combined_modules);
 
         // Extract indices for base delegate
         let filtered_indices: Vec<usize> = filtered.iter().map(|f| f.index).collect();
         self.base.apply_filtered_indices(filtered_indices);
 
         // Update sections with scores
+        self.sections
+            .set_suggestion_count(self.dynamic.suggestion_count());
+        self.sections.set_file_count(self.dynamic.file_count());
         self.sections.update_with_scores(
             self.base.items(),
             &filtered,
@@ -219,12 +594,15 @@ impl ItemListDelegate {
         if self.base.selected_index().is_none() && self.filtered_count() > 0 {
             self.base.set_selected_unchecked(0);
         }
+
+        // The switcher starts on the previously focused window
+        if self.switcher && query.is_empty() && self.filtered_count() > 1 {
+            self.base.set_selected_unchecked(1);
+        }
     }
 
     /// Get an item at a global index (including dynamic items).
     pub fn get_item_at(&self, global_index: usize) -> Option<ListItem> {
-        // Track offset within regular items (excluding best match)
-        let mut regular_item_offset = 0;
         let mut current_start = 0;
 
         // Get the best match position if applicable
@@ -243,15 +621,24 @@ impl ItemListDelegate {
                         let best_pos = best_match_pos?;
                         self.base.get_filtered_item(best_pos).cloned()
                     }
-                    SectionType::Calculator => self
-                        .dynamic
-                        .calculator_item
-                        .clone()
-                        .map(ListItem::Calculator),
-                    SectionType::Windows | SectionType::Commands | SectionType::Applications => {
+                    SectionType::Calculator => match &self.dynamic.color_item {
+                        _ if self.dynamic.has_path() => {
+                            self.dynamic.path_item.clone().map(ListItem::Path)
+                        }
+                        Some(color) => Some(ListItem::Color(color.clone())),
+                        None => self
+                            .dynamic
+                            .calculator_item
+                            .clone()
+                            .map(ListItem::Calculator),
+                    },
+                    SectionType::Windows
+                    | SectionType::Commands
+                    | SectionType::Applications
+                    | SectionType::Results => {
                         // Calculate the actual index, skipping the best match if it was in this section
                         let base_idx = self.get_adjusted_base_index(
-                            regular_item_offset,
+                            self.sections.filtered_offset(section_type),
                             row,
                             section_type,
                             best_match_pos,
@@ -271,20 +658,21 @@ impl ItemListDelegate {
                                 .map(ListItem::Search)
                         }
                     }
+                    SectionType::Files => self
+                        .dynamic
+                        .file_items
+                        .get(row)
+                        .cloned()
+                        .map(ListItem::Path),
+                    SectionType::Suggestions => self
+                        .dynamic
+                        .suggestions
+                        .get(row)
+                        .cloned()
+                        .map(ListItem::Suggestion),
                 };
             }
 
-            // Track offset for regular items (excluding BestMatch and Calculator)
-            if matches!(
-                section_type,
-                SectionType::Windows | SectionType::Commands | SectionType::Applications
-            ) {
-                regular_item_offset += section_count;
-                // Add 1 if best match was from this section (since we subtracted it from count)
-                if self.sections.best_match_original_section() == Some(section_type) {
-                    regular_item_offset += 1;
-                }
-            }
             current_start = section_end;
         }
 
@@ -376,7 +764,7 @@ impl ListDelegate for ItemListDelegate {
 
     fn items_count(&self, section: usize, _cx: &App) -> usize {
         let section_type = self.sections.section_type_at(section);
-        self.sections.section_item_count(section_type)
+        self.sections.section_row_count(section_type)
     }
 
     fn render_section_header(
@@ -385,25 +773,19 @@ impl ListDelegate for ItemListDelegate {
         _window: &mut Window,
         _cx: &mut Context<'_, ListState<Self>>,
     ) -> Option<impl IntoElement> {
-        let section_type = self.sections.section_type_at(section);
-        let sections = self.sections.ordered_section_types();
-        let section_count = sections.len();
-
-        // Don't show headers if there's only one section
-        if section_count <= 1 {
+        if !self.sections.shows_header(section, self.section_headers) {
             return None;
         }
-
-        // SearchAndAi section: show header only when there are other sections
-        if section_type == SectionType::SearchAndAi {
-            let has_other_sections = sections.iter().any(|s| *s != SectionType::SearchAndAi);
-            if !has_other_sections {
-                return None;
-            }
-        }
+        let section_type = self.sections.section_type_at(section);
 
         let theme = theme();
-        let title = section_type.title();
+        let title = if section_type == SectionType::Calculator && self.dynamic.has_path() {
+            "Path"
+        } else if section_type == SectionType::Calculator && self.dynamic.has_color() {
+            "Color"
+        } else {
+            section_type.title()
+        };
 
         Some(
             div()
@@ -424,11 +806,35 @@ impl ListDelegate for ItemListDelegate {
         _window: &mut Window,
         _cx: &mut Context<'_, ListState<Self>>,
     ) -> Option<Self::Item> {
+        if self.sections.is_placeholder_row(ix.section, ix.row) {
+            let label = match self.sections.section_type_at(ix.section) {
+                SectionType::SearchAndAi => "Searching…",
+                _ => "Loading…",
+            };
+            return Some(
+                GpuiListItem::new(("loading-placeholder", ix.section))
+                    .py_0()
+                    .px_0()
+                    .child(render_loading_row(label)),
+            );
+        }
+
         let global_idx = self.sections.section_row_to_global(ix.section, ix.row);
         let selected = self.base.selected_index() == Some(global_idx);
 
         let item = self.get_item_at(global_idx)?;
         let item_content = render_item(&item, selected, global_idx);
+        let item_content = if selected {
+            let t = theme();
+            motion::highlight_selection(
+                item_content,
+                ("selection", global_idx),
+                t.item_background,
+                t.item_background_selected,
+            )
+        } else {
+            item_content.into_any_element()
+        };
 
         Some(
             GpuiListItem::new(("list-item", global_idx))
@@ -444,6 +850,10 @@ impl ListDelegate for ItemListDelegate {
         _window: &mut Window,
         _cx: &mut Context<ListState<Self>>,
     ) {
+        // Placeholders stand in for results that haven't arrived yet
+        if ix.is_some_and(|i| self.sections.is_placeholder_row(i.section, i.row)) {
+            return;
+        }
         let global_idx = ix
             .map(|i| self.sections.section_row_to_global(i.section, i.row))
             .unwrap_or(0);
@@ -494,3 +904,669 @@ impl ListDelegate for ItemListDelegate {
             )
     }
 }
+
+/// Split a `ws:N` token off a query.
+///
+/// Returns the workspace to restrict windows to and the rest of the query.
+fn parse_workspace_filter(query: &str) -> (Option<i32>, String) {
+    let mut workspace = None;
+    let rest: Vec<&str> = query
+        .split_whitespace()
+        .filter(|token| {
+            if workspace.is_none()
+                && let Some(ws) = token.strip_prefix("ws:").and_then(|n| n.parse().ok())
+            {
+                workspace = Some(ws);
+                return false;
+            }
+            true
+        })
+        .collect();
+    match workspace {
+        Some(_) => (workspace, rest.join(" ")),
+        None => (None, query.to_string()),
+    }
+}
+
+/// Item filter for the configured fuzzy matching and grouping, ranking by
+/// score alone for the flat grouping.
+fn filter_for(cfg: &AppConfig, history: &DelegateHistory) -> ItemFilter {
+    ItemFilter::new(cfg.fuzzy_match.clone())
+        .with_history(history.launches.clone())
+        .with_flat(cfg.grouping == ResultGrouping::Flat)
+}
+
+/// Section layout for the configured grouping.
+fn sections_for(combined_modules: &[ConfigModule], cfg: &AppConfig) -> SectionManager {
+    let flat = cfg.grouping == ResultGrouping::Flat;
+    SectionManager::new(combined_modules.to_vec(), cfg.fuzzy_match.show_best_match)
+        .with_flat(flat)
+        .with_dynamic_first(flat)
+}
+
+/// Dynamic item detection as configured.
+fn dynamic_items_for(
+    combined_modules: &[ConfigModule],
+    cfg: &AppConfig,
+    history: &DelegateHistory,
+) -> DynamicItems {
+    DynamicItems::with_calculator_trigger(cfg.calculator_trigger)
+        .with_triggers(Arc::new(TriggerRegistry::for_config(cfg)))
+        .with_answers(recent_answers())
+        .with_path_detection(combined_modules.contains(&ConfigModule::Applications))
+        .with_file_index(
+            file_index(),
+            Duration::from_secs(cfg.provider_cache_ttl_secs),
+        )
+        .with_empty_search_trigger(cfg.empty_search_trigger)
+        .with_query_history(if suggests_queries(combined_modules, cfg) {
+            history.queries.clone()
+        } else {
+            Arc::default()
+        })
+        .with_min_query_length(cfg.detector_min_query_length)
+        .with_network_debounce(Duration::from_millis(cfg.network_detector_debounce_ms))
+}
+
+/// Whether past queries are suggested: only with the search module, and
+/// unless query history is turned off.
+fn suggests_queries(combined_modules: &[ConfigModule], cfg: &AppConfig) -> bool {
+    cfg.query_history && combined_modules.contains(&ConfigModule::Search)
+}
+
+/// Section headers, which the flat grouping never shows.
+fn section_headers_for(cfg: &AppConfig) -> SectionHeaders {
+    match cfg.grouping {
+        ResultGrouping::Sections => cfg.section_headers,
+        ResultGrouping::Flat => SectionHeaders::Never,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::group_windows;
+    use crate::test_utils::{mock_application, mock_window};
+
+    /// Provider of applications, optionally named after the query.
+    struct AppProvider {
+        names: Vec<&'static str>,
+        echo: bool,
+    }
+
+    impl ItemProvider for AppProvider {
+        fn id(&self) -> &str {
+            if self.echo { "echo" } else { "apps" }
+        }
+
+        fn provide(&self, query: &str) -> Vec<ListItem> {
+            let echo = (self.echo && !query.is_empty()).then(|| format!("{query} echo"));
+            self.names
+                .iter()
+                .copied()
+                .chain(echo.as_deref())
+                .map(|name| ListItem::Application(mock_application(name)))
+                .collect()
+        }
+
+        fn depends_on_query(&self) -> bool {
+            self.echo
+        }
+    }
+
+    #[test]
+    fn test_providers_merge_into_results() {
+        let mut delegate = ItemListDelegate::new(
+            vec![ListItem::Application(mock_application("Firefox"))],
+            vec![ConfigModule::Applications],
+        )
+        .with_provider(AppProvider {
+            names: vec!["Firefly", "Kitty"],
+            echo: false,
+        })
+        .with_provider(AppProvider {
+            names: Vec::new(),
+            echo: true,
+        });
+        assert_eq!(delegate.filtered_count(), 3);
+
+        delegate.set_query("fire".to_string());
+        let mut names: Vec<String> = (0..delegate.filtered_count())
+            .filter_map(|idx| delegate.get_item_at(idx))
+            .map(|item| item.name().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Firefly", "Firefox", "fire echo"]);
+
+        // Items of the previous query are replaced
+        delegate.set_query("kit".to_string());
+        assert_eq!(delegate.filtered_count(), 2);
+        delegate.set_query(String::new());
+        assert_eq!(delegate.filtered_count(), 3);
+    }
+
+    #[test]
+    fn test_set_items_reruns_query() {
+        let mut delegate = ItemListDelegate::new(
+            vec![ListItem::Application(mock_application("Firefox"))],
+            vec![ConfigModule::Applications],
+        );
+        delegate.set_query("chrom".to_string());
+        assert_eq!(delegate.filtered_count(), 0);
+
+        delegate.set_items(vec![
+            ListItem::Application(mock_application("Firefox")),
+            ListItem::Application(mock_application("Chromium")),
+        ]);
+
+        assert_eq!(delegate.query(), "chrom");
+        assert_eq!(delegate.filtered_count(), 1);
+        assert_eq!(delegate.selected_index(), Some(0));
+        assert_eq!(
+            delegate.get_item_at(0).map(|item| item.name().to_string()),
+            Some("Chromium".to_string())
+        );
+    }
+
+    #[test]
+    fn test_window_switcher_only_windows_by_recency() {
+        let mut focused = mock_window("Editor", "code");
+        focused.focused = true;
+        let items = vec![
+            ListItem::Application(mock_application("Firefox")),
+            ListItem::Window(mock_window("Browser", "firefox")),
+            ListItem::Window(focused),
+            ListItem::Window(mock_window("Terminal", "kitty")),
+        ];
+
+        let delegate = ItemListDelegate::window_switcher(items);
+        let windows: Vec<ListItem> = (0..delegate.filtered_count())
+            .filter_map(|idx| delegate.get_item_at(idx))
+            .collect();
+
+        assert!(
+            windows
+                .iter()
+                .all(|item| matches!(item, ListItem::Window(_)))
+        );
+        let titles: Vec<&str> = windows.iter().map(|item| item.name()).collect();
+        assert_eq!(titles, vec!["Editor", "Browser", "Terminal"]);
+        // The previously focused window is preselected
+        assert_eq!(delegate.selected_index(), Some(1));
+    }
+
+    #[test]
+    fn test_window_switcher_splits_groups() {
+        let items = group_windows(vec![
+            mock_window("One", "kitty"),
+            mock_window("Two", "kitty"),
+        ])
+        .into_iter()
+        .map(ListItem::Window)
+        .collect();
+
+        let delegate = ItemListDelegate::window_switcher(items);
+        assert_eq!(delegate.filtered_count(), 2);
+        assert_eq!(
+            delegate.get_item_at(1).map(|item| item.name().to_string()),
+            Some("Two".to_string())
+        );
+    }
+
+    #[test]
+    fn test_keep_selection_follows_item() {
+        let names = |delegate: &ItemListDelegate| -> Vec<String> {
+            (0..delegate.filtered_count())
+                .filter_map(|idx| delegate.get_item_at(idx))
+                .map(|item| item.name().to_string())
+                .collect()
+        };
+        let mut delegate = ItemListDelegate::with_items(
+            vec![
+                ListItem::Application(mock_application("Alpha Fox")),
+                ListItem::Application(mock_application("Fox")),
+            ],
+            vec![ConfigModule::Applications],
+            DelegateOptions::without_builtins(),
+        );
+        delegate.keep_selection = true;
+
+        let alpha = names(&delegate)
+            .iter()
+            .position(|name| name == "Alpha Fox")
+            .unwrap();
+        delegate.set_selected(alpha);
+
+        // "Fox" is now the exact match, so "Alpha Fox" moves down
+        delegate.set_query("fox".to_string());
+        assert_eq!(names(&delegate), vec!["Fox", "Alpha Fox"]);
+        assert_eq!(delegate.selected_index(), Some(1));
+
+        // Falls back to the first result once the item no longer matches
+        delegate.set_selected(0);
+        delegate.set_query("alpha".to_string());
+        assert_eq!(names(&delegate), vec!["Alpha Fox"]);
+        assert_eq!(delegate.selected_index(), Some(0));
+
+        // Without the option the selection resets on every keystroke
+        delegate.keep_selection = false;
+        delegate.set_query("".to_string());
+        delegate.set_selected(alpha);
+        delegate.set_query("fox".to_string());
+        assert_eq!(delegate.selected_index(), Some(0));
+    }
+
+    #[test]
+    fn test_with_items_without_builtins() {
+        let modules = vec![
+            ConfigModule::Applications,
+            ConfigModule::Emojis,
+            ConfigModule::Clipboard,
+            ConfigModule::Actions,
+        ];
+        let items = vec![
+            ListItem::Application(mock_application("Firefox")),
+            ListItem::Window(mock_window("Terminal", "kitty")),
+        ];
+
+        let delegate = ItemListDelegate::with_items(
+            items.clone(),
+            modules.clone(),
+            DelegateOptions::without_builtins(),
+        );
+        let ids: Vec<String> = delegate
+            .base
+            .items()
+            .iter()
+            .map(|item| item.id().to_string())
+            .collect();
+        let expected: Vec<String> = items.iter().map(|item| item.id().to_string()).collect();
+        assert_eq!(ids, expected);
+
+        // The default still injects the built-ins
+        let delegate = ItemListDelegate::new(items, modules);
+        assert!(
+            delegate
+                .base
+                .items()
+                .iter()
+                .any(|item| item.id() == "submenu-emojis")
+        );
+        assert!(
+            delegate
+                .base
+                .items()
+                .iter()
+                .any(|item| item.id() == "action-shutdown")
+        );
+    }
+
+    #[test]
+    fn test_no_windows_omits_windows_section() {
+        let mut delegate = ItemListDelegate::new(
+            vec![ListItem::Application(mock_application("Firefox"))],
+            vec![ConfigModule::Windows, ConfigModule::Applications],
+        );
+        assert!(
+            !delegate
+                .sections
+                .ordered_section_types()
+                .contains(&SectionType::Windows)
+        );
+
+        delegate.set_query("fire".to_string());
+        assert_eq!(delegate.filtered_count(), 1);
+        assert!(
+            !delegate
+                .sections
+                .ordered_section_types()
+                .contains(&SectionType::Windows)
+        );
+    }
+
+    #[test]
+    fn test_parse_workspace_filter() {
+        assert_eq!(
+            parse_workspace_filter("ws:2 firefox"),
+            (Some(2), "firefox".to_string())
+        );
+        assert_eq!(
+            parse_workspace_filter("fire ws:10 fox"),
+            (Some(10), "fire fox".to_string())
+        );
+        assert_eq!(parse_workspace_filter("ws:3"), (Some(3), String::new()));
+        assert_eq!(
+            parse_workspace_filter("ws:x firefox"),
+            (None, "ws:x firefox".to_string())
+        );
+        assert_eq!(
+            parse_workspace_filter("firefox "),
+            (None, "firefox ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_workspace_token_filters_windows() {
+        let mut work = mock_window("Firefox", "firefox");
+        work.workspace = 2;
+        let mut delegate = ItemListDelegate::new(
+            vec![
+                ListItem::Window(mock_window("Firefox", "firefox")),
+                ListItem::Window(work),
+                ListItem::Application(mock_application("Firefox")),
+            ],
+            vec![ConfigModule::Windows, ConfigModule::Applications],
+        );
+
+        delegate.set_query("ws:2 fire".to_string());
+        assert_eq!(delegate.filtered_count(), 2);
+
+        delegate.set_query("ws:2".to_string());
+        assert_eq!(delegate.filtered_count(), 1);
+        assert!(matches!(
+            delegate.get_item_at(0),
+            Some(ListItem::Window(w)) if w.workspace == 2
+        ));
+    }
+
+    #[test]
+    fn test_query_results_calculator_at_top() {
+        let items = vec![ListItem::Application(mock_application("Firefox"))];
+        let results = ItemListDelegate::query_results(&items, "2+2", &AppConfig::default());
+        assert!(matches!(results.first(), Some(ListItem::Calculator(_))));
+    }
+
+    #[test]
+    fn test_query_results_search_fallback() {
+        let items = vec![ListItem::Application(mock_application("Firefox"))];
+        let results = ItemListDelegate::query_results(&items, "zzqx", &AppConfig::default());
+
+        let search_count = results
+            .iter()
+            .filter(|item| matches!(item, ListItem::Search(_)))
+            .count();
+        assert_eq!(search_count, 4);
+        assert!(!results.iter().any(|item| item.is_application()));
+    }
+
+    #[test]
+    fn test_query_results_app_ordering() {
+        let cfg = AppConfig {
+            combined_modules: Some(vec![ConfigModule::Applications]),
+            ..AppConfig::default()
+        };
+        let items = vec![
+            ListItem::Application(mock_application("Firefox Developer Edition")),
+            ListItem::Application(mock_application("Chrome")),
+            ListItem::Application(mock_application("Firefox")),
+        ];
+
+        let names: Vec<String> = ItemListDelegate::query_results(&items, "firefox", &cfg)
+            .iter()
+            .map(|item| item.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["Firefox", "Firefox Developer Edition"]);
+    }
+
+    #[test]
+    fn test_query_suggestions_need_search_and_history() {
+        let mut cfg = AppConfig::default();
+        let with_search = [ConfigModule::Applications, ConfigModule::Search];
+        assert!(suggests_queries(&with_search, &cfg));
+        assert!(!suggests_queries(&[ConfigModule::Applications], &cfg));
+
+        cfg.query_history = false;
+        assert!(!suggests_queries(&with_search, &cfg));
+    }
+
+    #[test]
+    fn test_injected_history_is_kept_across_reloads() {
+        let mut queries = QueryHistory::default();
+        queries.record("rust book");
+        let history = DelegateHistory {
+            queries: Arc::new(queries),
+            ..DelegateHistory::default()
+        };
+        let items = vec![ListItem::Application(mock_application("Firefox"))];
+        let modules = vec![ConfigModule::Applications, ConfigModule::Search];
+        let mut delegate = ItemListDelegate::with_config(
+            items.clone(),
+            modules,
+            &AppConfig::default(),
+            Some(history),
+        );
+
+        delegate.set_query("rust".to_string());
+        assert_eq!(delegate.dynamic.suggestion_count(), 1);
+
+        delegate.set_items(items);
+        assert_eq!(delegate.dynamic.suggestion_count(), 1);
+    }
+
+    #[test]
+    fn test_explain() {
+        let items = vec![ListItem::Application(mock_application("Firefox"))];
+        let cfg = AppConfig::default();
+
+        let calc = ItemListDelegate::explain(&items, "2+2", &cfg);
+        assert_eq!(calc.calculator.as_deref(), Some("4"));
+        assert_eq!(calc.trigger, None);
+
+        let search = ItemListDelegate::explain(&items, "!g rust", &cfg);
+        assert!(matches!(
+            &search.trigger,
+            Some(TriggerTarget::Search(provider)) if provider.name == "Google"
+        ));
+        assert_eq!(search.search_providers, vec!["Google"]);
+        assert_eq!(search.calculator, None);
+
+        let fuzzy = ItemListDelegate::explain(&items, "firefox", &cfg);
+        assert_eq!(fuzzy.fuzzy_matches, 1);
+        assert_eq!(fuzzy.trigger, None);
+        assert_eq!(fuzzy.calculator, None);
+    }
+
+    #[test]
+    fn test_ai_disabled_in_config() {
+        let items = vec![ListItem::Application(mock_application("Firefox"))];
+        let cfg = AppConfig {
+            ai_enabled: false,
+            ..AppConfig::default()
+        };
+        let mut delegate = ItemListDelegate::with_config(
+            items.clone(),
+            combined_modules_for(&cfg),
+            &cfg,
+            Some(DelegateHistory::default()),
+        );
+        delegate.llm_configured = true;
+
+        delegate.set_query("!ai foo".to_string());
+        assert!(!delegate.dynamic.has_ai());
+        delegate.set_query("zzzz".to_string());
+        assert!(!delegate.dynamic.has_ai());
+
+        // The same query offers AI when enabled
+        let cfg = AppConfig::default();
+        let mut delegate = ItemListDelegate::with_config(
+            items,
+            combined_modules_for(&cfg),
+            &cfg,
+            Some(DelegateHistory::default()),
+        );
+        delegate.llm_configured = true;
+        delegate.set_query("!ai foo".to_string());
+        assert!(delegate.dynamic.has_ai());
+    }
+
+    #[test]
+    fn test_network_detector_debounce() {
+        let items = vec![ListItem::Application(mock_application("Firefox"))];
+        let cfg = AppConfig {
+            detector_min_query_length: 3,
+            network_detector_debounce_ms: 60_000,
+            ..AppConfig::default()
+        };
+        let mut delegate = ItemListDelegate::with_config(
+            items.clone(),
+            combined_modules_for(&cfg),
+            &cfg,
+            Some(DelegateHistory::default()),
+        );
+
+        delegate.set_query("fi".to_string());
+        assert_eq!(delegate.dynamic.search_count(), 0);
+        assert_eq!(delegate.pending_detector_delay(), None);
+
+        // Typing waits for the debounce; results still update right away
+        delegate.set_query("fire".to_string());
+        assert_eq!(delegate.dynamic.search_count(), 0);
+        assert!(delegate.pending_detector_delay().is_some());
+        assert!(!delegate.run_pending_detectors());
+        assert_eq!(delegate.base.filtered_count(), 1);
+
+        // The headless pipeline reports the settled results
+        let explained = ItemListDelegate::explain(&items, "fire", &cfg);
+        assert_eq!(explained.search_providers.len(), 4);
+    }
+
+    #[test]
+    fn test_expand_window_group() {
+        let windows = group_windows(vec![
+            mock_window("GitHub", "firefox"),
+            mock_window("Docs", "firefox"),
+        ]);
+        let items = windows.into_iter().map(ListItem::Window).collect();
+        let mut delegate = ItemListDelegate::new(items, vec![ConfigModule::Windows]);
+        assert_eq!(delegate.filtered_count(), 1);
+
+        assert!(delegate.expand_window_group("window-group-firefox"));
+        assert_eq!(delegate.filtered_count(), 2);
+        assert!(!delegate.expand_window_group("window-group-firefox"));
+    }
+
+    #[test]
+    fn test_very_long_query_is_fast_and_not_calculated() {
+        let items = vec![
+            ListItem::Application(mock_application("Firefox")),
+            ListItem::Application(mock_application("Chromium")),
+        ];
+        let query = "1+(2*3)-".repeat(1250);
+        assert_eq!(query.len(), 10_000);
+
+        let start = std::time::Instant::now();
+        let results = ItemListDelegate::query_results(&items, &query, &AppConfig::default());
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+        assert!(
+            !results
+                .iter()
+                .any(|item| matches!(item, ListItem::Calculator(_)))
+        );
+    }
+
+    #[test]
+    fn test_result_caps_keep_sections_consistent() {
+        let mut cfg = AppConfig {
+            combined_modules: Some(vec![ConfigModule::Windows, ConfigModule::Applications]),
+            ..AppConfig::default()
+        };
+        cfg.fuzzy_match.max_results_per_type = 3;
+        cfg.fuzzy_match.max_results = 5;
+
+        let mut items: Vec<ListItem> = (0..10)
+            .map(|i| ListItem::Application(mock_application(&format!("Editor {i}"))))
+            .collect();
+        items.extend((0..10).map(|i| ListItem::Window(mock_window(&format!("Editor {i}"), "ed"))));
+
+        let mut delegate = ItemListDelegate::with_config(
+            items,
+            combined_modules_for(&cfg),
+            &cfg,
+            Some(DelegateHistory::default()),
+        );
+        assert_eq!(delegate.filtered_count(), 5);
+
+        delegate.set_query("editor".to_string());
+        assert_eq!(delegate.filtered_count(), 5);
+        let results: Vec<ListItem> = (0..delegate.filtered_count())
+            .map(|idx| delegate.get_item_at(idx).unwrap())
+            .collect();
+        let windows = results
+            .iter()
+            .filter(|item| matches!(item, ListItem::Window(_)));
+        assert_eq!(windows.count(), 3);
+        assert_eq!(
+            results.iter().filter(|item| item.is_application()).count(),
+            2
+        );
+        assert!(delegate.get_item_at(delegate.filtered_count()).is_none());
+        for idx in 0..delegate.filtered_count() {
+            assert!(delegate.global_to_index_path(idx).is_some());
+        }
+    }
+
+    #[test]
+    fn test_flat_grouping_ranks_by_score() {
+        let items = vec![
+            ListItem::Application(mock_application("Firefox Developer Edition")),
+            ListItem::Window(mock_window("Firefox", "firefox")),
+        ];
+        let mut cfg = AppConfig {
+            combined_modules: Some(vec![
+                ConfigModule::Applications,
+                ConfigModule::Windows,
+                ConfigModule::Search,
+            ]),
+            ..AppConfig::default()
+        };
+        cfg.fuzzy_match.show_best_match = false;
+        let names = |cfg: &AppConfig| -> Vec<(bool, String)> {
+            ItemListDelegate::query_results(&items, "firefox", cfg)
+                .iter()
+                .map(|item| (matches!(item, ListItem::Search(_)), item.name().to_string()))
+                .collect()
+        };
+
+        // Sections follow combined_modules, search last
+        let sectioned = names(&cfg);
+        assert_eq!(
+            sectioned[0],
+            (false, "Firefox Developer Edition".to_string())
+        );
+        assert_eq!(sectioned[1], (false, "Firefox".to_string()));
+        assert!(sectioned[2..].iter().all(|(search, _)| *search));
+
+        // Flat puts search first, then the exact window match above the app
+        cfg.grouping = ResultGrouping::Flat;
+        let flat = names(&cfg);
+        assert_eq!(flat.len(), sectioned.len());
+        let searches = flat.iter().take_while(|(search, _)| *search).count();
+        assert_eq!(searches, sectioned.len() - 2);
+        assert_eq!(flat[searches].1, "Firefox");
+        assert_eq!(flat[searches + 1].1, "Firefox Developer Edition");
+        assert_eq!(section_headers_for(&cfg), SectionHeaders::Never);
+    }
+
+    #[test]
+    fn test_result_caps_exempt_dynamic_items() {
+        let mut cfg = AppConfig::default();
+        cfg.fuzzy_match.max_results = 1;
+        let items = vec![
+            ListItem::Application(mock_application("Firefox")),
+            ListItem::Application(mock_application("Firefox Nightly")),
+        ];
+
+        let results = ItemListDelegate::query_results(&items, "firefox", &cfg);
+        assert_eq!(
+            results.iter().filter(|item| item.is_application()).count(),
+            1
+        );
+        assert_eq!(
+            results
+                .iter()
+                .filter(|item| matches!(item, ListItem::Search(_)))
+                .count(),
+            4
+        );
+    }
+}
@@ -6,15 +6,22 @@
 //! - Prefix matches (name starts with query)
 //! - Word prefix matches (query matches start of any word)
 //! - Contiguous character matches
+//! - User-configured aliases (query equals an alias key for the application)
+//!
+//! Matches are then ranked by a weighted mix of the fuzzy score and the
+//! frecency of the item (see [`crate::frecency`]).
 //!
 //! And penalizes:
 //! - Description-only matches (name doesn't match, only description does)
 //! - Action/submenu items in combined mode (demotes system actions)
 
-use crate::config::{ConfigModule, FuzzyMatchConfig};
+use crate::config::{CaseMatching, ConfigModule, FuzzyMatchConfig};
+use crate::frecency::{FrecencyDecay, LaunchHistory};
 use crate::items::ListItem;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A filtered item with its index and score.
 #[derive(Debug, Clone, Copy)]
@@ -31,6 +38,10 @@ pub struct ItemFilter {
     matcher: SkimMatcherV2,
     /// Configuration for scoring adjustments.
     pub config: FuzzyMatchConfig,
+    /// Launch history used for frecency ranking.
+    history: Arc<LaunchHistory>,
+    /// Rank all modules together instead of grouping by module first.
+    flat: bool,
 }
 
 impl Default for ItemFilter {
@@ -42,12 +53,31 @@ impl Default for ItemFilter {
 impl ItemFilter {
     /// Create a new item filter with the given configuration.
     pub fn new(config: FuzzyMatchConfig) -> Self {
+        let matcher = match config.case_matching {
+            CaseMatching::Smart => SkimMatcherV2::default().smart_case(),
+            CaseMatching::Ignore => SkimMatcherV2::default().ignore_case(),
+            CaseMatching::Respect => SkimMatcherV2::default().respect_case(),
+        };
         Self {
-            matcher: SkimMatcherV2::default(),
+            matcher,
             config,
+            history: Arc::default(),
+            flat: false,
         }
     }
 
+    /// Use the given launch history for frecency ranking (builder pattern).
+    pub fn with_history(mut self, history: Arc<LaunchHistory>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Rank results of all modules together by score (builder pattern).
+    pub fn with_flat(mut self, flat: bool) -> Self {
+        self.flat = flat;
+        self
+    }
+
     /// Filter items by query, returning indices of matching items.
     ///
     /// This is a convenience method that wraps `filter_with_scores`
@@ -71,49 +101,109 @@ impl ItemFilter {
     /// the score of each item to determine which should be promoted.
     ///
     /// When query is empty, returns all items with score 0.
-    /// When query is non-empty, returns matching items sorted by:
-    /// 1. Module position in combined_modules (primary)
-    /// 2. Enhanced fuzzy score (secondary, higher is better)
+    /// When query is non-empty, returns matching items sorted by their
+    /// ranking score, which combines the enhanced fuzzy score and frecency
+    /// using the configured weights. Unless flat, items are grouped by
+    /// module position in combined_modules first.
     pub fn filter_with_scores(
         &self,
         items: &[ListItem],
         query: &str,
         combined_modules: &[ConfigModule],
     ) -> Vec<FilteredItem> {
+        self.filter_in_workspace(items, query, None, combined_modules)
+    }
+
+    /// Like `filter_with_scores`, restricting windows to `workspace`.
+    ///
+    /// Other items are only dropped when the query is empty, so `ws:2`
+    /// alone lists just the windows of workspace 2.
+    pub fn filter_in_workspace(
+        &self,
+        items: &[ListItem],
+        query: &str,
+        workspace: Option<i32>,
+        combined_modules: &[ConfigModule],
+    ) -> Vec<FilteredItem> {
+        let in_workspace = |item: &ListItem| match (workspace, item) {
+            (None, _) => true,
+            (Some(ws), ListItem::Window(win)) => {
+                win.workspace == ws || win.group.iter().any(|w| w.workspace == ws)
+            }
+            (Some(_), _) => !query.is_empty(),
+        };
+
         if query.is_empty() {
-            return (0..items.len())
+            let all = (0..items.len())
+                .filter(|&index| in_workspace(&items[index]))
                 .map(|index| FilteredItem { index, score: 0 })
                 .collect();
+            return self.apply_limits(items, all);
         }
 
+        let now = crate::frecency::now();
+        let decay = FrecencyDecay {
+            half_life_days: self.config.frecency_half_life_days,
+            count_weight: self.config.frecency_count_weight,
+        };
+        let module_pos = |item: &ListItem| {
+            let module = item.config_module();
+            combined_modules
+                .iter()
+                .position(|m| m == &module)
+                .unwrap_or(combined_modules.len())
+        };
+
         let mut scored: Vec<FilteredItem> = items
             .iter()
             .enumerate()
+            .filter(|(_, item)| in_workspace(item))
             .filter_map(|(idx, item)| {
                 let score = self.score_item(item, query)?;
-                Some(FilteredItem { index: idx, score })
+                let ranked = score as f64 * self.config.score_weight
+                    + self.history.score_at(item.id(), now, &decay) * self.config.frecency_weight;
+                Some(FilteredItem {
+                    index: idx,
+                    score: ranked as i64,
+                })
             })
             .collect();
 
-        // Sort by module position, then by score within same module
+        // Primary: module position (unless flat), Secondary: ranking score
+        // (higher is better). The sort is stable, so ties keep item order.
         scored.sort_by(|a, b| {
-            let module_a = items[a.index].config_module();
-            let module_b = items[b.index].config_module();
+            let group = if self.flat {
+                std::cmp::Ordering::Equal
+            } else {
+                module_pos(&items[a.index]).cmp(&module_pos(&items[b.index]))
+            };
+            group.then_with(|| b.score.cmp(&a.score))
+        });
 
-            let pos_a = combined_modules
-                .iter()
-                .position(|m| m == &module_a)
-                .unwrap_or(usize::MAX);
-            let pos_b = combined_modules
-                .iter()
-                .position(|m| m == &module_b)
-                .unwrap_or(usize::MAX);
+        self.apply_limits(items, scored)
+    }
 
-            // Primary: module position, Secondary: fuzzy score (higher is better)
-            pos_a.cmp(&pos_b).then_with(|| b.score.cmp(&a.score))
-        });
+    /// Drop results beyond the per-type and total caps, keeping rank order.
+    fn apply_limits(&self, items: &[ListItem], filtered: Vec<FilteredItem>) -> Vec<FilteredItem> {
+        let per_type = match self.config.max_results_per_type {
+            0 => usize::MAX,
+            n => n,
+        };
+        let total = match self.config.max_results {
+            0 => usize::MAX,
+            n => n,
+        };
 
-        scored
+        let mut counts: HashMap<ConfigModule, usize> = HashMap::new();
+        filtered
+            .into_iter()
+            .filter(|f| {
+                let count = counts.entry(items[f.index].config_module()).or_insert(0);
+                *count += 1;
+                *count <= per_type
+            })
+            .take(total)
+            .collect()
     }
 
     /// Get the enhanced fuzzy score for an item against a query.
@@ -123,7 +213,19 @@ impl ItemFilter {
     /// 2. Fall back to description match with penalty
     /// 3. Apply bonuses for exact/prefix/contiguous matches
     /// 4. Apply item type multipliers (demote actions/submenus)
+    /// 5. Add the alias bonus (aliased apps match even without a fuzzy match)
     fn score_item(&self, item: &ListItem, query: &str) -> Option<i64> {
+        let alias_bonus = self.alias_bonus(item, query);
+
+        match self.score_fuzzy(item, query) {
+            Some(score) => Some(score + alias_bonus),
+            None if alias_bonus > 0 => Some(alias_bonus),
+            None => None,
+        }
+    }
+
+    /// Get the fuzzy score of the name, falling back to the description.
+    fn score_fuzzy(&self, item: &ListItem, query: &str) -> Option<i64> {
         let name = item.name();
 
         // Try name match first (preferred)
@@ -141,6 +243,28 @@ impl ItemFilter {
         None
     }
 
+    /// Get the alias bonus for an item.
+    ///
+    /// Returns `alias_bonus` if the query equals an alias key whose target
+    /// is this application's id or name, 0 otherwise.
+    fn alias_bonus(&self, item: &ListItem, query: &str) -> i64 {
+        let (ListItem::Application(app), Some(aliases)) = (item, &self.config.aliases) else {
+            return 0;
+        };
+
+        let query_lower = query.trim().to_lowercase();
+        let is_aliased = aliases.iter().any(|(alias, target)| {
+            alias.to_lowercase() == query_lower
+                && (target.eq_ignore_ascii_case(&app.id) || target.eq_ignore_ascii_case(&app.name))
+        });
+
+        if is_aliased {
+            self.config.alias_bonus
+        } else {
+            0
+        }
+    }
+
     /// Score a text match against a query, trying multiple query normalizations.
     ///
     /// Handles cases like "counter strike" matching "Counter-Strike" by:
@@ -179,6 +303,9 @@ impl ItemFilter {
         });
 
         let (base_score, indices) = match_result?;
+        if base_score < self.min_score(query) {
+            return None;
+        }
         let mut score = base_score;
 
         // Apply bonuses only for name matches, not descriptions
@@ -211,6 +338,12 @@ impl ItemFilter {
         Some(score)
     }
 
+    /// Lowest raw fuzzy score kept for a query, scaled by its length.
+    fn min_score(&self, query: &str) -> i64 {
+        let len = query.chars().filter(|c| !c.is_whitespace()).count() as i64;
+        self.config.min_score_per_char * len
+    }
+
     /// Calculate bonus based on how contiguous (adjacent) the matched characters are.
     ///
     /// Returns a value between 0 and `contiguity_bonus` config value.
@@ -254,7 +387,21 @@ impl ItemFilter {
 mod tests {
     use super::*;
     use crate::items::{ActionItem, ActionKind};
-    use crate::test_utils::{mock_application, mock_application_with_desc};
+    use crate::test_utils::{mock_application, mock_application_with_desc, mock_window};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn filter_with_aliases(aliases: &[(&str, &str)]) -> ItemFilter {
+        ItemFilter::new(FuzzyMatchConfig {
+            aliases: Some(
+                aliases
+                    .iter()
+                    .map(|(alias, target)| (alias.to_string(), target.to_string()))
+                    .collect::<HashMap<_, _>>(),
+            ),
+            ..Default::default()
+        })
+    }
 
     #[test]
     fn test_empty_query_returns_all() {
@@ -427,7 +574,10 @@ mod tests {
             "TestAction".to_string(),
             Some("A test action".to_string()),
             None,
-            ActionKind::Command("echo test".to_string()),
+            ActionKind::Command {
+                command: "echo test".to_string(),
+                shell: false,
+            },
         );
 
         let items: Vec<ListItem> = vec![
@@ -535,4 +685,197 @@ mod tests {
         let result2 = filter.filter_indices(&items, "android studio", &[]);
         assert!(result2.contains(&1), "Should match 'Android Studio'");
     }
+
+    #[test]
+    fn test_alias_beats_coincidental_fuzzy_match() {
+        let items: Vec<ListItem> = vec![
+            ListItem::Application(mock_application("Codec Tool")),
+            ListItem::Application(mock_application("Visual Studio Code")),
+        ];
+
+        // Without an alias, "codec" prefers the coincidental prefix match
+        let result = ItemFilter::default().filter_indices(&items, "codec", &[]);
+        assert_eq!(result[0], 0);
+
+        // The alias elevates the intended app
+        let filter = filter_with_aliases(&[("codec", "app-visual-studio-code")]);
+        let result = filter.filter_indices(&items, "codec", &[]);
+        assert_eq!(result[0], 1);
+    }
+
+    #[test]
+    fn test_alias_matches_without_fuzzy_match() {
+        let filter = filter_with_aliases(&[("VSC", "Visual Studio Code")]);
+        let items: Vec<ListItem> = vec![
+            ListItem::Application(mock_application("Firefox")),
+            ListItem::Application(mock_application("Visual Studio Code")),
+        ];
+
+        // Case-insensitive key, target matched by name
+        let result = filter.filter_indices(&items, "vsc", &[]);
+        assert_eq!(result, vec![1]);
+    }
+
+    #[test]
+    fn test_alias_is_additive() {
+        let filter = filter_with_aliases(&[("discord", "app-discord-canary")]);
+        let items: Vec<ListItem> = vec![
+            ListItem::Application(mock_application("Discord")),
+            ListItem::Application(mock_application("Discord Canary")),
+        ];
+
+        // Both still match normally; the aliased app ranks first
+        let result = filter.filter_indices(&items, "discord", &[]);
+        assert_eq!(result, vec![1, 0]);
+    }
+
+    fn ranking_fixture() -> (Vec<ListItem>, Arc<LaunchHistory>) {
+        let items = vec![
+            ListItem::Application(mock_application("Firefox")),
+            ListItem::Application(mock_application("Firefox Developer Edition")),
+            ListItem::Window(mock_window("Firefox", "firefox")),
+        ];
+        let mut history = LaunchHistory::default();
+        for _ in 0..5 {
+            history.record_at(items[1].id(), crate::frecency::now());
+        }
+        (items, Arc::new(history))
+    }
+
+    #[test]
+    fn test_default_ranking_ignores_frecency() {
+        let (items, history) = ranking_fixture();
+        let filter = ItemFilter::default().with_history(history);
+        let modules = [ConfigModule::Applications, ConfigModule::Windows];
+
+        // Exact match first within the applications group, windows after
+        let result = filter.filter_indices(&items, "firefox", &modules);
+        assert_eq!(result, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_frecency_weight_promotes_frequent_item() {
+        let (items, history) = ranking_fixture();
+        let filter = ItemFilter::new(FuzzyMatchConfig {
+            frecency_weight: 100_000.0,
+            ..Default::default()
+        })
+        .with_history(history);
+        let modules = [ConfigModule::Applications, ConfigModule::Windows];
+
+        let result = filter.filter_indices(&items, "firefox", &modules);
+        assert_eq!(result, vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_flat_ranking_interleaves_types() {
+        let (items, _) = ranking_fixture();
+        let filter = ItemFilter::default().with_flat(true);
+        let modules = [ConfigModule::Applications, ConfigModule::Windows];
+
+        // The exact window title match outranks the longer app name
+        let result = filter.filter_indices(&items, "firefox", &modules);
+        assert_eq!(result, vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_workspace_filter() {
+        let filter = ItemFilter::default();
+        let on_workspace = |title: &str, app_id: &str, workspace: i32| {
+            let mut window = mock_window(title, app_id);
+            window.workspace = workspace;
+            ListItem::Window(window)
+        };
+        let items = vec![
+            on_workspace("Firefox", "firefox", 1),
+            on_workspace("Firefox Docs", "firefox", 2),
+            on_workspace("Terminal", "kitty", 2),
+            ListItem::Application(mock_application("Firefox")),
+        ];
+
+        let result: Vec<usize> = filter
+            .filter_in_workspace(&items, "firefox", Some(2), &[])
+            .iter()
+            .map(|f| f.index)
+            .collect();
+        assert!(result.contains(&1) && result.contains(&3));
+        assert!(!result.contains(&0));
+
+        // Only the token: just that workspace's windows
+        let result: Vec<usize> = filter
+            .filter_in_workspace(&items, "", Some(2), &[])
+            .iter()
+            .map(|f| f.index)
+            .collect();
+        assert_eq!(result, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_min_score_prunes_weak_matches() {
+        let items = vec![
+            ListItem::Application(mock_application("Fire")),
+            ListItem::Application(mock_application("xfxxxxixxxxrxxxxex")),
+        ];
+
+        // Default keeps every match
+        let filter = ItemFilter::default();
+        assert_eq!(filter.filter_indices(&items, "fire", &[]), vec![0, 1]);
+
+        let filter = ItemFilter::new(FuzzyMatchConfig {
+            min_score_per_char: 20,
+            ..Default::default()
+        });
+        assert_eq!(filter.filter_indices(&items, "fire", &[]), vec![0]);
+    }
+
+    #[test]
+    fn test_result_caps() {
+        let items: Vec<ListItem> = (0..6)
+            .map(|i| ListItem::Application(mock_application(&format!("Term {i}"))))
+            .chain((0..6).map(|i| ListItem::Window(mock_window(&format!("Term {i}"), "term"))))
+            .collect();
+        let modules = [ConfigModule::Applications, ConfigModule::Windows];
+
+        let filter = ItemFilter::new(FuzzyMatchConfig {
+            max_results_per_type: 2,
+            ..Default::default()
+        });
+        assert_eq!(filter.filter_indices(&items, "term", &modules).len(), 4);
+        assert_eq!(
+            filter.filter_indices(&items, "", &modules),
+            vec![0, 1, 6, 7]
+        );
+
+        let filter = ItemFilter::new(FuzzyMatchConfig {
+            max_results: 3,
+            max_results_per_type: 0,
+            ..Default::default()
+        });
+        assert_eq!(filter.filter_indices(&items, "", &modules), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_case_matching() {
+        let items = vec![
+            ListItem::Application(mock_application("README.md")),
+            ListItem::Application(mock_application("readme.txt")),
+        ];
+        let modules = [ConfigModule::Applications];
+        let filter_with = |case_matching| {
+            ItemFilter::new(FuzzyMatchConfig {
+                case_matching,
+                ..Default::default()
+            })
+        };
+
+        let smart = filter_with(CaseMatching::Smart);
+        assert_eq!(smart.filter_indices(&items, "readme", &modules).len(), 2);
+        assert_eq!(smart.filter_indices(&items, "README", &modules), vec![0]);
+
+        let ignore = filter_with(CaseMatching::Ignore);
+        assert_eq!(ignore.filter_indices(&items, "README", &modules).len(), 2);
+
+        let respect = filter_with(CaseMatching::Respect);
+        assert_eq!(respect.filter_indices(&items, "readme", &modules), vec![1]);
+    }
 }
@@ -11,7 +11,8 @@
 //! # Delegate Types
 //!
 //! - [`ItemListDelegate`] - Main launcher list (applications, windows, actions, etc.)
-//! - [`EmojiGridDelegate`] - Grid-based emoji picker
+//! - [`GridDelegate`] - Grid-based pickers, as [`EmojiGridDelegate`] for emoji
+//!   and [`GlyphGridDelegate`] for Nerd Font icons
 //! - [`ClipboardListDelegate`] - Clipboard history with preview panel
 //! - [`ThemeListDelegate`] - Theme selection list
 //!
@@ -32,6 +33,8 @@ mod base;
 mod clipboard_delegate;
 mod dynamic_items;
 mod emoji_delegate;
+mod glyph_delegate;
+mod grid_delegate;
 mod item_delegate;
 mod item_filter;
 mod section_manager;
@@ -40,6 +43,9 @@ mod theme_delegate;
 pub use base::BaseDelegate;
 pub use clipboard_delegate::ClipboardListDelegate;
 pub use emoji_delegate::EmojiGridDelegate;
-pub use item_delegate::ItemListDelegate;
+pub use glyph_delegate::GlyphGridDelegate;
+pub use grid_delegate::{GridDelegate, GridItem};
+pub use item_delegate::{DelegateOptions, ItemListDelegate, QueryExplanation};
 pub use item_filter::FilteredItem;
+pub use section_manager::SectionType;
 pub use theme_delegate::ThemeListDelegate;
@@ -3,7 +3,7 @@
 //! Handles organizing items into sections and converting between
 //! global indices and section-based IndexPaths.
 
-use crate::config::ConfigModule;
+use crate::config::{ConfigModule, SectionHeaders};
 use crate::items::ListItem;
 use gpui_component::IndexPath;
 
@@ -18,12 +18,18 @@ pub enum SectionType {
     Calculator,
     /// Open windows.
     Windows,
-    /// Submenus and actions (emojis, clipboard, themes, actions).
+    /// Submenus and actions (emojis, icons, clipboard, themes, actions).
     Commands,
     /// Desktop applications.
     Applications,
     /// Combined Search + AI section (positioned by first occurrence in combined_modules).
     SearchAndAi,
+    /// All windows, commands and applications ranked together (ungrouped mode).
+    Results,
+    /// Indexed files matching the query (after the applications).
+    Files,
+    /// Previously confirmed queries starting with the query (always last).
+    Suggestions,
 }
 
 impl SectionType {
@@ -36,6 +42,9 @@ impl SectionType {
             SectionType::Commands => "Commands",
             SectionType::Applications => "Applications",
             SectionType::SearchAndAi => "Search and AI",
+            SectionType::Results => "Results",
+            SectionType::Files => "Files",
+            SectionType::Suggestions => "Recent Searches",
         }
     }
 }
@@ -86,6 +95,10 @@ pub struct SectionManager {
     has_ai: bool,
     /// Number of search items.
     search_count: usize,
+    /// Number of query suggestions.
+    suggestion_count: usize,
+    /// Number of indexed files matching the query.
+    file_count: usize,
     /// Whether best match feature is enabled.
     show_best_match: bool,
     /// Index of best match in filtered results (if promoted).
@@ -93,6 +106,12 @@ pub struct SectionManager {
     best_match_filtered_pos: Option<usize>,
     /// The section type that the best match was promoted from.
     best_match_original_section: Option<SectionType>,
+    /// Show windows, commands and applications in one ranked section.
+    flat: bool,
+    /// In the flat layout, show AI and search items above the results.
+    dynamic_first: bool,
+    /// Sections whose results are still being fetched in the background.
+    loading: Vec<SectionType>,
 }
 
 impl SectionManager {
@@ -104,12 +123,66 @@ impl SectionManager {
             has_calculator: false,
             has_ai: false,
             search_count: 0,
+            suggestion_count: 0,
+            file_count: 0,
             show_best_match,
             best_match_filtered_pos: None,
             best_match_original_section: None,
+            flat: false,
+            dynamic_first: false,
+            loading: Vec::new(),
         }
     }
 
+    /// Show windows, commands and applications in a single section in
+    /// filter order instead of grouping them by type (builder pattern).
+    pub fn with_flat(mut self, flat: bool) -> Self {
+        self.flat = flat;
+        self
+    }
+
+    /// In the flat layout, place the AI and search items before the
+    /// results instead of after them (builder pattern).
+    pub fn with_dynamic_first(mut self, dynamic_first: bool) -> Self {
+        self.dynamic_first = dynamic_first;
+        self
+    }
+
+    /// Mark a section as waiting for background results.
+    ///
+    /// A loading section is shown even without items, with a placeholder
+    /// row after its items until it is marked as loaded.
+    pub fn set_loading(&mut self, section_type: SectionType, loading: bool) {
+        self.loading.retain(|s| *s != section_type);
+        if loading {
+            self.loading.push(section_type);
+        }
+    }
+
+    /// Whether a section is waiting for background results.
+    pub fn is_loading(&self, section_type: SectionType) -> bool {
+        match section_type {
+            SectionType::Results => [
+                SectionType::Windows,
+                SectionType::Commands,
+                SectionType::Applications,
+            ]
+            .iter()
+            .any(|s| self.loading.contains(s)),
+            _ => self.loading.contains(&section_type),
+        }
+    }
+
+    /// Set the number of query suggestions shown after the other sections.
+    pub fn set_suggestion_count(&mut self, count: usize) {
+        self.suggestion_count = count;
+    }
+
+    /// Set the number of indexed files shown after the applications.
+    pub fn set_file_count(&mut self, count: usize) {
+        self.file_count = count;
+    }
+
     /// Update the section info from filtered items and dynamic item state.
     pub fn update(
         &mut self,
@@ -148,7 +221,8 @@ impl SectionManager {
         self.best_match_original_section = None;
 
         // Determine if we should promote a best match
-        if self.show_best_match && !filtered.is_empty() {
+        // (the flat section is already ordered by rank)
+        if self.show_best_match && !self.flat && !filtered.is_empty() {
             self.compute_best_match(items, filtered);
         }
     }
@@ -199,6 +273,7 @@ impl SectionManager {
             ConfigModule::Search | ConfigModule::Ai => SectionType::SearchAndAi,
             ConfigModule::Actions
             | ConfigModule::Emojis
+            | ConfigModule::Icons
             | ConfigModule::Clipboard
             | ConfigModule::Themes => SectionType::Commands,
             ConfigModule::Calculator => SectionType::Calculator,
@@ -225,19 +300,48 @@ impl SectionManager {
         let mut sections = Vec::new();
         let mut seen_commands = false;
         let mut seen_search_and_ai = false;
-        let has_search_and_ai = self.has_ai || self.search_count > 0;
+        // A section whose only item was promoted to best match is left out
+        let has_items = |section_type| {
+            self.section_item_count(section_type) > 0 || self.is_loading(section_type)
+        };
+        let has_search_and_ai = has_items(SectionType::SearchAndAi);
+        let has_windows = has_items(SectionType::Windows);
+        let has_commands = has_items(SectionType::Commands);
+        let has_apps = has_items(SectionType::Applications);
+
+        if self.flat {
+            if has_search_and_ai && self.dynamic_first {
+                sections.push(SectionType::SearchAndAi);
+            }
+            if self.regular_count() > 0 || self.is_loading(SectionType::Results) {
+                sections.push(SectionType::Results);
+            }
+            if self.file_count > 0 {
+                sections.push(SectionType::Files);
+            }
+            if has_search_and_ai && !self.dynamic_first {
+                sections.push(SectionType::SearchAndAi);
+            }
+            if self.suggestion_count > 0 {
+                sections.push(SectionType::Suggestions);
+            }
+            return sections;
+        }
 
         for module in &self.combined_modules {
             match module {
-                ConfigModule::Windows if self.section_info.window_count > 0 => {
+                ConfigModule::Windows if has_windows => {
                     if !sections.contains(&SectionType::Windows) {
                         sections.push(SectionType::Windows);
                     }
                 }
-                ConfigModule::Applications if self.section_info.app_count > 0 => {
-                    if !sections.contains(&SectionType::Applications) {
+                ConfigModule::Applications => {
+                    if has_apps && !sections.contains(&SectionType::Applications) {
                         sections.push(SectionType::Applications);
                     }
+                    if self.file_count > 0 && !sections.contains(&SectionType::Files) {
+                        sections.push(SectionType::Files);
+                    }
                 }
                 // Search and AI are combined into one section, positioned at first occurrence
                 ConfigModule::Search | ConfigModule::Ai
@@ -246,12 +350,13 @@ impl SectionManager {
                     sections.push(SectionType::SearchAndAi);
                     seen_search_and_ai = true;
                 }
-                // Actions, Emojis, Clipboard, Themes all map to Commands section
+                // Actions, Emojis, Icons, Clipboard, Themes all map to Commands section
                 ConfigModule::Actions
                 | ConfigModule::Emojis
+                | ConfigModule::Icons
                 | ConfigModule::Clipboard
                 | ConfigModule::Themes
-                    if self.section_info.command_count > 0 && !seen_commands =>
+                    if has_commands && !seen_commands =>
                 {
                     sections.push(SectionType::Commands);
                     seen_commands = true;
@@ -260,11 +365,65 @@ impl SectionManager {
             }
         }
 
+        if self.suggestion_count > 0 {
+            sections.push(SectionType::Suggestions);
+        }
+
         sections
     }
 
+    /// Sections of regular items (windows, commands, applications) in the
+    /// order of the filtered results, whether or not they have items.
+    fn regular_section_order(&self) -> Vec<SectionType> {
+        if self.flat {
+            return vec![SectionType::Results];
+        }
+        let mut order = Vec::new();
+        for module in &self.combined_modules {
+            let section_type = self.section_type_for_module(module);
+            let regular = matches!(
+                section_type,
+                SectionType::Windows | SectionType::Commands | SectionType::Applications
+            );
+            if regular && !order.contains(&section_type) {
+                order.push(section_type);
+            }
+        }
+        order
+    }
+
+    /// Number of filtered items of a regular section, including a
+    /// promoted best match.
+    fn filtered_section_count(&self, section_type: SectionType) -> usize {
+        match section_type {
+            SectionType::Windows => self.section_info.window_count,
+            SectionType::Commands => self.section_info.command_count,
+            SectionType::Applications => self.section_info.app_count,
+            SectionType::Results => self.regular_count(),
+            _ => 0,
+        }
+    }
+
+    /// Position in the filtered results of the first item of a regular
+    /// section, counting a best match promoted out of an earlier section.
+    pub fn filtered_offset(&self, section_type: SectionType) -> usize {
+        self.regular_section_order()
+            .into_iter()
+            .take_while(|s| *s != section_type)
+            .map(|s| self.filtered_section_count(s))
+            .sum()
+    }
+
+    /// Number of windows, commands and applications.
+    fn regular_count(&self) -> usize {
+        self.section_info.window_count
+            + self.section_info.command_count
+            + self.section_info.app_count
+    }
+
     /// Get the ordered list of section types based on combined_modules.
-    /// If a best match is promoted, BestMatch appears first.
+    /// If a best match is promoted, BestMatch appears first, followed by
+    /// the calculator result (if present).
     pub fn ordered_section_types(&self) -> Vec<SectionType> {
         let mut sections = Vec::new();
 
@@ -273,6 +432,11 @@ impl SectionManager {
             sections.push(SectionType::BestMatch);
         }
 
+        // Calculator next (if present)
+        if self.has_calculator {
+            sections.push(SectionType::Calculator);
+        }
+
         // Add the rest of the sections
         sections.extend(self.ordered_section_types_internal());
 
@@ -281,47 +445,15 @@ impl SectionManager {
 
     /// Get the total number of sections (including calculator and best match if present).
     pub fn sections_count(&self) -> usize {
-        let mut count = 0;
-        if self.has_best_match() {
-            count += 1;
-        }
-        if self.has_calculator {
-            count += 1;
-        }
-        count += self.ordered_section_types_internal().len();
-        count
+        self.ordered_section_types().len()
     }
 
     /// Determine what type of section is at the given section index.
     pub fn section_type_at(&self, section: usize) -> SectionType {
-        let mut current_section = 0;
-
-        // BestMatch always first (if present)
-        if self.has_best_match() {
-            if section == current_section {
-                return SectionType::BestMatch;
-            }
-            current_section += 1;
-        }
-
-        // Calculator next (if present)
-        if self.has_calculator {
-            if section == current_section {
-                return SectionType::Calculator;
-            }
-            current_section += 1;
-        }
-
-        // Sections in combined_modules order
-        for section_type in self.ordered_section_types_internal() {
-            if section == current_section {
-                return section_type;
-            }
-            current_section += 1;
-        }
-
-        // Default (shouldn't reach here)
-        SectionType::Applications
+        self.ordered_section_types()
+            .get(section)
+            .copied()
+            .unwrap_or(SectionType::Applications)
     }
 
     /// Get the number of items in a section type.
@@ -366,6 +498,9 @@ impl SectionManager {
                     count
                 }
             }
+            SectionType::Results => self.regular_count(),
+            SectionType::Suggestions => self.suggestion_count,
+            SectionType::Files => self.file_count,
             SectionType::SearchAndAi => {
                 let ai_count = if self.has_ai { 1 } else { 0 };
                 let count = ai_count + self.search_count;
@@ -378,6 +513,31 @@ impl SectionManager {
         }
     }
 
+    /// Whether the section at the given index gets a header.
+    pub fn shows_header(&self, section: usize, mode: SectionHeaders) -> bool {
+        match mode {
+            SectionHeaders::Never => false,
+            // Calculator and search/AI results explain themselves
+            SectionHeaders::Always => !matches!(
+                self.section_type_at(section),
+                SectionType::Calculator | SectionType::SearchAndAi
+            ),
+            // Headers only help to tell several sections apart
+            SectionHeaders::Adaptive => self.sections_count() > 1,
+        }
+    }
+
+    /// Get the number of rows in a section, including the loading placeholder.
+    pub fn section_row_count(&self, section_type: SectionType) -> usize {
+        self.section_item_count(section_type) + usize::from(self.is_loading(section_type))
+    }
+
+    /// Whether a row is the loading placeholder of its section rather than an item.
+    pub fn is_placeholder_row(&self, section: usize, row: usize) -> bool {
+        let section_type = self.section_type_at(section);
+        self.is_loading(section_type) && row >= self.section_item_count(section_type)
+    }
+
     /// Get the starting global index for a given section type.
     pub fn section_start_index(&self, section_type: SectionType) -> usize {
         let mut offset = 0;
@@ -389,11 +549,6 @@ impl SectionManager {
             offset += self.section_item_count(st);
         }
 
-        // Include calculator if it comes before this section
-        if self.has_calculator && section_type != SectionType::Calculator {
-            offset += 1;
-        }
-
         offset
     }
 
@@ -412,25 +567,14 @@ impl SectionManager {
             let section_count = self.section_item_count(section_type);
             let section_end = current_start + section_count;
 
-            if section_count > 0 {
-                if global_idx >= current_start && global_idx < section_end {
-                    return Some(
-                        IndexPath::new(global_idx - current_start).section(current_section),
-                    );
-                }
-                current_section += 1;
+            if global_idx >= current_start && global_idx < section_end {
+                return Some(IndexPath::new(global_idx - current_start).section(current_section));
             }
+            // Every listed section has an index, even while it has no items
+            current_section += 1;
             current_start = section_end;
         }
 
-        // Check calculator section
-        if self.has_calculator {
-            let calc_start = current_start;
-            if global_idx == calc_start {
-                return Some(IndexPath::new(0).section(current_section));
-            }
-        }
-
         None
     }
 }
@@ -438,8 +582,46 @@ impl SectionManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::items::SubmenuItem;
     use crate::test_utils::{mock_application, mock_window};
 
+    /// Windows, then commands, then applications.
+    fn mixed_items(windows: usize, commands: usize, apps: usize) -> Vec<ListItem> {
+        let windows = (0..windows).map(|i| ListItem::Window(mock_window(&format!("W{i}"), "w")));
+        let commands = (0..commands)
+            .map(|i| ListItem::Submenu(SubmenuItem::list(format!("c{i}"), format!("C{i}"))));
+        let apps = (0..apps).map(|i| ListItem::Application(mock_application(&format!("A{i}"))));
+        windows.chain(commands).chain(apps).collect()
+    }
+
+    /// Check that every global index maps to a section row and back, and
+    /// return the listed sections with their item counts.
+    fn round_trip(manager: &SectionManager) -> Vec<(SectionType, usize)> {
+        let sections: Vec<(SectionType, usize)> = manager
+            .ordered_section_types()
+            .into_iter()
+            .map(|section_type| (section_type, manager.section_item_count(section_type)))
+            .collect();
+        let total: usize = sections.iter().map(|(_, count)| count).sum();
+
+        for global in 0..total {
+            let path = manager
+                .global_to_index_path(global)
+                .unwrap_or_else(|| panic!("no path for {global}"));
+            let section_type = manager.section_type_at(path.section);
+            assert_eq!(sections[path.section].0, section_type);
+            assert!(path.row < manager.section_item_count(section_type));
+            assert_eq!(
+                manager.section_row_to_global(path.section, path.row),
+                global
+            );
+            assert_eq!(manager.section_start_index(section_type) + path.row, global);
+        }
+        assert_eq!(manager.global_to_index_path(total), None);
+
+        sections
+    }
+
     #[test]
     fn test_section_type_title() {
         assert_eq!(SectionType::BestMatch.title(), "Best Match");
@@ -602,4 +784,416 @@ mod tests {
         // Applications has 2 - 1 (promoted) = 1 item
         assert_eq!(manager.section_item_count(SectionType::Applications), 1);
     }
+
+    #[test]
+    fn test_calculator_section_mapping() {
+        let mut manager = SectionManager::new(
+            vec![ConfigModule::Windows, ConfigModule::Applications],
+            false,
+        );
+
+        let items: Vec<ListItem> = vec![
+            ListItem::Window(mock_window("Window", "window")),
+            ListItem::Application(mock_application("App")),
+        ];
+
+        manager.update(&items, &[0, 1], true, false, 0);
+
+        // Calculator is the first section and global index 0
+        assert_eq!(manager.sections_count(), 3);
+        assert_eq!(manager.section_type_at(0), SectionType::Calculator);
+        assert_eq!(manager.section_type_at(1), SectionType::Windows);
+        assert_eq!(manager.section_row_to_global(0, 0), 0);
+        assert_eq!(manager.section_row_to_global(2, 0), 2);
+        assert_eq!(
+            manager.global_to_index_path(0),
+            Some(IndexPath::new(0).section(0))
+        );
+        assert_eq!(
+            manager.global_to_index_path(2),
+            Some(IndexPath::new(0).section(2))
+        );
+        assert_eq!(manager.global_to_index_path(3), None);
+    }
+
+    #[test]
+    fn test_section_mapping_combinations() {
+        use ConfigModule::*;
+        use SectionType as S;
+
+        let all = vec![Calculator, Windows, Actions, Applications, Search, Ai];
+        // (modules, windows, commands, apps, calculator, ai, searches, expected sections)
+        let cases = vec![
+            (all.clone(), 0, 0, 0, false, false, 0, vec![]),
+            (
+                all.clone(),
+                0,
+                0,
+                0,
+                true,
+                false,
+                0,
+                vec![(S::Calculator, 1)],
+            ),
+            (
+                all.clone(),
+                0,
+                0,
+                0,
+                true,
+                true,
+                0,
+                vec![(S::Calculator, 1), (S::SearchAndAi, 1)],
+            ),
+            (
+                all.clone(),
+                0,
+                0,
+                0,
+                false,
+                false,
+                3,
+                vec![(S::SearchAndAi, 3)],
+            ),
+            (
+                all.clone(),
+                0,
+                0,
+                0,
+                true,
+                true,
+                2,
+                vec![(S::Calculator, 1), (S::SearchAndAi, 3)],
+            ),
+            (
+                all.clone(),
+                0,
+                0,
+                2,
+                true,
+                false,
+                0,
+                vec![(S::Calculator, 1), (S::Applications, 2)],
+            ),
+            (
+                all.clone(),
+                1,
+                0,
+                1,
+                false,
+                false,
+                0,
+                vec![(S::Windows, 1), (S::Applications, 1)],
+            ),
+            (
+                all.clone(),
+                0,
+                2,
+                0,
+                false,
+                false,
+                1,
+                vec![(S::Commands, 2), (S::SearchAndAi, 1)],
+            ),
+            (
+                all.clone(),
+                2,
+                1,
+                3,
+                true,
+                true,
+                1,
+                vec![
+                    (S::Calculator, 1),
+                    (S::Windows, 2),
+                    (S::Commands, 1),
+                    (S::Applications, 3),
+                    (S::SearchAndAi, 2),
+                ],
+            ),
+            // Search and AI placed by their first module
+            (
+                vec![Ai, Applications, Search],
+                0,
+                0,
+                2,
+                false,
+                true,
+                1,
+                vec![(S::SearchAndAi, 2), (S::Applications, 2)],
+            ),
+            // Commands placed by the first command module
+            (
+                vec![Emojis, Applications, Actions],
+                0,
+                1,
+                1,
+                false,
+                false,
+                0,
+                vec![(S::Commands, 1), (S::Applications, 1)],
+            ),
+            (
+                vec![Applications, Windows],
+                1,
+                0,
+                1,
+                true,
+                false,
+                0,
+                vec![(S::Calculator, 1), (S::Applications, 1), (S::Windows, 1)],
+            ),
+            (
+                vec![Windows],
+                3,
+                0,
+                0,
+                false,
+                false,
+                0,
+                vec![(S::Windows, 3)],
+            ),
+        ];
+
+        for (i, (modules, windows, commands, apps, calculator, ai, searches, expected)) in
+            cases.into_iter().enumerate()
+        {
+            let items = mixed_items(windows, commands, apps);
+            let indices: Vec<usize> = (0..items.len()).collect();
+            let mut manager = SectionManager::new(modules, false);
+            manager.update(&items, &indices, calculator, ai, searches);
+            assert_eq!(round_trip(&manager), expected, "case {i}");
+        }
+    }
+
+    #[test]
+    fn test_best_match_empties_its_section() {
+        // The only application is promoted; its section disappears
+        let items = mixed_items(1, 2, 1);
+        // Filtered in module order: window, application, commands
+        let filtered: Vec<FilteredItem> = [(0, 10), (3, 100), (1, 10), (2, 10)]
+            .into_iter()
+            .map(|(index, score)| FilteredItem { index, score })
+            .collect();
+        let mut manager = SectionManager::new(
+            vec![
+                ConfigModule::Windows,
+                ConfigModule::Applications,
+                ConfigModule::Actions,
+            ],
+            true,
+        );
+        manager.update_with_scores(&items, &filtered, true, false, 0);
+
+        assert_eq!(
+            round_trip(&manager),
+            vec![
+                (SectionType::BestMatch, 1),
+                (SectionType::Calculator, 1),
+                (SectionType::Windows, 1),
+                (SectionType::Commands, 2),
+            ]
+        );
+
+        // Filtered positions still count the promoted item
+        assert_eq!(manager.filtered_offset(SectionType::Windows), 0);
+        assert_eq!(manager.filtered_offset(SectionType::Applications), 1);
+        assert_eq!(manager.filtered_offset(SectionType::Commands), 2);
+    }
+
+    #[test]
+    fn test_header_visibility_modes() {
+        let items = vec![
+            ListItem::Window(mock_window("Window", "window")),
+            ListItem::Application(mock_application("App")),
+        ];
+        let mut manager = SectionManager::new(
+            vec![ConfigModule::Windows, ConfigModule::Applications],
+            false,
+        );
+
+        // Calculator, Windows and Applications
+        manager.update(&items, &[0, 1], true, false, 0);
+        let headers = |manager: &SectionManager, mode| {
+            (0..manager.sections_count())
+                .map(|section| manager.shows_header(section, mode))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            headers(&manager, SectionHeaders::Adaptive),
+            [true, true, true]
+        );
+        assert_eq!(
+            headers(&manager, SectionHeaders::Always),
+            [false, true, true]
+        );
+        assert_eq!(
+            headers(&manager, SectionHeaders::Never),
+            [false, false, false]
+        );
+
+        // Only Applications
+        manager.update(&items, &[1], false, false, 0);
+        assert_eq!(headers(&manager, SectionHeaders::Adaptive), [false]);
+        assert_eq!(headers(&manager, SectionHeaders::Always), [true]);
+        assert_eq!(headers(&manager, SectionHeaders::Never), [false]);
+    }
+
+    #[test]
+    fn test_loading_placeholder_transitions() {
+        let items = vec![ListItem::Application(mock_application("App"))];
+        let mut manager = SectionManager::new(
+            vec![ConfigModule::Applications, ConfigModule::Search],
+            false,
+        );
+
+        // Pending: the section is listed with only the placeholder row
+        manager.set_loading(SectionType::SearchAndAi, true);
+        manager.update(&items, &[0], false, false, 0);
+        assert_eq!(
+            manager.ordered_section_types(),
+            vec![SectionType::Applications, SectionType::SearchAndAi]
+        );
+        assert_eq!(manager.section_item_count(SectionType::SearchAndAi), 0);
+        assert_eq!(manager.section_row_count(SectionType::SearchAndAi), 1);
+        assert!(manager.is_placeholder_row(1, 0));
+        assert!(!manager.is_placeholder_row(0, 0));
+        // The placeholder has no global index, so it can't be selected
+        assert_eq!(manager.global_to_index_path(1), None);
+
+        // Loaded: the results replace the placeholder
+        manager.set_loading(SectionType::SearchAndAi, false);
+        manager.update(&items, &[0], false, false, 2);
+        assert_eq!(manager.section_row_count(SectionType::SearchAndAi), 2);
+        assert!(!manager.is_placeholder_row(1, 1));
+        assert_eq!(
+            manager.global_to_index_path(2),
+            Some(IndexPath::new(1).section(1))
+        );
+
+        // Empty: a finished section without results disappears
+        manager.update(&items, &[0], false, false, 0);
+        assert_eq!(
+            manager.ordered_section_types(),
+            vec![SectionType::Applications]
+        );
+    }
+
+    #[test]
+    fn test_flat_sections() {
+        let items = vec![
+            ListItem::Application(mock_application("Firefox")),
+            ListItem::Window(mock_window("Firefox", "firefox")),
+        ];
+        let filtered = vec![
+            FilteredItem {
+                index: 1,
+                score: 200,
+            },
+            FilteredItem {
+                index: 0,
+                score: 100,
+            },
+        ];
+        let mut manager = SectionManager::new(
+            vec![
+                ConfigModule::Applications,
+                ConfigModule::Windows,
+                ConfigModule::Search,
+            ],
+            true,
+        )
+        .with_flat(true);
+        manager.update_with_scores(&items, &filtered, false, false, 2);
+
+        assert!(!manager.has_best_match());
+        assert_eq!(
+            manager.ordered_section_types(),
+            vec![SectionType::Results, SectionType::SearchAndAi]
+        );
+        assert_eq!(manager.section_item_count(SectionType::Results), 2);
+
+        let mut manager = manager.with_dynamic_first(true);
+        manager.update_with_scores(&items, &filtered, true, false, 2);
+        assert_eq!(
+            round_trip(&manager),
+            vec![
+                (SectionType::Calculator, 1),
+                (SectionType::SearchAndAi, 2),
+                (SectionType::Results, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suggestions_section_is_last() {
+        let items = mixed_items(1, 0, 2);
+        let mut manager = SectionManager::new(
+            vec![
+                ConfigModule::Search,
+                ConfigModule::Windows,
+                ConfigModule::Applications,
+            ],
+            false,
+        );
+        manager.set_suggestion_count(2);
+        manager.update(&items, &[0, 1, 2], true, false, 1);
+
+        assert_eq!(
+            round_trip(&manager),
+            vec![
+                (SectionType::Calculator, 1),
+                (SectionType::SearchAndAi, 1),
+                (SectionType::Windows, 1),
+                (SectionType::Applications, 2),
+                (SectionType::Suggestions, 2),
+            ]
+        );
+
+        manager.set_suggestion_count(0);
+        assert!(
+            !manager
+                .ordered_section_types()
+                .contains(&SectionType::Suggestions)
+        );
+    }
+
+    #[test]
+    fn test_files_section_follows_applications() {
+        let items = mixed_items(1, 0, 1);
+        let modules = vec![
+            ConfigModule::Applications,
+            ConfigModule::Windows,
+            ConfigModule::Search,
+        ];
+        let mut manager = SectionManager::new(modules.clone(), false);
+        manager.set_file_count(3);
+        manager.update(&items, &[0, 1], false, false, 1);
+
+        assert_eq!(
+            round_trip(&manager),
+            vec![
+                (SectionType::Applications, 1),
+                (SectionType::Files, 3),
+                (SectionType::Windows, 1),
+                (SectionType::SearchAndAi, 1),
+            ]
+        );
+
+        // Files without matching applications keep their place
+        manager.update(&items, &[], false, false, 0);
+        assert_eq!(round_trip(&manager), vec![(SectionType::Files, 3)]);
+
+        let mut manager = SectionManager::new(modules, false).with_flat(true);
+        manager.set_file_count(1);
+        manager.update(&items, &[0, 1], false, false, 1);
+        assert_eq!(
+            round_trip(&manager),
+            vec![
+                (SectionType::Results, 2),
+                (SectionType::Files, 1),
+                (SectionType::SearchAndAi, 1),
+            ]
+        );
+    }
 }
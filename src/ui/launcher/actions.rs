@@ -1,19 +1,37 @@
 //! Action handlers for LauncherView.
 //!
-//! Handles confirm, cancel, and go_back actions.
+//! Handles confirm, secondary confirm, cancel, and go_back actions.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use gpui::{Context, Window};
+use gpui::{Context, Entity, ModifiersChangedEvent, Window};
 
+use crate::calculator::record_answer;
 use crate::clipboard::copy_to_clipboard;
 use crate::compositor::Compositor;
-use crate::config::LauncherMode;
-use crate::desktop::launch_application;
-use crate::items::{Executable, ListItem};
+use crate::config::{LauncherMode, config};
+use crate::desktop::{
+    DesktopEntry, edit_desktop_file, launch_application, launch_application_on_gpu,
+};
+use crate::emoji::record_emoji_usage;
+use crate::error::{ClipboardError, ConfirmError};
+use crate::frecency::record_launch;
+use crate::items::{
+    ActionItem, ActionKind, ApplicationItem, Executable, ListItem, window_for_application,
+};
+use crate::query_history::record_query;
+use crate::ui::delegates::SectionType;
+use crate::ui::modes::OnCopied;
 
-use super::state::ViewMode;
-use super::{Cancel, Confirm, GoBack, LauncherView};
+use super::state::{EscapeAction, ViewMode};
+use super::{
+    Cancel, Confirm, ConfirmDiscreteGpu, ConfirmSecondary, CopyInfo, CycleSearchProvider, GoBack,
+    LauncherView, ToggleKeepAbove, ToggleMark,
+};
+
+/// How long the error banner stays visible.
+const CONFIRM_ERROR_DURATION: Duration = Duration::from_secs(5);
 
 impl LauncherView {
     /// Handle confirming the selected item.
@@ -29,29 +47,40 @@ impl LauncherView {
                         .unwrap_or(0),
                 ) {
                     match item {
-                        ListItem::Submenu(submenu) => match submenu.id.as_str() {
-                            "submenu-emojis" => {
-                                self.navigated_into_submenu = true;
-                                self.enter_emoji_mode(window, cx);
-                                return;
-                            }
-                            "submenu-clipboard" => {
+                        ListItem::Submenu(submenu) => {
+                            if let Some(mode) = ViewMode::for_submenu(&submenu) {
                                 self.navigated_into_submenu = true;
-                                self.enter_clipboard_mode(window, cx);
+                                self.enter_view_mode(mode, window, cx);
                                 return;
                             }
-                            "submenu-themes" => {
-                                self.navigated_into_submenu = true;
-                                self.enter_theme_mode(window, cx);
+                            if !submenu.children.is_empty() {
+                                self.submenu_stack.push(submenu);
+                                self.show_current_submenu(window, cx);
                                 return;
                             }
-                            _ => {}
-                        },
+                        }
                         ListItem::Ai(_) => {
                             self.navigated_into_submenu = true;
                             self.enter_ai_mode(window, cx);
                             return;
                         }
+                        ListItem::Suggestion(suggestion) => {
+                            // Fill in the remembered query instead of running anything
+                            let query = suggestion.query;
+                            self.list_state.update(cx, |state, cx| {
+                                state.delegate_mut().set_query(query.clone());
+                                cx.notify();
+                            });
+                            self.input_state.update(cx, |input, cx| {
+                                input.set_value(query, window, cx);
+                            });
+                            return;
+                        }
+                        // Remember searches for suggestions; queries that
+                        // only picked a regular item aren't worth repeating
+                        ListItem::Search(_) => {
+                            record_query(&self.input_state.read(cx).value());
+                        }
                         _ => {}
                     }
                 }
@@ -59,6 +88,7 @@ impl LauncherView {
                 self.list_state.update(cx, |state, _cx| {
                     state.delegate().do_confirm();
                 });
+                self.show_pending_confirm_error(cx);
             }
             ViewMode::EmojiPicker => {
                 if let Some(emoji_state) = self.emoji_mode_handler.as_ref().map(|h| h.list_state())
@@ -67,6 +97,16 @@ impl LauncherView {
                         state.delegate().do_confirm();
                     });
                 }
+                self.show_pending_confirm_error(cx);
+            }
+            ViewMode::GlyphPicker => {
+                if let Some(glyph_state) = self.glyph_mode_handler.as_ref().map(|h| h.list_state())
+                {
+                    glyph_state.update(cx, |state, _cx| {
+                        state.delegate().do_confirm();
+                    });
+                }
+                self.show_pending_confirm_error(cx);
             }
             ViewMode::ClipboardHistory => {
                 if let Some(clipboard_state) =
@@ -76,6 +116,7 @@ impl LauncherView {
                         state.delegate().do_confirm();
                     });
                 }
+                self.show_pending_confirm_error(cx);
             }
             ViewMode::ThemePicker => {
                 if let Some(theme_state) = self.theme_mode_handler.as_ref().map(|h| h.list_state())
@@ -90,14 +131,261 @@ impl LauncherView {
             ViewMode::AiResponse => {
                 // If already in AI mode, then send a new prompt
                 self.update_ai_mode(window, cx);
+                self.show_pending_confirm_error(cx);
+            }
+            ViewMode::CalculatorScratchpad => {
+                self.submit_calculator_input(window, cx);
+            }
+        }
+    }
+
+    /// Launch the selected application on the discrete GPU (ctrl+enter).
+    /// Other items fall back to the regular confirm.
+    pub fn confirm_discrete_gpu(
+        &mut self,
+        _: &ConfirmDiscreteGpu,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode == ViewMode::Main {
+            let delegate = self.list_state.read(cx).delegate();
+            if let Some(ListItem::Application(app)) =
+                delegate.get_item_at(delegate.selected_index().unwrap_or(0))
+            {
+                record_launch(&app.id);
+                match launch_application_on_gpu(&desktop_entry(&app), true) {
+                    Ok(()) => (self.on_hide)(),
+                    Err(e) => {
+                        tracing::warn!(%e, "Failed to launch on the discrete GPU");
+                        if let Ok(mut pending) = self.pending_confirm_error.lock() {
+                            *pending = Some(e.into());
+                        }
+                        self.show_pending_confirm_error(cx);
+                    }
+                }
+                return;
             }
         }
+
+        self.confirm(&Confirm, window, cx);
+    }
+
+    /// Copy the selected item's payload (see `ListItem::copy_payload`).
+    pub fn copy_info(&mut self, _: &CopyInfo, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Main {
+            return;
+        }
+
+        let delegate = self.list_state.read(cx).delegate();
+        let Some(payload) = delegate
+            .get_item_at(delegate.selected_index().unwrap_or(0))
+            .and_then(|item| item.copy_payload())
+        else {
+            return;
+        };
+        self.copy_handler()(copy_to_clipboard(&payload));
+        self.show_pending_confirm_error(cx);
+    }
+
+    /// Toggle whether the selected window is kept above others (ctrl+t),
+    /// where the compositor supports it.
+    pub fn toggle_keep_above(
+        &mut self,
+        _: &ToggleKeepAbove,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Main {
+            return;
+        }
+
+        let delegate = self.list_state.read(cx).delegate();
+        let Some(ListItem::Window(win)) =
+            delegate.get_item_at(delegate.selected_index().unwrap_or(0))
+        else {
+            return;
+        };
+        let Some(on) = win.keep_above_toggle(&self.compositor.capabilities()) else {
+            return;
+        };
+        match self.compositor.set_keep_above(&win.address, on) {
+            Ok(()) => (self.on_hide)(),
+            Err(e) => tracing::warn!(%e, "Failed to toggle keep above"),
+        }
+    }
+
+    /// Switch the selected search item to the next provider (ctrl+p),
+    /// keeping the query.
+    pub fn cycle_search_provider(
+        &mut self,
+        _: &CycleSearchProvider,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Main {
+            return;
+        }
+
+        self.list_state.update(cx, |state, cx| {
+            if state.delegate_mut().cycle_search_provider() {
+                cx.notify();
+            }
+        });
+    }
+
+    /// In switcher mode, confirm the selected window once Alt is released.
+    pub fn modifiers_changed(
+        &mut self,
+        event: &ModifiersChangedEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.mode_state.current_mode() != &LauncherMode::Switcher
+            || self.view_mode != ViewMode::Main
+        {
+            return;
+        }
+
+        // Only a release after Alt was seen held counts, so opening the
+        // switcher without the modifier doesn't confirm immediately
+        if event.modifiers.alt {
+            self.switcher_modifier_held = true;
+        } else if self.switcher_modifier_held {
+            self.switcher_modifier_held = false;
+            self.confirm(&Confirm, window, cx);
+        }
+    }
+
+    /// Handle the secondary confirm (shift+enter).
+    /// Items without a secondary action (see `ListItem::secondary_action_label`)
+    /// fall back to the regular confirm.
+    pub fn confirm_secondary(
+        &mut self,
+        _: &ConfirmSecondary,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode == ViewMode::Main {
+            let delegate = self.list_state.read(cx).delegate();
+            let item = delegate
+                .get_item_at(delegate.selected_index().unwrap_or(0))
+                .filter(|item| item.secondary_action_label().is_some());
+            match item {
+                Some(ListItem::Color(color)) => {
+                    self.copy_handler()(copy_to_clipboard(&color.text_for_clipboard(true)));
+                    self.show_pending_confirm_error(cx);
+                    return;
+                }
+                Some(ListItem::Calculator(calc)) => {
+                    // Offer the result in other representations
+                    self.submenu_stack.push(calc.copy_formats_submenu());
+                    self.show_current_submenu(window, cx);
+                    return;
+                }
+                Some(ListItem::Path(path)) => {
+                    // Reveal the path in the file manager instead of opening it
+                    match path.reveal() {
+                        Ok(()) => (self.on_hide)(),
+                        Err(e) => {
+                            tracing::warn!(%e, "Failed to reveal path");
+                            if let Ok(mut pending) = self.pending_confirm_error.lock() {
+                                *pending = Some(e.into());
+                            }
+                            self.show_pending_confirm_error(cx);
+                        }
+                    }
+                    return;
+                }
+                Some(ListItem::Application(app)) => {
+                    // Edit the desktop entry, or reveal it without an editor
+                    let path = app.desktop_path.clone();
+                    match edit_desktop_file(&path) {
+                        Ok(()) => (self.on_hide)(),
+                        Err(e) => {
+                            tracing::warn!(%e, "Failed to open desktop file");
+                            if let Ok(mut pending) = self.pending_confirm_error.lock() {
+                                *pending = Some(e.into());
+                            }
+                            self.show_pending_confirm_error(cx);
+                        }
+                    }
+                    return;
+                }
+                Some(ListItem::Window(win)) if !win.is_group() => {
+                    // Copy the title or class instead of switching
+                    let result = win.copy_with(config().window_secondary_copy, copy_to_clipboard);
+                    self.copy_handler()(result);
+                    self.show_pending_confirm_error(cx);
+                    return;
+                }
+                Some(ListItem::Window(win)) => {
+                    // Expand the group into its individual windows
+                    let id = win.id.clone();
+                    self.list_state.update(cx, |state, cx| {
+                        state.delegate_mut().expand_window_group(&id);
+                        cx.notify();
+                    });
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        if self.view_mode == ViewMode::EmojiPicker
+            && let Some(emoji_state) = self.emoji_mode_handler.as_ref().map(|h| h.list_state())
+        {
+            // Copy the shortcode or name instead of the glyph
+            let delegate = emoji_state.read(cx).delegate();
+            if let Some(text) =
+                delegate.selected_clipboard_text(true, config().emoji_secondary_copy)
+            {
+                let result = copy_to_clipboard(&text);
+                if result.is_ok()
+                    && let Some(item) = delegate
+                        .selected_index()
+                        .and_then(|index| delegate.get_item_at(index))
+                {
+                    record_emoji_usage(&item.emoji);
+                }
+                self.copy_handler()(result);
+                self.show_pending_confirm_error(cx);
+            }
+            return;
+        }
+
+        self.confirm(&Confirm, window, cx);
+    }
+
+    /// Toggle the batch-copy mark of the selected clipboard entry or emoji.
+    pub fn toggle_mark(&mut self, _: &ToggleMark, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode == ViewMode::EmojiPicker
+            && let Some(emoji_state) = self.emoji_mode_handler.as_ref().map(|h| h.list_state())
+        {
+            emoji_state.update(cx, |state, cx| {
+                state.delegate_mut().toggle_selected_marked();
+                cx.notify();
+            });
+        }
+        if self.view_mode == ViewMode::ClipboardHistory
+            && let Some(clipboard_state) =
+                self.clipboard_mode_handler.as_ref().map(|h| h.list_state())
+        {
+            clipboard_state.update(cx, |state, cx| {
+                state.delegate_mut().toggle_selected_marked();
+                cx.notify();
+            });
+        }
     }
 
     /// Handle cancel action.
     pub fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
         match self.view_mode {
             ViewMode::Main => {
+                // Escape in a nested submenu returns to its parent
+                if self.submenu_stack.escape() == EscapeAction::Pop {
+                    self.show_current_submenu(window, cx);
+                    return;
+                }
                 self.list_state.update(cx, |state, _cx| {
                     state.delegate().do_cancel();
                 });
@@ -116,6 +404,10 @@ impl LauncherView {
 
         match self.view_mode {
             ViewMode::Main => {
+                if self.submenu_stack.escape() == EscapeAction::Pop {
+                    self.show_current_submenu(window, cx);
+                    return;
+                }
                 // Already at main, do nothing (or hide in direct mode)
                 if is_direct_mode {
                     (self.on_hide)();
@@ -125,6 +417,10 @@ impl LauncherView {
                 // In direct emoji mode, hide the launcher
                 (self.on_hide)();
             }
+            ViewMode::GlyphPicker if is_direct_mode => {
+                // In direct icon mode, hide the launcher
+                (self.on_hide)();
+            }
             ViewMode::ClipboardHistory if is_direct_mode => {
                 // In direct clipboard mode, hide the launcher
                 (self.on_hide)();
@@ -138,6 +434,9 @@ impl LauncherView {
             ViewMode::EmojiPicker => {
                 self.exit_emoji_mode(window, cx);
             }
+            ViewMode::GlyphPicker => {
+                self.exit_glyph_mode(window, cx);
+            }
             ViewMode::ClipboardHistory => {
                 self.exit_clipboard_mode(window, cx);
             }
@@ -147,26 +446,166 @@ impl LauncherView {
             ViewMode::AiResponse => {
                 self.exit_ai_mode(window, cx);
             }
+            ViewMode::CalculatorScratchpad => {
+                self.exit_calculator_mode(window, cx);
+            }
         }
     }
 
+    /// Build the confirm callback for the main list.
+    ///
+    /// Hides the launcher on success (copies only with `close_after_copy`).
+    /// Failures keep it open and are stored in `pending` for the error banner.
+    pub(crate) fn confirm_handler(
+        compositor: Arc<dyn Compositor>,
+        applications: Arc<Mutex<Vec<ApplicationItem>>>,
+        on_hide: Arc<dyn Fn() + Send + Sync>,
+        pending: Arc<Mutex<Option<ConfirmError>>>,
+    ) -> impl Fn(&ListItem) + Send + Sync + 'static {
+        move |item| {
+            let result = match applications.lock() {
+                Ok(applications) => Self::handle_item_confirm(item, &compositor, &applications),
+                Err(_) => Self::handle_item_confirm(item, &compositor, &[]),
+            };
+            if let Err(e) = Self::finish_confirm(item, result, config().close_after_copy, &*on_hide)
+            {
+                tracing::warn!(%e, "Failed to confirm item");
+                if let Ok(mut pending) = pending.lock() {
+                    *pending = Some(e);
+                }
+            }
+        }
+    }
+
+    /// Close the launcher after a successful confirm.
+    ///
+    /// Items that copy to the clipboard only close with `close_after_copy`.
+    fn finish_confirm(
+        item: &ListItem,
+        result: Result<(), ConfirmError>,
+        close_after_copy: bool,
+        on_hide: &dyn Fn(),
+    ) -> Result<(), ConfirmError> {
+        result?;
+        let copies = matches!(
+            item,
+            ListItem::Calculator(_)
+                | ListItem::Color(_)
+                | ListItem::Action(ActionItem {
+                    kind: ActionKind::Copy { .. },
+                    ..
+                })
+        );
+        if !copies || close_after_copy {
+            on_hide();
+        }
+        Ok(())
+    }
+
+    /// Build the callback for subviews that copy the selection.
+    pub(crate) fn copy_handler(&self) -> OnCopied {
+        let on_hide = self.on_hide.clone();
+        let pending = self.pending_confirm_error.clone();
+        Arc::new(move |result: Result<(), ClipboardError>| match result {
+            Ok(()) => {
+                if config().close_after_copy {
+                    on_hide();
+                }
+            }
+            Err(e) => {
+                tracing::warn!(%e, "Failed to copy to clipboard");
+                if let Ok(mut pending) = pending.lock() {
+                    *pending = Some(e.into());
+                }
+            }
+        })
+    }
+
+    /// Show errors from confirms in `list_state` that don't go through
+    /// [`Self::confirm`], like mouse clicks.
+    pub(crate) fn observe_confirm_errors<T: 'static>(
+        list_state: &Entity<T>,
+        cx: &mut Context<Self>,
+    ) {
+        cx.observe(list_state, |this, _, cx| {
+            this.show_pending_confirm_error(cx)
+        })
+        .detach();
+    }
+
+    /// Show a launch failure reported by the confirm callback, if any.
+    pub(crate) fn show_pending_confirm_error(&mut self, cx: &mut Context<Self>) {
+        let Some(error) = self
+            .pending_confirm_error
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.take())
+        else {
+            return;
+        };
+
+        self.confirm_error = Some(error);
+        self._confirm_error_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(CONFIRM_ERROR_DURATION).await;
+            let _ = this.update(cx, |this, cx| {
+                this.confirm_error = None;
+                cx.notify();
+            });
+        }));
+        cx.notify();
+    }
+
+    /// Run the network detectors deferred by the last query change once
+    /// their debounce passes. A newer query replaces the task.
+    /// The search and AI section shows a placeholder while they wait.
+    pub(crate) fn schedule_pending_detectors(&mut self, cx: &mut Context<Self>) {
+        let delay = self.list_state.read(cx).delegate().pending_detector_delay();
+        self.list_state.update(cx, |state, cx| {
+            state
+                .delegate_mut()
+                .set_section_loading(SectionType::SearchAndAi, delay.is_some());
+            cx.notify();
+        });
+        let Some(delay) = delay else {
+            self._detector_task = None;
+            return;
+        };
+
+        let list_state = self.list_state.clone();
+        self._detector_task = Some(cx.spawn(async move |_this, cx| {
+            cx.background_executor().timer(delay).await;
+            let _ = list_state.update(cx, |state, cx| {
+                let delegate = state.delegate_mut();
+                delegate.run_pending_detectors();
+                delegate.set_section_loading(SectionType::SearchAndAi, false);
+                cx.notify();
+            });
+        }));
+    }
+
     /// Handle confirming an item (static method for callbacks).
-    pub fn handle_item_confirm(item: &ListItem, compositor: &Arc<dyn Compositor>) {
+    ///
+    /// Returns an error when an application fails to start or a copy fails;
+    /// other failures are logged.
+    pub fn handle_item_confirm(
+        item: &ListItem,
+        compositor: &Arc<dyn Compositor>,
+        applications: &[ApplicationItem],
+    ) -> Result<(), ConfirmError> {
+        // Remember launches of items with stable ids for frecency ranking
+        if item.is_application() || item.is_action() {
+            record_launch(item.id());
+        }
+
         match item {
             ListItem::Application(app) => {
-                // Convert to DesktopEntry and launch
-                let entry = crate::desktop::DesktopEntry::new(
-                    app.id.clone(),
-                    app.name.clone(),
-                    app.exec.clone(),
-                    None,
-                    app.icon_path.clone(),
-                    app.description.clone(),
-                    vec![],
-                    app.terminal,
-                    app.desktop_path.clone(),
-                );
-                let _ = launch_application(&entry);
+                if config().focus_running_apps
+                    && focus_running_window(app, applications, compositor.as_ref())
+                {
+                    return Ok(());
+                }
+                // Some apps are configured to always use the discrete GPU
+                launch_application(&desktop_entry(app))?;
             }
             ListItem::Window(win) => {
                 if let Err(e) = compositor.focus_window(&win.address) {
@@ -174,10 +613,19 @@ impl LauncherView {
                 }
             }
             ListItem::Calculator(calc) => {
-                if let Err(e) = copy_to_clipboard(calc.text_for_clipboard()) {
-                    tracing::warn!(%e, "Failed to copy to clipboard");
+                copy_to_clipboard(calc.text_for_clipboard())?;
+                if let Some(value) = &calc.clipboard_result {
+                    record_answer(value);
                 }
             }
+            ListItem::Color(color) => {
+                copy_to_clipboard(&color.text_for_clipboard(false))?;
+            }
+            ListItem::Path(path) => path.open()?,
+            ListItem::Action(ActionItem {
+                kind: ActionKind::Copy { text },
+                ..
+            }) => copy_to_clipboard(text)?,
             ListItem::Action(act) => {
                 if let Err(e) = act.execute() {
                     tracing::warn!(%e, "Failed to execute action");
@@ -196,10 +644,112 @@ impl LauncherView {
                 // AI items would trigger AI mode
                 tracing::debug!("AI item selected");
             }
+            ListItem::Suggestion(suggestion) => {
+                // Suggestions fill in the query in LauncherView::confirm
+                tracing::debug!(query = %suggestion.query, "Suggestion selected");
+            }
             ListItem::Theme(_theme) => {
                 // Theme items are handled in theme mode
                 tracing::debug!("Theme item selected");
             }
         }
+
+        Ok(())
+    }
+}
+
+/// Focus an open window of the application. Returns false if there is
+/// none, so the caller launches it instead.
+fn focus_running_window(
+    app: &ApplicationItem,
+    applications: &[ApplicationItem],
+    compositor: &dyn Compositor,
+) -> bool {
+    let windows = compositor.list_windows().unwrap_or_default();
+    let Some(window) = window_for_application(app, applications, &windows) else {
+        return false;
+    };
+    match compositor.focus_window(&window.address) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!(%e, "Failed to focus running window, launching instead");
+            false
+        }
+    }
+}
+
+/// Convert an application item to the desktop entry used for launching.
+fn desktop_entry(app: &ApplicationItem) -> DesktopEntry {
+    DesktopEntry::new(
+        app.id.clone(),
+        app.name.clone(),
+        app.exec.clone(),
+        None,
+        app.icon_path.clone(),
+        app.description.clone(),
+        vec![],
+        app.terminal,
+        app.desktop_path.clone(),
+    )
+    .with_startup_notify(app.startup_notify)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ProcessError;
+    use crate::items::CalculatorItem;
+    use crate::test_utils::mock_application;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn calculator() -> ListItem {
+        ListItem::Calculator(CalculatorItem {
+            id: "calc".to_string(),
+            expression: "2+2".to_string(),
+            display_result: "4".to_string(),
+            clipboard_result: Some("4".to_string()),
+            is_error: false,
+            delta: None,
+            bindings: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_calculator_confirm_closes_after_copy() {
+        let closed = AtomicUsize::new(0);
+        let on_hide = || {
+            closed.fetch_add(1, Ordering::SeqCst);
+        };
+
+        assert!(LauncherView::finish_confirm(&calculator(), Ok(()), true, &on_hide).is_ok());
+        assert_eq!(closed.load(Ordering::SeqCst), 1);
+
+        assert!(LauncherView::finish_confirm(&calculator(), Ok(()), false, &on_hide).is_ok());
+        assert_eq!(closed.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_failed_confirm_keeps_launcher_open() {
+        let closed = AtomicUsize::new(0);
+        let on_hide = || {
+            closed.fetch_add(1, Ordering::SeqCst);
+        };
+
+        let copy_failed = Err(ClipboardError::AccessFailed("no display".to_string()).into());
+        let result = LauncherView::finish_confirm(&calculator(), copy_failed, true, &on_hide);
+        assert!(matches!(result, Err(ConfirmError::Copy(_))));
+
+        let app = ListItem::Application(mock_application("Firefox"));
+        let launch_failed = Err(crate::error::LaunchError {
+            app: "Firefox".to_string(),
+            source: ProcessError::EmptyCommand,
+        }
+        .into());
+        assert!(LauncherView::finish_confirm(&app, launch_failed, true, &on_hide).is_err());
+        assert_eq!(closed.load(Ordering::SeqCst), 0);
+
+        // Launching closes regardless of the copy setting
+        assert!(LauncherView::finish_confirm(&app, Ok(()), false, &on_hide).is_ok());
+        assert_eq!(closed.load(Ordering::SeqCst), 1);
     }
 }
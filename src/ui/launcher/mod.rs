@@ -19,17 +19,23 @@
 //!
 //! - **Main** - Combined view showing applications, windows, calculator, etc.
 //! - **EmojiPicker** - Grid-based emoji selection
+//! - **GlyphPicker** - Grid-based Nerd Font icon selection
 //! - **ClipboardHistory** - List of recent clipboard entries with preview
 //! - **AiResponse** - Streaming AI chat interface
 //! - **ThemePicker** - Theme selection with live preview
+//! - **CalculatorScratchpad** - Running history of evaluated expressions
 //! - **Combined** - Customizable combined view with module ordering
 //!
 //! # Key Bindings
 //!
 //! - `Up/Down` - Navigate items
-//! - `Tab/Shift+Tab` - Grid navigation (emoji mode)
+//! - `Tab/Shift+Tab` - Grid navigation (emoji and icon modes)
 //! - `Ctrl+Tab/Ctrl+Shift+Tab` - Switch between modes
 //! - `Enter` - Execute selected item
+//! - `Alt+Tab/Alt+Shift+Tab` - Cycle windows in switcher mode; releasing `Alt` confirms
+//! - `Ctrl+Space` - Mark clipboard entries or emoji for batch copy
+//! - `Ctrl+Shift+C` - Copy the selected item's command, title, result or URL
+//! - `Ctrl+P` - Switch the selected web search to the next provider
 //! - `Escape` - Hide launcher or go back
 //! - `Backspace` (empty input) - Return to previous mode
 
@@ -39,20 +45,25 @@ mod navigation;
 mod render;
 mod state;
 
-pub use state::{ModeState, ViewMode};
+pub use state::{EscapeAction, ModeState, SubmenuStack, ViewMode, ViewSelections};
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
-use gpui::{App, AppContext, Context, Entity, FocusHandle, Focusable, KeyBinding, Window, actions};
+use gpui::{
+    App, AppContext, Context, Entity, FocusHandle, Focusable, KeyBinding, Task, Window, actions,
+};
 use gpui_component::input::{InputEvent, InputState};
 use gpui_component::list::ListState;
 
-use crate::compositor::Compositor;
+use crate::compositor::{Compositor, take_compositor_notice};
 use crate::config::{ConfigModule, LauncherMode, get_combined_modules};
-use crate::items::ListItem;
+use crate::error::ConfirmError;
+use crate::items::{ApplicationItem, ListItem};
 use crate::ui::delegates::ItemListDelegate;
 use crate::ui::modes::{
-    AiModeAccess, AiModeHandler, ClipboardModeHandler, EmojiModeHandler, ThemeModeHandler,
+    AiModeAccess, AiModeHandler, CalculatorModeHandler, ClipboardModeHandler, EmojiModeHandler,
+    GlyphModeHandler, ThemeModeHandler,
 };
 use crate::ui::theme::LauncherTheme;
 
@@ -65,10 +76,16 @@ actions!(
         SelectTab,
         SelectTabPrev,
         Confirm,
+        ConfirmSecondary,
+        ConfirmDiscreteGpu,
+        CopyInfo,
+        ToggleKeepAbove,
+        CycleSearchProvider,
         Cancel,
         GoBack,
         SwitchModeNext,
-        SwitchModePrev
+        SwitchModePrev,
+        ToggleMark
     ]
 );
 
@@ -80,10 +97,18 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("tab", SelectTab, Some("LauncherView")),
         KeyBinding::new("shift-tab", SelectTabPrev, Some("LauncherView")),
         KeyBinding::new("enter", Confirm, Some("LauncherView")),
+        KeyBinding::new("shift-enter", ConfirmSecondary, Some("LauncherView")),
+        KeyBinding::new("ctrl-enter", ConfirmDiscreteGpu, Some("LauncherView")),
         KeyBinding::new("escape", Cancel, Some("LauncherView")),
         KeyBinding::new("backspace", GoBack, Some("LauncherView")),
         KeyBinding::new("ctrl-tab", SwitchModeNext, Some("LauncherView")),
         KeyBinding::new("ctrl-shift-tab", SwitchModePrev, Some("LauncherView")),
+        KeyBinding::new("alt-tab", SelectTab, Some("LauncherView")),
+        KeyBinding::new("alt-shift-tab", SelectTabPrev, Some("LauncherView")),
+        KeyBinding::new("ctrl-space", ToggleMark, Some("LauncherView")),
+        KeyBinding::new("ctrl-shift-c", CopyInfo, Some("LauncherView")),
+        KeyBinding::new("ctrl-t", ToggleKeepAbove, Some("LauncherView")),
+        KeyBinding::new("ctrl-p", CycleSearchProvider, Some("LauncherView")),
     ]);
 }
 
@@ -95,20 +120,31 @@ pub struct LauncherView {
     pub(crate) mode_state: ModeState,
     /// Whether we navigated into a submenu from combined view (vs direct mode)
     pub(crate) navigated_into_submenu: bool,
+    /// Nested submenus opened in the main list
+    pub(crate) submenu_stack: SubmenuStack,
+    /// Selections of the picker views, restored when they are re-entered
+    pub(crate) view_selections: ViewSelections,
     /// Main list state
     pub(crate) list_state: Entity<ListState<ItemListDelegate>>,
     /// Original items (for recreating filtered delegates)
     pub(crate) original_items: Vec<ListItem>,
+    /// Applications among the original items, for matching windows to
+    /// applications in confirm callbacks
+    pub(crate) applications: Arc<Mutex<Vec<ApplicationItem>>>,
     /// Compositor reference (for item confirm callbacks)
     pub(crate) compositor: Arc<dyn Compositor>,
     /// Emoji mode handler (created on demand)
     pub(crate) emoji_mode_handler: Option<EmojiModeHandler>,
+    /// Icon glyph mode handler (created on demand)
+    pub(crate) glyph_mode_handler: Option<GlyphModeHandler>,
     /// Clipboard mode handler (created on demand)
     pub(crate) clipboard_mode_handler: Option<ClipboardModeHandler>,
     /// AI mode handler (created on demand)
     pub(crate) ai_mode_handler: Option<AiModeHandler>,
     /// Theme mode handler (created on demand)
     pub(crate) theme_mode_handler: Option<ThemeModeHandler>,
+    /// Calculator scratchpad handler (created on demand)
+    pub(crate) calculator_mode_handler: Option<CalculatorModeHandler>,
     /// Current theme (for live preview)
     pub(crate) current_theme: LauncherTheme,
     /// Theme preview subscription
@@ -119,6 +155,22 @@ pub struct LauncherView {
     pub(crate) focus_handle: FocusHandle,
     /// Callback to hide the launcher
     pub(crate) on_hide: Arc<dyn Fn() + Send + Sync>,
+    /// When the launcher was shown (to ignore focus changes while it opens)
+    pub(crate) shown_at: Instant,
+    /// Whether Alt has been seen held in switcher mode (releasing it confirms)
+    pub(crate) switcher_modifier_held: bool,
+    /// Launch or copy failure reported by a confirm callback, not yet shown
+    pub(crate) pending_confirm_error: Arc<Mutex<Option<ConfirmError>>>,
+    /// Launch or copy failure currently shown in the error banner
+    pub(crate) confirm_error: Option<ConfirmError>,
+    /// Task that dismisses the error banner
+    pub(crate) _confirm_error_task: Option<Task<()>>,
+    /// One-time notice about unavailable window switching
+    pub(crate) compositor_notice: Option<String>,
+    /// Task that runs network detectors once typing pauses
+    pub(crate) _detector_task: Option<Task<()>>,
+    /// Whether the launcher is fading out before its window is removed
+    pub(crate) closing: bool,
 }
 
 impl LauncherView {
@@ -134,24 +186,25 @@ impl LauncherView {
         let on_hide = Arc::new(on_hide);
         let mode_state = ModeState::new(modes);
 
-        // Determine modules to show based on current mode
-        let modules_for_delegate = Self::modules_for_mode(mode_state.current_mode());
-
         // Create main delegate with callbacks
-        let mut delegate = ItemListDelegate::new(items.clone(), modules_for_delegate);
-        let on_hide_for_confirm = on_hide.clone();
-        let compositor_for_confirm = compositor.clone();
-
-        delegate.set_on_confirm(move |item| {
-            Self::handle_item_confirm(item, &compositor_for_confirm);
-            on_hide_for_confirm();
-        });
+        let mut delegate = Self::delegate_for_mode(mode_state.current_mode(), items.clone());
+        let pending_confirm_error = Arc::new(Mutex::new(None));
+        let applications = Arc::new(Mutex::new(application_items(&items)));
+        delegate.set_on_confirm(Self::confirm_handler(
+            compositor.clone(),
+            applications.clone(),
+            on_hide.clone(),
+            pending_confirm_error.clone(),
+        ));
 
         let on_hide_for_cancel = on_hide.clone();
         delegate.set_on_cancel(move || on_hide_for_cancel());
 
         let list_state = cx.new(|cx| ListState::new(delegate, window, cx));
 
+        // Pick up launch and copy failures from mouse confirms
+        Self::observe_confirm_errors(&list_state, cx);
+
         // Create input state with placeholder based on initial mode
         let initial_placeholder = Self::placeholder_for_mode(mode_state.current_mode());
         let input_state = cx.new(|cx| InputState::new(window, cx).placeholder(initial_placeholder));
@@ -160,7 +213,7 @@ impl LauncherView {
         let list_state_for_subscribe = list_state.clone();
         cx.subscribe(
             &input_state,
-            move |_this, input: Entity<InputState>, event: &InputEvent, cx: &mut Context<Self>| {
+            move |this, input: Entity<InputState>, event: &InputEvent, cx: &mut Context<Self>| {
                 if let InputEvent::Change = event {
                     let text = input.read(cx).value().to_string();
                     // Update the delegate's query directly (synchronous filtering)
@@ -172,6 +225,7 @@ impl LauncherView {
                             cx.notify();
                         },
                     );
+                    this.schedule_pending_detectors(cx);
                 }
             },
         )
@@ -181,8 +235,11 @@ impl LauncherView {
 
         // Hide when the view loses focus
         let on_hide_for_blur = on_hide.clone();
-        cx.on_blur(&focus_handle, window, move |_this, _window, _cx| {
-            on_hide_for_blur();
+        cx.on_blur(&focus_handle, window, move |this, _window, _cx| {
+            let enabled = crate::config::config().close_on_focus_loss;
+            if state::should_close_on_focus_loss(enabled, this.shown_at.elapsed()) {
+                on_hide_for_blur();
+            }
         })
         .detach();
 
@@ -190,6 +247,7 @@ impl LauncherView {
         let initial_view_mode = match mode_state.current_mode() {
             LauncherMode::Combined => ViewMode::Main,
             LauncherMode::Emojis => ViewMode::EmojiPicker,
+            LauncherMode::Icons => ViewMode::GlyphPicker,
             LauncherMode::Clipboard => ViewMode::ClipboardHistory,
             LauncherMode::Themes => ViewMode::ThemePicker,
             LauncherMode::Ai => ViewMode::AiResponse,
@@ -202,18 +260,31 @@ impl LauncherView {
             view_mode: initial_view_mode,
             mode_state,
             navigated_into_submenu: false,
+            submenu_stack: SubmenuStack::default(),
+            view_selections: ViewSelections::default(),
             list_state,
             original_items: items,
+            applications,
             compositor,
             emoji_mode_handler: None,
+            glyph_mode_handler: None,
             clipboard_mode_handler: None,
             ai_mode_handler: None,
             theme_mode_handler: None,
+            calculator_mode_handler: None,
             current_theme: crate::config::load_configured_theme(),
             _theme_preview_subscription: None,
             input_state,
             focus_handle,
             on_hide,
+            shown_at: Instant::now(),
+            switcher_modifier_held: false,
+            pending_confirm_error,
+            confirm_error: None,
+            _confirm_error_task: None,
+            compositor_notice: take_compositor_notice(),
+            _detector_task: None,
+            closing: false,
         };
 
         // Initialize mode handler if starting in a direct mode
@@ -222,6 +293,14 @@ impl LauncherView {
         launcher
     }
 
+    /// Create the main delegate for a launcher mode.
+    pub fn delegate_for_mode(mode: &LauncherMode, items: Vec<ListItem>) -> ItemListDelegate {
+        match mode {
+            LauncherMode::Switcher => ItemListDelegate::window_switcher(items),
+            _ => ItemListDelegate::new(items, Self::modules_for_mode(mode)),
+        }
+    }
+
     /// Get the modules to show for a given launcher mode.
     pub fn modules_for_mode(mode: &LauncherMode) -> Vec<ConfigModule> {
         match mode {
@@ -229,12 +308,13 @@ impl LauncherView {
             // Modes with dedicated handlers - return combined modules
             // (they don't use the main delegate anyway)
             LauncherMode::Emojis
+            | LauncherMode::Icons
             | LauncherMode::Clipboard
             | LauncherMode::Themes
             | LauncherMode::Ai => get_combined_modules(),
             // Single-module modes - return just that module
             LauncherMode::Applications => vec![ConfigModule::Applications],
-            LauncherMode::Windows => vec![ConfigModule::Windows],
+            LauncherMode::Windows | LauncherMode::Switcher => vec![ConfigModule::Windows],
             LauncherMode::Actions => vec![ConfigModule::Actions],
             LauncherMode::Search => vec![ConfigModule::Search],
             LauncherMode::Calculator => vec![ConfigModule::Calculator],
@@ -247,8 +327,10 @@ impl LauncherView {
             LauncherMode::Combined => "Search anything...",
             LauncherMode::Applications => "Search applications...",
             LauncherMode::Windows => "Search windows...",
+            LauncherMode::Switcher => "Switch to window...",
             LauncherMode::Actions => "Search actions...",
             LauncherMode::Emojis => "Search emojis...",
+            LauncherMode::Icons => "Search icons...",
             LauncherMode::Clipboard => "Search clipboard...",
             LauncherMode::Themes => "Search themes...",
             LauncherMode::Ai => "Ask AI...",
@@ -264,6 +346,9 @@ impl LauncherView {
             LauncherMode::Emojis => {
                 self.enter_emoji_mode(window, cx);
             }
+            LauncherMode::Icons => {
+                self.enter_glyph_mode(window, cx);
+            }
             LauncherMode::Clipboard => {
                 self.enter_clipboard_mode(window, cx);
             }
@@ -274,6 +359,13 @@ impl LauncherView {
         }
     }
 
+    /// Fade the launcher out. The daemon removes the window once the fade
+    /// is over.
+    pub fn start_closing(&mut self, cx: &mut Context<Self>) {
+        self.closing = true;
+        cx.notify();
+    }
+
     /// Refresh the current theme from the global state.
     /// Called when the theme is changed via IPC while the window is open.
     pub fn refresh_theme(&mut self, cx: &mut Context<Self>) {
@@ -294,12 +386,42 @@ impl LauncherView {
             .into_iter()
             .map(ListItem::Application)
             .collect();
+        self.sync_applications();
 
         // Recreate the delegate (reuses existing mode_switching.rs logic)
         self.recreate_delegate_for_mode(window, cx);
         cx.notify();
     }
 
+    /// Share the current applications with the confirm callbacks.
+    fn sync_applications(&self) {
+        if let Ok(mut applications) = self.applications.lock() {
+            *applications = application_items(&self.original_items);
+        }
+    }
+
+    /// Swap in reloaded applications without resetting the view.
+    ///
+    /// Open windows are kept, and the main delegate re-runs the current
+    /// query against the new items. Called after a SIGHUP reload.
+    pub fn reload_items(
+        &mut self,
+        applications: Vec<crate::items::ApplicationItem>,
+        cx: &mut Context<Self>,
+    ) {
+        self.original_items.retain(|item| !item.is_application());
+        self.original_items
+            .extend(applications.into_iter().map(ListItem::Application));
+        self.sync_applications();
+
+        let items = self.original_items.clone();
+        self.list_state.update(cx, |state, cx| {
+            state.delegate_mut().set_items(items);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
     /// Focus the launcher input.
     pub fn focus(&self, window: &mut Window, cx: &mut Context<Self>) {
         self.input_state.update(cx, |input: &mut InputState, cx| {
@@ -331,3 +453,14 @@ impl AiModeAccess for LauncherView {
         self.ai_mode_handler.as_mut()
     }
 }
+
+/// Collect the applications among the launcher items.
+fn application_items(items: &[ListItem]) -> Vec<ApplicationItem> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            ListItem::Application(app) => Some(app.clone()),
+            _ => None,
+        })
+        .collect()
+}
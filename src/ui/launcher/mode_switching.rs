@@ -1,15 +1,19 @@
 //! Mode switching and management for LauncherView.
 //!
-//! Handles entering/exiting different modes (emoji, clipboard, AI, theme)
+//! Handles entering/exiting different modes (emoji, icons, clipboard, AI, theme,
+//! calculator)
 //! and switching between launcher modes.
 
 use std::sync::Arc;
 
 use gpui::{Context, IntoElement, Window};
 
-use crate::config::LauncherMode;
+use crate::config::{ConfigModule, LauncherMode};
 use crate::ui::delegates::ItemListDelegate;
-use crate::ui::modes::{AiModeHandler, ClipboardModeHandler, EmojiModeHandler, ThemeModeHandler};
+use crate::ui::modes::{
+    AiModeHandler, CalculatorModeHandler, ClipboardModeHandler, EmojiModeHandler, GlyphModeHandler,
+    ThemeModeHandler, base,
+};
 use crate::ui::theme::LauncherTheme;
 use gpui_component::list::ListState;
 
@@ -17,16 +21,37 @@ use super::state::ViewMode;
 use super::{LauncherView, SwitchModeNext, SwitchModePrev};
 
 impl LauncherView {
+    /// Enter the view for a submenu or launcher mode.
+    pub fn enter_view_mode(&mut self, mode: ViewMode, window: &mut Window, cx: &mut Context<Self>) {
+        match mode {
+            ViewMode::EmojiPicker => self.enter_emoji_mode(window, cx),
+            ViewMode::GlyphPicker => self.enter_glyph_mode(window, cx),
+            ViewMode::ClipboardHistory => self.enter_clipboard_mode(window, cx),
+            ViewMode::ThemePicker => self.enter_theme_mode(window, cx),
+            ViewMode::AiResponse => self.enter_ai_mode(window, cx),
+            ViewMode::CalculatorScratchpad => self.enter_calculator_mode(window, cx),
+            ViewMode::Main => {}
+        }
+    }
+
     /// Enter emoji picker mode.
     pub fn enter_emoji_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         // Create emoji mode handler
-        let handler = EmojiModeHandler::new(&self.input_state, self.on_hide.clone(), window, cx);
+        let handler = EmojiModeHandler::new(&self.input_state, self.copy_handler(), window, cx);
+        Self::observe_confirm_errors(handler.list_state(), cx);
 
         // Update input
         self.input_state.update(cx, |input, cx| {
             EmojiModeHandler::setup_input(input, window, cx);
         });
 
+        if let Some(index) = self.view_selections.restore(
+            ViewMode::EmojiPicker,
+            base::item_count(handler.list_state(), cx),
+        ) {
+            base::select(handler.list_state(), index, window, cx);
+        }
+
         self.emoji_mode_handler = Some(handler);
         self.view_mode = ViewMode::EmojiPicker;
         cx.notify();
@@ -34,6 +59,7 @@ impl LauncherView {
 
     /// Exit emoji picker mode.
     pub fn exit_emoji_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.remember_selection(cx);
         self.view_mode = ViewMode::Main;
         self.emoji_mode_handler = None;
         self.navigated_into_submenu = false;
@@ -42,17 +68,58 @@ impl LauncherView {
         cx.notify();
     }
 
+    /// Enter icon glyph picker mode.
+    pub fn enter_glyph_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Create glyph mode handler
+        let handler = GlyphModeHandler::new(&self.input_state, self.copy_handler(), window, cx);
+        Self::observe_confirm_errors(handler.list_state(), cx);
+
+        // Update input
+        self.input_state.update(cx, |input, cx| {
+            GlyphModeHandler::setup_input(input, window, cx);
+        });
+
+        if let Some(index) = self.view_selections.restore(
+            ViewMode::GlyphPicker,
+            base::item_count(handler.list_state(), cx),
+        ) {
+            base::select(handler.list_state(), index, window, cx);
+        }
+
+        self.glyph_mode_handler = Some(handler);
+        self.view_mode = ViewMode::GlyphPicker;
+        cx.notify();
+    }
+
+    /// Exit icon glyph picker mode.
+    pub fn exit_glyph_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.remember_selection(cx);
+        self.view_mode = ViewMode::Main;
+        self.glyph_mode_handler = None;
+        self.navigated_into_submenu = false;
+
+        self.reset_search(window, cx);
+        cx.notify();
+    }
+
     /// Enter clipboard history mode.
     pub fn enter_clipboard_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         // Create clipboard mode handler
-        let handler =
-            ClipboardModeHandler::new(&self.input_state, self.on_hide.clone(), window, cx);
+        let handler = ClipboardModeHandler::new(&self.input_state, self.copy_handler(), window, cx);
+        Self::observe_confirm_errors(handler.list_state(), cx);
 
         // Update input
         self.input_state.update(cx, |input, cx| {
             ClipboardModeHandler::setup_input(input, window, cx);
         });
 
+        if let Some(index) = self.view_selections.restore(
+            ViewMode::ClipboardHistory,
+            base::item_count(handler.list_state(), cx),
+        ) {
+            base::select(handler.list_state(), index, window, cx);
+        }
+
         self.clipboard_mode_handler = Some(handler);
         self.view_mode = ViewMode::ClipboardHistory;
         cx.notify();
@@ -60,6 +127,7 @@ impl LauncherView {
 
     /// Exit clipboard history mode.
     pub fn exit_clipboard_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.remember_selection(cx);
         self.view_mode = ViewMode::Main;
         self.clipboard_mode_handler = None;
         self.navigated_into_submenu = false;
@@ -198,6 +266,46 @@ impl LauncherView {
         cx.notify();
     }
 
+    /// Enter calculator scratchpad mode.
+    pub fn enter_calculator_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.calculator_mode_handler = Some(CalculatorModeHandler::new(self.copy_handler()));
+
+        self.input_state.update(cx, |input, cx| {
+            CalculatorModeHandler::setup_input(input, window, cx);
+        });
+
+        self.view_mode = ViewMode::CalculatorScratchpad;
+        cx.notify();
+    }
+
+    /// Evaluate the input in the scratchpad, or copy the selected line
+    /// when the input is empty.
+    pub fn submit_calculator_input(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(handler) = self.calculator_mode_handler.as_mut() else {
+            return;
+        };
+        let expression = self.input_state.read(cx).value().to_string();
+        if handler.submit(&expression) {
+            self.input_state.update(cx, |input, cx| {
+                CalculatorModeHandler::clear_input(input, window, cx);
+            });
+        } else {
+            handler.copy_selected();
+            self.show_pending_confirm_error(cx);
+        }
+        cx.notify();
+    }
+
+    /// Exit calculator scratchpad mode.
+    pub fn exit_calculator_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.view_mode = ViewMode::Main;
+        self.calculator_mode_handler = None;
+        self.navigated_into_submenu = false;
+
+        self.reset_search(window, cx);
+        cx.notify();
+    }
+
     /// Render clipboard preview panel.
     pub fn render_clipboard_preview(
         &self,
@@ -252,6 +360,9 @@ impl LauncherView {
             LauncherMode::Emojis => {
                 self.enter_emoji_mode(window, cx);
             }
+            LauncherMode::Icons => {
+                self.enter_glyph_mode(window, cx);
+            }
             LauncherMode::Clipboard => {
                 self.enter_clipboard_mode(window, cx);
             }
@@ -272,18 +383,45 @@ impl LauncherView {
 
     /// Recreate the main delegate for the current mode with appropriate module filtering.
     pub fn recreate_delegate_for_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let modules = Self::modules_for_mode(self.mode_state.current_mode());
+        self.submenu_stack.clear();
+        let delegate =
+            Self::delegate_for_mode(self.mode_state.current_mode(), self.original_items.clone());
+        self.set_main_delegate(delegate, window, cx);
+    }
 
-        // Create new delegate with filtered modules
-        let mut delegate = ItemListDelegate::new(self.original_items.clone(), modules);
+    /// Show the innermost open submenu in the main list, or the root items
+    /// when the submenu stack is empty.
+    pub fn show_current_submenu(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        match self.submenu_stack.current() {
+            Some(submenu) => {
+                let mut modules = Self::modules_for_mode(self.mode_state.current_mode());
+                // Children are commands, also in single-module modes
+                if !modules.contains(&ConfigModule::Actions) {
+                    modules.push(ConfigModule::Actions);
+                }
+                let delegate = ItemListDelegate::nested(submenu.children.clone(), modules);
+                self.set_main_delegate(delegate, window, cx);
+            }
+            None => self.recreate_delegate_for_mode(window, cx),
+        }
+        self.reset_search(window, cx);
+        cx.notify();
+    }
 
+    /// Install a delegate in the main list with the standard callbacks.
+    fn set_main_delegate(
+        &mut self,
+        mut delegate: ItemListDelegate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         // Set up callbacks
-        let on_hide = self.on_hide.clone();
-        let compositor = self.compositor.clone();
-        delegate.set_on_confirm(move |item| {
-            Self::handle_item_confirm(item, &compositor);
-            on_hide();
-        });
+        delegate.set_on_confirm(Self::confirm_handler(
+            self.compositor.clone(),
+            self.applications.clone(),
+            self.on_hide.clone(),
+            self.pending_confirm_error.clone(),
+        ));
 
         let on_hide_for_cancel = self.on_hide.clone();
         delegate.set_on_cancel(move || on_hide_for_cancel());
@@ -294,12 +432,38 @@ impl LauncherView {
         });
     }
 
+    /// Remember the selection of the picker view being left.
+    fn remember_selection(&mut self, cx: &mut Context<Self>) {
+        let selection = match self.view_mode {
+            ViewMode::EmojiPicker => self
+                .emoji_mode_handler
+                .as_ref()
+                .and_then(|h| base::saved_selection(h.list_state(), cx)),
+            ViewMode::GlyphPicker => self
+                .glyph_mode_handler
+                .as_ref()
+                .and_then(|h| base::saved_selection(h.list_state(), cx)),
+            ViewMode::ClipboardHistory => self
+                .clipboard_mode_handler
+                .as_ref()
+                .and_then(|h| base::saved_selection(h.list_state(), cx)),
+            ViewMode::Main
+            | ViewMode::AiResponse
+            | ViewMode::ThemePicker
+            | ViewMode::CalculatorScratchpad => return,
+        };
+        self.view_selections.save(self.view_mode, selection);
+    }
+
     /// Clean up all mode handlers.
-    pub fn cleanup_mode_handlers(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
+    pub fn cleanup_mode_handlers(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        self.remember_selection(cx);
         self.emoji_mode_handler = None;
+        self.glyph_mode_handler = None;
         self.clipboard_mode_handler = None;
         self.ai_mode_handler = None;
         self.theme_mode_handler = None;
+        self.calculator_mode_handler = None;
         self._theme_preview_subscription = None;
     }
 }
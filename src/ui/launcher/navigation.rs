@@ -2,8 +2,11 @@
 //!
 //! Handles up/down/tab navigation across all view modes.
 
-use gpui::{Context, ScrollStrategy, Window};
+use gpui::{Context, Entity, ScrollStrategy, Window};
 use gpui_component::IndexPath;
+use gpui_component::list::ListState;
+
+use crate::ui::delegates::{GridDelegate, GridItem};
 
 use super::state::ViewMode;
 use super::{LauncherView, SelectNext, SelectPrev, SelectTab, SelectTabPrev};
@@ -25,23 +28,18 @@ impl LauncherView {
                     cx.notify();
                 });
             }
-            ViewMode::EmojiPicker => {
-                if let Some(emoji_state) = self.emoji_mode_handler.as_ref().map(|h| h.list_state())
-                {
-                    emoji_state.update(cx, |state, cx| {
-                        state.delegate_mut().select_down();
-                        if let Some(row) = state.delegate().selected_row() {
-                            state.scroll_to_item(
-                                IndexPath::new(row),
-                                ScrollStrategy::Top,
-                                window,
-                                cx,
-                            );
-                        }
-                        cx.notify();
-                    });
-                }
-            }
+            ViewMode::EmojiPicker => Self::move_grid_selection(
+                self.emoji_mode_handler.as_ref().map(|h| h.list_state()),
+                GridDelegate::select_down,
+                window,
+                cx,
+            ),
+            ViewMode::GlyphPicker => Self::move_grid_selection(
+                self.glyph_mode_handler.as_ref().map(|h| h.list_state()),
+                GridDelegate::select_down,
+                window,
+                cx,
+            ),
             ViewMode::ClipboardHistory => {
                 if let Some(clipboard_state) =
                     self.clipboard_mode_handler.as_ref().map(|h| h.list_state())
@@ -80,6 +78,12 @@ impl LauncherView {
             ViewMode::AiResponse => {
                 // No navigation in AI response mode
             }
+            ViewMode::CalculatorScratchpad => {
+                if let Some(handler) = self.calculator_mode_handler.as_mut() {
+                    handler.select_next();
+                    cx.notify();
+                }
+            }
         }
     }
 
@@ -99,23 +103,18 @@ impl LauncherView {
                     cx.notify();
                 });
             }
-            ViewMode::EmojiPicker => {
-                if let Some(emoji_state) = self.emoji_mode_handler.as_ref().map(|h| h.list_state())
-                {
-                    emoji_state.update(cx, |state, cx| {
-                        state.delegate_mut().select_up();
-                        if let Some(row) = state.delegate().selected_row() {
-                            state.scroll_to_item(
-                                IndexPath::new(row),
-                                ScrollStrategy::Top,
-                                window,
-                                cx,
-                            );
-                        }
-                        cx.notify();
-                    });
-                }
-            }
+            ViewMode::EmojiPicker => Self::move_grid_selection(
+                self.emoji_mode_handler.as_ref().map(|h| h.list_state()),
+                GridDelegate::select_up,
+                window,
+                cx,
+            ),
+            ViewMode::GlyphPicker => Self::move_grid_selection(
+                self.glyph_mode_handler.as_ref().map(|h| h.list_state()),
+                GridDelegate::select_up,
+                window,
+                cx,
+            ),
             ViewMode::ClipboardHistory => {
                 if let Some(clipboard_state) =
                     self.clipboard_mode_handler.as_ref().map(|h| h.list_state())
@@ -154,6 +153,12 @@ impl LauncherView {
             ViewMode::AiResponse => {
                 // No navigation in AI response mode
             }
+            ViewMode::CalculatorScratchpad => {
+                if let Some(handler) = self.calculator_mode_handler.as_mut() {
+                    handler.select_prev();
+                    cx.notify();
+                }
+            }
         }
     }
 
@@ -178,23 +183,18 @@ impl LauncherView {
                     cx.notify();
                 });
             }
-            ViewMode::EmojiPicker => {
-                if let Some(emoji_state) = self.emoji_mode_handler.as_ref().map(|h| h.list_state())
-                {
-                    emoji_state.update(cx, |state, cx| {
-                        state.delegate_mut().select_right();
-                        if let Some(row) = state.delegate().selected_row() {
-                            state.scroll_to_item(
-                                IndexPath::new(row),
-                                ScrollStrategy::Top,
-                                window,
-                                cx,
-                            );
-                        }
-                        cx.notify();
-                    });
-                }
-            }
+            ViewMode::EmojiPicker => Self::move_grid_selection(
+                self.emoji_mode_handler.as_ref().map(|h| h.list_state()),
+                GridDelegate::select_right,
+                window,
+                cx,
+            ),
+            ViewMode::GlyphPicker => Self::move_grid_selection(
+                self.glyph_mode_handler.as_ref().map(|h| h.list_state()),
+                GridDelegate::select_right,
+                window,
+                cx,
+            ),
             ViewMode::ClipboardHistory => {
                 if let Some(clipboard_state) =
                     self.clipboard_mode_handler.as_ref().map(|h| h.list_state())
@@ -233,6 +233,12 @@ impl LauncherView {
             ViewMode::AiResponse => {
                 // No navigation in AI response mode
             }
+            ViewMode::CalculatorScratchpad => {
+                if let Some(handler) = self.calculator_mode_handler.as_mut() {
+                    handler.select_next();
+                    cx.notify();
+                }
+            }
         }
     }
 
@@ -262,23 +268,18 @@ impl LauncherView {
                     cx.notify();
                 });
             }
-            ViewMode::EmojiPicker => {
-                if let Some(emoji_state) = self.emoji_mode_handler.as_ref().map(|h| h.list_state())
-                {
-                    emoji_state.update(cx, |state, cx| {
-                        state.delegate_mut().select_left();
-                        if let Some(row) = state.delegate().selected_row() {
-                            state.scroll_to_item(
-                                IndexPath::new(row),
-                                ScrollStrategy::Top,
-                                window,
-                                cx,
-                            );
-                        }
-                        cx.notify();
-                    });
-                }
-            }
+            ViewMode::EmojiPicker => Self::move_grid_selection(
+                self.emoji_mode_handler.as_ref().map(|h| h.list_state()),
+                GridDelegate::select_left,
+                window,
+                cx,
+            ),
+            ViewMode::GlyphPicker => Self::move_grid_selection(
+                self.glyph_mode_handler.as_ref().map(|h| h.list_state()),
+                GridDelegate::select_left,
+                window,
+                cx,
+            ),
             ViewMode::ClipboardHistory => {
                 if let Some(clipboard_state) =
                     self.clipboard_mode_handler.as_ref().map(|h| h.list_state())
@@ -317,6 +318,30 @@ impl LauncherView {
             ViewMode::AiResponse => {
                 // No navigation in AI response mode
             }
+            ViewMode::CalculatorScratchpad => {
+                if let Some(handler) = self.calculator_mode_handler.as_mut() {
+                    handler.select_prev();
+                    cx.notify();
+                }
+            }
+        }
+    }
+
+    /// Move the selection of a picker grid and scroll to the selected row.
+    fn move_grid_selection<T: GridItem>(
+        grid_state: Option<&Entity<ListState<GridDelegate<T>>>>,
+        movement: fn(&mut GridDelegate<T>),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(grid_state) = grid_state {
+            grid_state.update(cx, |state, cx| {
+                movement(state.delegate_mut());
+                if let Some(row) = state.delegate().selected_row() {
+                    state.scroll_to_item(IndexPath::new(row), ScrollStrategy::Top, window, cx);
+                }
+                cx.notify();
+            });
         }
     }
 }
@@ -1,11 +1,12 @@
 //! Rendering implementation for LauncherView.
 
-use gpui::{Context, Length, Window, div, image_cache, prelude::*, px, retain_all};
+use gpui::{Context, Length, SharedString, Window, div, image_cache, prelude::*, px, retain_all};
 use gpui_component::list::List;
 use gpui_component::{ActiveTheme, Icon, IconName};
 
 use super::LauncherView;
 use super::state::ViewMode;
+use crate::ui::motion;
 
 impl gpui::Render for LauncherView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
@@ -51,13 +52,17 @@ impl gpui::Render for LauncherView {
                             .prefix(input_prefix),
                     ),
             )
+            // Launch error banner
+            .children(self.render_confirm_error(cx))
+            // Unavailable window switching notice
+            .children(self.render_compositor_notice(cx))
             // List content
             .child(list_content);
 
         if config.enable_backdrop {
             // With backdrop: fullscreen container with centered panel and click-outside-to-close
             let on_hide = self.on_hide.clone();
-            div()
+            let root = div()
                 .track_focus(&self.focus_handle)
                 .key_context("LauncherView")
                 .on_action(cx.listener(Self::select_next))
@@ -65,10 +70,17 @@ impl gpui::Render for LauncherView {
                 .on_action(cx.listener(Self::select_tab))
                 .on_action(cx.listener(Self::select_tab_prev))
                 .on_action(cx.listener(Self::confirm))
+                .on_action(cx.listener(Self::confirm_secondary))
+                .on_action(cx.listener(Self::confirm_discrete_gpu))
+                .on_action(cx.listener(Self::toggle_mark))
+                .on_action(cx.listener(Self::copy_info))
+                .on_action(cx.listener(Self::toggle_keep_above))
+                .on_action(cx.listener(Self::cycle_search_provider))
                 .on_action(cx.listener(Self::cancel))
                 .on_action(cx.listener(Self::go_back))
                 .on_action(cx.listener(Self::switch_mode_next))
                 .on_action(cx.listener(Self::switch_mode_prev))
+                .on_modifiers_changed(cx.listener(Self::modifiers_changed))
                 .size_full()
                 .flex()
                 .items_center()
@@ -83,11 +95,11 @@ impl gpui::Render for LauncherView {
                     |_event, _window, cx| {
                         cx.stop_propagation();
                     },
-                ))
-                .into_any_element()
+                ));
+            motion::fade_launcher(root, self.closing)
         } else {
             // No backdrop: just the launcher panel filling the window
-            launcher_panel
+            let root = launcher_panel
                 .track_focus(&self.focus_handle)
                 .key_context("LauncherView")
                 .on_action(cx.listener(Self::select_next))
@@ -95,25 +107,118 @@ impl gpui::Render for LauncherView {
                 .on_action(cx.listener(Self::select_tab))
                 .on_action(cx.listener(Self::select_tab_prev))
                 .on_action(cx.listener(Self::confirm))
+                .on_action(cx.listener(Self::confirm_secondary))
+                .on_action(cx.listener(Self::confirm_discrete_gpu))
+                .on_action(cx.listener(Self::toggle_mark))
+                .on_action(cx.listener(Self::copy_info))
+                .on_action(cx.listener(Self::toggle_keep_above))
+                .on_action(cx.listener(Self::cycle_search_provider))
                 .on_action(cx.listener(Self::cancel))
                 .on_action(cx.listener(Self::go_back))
                 .on_action(cx.listener(Self::switch_mode_next))
                 .on_action(cx.listener(Self::switch_mode_prev))
-                .into_any_element()
+                .on_modifiers_changed(cx.listener(Self::modifiers_changed));
+            motion::fade_launcher(root, self.closing)
         }
     }
 }
 
 impl LauncherView {
+    /// Render the banner for a failed launch or copy.
+    fn render_confirm_error(&self, cx: &mut Context<Self>) -> Option<gpui::AnyElement> {
+        let error = self.confirm_error.as_ref()?;
+        Some(
+            div()
+                .w_full()
+                .px_4()
+                .py_2()
+                .flex()
+                .flex_row()
+                .items_center()
+                .gap_2()
+                .bg(cx.theme().danger.alpha(0.15))
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(Icon::new(IconName::CircleX).text_color(cx.theme().danger))
+                .child(
+                    div()
+                        .flex_1()
+                        .overflow_hidden()
+                        .text_sm()
+                        .text_color(cx.theme().foreground)
+                        .child(error.title()),
+                )
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(error.detail()),
+                )
+                .into_any_element(),
+        )
+    }
+
+    /// Render the notice shown the first time window switching turns out
+    /// to be unavailable.
+    fn render_compositor_notice(&self, cx: &mut Context<Self>) -> Option<gpui::AnyElement> {
+        let notice = self.compositor_notice.as_ref()?;
+        Some(
+            div()
+                .w_full()
+                .px_4()
+                .py_2()
+                .flex()
+                .flex_row()
+                .items_center()
+                .gap_2()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(Icon::new(IconName::Info).text_color(cx.theme().muted_foreground))
+                .child(
+                    div()
+                        .flex_1()
+                        .overflow_hidden()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(SharedString::from(notice.clone())),
+                )
+                .into_any_element(),
+        )
+    }
+
     /// Render the input prefix icon based on current mode and navigation state.
     fn render_input_prefix(&self, cx: &mut Context<Self>) -> gpui::AnyElement {
         match self.view_mode {
+            ViewMode::Main if !self.submenu_stack.is_root() => {
+                // Back arrow and breadcrumb path inside nested submenus
+                div()
+                    .id("back-submenu")
+                    .cursor_pointer()
+                    .mr_2()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.submenu_stack.pop();
+                        this.show_current_submenu(window, cx);
+                    }))
+                    .child(Icon::new(IconName::ArrowLeft).text_color(cx.theme().muted_foreground))
+                    .child(
+                        div()
+                            .text_sm()
+                            .whitespace_nowrap()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(SharedString::from(self.submenu_stack.path().join(" › "))),
+                    )
+                    .into_any_element()
+            }
             ViewMode::Main => {
                 // Icon based on current launcher mode
                 let icon = match self.mode_state.current_mode() {
                     crate::config::LauncherMode::Combined => IconName::Search,
                     crate::config::LauncherMode::Applications => IconName::Search,
-                    crate::config::LauncherMode::Windows => IconName::LayoutDashboard,
+                    crate::config::LauncherMode::Windows
+                    | crate::config::LauncherMode::Switcher => IconName::LayoutDashboard,
                     crate::config::LauncherMode::Actions => IconName::Settings,
                     crate::config::LauncherMode::Search => IconName::Globe,
                     crate::config::LauncherMode::Calculator => IconName::Search,
@@ -146,6 +251,26 @@ impl LauncherView {
                         .into_any_element()
                 }
             }
+            ViewMode::GlyphPicker => {
+                if self.navigated_into_submenu {
+                    div()
+                        .id("back-glyph")
+                        .cursor_pointer()
+                        .mr_2()
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.exit_glyph_mode(window, cx);
+                        }))
+                        .child(
+                            Icon::new(IconName::ArrowLeft).text_color(cx.theme().muted_foreground),
+                        )
+                        .into_any_element()
+                } else {
+                    Icon::new(IconName::Search)
+                        .text_color(cx.theme().muted_foreground)
+                        .mr_2()
+                        .into_any_element()
+                }
+            }
             ViewMode::ClipboardHistory => {
                 if self.navigated_into_submenu {
                     div()
@@ -206,6 +331,15 @@ impl LauncherView {
                         .into_any_element()
                 }
             }
+            ViewMode::CalculatorScratchpad => div()
+                .id("back-calculator")
+                .cursor_pointer()
+                .mr_2()
+                .on_click(cx.listener(|this, _, window, cx| {
+                    this.exit_calculator_mode(window, cx);
+                }))
+                .child(Icon::new(IconName::ArrowLeft).text_color(cx.theme().muted_foreground))
+                .into_any_element(),
         }
     }
 
@@ -237,6 +371,19 @@ impl LauncherView {
                     div().flex_1().into_any_element()
                 }
             }
+            ViewMode::GlyphPicker => {
+                if let Some(glyph_state) = self.glyph_mode_handler.as_ref().map(|h| h.list_state())
+                {
+                    div()
+                        .flex_1()
+                        .overflow_hidden()
+                        .py_2()
+                        .child(List::new(glyph_state))
+                        .into_any_element()
+                } else {
+                    div().flex_1().into_any_element()
+                }
+            }
             ViewMode::ClipboardHistory => {
                 if let Some(clipboard_state) =
                     self.clipboard_mode_handler.as_ref().map(|h| h.list_state())
@@ -302,6 +449,17 @@ impl LauncherView {
                     div().flex_1().into_any_element()
                 }
             }
+            ViewMode::CalculatorScratchpad => {
+                if let Some(ref handler) = self.calculator_mode_handler {
+                    crate::ui::views::render_scratchpad(
+                        handler.scratchpad(),
+                        handler.scroll_handle(),
+                    )
+                    .into_any_element()
+                } else {
+                    div().flex_1().into_any_element()
+                }
+            }
         }
     }
 }
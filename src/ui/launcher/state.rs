@@ -2,7 +2,21 @@
 //!
 //! Contains mode state tracking and view mode definitions.
 
+use std::collections::HashMap;
+use std::time::Duration;
+
 use crate::config::LauncherMode;
+use crate::items::{SubmenuItem, SubmenuLayout, SubmenuSource};
+use crate::ui::modes::SavedSelection;
+
+/// Focus changes this soon after showing the launcher are ignored, since
+/// compositors may briefly move focus while mapping the window.
+pub const FOCUS_LOSS_GRACE: Duration = Duration::from_millis(300);
+
+/// Whether a focus loss `since_shown` after the launcher appeared should hide it.
+pub fn should_close_on_focus_loss(enabled: bool, since_shown: Duration) -> bool {
+    enabled && since_shown >= FOCUS_LOSS_GRACE
+}
 
 /// Tracks the active modes list and current mode index.
 #[derive(Clone, Debug)]
@@ -54,17 +68,219 @@ impl Default for ModeState {
 }
 
 /// The current view mode of the launcher.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum ViewMode {
     /// Main launcher view showing apps, windows, commands.
     #[default]
     Main,
     /// Emoji picker grid view.
     EmojiPicker,
+    /// Nerd Font icon picker grid view.
+    GlyphPicker,
     /// Clipboard history view.
     ClipboardHistory,
     /// AI response streaming view.
     AiResponse,
     /// Theme picker view.
     ThemePicker,
+    /// Calculator scratchpad view.
+    CalculatorScratchpad,
+}
+
+/// Result of pressing Escape in the main list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscapeAction {
+    /// Returned to the parent submenu.
+    Pop,
+    /// Already at the root; the launcher should cancel.
+    Cancel,
+}
+
+/// Back stack of nested submenus opened in the main list.
+#[derive(Clone, Debug, Default)]
+pub struct SubmenuStack {
+    frames: Vec<SubmenuItem>,
+}
+
+impl SubmenuStack {
+    /// Open a child submenu.
+    pub fn push(&mut self, submenu: SubmenuItem) {
+        self.frames.push(submenu);
+    }
+
+    /// Return to the parent, if any.
+    pub fn pop(&mut self) -> Option<SubmenuItem> {
+        self.frames.pop()
+    }
+
+    /// The innermost open submenu, or None at the root.
+    pub fn current(&self) -> Option<&SubmenuItem> {
+        self.frames.last()
+    }
+
+    /// Whether no submenu is open.
+    pub fn is_root(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Names of the open submenus, outermost first.
+    pub fn path(&self) -> Vec<&str> {
+        self.frames
+            .iter()
+            .map(|frame| frame.name.as_str())
+            .collect()
+    }
+
+    /// Close all submenus.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Handle Escape: pop one level, or cancel at the root.
+    pub fn escape(&mut self) -> EscapeAction {
+        match self.pop() {
+            Some(_) => EscapeAction::Pop,
+            None => EscapeAction::Cancel,
+        }
+    }
+}
+
+/// Selections remembered per view for the rest of the session.
+#[derive(Clone, Debug, Default)]
+pub struct ViewSelections {
+    saved: HashMap<ViewMode, SavedSelection>,
+}
+
+impl ViewSelections {
+    /// Remember the selection of a view being left, or forget it.
+    pub fn save(&mut self, mode: ViewMode, selection: Option<SavedSelection>) {
+        match selection {
+            Some(selection) => self.saved.insert(mode, selection),
+            None => self.saved.remove(&mode),
+        };
+    }
+
+    /// The index to reselect when re-entering a view with `item_count` items.
+    pub fn restore(&self, mode: ViewMode, item_count: usize) -> Option<usize> {
+        self.saved.get(&mode)?.index_for(item_count)
+    }
+}
+
+impl ViewMode {
+    /// The view a submenu opens, chosen by its layout and data source.
+    ///
+    /// Returns None for submenus without a source, or whose source has no
+    /// view for the requested layout.
+    pub fn for_submenu(submenu: &SubmenuItem) -> Option<Self> {
+        match (&submenu.layout, submenu.source?) {
+            (SubmenuLayout::Grid { .. }, SubmenuSource::Emojis) => Some(Self::EmojiPicker),
+            (SubmenuLayout::Grid { .. }, SubmenuSource::Icons) => Some(Self::GlyphPicker),
+            (SubmenuLayout::List, SubmenuSource::Clipboard) => Some(Self::ClipboardHistory),
+            (SubmenuLayout::List, SubmenuSource::Themes) => Some(Self::ThemePicker),
+            (SubmenuLayout::List, SubmenuSource::Calculator) => Some(Self::CalculatorScratchpad),
+            _ => None,
+        }
+    }
+
+    /// Whether this view renders its items as a grid.
+    pub fn is_grid(self) -> bool {
+        matches!(self, Self::EmojiPicker | Self::GlyphPicker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_loss_ignored_right_after_show() {
+        assert!(!should_close_on_focus_loss(true, Duration::from_millis(50)));
+        assert!(should_close_on_focus_loss(true, FOCUS_LOSS_GRACE));
+        assert!(should_close_on_focus_loss(true, Duration::from_secs(5)));
+        assert!(!should_close_on_focus_loss(false, Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_view_selections_restore_unchanged_items() {
+        let mut selections = ViewSelections::default();
+        selections.save(
+            ViewMode::EmojiPicker,
+            Some(SavedSelection {
+                index: 17,
+                item_count: 300,
+            }),
+        );
+
+        assert_eq!(selections.restore(ViewMode::EmojiPicker, 300), Some(17));
+        assert_eq!(selections.restore(ViewMode::EmojiPicker, 301), None);
+        assert_eq!(selections.restore(ViewMode::ClipboardHistory, 300), None);
+
+        selections.save(ViewMode::EmojiPicker, None);
+        assert_eq!(selections.restore(ViewMode::EmojiPicker, 300), None);
+    }
+
+    #[test]
+    fn test_submenu_stack_push_pop() {
+        let mut stack = SubmenuStack::default();
+        assert!(stack.is_root());
+
+        stack.push(SubmenuItem::list("tools", "Tools"));
+        stack.push(SubmenuItem::list("network", "Network"));
+        assert_eq!(stack.path(), vec!["Tools", "Network"]);
+        assert_eq!(stack.current().unwrap().id, "network");
+
+        assert_eq!(stack.pop().unwrap().id, "network");
+        assert_eq!(stack.path(), vec!["Tools"]);
+        stack.clear();
+        assert!(stack.current().is_none());
+    }
+
+    #[test]
+    fn test_escape_pops_child_and_cancels_at_root() {
+        let mut stack = SubmenuStack::default();
+        stack.push(SubmenuItem::list("tools", "Tools"));
+
+        assert_eq!(stack.escape(), EscapeAction::Pop);
+        assert!(stack.is_root());
+        assert_eq!(stack.escape(), EscapeAction::Cancel);
+    }
+
+    #[test]
+    fn test_grid_submenu_opens_grid_view() {
+        let submenu =
+            SubmenuItem::grid("custom-emojis", "More Emojis", 6).with_source(SubmenuSource::Emojis);
+        let mode = ViewMode::for_submenu(&submenu).unwrap();
+        assert_eq!(mode, ViewMode::EmojiPicker);
+        assert!(mode.is_grid());
+    }
+
+    #[test]
+    fn test_list_submenu_opens_list_view() {
+        let submenu =
+            SubmenuItem::list("custom-clipboard", "Pasted").with_source(SubmenuSource::Clipboard);
+        let mode = ViewMode::for_submenu(&submenu).unwrap();
+        assert_eq!(mode, ViewMode::ClipboardHistory);
+        assert!(!mode.is_grid());
+    }
+
+    #[test]
+    fn test_calculator_submenu_opens_scratchpad() {
+        let submenu =
+            SubmenuItem::list("calc", "Calculator").with_source(SubmenuSource::Calculator);
+        assert_eq!(
+            ViewMode::for_submenu(&submenu),
+            Some(ViewMode::CalculatorScratchpad)
+        );
+    }
+
+    #[test]
+    fn test_submenu_without_matching_view() {
+        assert_eq!(
+            ViewMode::for_submenu(&SubmenuItem::list("plain", "Plain")),
+            None
+        );
+        let list_emojis =
+            SubmenuItem::list("emoji-list", "Emojis").with_source(SubmenuSource::Emojis);
+        assert_eq!(ViewMode::for_submenu(&list_emojis), None);
+    }
 }
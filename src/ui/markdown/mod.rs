@@ -7,6 +7,13 @@
 //! - Links, images
 //! - Ordered/unordered lists (nested)
 //! - Blockquotes, tables, horizontal rules
+//!
+//! Streamed responses are split into blocks by [`MarkdownStream`] so that
+//! only the block still being received is re-parsed on every token.
+
+mod stream;
+
+pub use stream::MarkdownStream;
 
 use std::sync::Arc;
 
@@ -77,3 +84,39 @@ pub fn render_markdown_with_id(
             .selectable(true),
     )
 }
+
+/// Render a streamed markdown buffer block by block.
+///
+/// Complete blocks keep a stable element ID per index, so their parsed
+/// state is reused and only the tail is parsed again as text arrives.
+/// `cursor` is appended to the tail while streaming.
+pub fn render_markdown_stream(
+    id_prefix: &str,
+    stream: &MarkdownStream,
+    cursor: Option<&str>,
+    window: &mut Window,
+    cx: &mut App,
+) -> impl IntoElement {
+    let mut container = div().flex().flex_col().gap(rems(1.5)).w_full();
+
+    for (i, block) in stream.blocks().iter().enumerate() {
+        container = container.child(render_markdown_with_id(
+            format!("{}-{}", id_prefix, i),
+            block,
+            window,
+            cx,
+        ));
+    }
+
+    let tail = stream.tail_with(cursor.unwrap_or_default());
+    if !tail.trim().is_empty() {
+        container = container.child(render_markdown_with_id(
+            format!("{}-tail", id_prefix),
+            &tail,
+            window,
+            cx,
+        ));
+    }
+
+    container
+}
@@ -0,0 +1,206 @@
+//! Incremental block splitting for streamed markdown.
+//!
+//! AI responses arrive token by token. Instead of re-parsing the whole
+//! response on every token, the text is split into top-level blocks as soon
+//! as they are complete. Complete blocks never change, so only the tail
+//! (the block still being received) has to be parsed and laid out again.
+
+use gpui::SharedString;
+
+/// A growing markdown buffer split into complete blocks and a tail.
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownStream {
+    /// The full text received so far.
+    text: String,
+    /// Blocks that are complete and will not change anymore.
+    blocks: Vec<SharedString>,
+    /// Start of the block still being received.
+    tail_start: usize,
+    /// Start of the first line that has not been scanned yet.
+    scan_pos: usize,
+    /// Start of the blank line that may end the current block.
+    pending_break: Option<usize>,
+    /// Marker of the open code fence (e.g. "```"), if any.
+    fence: Option<String>,
+}
+
+impl MarkdownStream {
+    /// Create a stream from already received text.
+    pub fn new(text: &str) -> Self {
+        let mut stream = Self::default();
+        stream.push(text);
+        stream
+    }
+
+    /// Append text and split off any blocks completed by it.
+    pub fn push(&mut self, text: &str) {
+        self.text.push_str(text);
+
+        // Only complete lines are scanned; a partial line stays in the tail
+        while let Some(len) = self.text[self.scan_pos..].find('\n') {
+            let line_start = self.scan_pos;
+            let line_end = line_start + len + 1;
+            self.scan_pos = line_end;
+            self.scan_line(line_start, line_end);
+        }
+
+        // The first character of a partial line already decides whether a
+        // blank line ended the previous block
+        if self.fence.is_none()
+            && let Some(break_start) = self.pending_break
+            && self.text[self.scan_pos..]
+                .chars()
+                .next()
+                .is_some_and(|c| !c.is_whitespace())
+        {
+            self.pending_break = None;
+            self.finish_block(break_start);
+            self.tail_start = self.scan_pos;
+        }
+    }
+
+    /// Classify one complete line and update the block state.
+    fn scan_line(&mut self, start: usize, end: usize) {
+        let line = self.text[start..end].trim_end();
+        let is_blank = line.trim().is_empty();
+        let is_indented = line.starts_with([' ', '\t']);
+        let closes_fence = self
+            .fence
+            .as_deref()
+            .is_some_and(|marker| is_fence_close(line, marker));
+        let opens_fence = fence_open(line);
+
+        if self.fence.is_some() {
+            if closes_fence {
+                // A closed code fence is a complete block
+                self.fence = None;
+                self.finish_block(end);
+            }
+            return;
+        }
+
+        if is_blank {
+            self.pending_break.get_or_insert(start);
+            return;
+        }
+
+        // Indented lines after a blank line continue the block (nested list
+        // content, indented code), anything else starts a new one.
+        if let Some(break_start) = self.pending_break.take()
+            && !is_indented
+        {
+            self.finish_block(break_start);
+            self.tail_start = start;
+        }
+
+        if let Some(marker) = opens_fence {
+            if !self.text[self.tail_start..start].trim().is_empty() {
+                self.finish_block(start);
+            }
+            self.tail_start = start;
+            self.fence = Some(marker);
+        }
+    }
+
+    /// Move the tail up to `end` into the complete blocks.
+    fn finish_block(&mut self, end: usize) {
+        let block = self.text[self.tail_start..end].trim();
+        if !block.is_empty() {
+            self.blocks.push(block.to_string().into());
+        }
+        self.tail_start = end;
+    }
+
+    /// The full text received so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Blocks that are complete and will not change anymore.
+    pub fn blocks(&self) -> &[SharedString] {
+        &self.blocks
+    }
+
+    /// The block still being received, made renderable.
+    ///
+    /// An unclosed code fence is closed so the partial code renders as a
+    /// code block instead of swallowing the rest of the layout.
+    pub fn tail(&self) -> String {
+        self.tail_with("")
+    }
+
+    /// Like [`Self::tail`], with `suffix` (e.g. a cursor) appended to the
+    /// received text, inside an unclosed code fence.
+    pub fn tail_with(&self, suffix: &str) -> String {
+        let mut tail = self.text[self.tail_start..].trim().to_string();
+        tail.push_str(suffix);
+        if let Some(marker) = &self.fence {
+            tail.push('\n');
+            tail.push_str(marker);
+        }
+        tail
+    }
+}
+
+/// Get the marker if a line opens a code fence.
+fn fence_open(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let ch = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == ch).count();
+    (len >= 3).then(|| ch.to_string().repeat(len))
+}
+
+/// Check if a line closes the code fence opened with `marker`.
+fn is_fence_close(line: &str, marker: &str) -> bool {
+    let trimmed = line.trim();
+    let ch = marker.chars().next().unwrap_or('`');
+    trimmed.len() >= marker.len() && trimmed.chars().all(|c| c == ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete_prefix_is_stable() {
+        let text = "# Title\n\nFirst paragraph.\n\n- a\n- b\n\nLast paragraph";
+        let full = MarkdownStream::new(text);
+
+        // Feed the same text in small chunks
+        let mut stream = MarkdownStream::default();
+        let mut seen: Vec<SharedString> = Vec::new();
+        for chunk in text.as_bytes().chunks(3) {
+            stream.push(std::str::from_utf8(chunk).unwrap());
+            // Already complete blocks never change
+            assert_eq!(&stream.blocks()[..seen.len()], seen.as_slice());
+            seen = stream.blocks().to_vec();
+        }
+
+        assert_eq!(stream.blocks(), full.blocks());
+        assert_eq!(stream.blocks(), ["# Title", "First paragraph.", "- a\n- b"]);
+        assert_eq!(stream.tail(), "Last paragraph");
+    }
+
+    #[test]
+    fn test_unclosed_code_fence_renders_closed() {
+        let mut stream = MarkdownStream::new("Intro\n```rust\nfn main() {\n\n");
+        assert_eq!(stream.blocks(), ["Intro"]);
+        assert_eq!(stream.tail(), "```rust\nfn main() {\n```");
+        assert_eq!(stream.tail_with(" |"), "```rust\nfn main() { |\n```");
+
+        // Blank lines inside the fence do not split it
+        stream.push("}\n```\nAfter");
+        assert_eq!(stream.blocks(), ["Intro", "```rust\nfn main() {\n\n}\n```"]);
+        assert_eq!(stream.tail(), "After");
+    }
+
+    #[test]
+    fn test_indented_continuation_stays_in_block() {
+        let stream = MarkdownStream::new("1. item\n\n   more about item\n\nNext\n");
+        assert_eq!(stream.blocks(), ["1. item\n\n   more about item"]);
+        assert_eq!(stream.tail(), "Next");
+    }
+}
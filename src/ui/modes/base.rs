@@ -12,8 +12,129 @@
 //!
 //! Instead, we provide shared utility functions that mode handlers can use.
 
-use gpui::{Context, Window};
+use crate::error::ClipboardError;
+use crate::ui::delegates::{ClipboardListDelegate, GridDelegate, GridItem};
+use gpui::{App, Context, Entity, ScrollStrategy, Window};
+use gpui_component::IndexPath;
 use gpui_component::input::InputState;
+use gpui_component::list::{ListDelegate, ListState};
+use std::sync::Arc;
+
+/// Callback run with the result of copying a selection to the clipboard.
+/// Closes the launcher on success (if configured) and reports failures.
+pub type OnCopied = Arc<dyn Fn(Result<(), ClipboardError>) + Send + Sync>;
+
+/// Selection remembered for a list view between visits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SavedSelection {
+    /// Selected index into the unfiltered items
+    pub index: usize,
+    /// Number of items when the selection was saved
+    pub item_count: usize,
+}
+
+impl SavedSelection {
+    /// The index to reselect, if the view still has the same item set.
+    pub fn index_for(self, item_count: usize) -> Option<usize> {
+        (item_count == self.item_count && self.index < item_count).then_some(self.index)
+    }
+}
+
+/// A list delegate whose selection is remembered between visits.
+pub trait SelectableDelegate: ListDelegate + Sized + 'static {
+    /// The current query.
+    fn query(&self) -> &str;
+    /// The selected index into the filtered items.
+    fn selected_index(&self) -> Option<usize>;
+    /// Number of items, ignoring the query.
+    fn item_count(&self) -> usize;
+    /// Select the item at an index into the filtered items.
+    fn set_selected(&mut self, index: usize);
+    /// The list row showing the selected item, to scroll to.
+    fn selected_row(&self) -> Option<usize>;
+}
+
+impl SelectableDelegate for ClipboardListDelegate {
+    fn query(&self) -> &str {
+        ClipboardListDelegate::query(self)
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        ClipboardListDelegate::selected_index(self)
+    }
+
+    fn item_count(&self) -> usize {
+        ClipboardListDelegate::item_count(self)
+    }
+
+    fn set_selected(&mut self, index: usize) {
+        ClipboardListDelegate::set_selected(self, index);
+    }
+
+    fn selected_row(&self) -> Option<usize> {
+        // One entry per row
+        ClipboardListDelegate::selected_index(self)
+    }
+}
+
+impl<T: GridItem> SelectableDelegate for GridDelegate<T> {
+    fn query(&self) -> &str {
+        GridDelegate::query(self)
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        GridDelegate::selected_index(self)
+    }
+
+    fn item_count(&self) -> usize {
+        GridDelegate::item_count(self)
+    }
+
+    fn set_selected(&mut self, index: usize) {
+        GridDelegate::set_selected(self, index);
+    }
+
+    fn selected_row(&self) -> Option<usize> {
+        GridDelegate::selected_row(self)
+    }
+}
+
+/// The selection to remember when leaving a list view.
+/// None while a query is active, as the index would be into filtered results.
+pub fn saved_selection<D: SelectableDelegate>(
+    list_state: &Entity<ListState<D>>,
+    cx: &App,
+) -> Option<SavedSelection> {
+    let delegate = list_state.read(cx).delegate();
+    if !delegate.query().is_empty() {
+        return None;
+    }
+    Some(SavedSelection {
+        index: delegate.selected_index()?,
+        item_count: delegate.item_count(),
+    })
+}
+
+/// Number of items in a list view, ignoring the query.
+pub fn item_count<D: SelectableDelegate>(list_state: &Entity<ListState<D>>, cx: &App) -> usize {
+    list_state.read(cx).delegate().item_count()
+}
+
+/// Select the item at `index` in a list view and scroll to it.
+pub fn select<D: SelectableDelegate, T: 'static>(
+    list_state: &Entity<ListState<D>>,
+    index: usize,
+    window: &mut Window,
+    cx: &mut Context<T>,
+) {
+    list_state.update(cx, |state, cx| {
+        state.delegate_mut().set_selected(index);
+        if let Some(row) = state.delegate().selected_row() {
+            state.scroll_to_item(IndexPath::new(row), ScrollStrategy::Top, window, cx);
+        }
+        cx.notify();
+    });
+}
 
 /// Default placeholder text for the main launcher view.
 pub const DEFAULT_PLACEHOLDER: &str = "Search applications...";
@@ -78,4 +199,15 @@ mod tests {
     fn test_default_placeholder() {
         assert_eq!(DEFAULT_PLACEHOLDER, "Search applications...");
     }
+
+    #[test]
+    fn test_saved_selection_requires_same_items() {
+        let saved = SavedSelection {
+            index: 42,
+            item_count: 100,
+        };
+        assert_eq!(saved.index_for(100), Some(42));
+        assert_eq!(saved.index_for(101), None);
+        assert_eq!(saved.index_for(10), None);
+    }
 }
@@ -0,0 +1,115 @@
+//! Calculator scratchpad mode handler.
+//!
+//! Encapsulates the calculator scratchpad:
+//! - Evaluating submitted expressions into a running history
+//! - Moving the selection between history lines
+//! - Copying the selected line
+
+use crate::calculator::{Scratchpad, record_answer};
+use crate::clipboard::copy_to_clipboard;
+use gpui::{Context, ScrollHandle, Window};
+use gpui_component::input::InputState;
+
+use super::OnCopied;
+
+/// Handler for calculator scratchpad mode.
+pub struct CalculatorModeHandler {
+    /// Evaluated lines of this session
+    scratchpad: Scratchpad,
+    /// Called after copying a line
+    on_copied: OnCopied,
+    /// Scroll position of the history
+    scroll_handle: ScrollHandle,
+}
+
+impl CalculatorModeHandler {
+    /// Create a handler with an empty scratchpad.
+    pub fn new(on_copied: OnCopied) -> Self {
+        Self {
+            scratchpad: Scratchpad::new(),
+            on_copied,
+            scroll_handle: ScrollHandle::new(),
+        }
+    }
+
+    /// Get the scratchpad for rendering.
+    pub fn scratchpad(&self) -> &Scratchpad {
+        &self.scratchpad
+    }
+
+    /// Get the scroll handle of the history.
+    pub fn scroll_handle(&self) -> &ScrollHandle {
+        &self.scroll_handle
+    }
+
+    /// Evaluate an expression and append it to the history.
+    ///
+    /// Returns false for blank input.
+    pub fn submit(&mut self, expression: &str) -> bool {
+        let appended = self.scratchpad.push(expression).is_some();
+        self.scroll_to_selected();
+        appended
+    }
+
+    /// Copy the selected line and remember it as the last answer.
+    pub fn copy_selected(&self) {
+        let Some(index) = self.scratchpad.selected_index() else {
+            return;
+        };
+        let line = &self.scratchpad.lines()[index];
+        if let Some(value) = &line.clipboard_result {
+            record_answer(value);
+        }
+        (self.on_copied)(copy_to_clipboard(line.text_for_clipboard()));
+    }
+
+    /// Select the next (newer) line.
+    pub fn select_next(&mut self) {
+        self.scratchpad.select_next();
+        self.scroll_to_selected();
+    }
+
+    /// Select the previous (older) line.
+    pub fn select_prev(&mut self) {
+        self.scratchpad.select_prev();
+        self.scroll_to_selected();
+    }
+
+    fn scroll_to_selected(&self) {
+        if let Some(index) = self.scratchpad.selected_index() {
+            self.scroll_handle.scroll_to_item(index);
+        }
+    }
+
+    /// Update input placeholder when entering calculator mode.
+    pub fn setup_input(
+        input_state: &mut InputState,
+        window: &mut Window,
+        cx: &mut Context<InputState>,
+    ) {
+        super::base::setup_list_mode_input(
+            input_state,
+            "Enter an expression, or press Enter to copy...",
+            window,
+            cx,
+        );
+    }
+
+    /// Clear the input value after submitting an expression.
+    pub fn clear_input(
+        input_state: &mut InputState,
+        window: &mut Window,
+        cx: &mut Context<InputState>,
+    ) {
+        super::base::clear_input_value(input_state, window, cx);
+    }
+
+    /// Restore input placeholder when exiting calculator mode.
+    pub fn restore_input(
+        input_state: &mut InputState,
+        window: &mut Window,
+        cx: &mut Context<InputState>,
+    ) {
+        super::base::restore_main_input(input_state, window, cx);
+    }
+}
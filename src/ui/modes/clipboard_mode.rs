@@ -5,14 +5,13 @@
 //! - Setting up input filtering
 //! - Handling clipboard item selection and pasting
 
-use crate::clipboard::{
-    ClipboardContent, copy_image_to_clipboard, copy_to_clipboard, data::search_items,
-};
+use crate::clipboard::{copy_to_clipboard, data::search_items, restore_item};
 use crate::ui::delegates::ClipboardListDelegate;
 use gpui::{AppContext, Context, Entity, Subscription, Window};
 use gpui_component::input::{InputEvent, InputState};
 use gpui_component::list::ListState;
-use std::sync::Arc;
+
+use super::OnCopied;
 
 /// Handler for clipboard history mode.
 pub struct ClipboardModeHandler {
@@ -26,7 +25,7 @@ impl ClipboardModeHandler {
     /// Create a new clipboard mode handler.
     pub fn new<T: 'static>(
         input_state: &Entity<InputState>,
-        on_hide: Arc<dyn Fn() + Send + Sync>,
+        on_copied: OnCopied,
         window: &mut Window,
         cx: &mut Context<T>,
     ) -> Self {
@@ -34,39 +33,14 @@ impl ClipboardModeHandler {
         let mut delegate = ClipboardListDelegate::new(search_items(""));
 
         // Set up confirm callback (copy item and hide)
+        let on_copied_for_marked = on_copied.clone();
         delegate.set_on_confirm(move |item| {
-            match &item.content {
-                ClipboardContent::Text(t) => {
-                    if let Err(e) = copy_to_clipboard(t) {
-                        tracing::warn!(%e, "Failed to copy text to clipboard");
-                    }
-                }
-                ClipboardContent::Image {
-                    width,
-                    height,
-                    rgba_bytes,
-                } => {
-                    if let Err(e) = copy_image_to_clipboard(*width, *height, rgba_bytes) {
-                        tracing::warn!(%e, "Failed to copy image to clipboard");
-                    }
-                }
-                ClipboardContent::FilePaths(paths) => {
-                    let text = paths
-                        .iter()
-                        .filter_map(|p| p.to_str())
-                        .collect::<Vec<_>>()
-                        .join("\n");
-                    if let Err(e) = copy_to_clipboard(&text) {
-                        tracing::warn!(%e, "Failed to copy file paths to clipboard");
-                    }
-                }
-                ClipboardContent::RichText { plain, .. } => {
-                    if let Err(e) = copy_to_clipboard(plain) {
-                        tracing::warn!(%e, "Failed to copy rich text to clipboard");
-                    }
-                }
-            }
-            on_hide();
+            on_copied(restore_item(item));
+        });
+
+        // Marked entries are copied joined, one per line
+        delegate.set_on_confirm_marked(move |text| {
+            on_copied_for_marked(copy_to_clipboard(text));
         });
 
         // Create list state
@@ -6,12 +6,13 @@
 //! - Handling emoji selection and copying
 
 use crate::clipboard::copy_to_clipboard;
-use crate::emoji::all_emojis;
+use crate::emoji::{all_emojis, emoji_usage, record_emoji_usage};
 use crate::ui::delegates::EmojiGridDelegate;
 use gpui::{AppContext, Context, Entity, Subscription, Window};
 use gpui_component::input::{InputEvent, InputState};
 use gpui_component::list::ListState;
-use std::sync::Arc;
+
+use super::OnCopied;
 
 /// Handler for emoji picker mode.
 pub struct EmojiModeHandler {
@@ -25,7 +26,7 @@ impl EmojiModeHandler {
     /// Create a new emoji mode handler.
     pub fn new<T: 'static>(
         input_state: &Entity<InputState>,
-        on_hide: Arc<dyn Fn() + Send + Sync>,
+        on_copied: OnCopied,
         window: &mut Window,
         cx: &mut Context<T>,
     ) -> Self {
@@ -33,14 +34,28 @@ impl EmojiModeHandler {
         let mut delegate = EmojiGridDelegate::new(
             all_emojis().to_vec(),
             crate::ui::theme::theme().emoji.columns,
-        );
+        )
+        .with_ranking(emoji_usage());
 
         // Set up confirm callback (copy emoji and hide)
+        let on_copied_for_marked = on_copied.clone();
         delegate.set_on_confirm(move |emoji| {
-            if let Err(e) = copy_to_clipboard(&emoji.emoji) {
-                tracing::warn!(%e, "Failed to copy emoji to clipboard");
+            let result = copy_to_clipboard(&emoji.glyph());
+            if result.is_ok() {
+                record_emoji_usage(&emoji.emoji);
+            }
+            on_copied(result);
+        });
+
+        // Marked emoji are copied together, in marking order
+        delegate.set_on_confirm_marked(move |text, emojis| {
+            let result = copy_to_clipboard(text);
+            if result.is_ok() {
+                for emoji in emojis {
+                    record_emoji_usage(&emoji.emoji);
+                }
             }
-            on_hide();
+            on_copied_for_marked(result);
         });
 
         // Create list state
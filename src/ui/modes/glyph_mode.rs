@@ -0,0 +1,87 @@
+//! Icon glyph picker mode handler.
+//!
+//! Encapsulates all icon picker functionality:
+//! - Creating and managing glyph grid state
+//! - Setting up input filtering
+//! - Handling glyph selection and copying
+
+use crate::clipboard::copy_to_clipboard;
+use crate::glyphs::all_glyphs;
+use crate::ui::delegates::GlyphGridDelegate;
+use gpui::{AppContext, Context, Entity, Subscription, Window};
+use gpui_component::input::{InputEvent, InputState};
+use gpui_component::list::ListState;
+
+use super::OnCopied;
+
+/// Handler for icon glyph picker mode.
+pub struct GlyphModeHandler {
+    /// The glyph grid list state
+    list_state: Entity<ListState<GlyphGridDelegate>>,
+    /// Subscription to input changes (for filtering)
+    _input_subscription: Subscription,
+}
+
+impl GlyphModeHandler {
+    /// Create a new glyph mode handler.
+    pub fn new<T: 'static>(
+        input_state: &Entity<InputState>,
+        on_copied: OnCopied,
+        window: &mut Window,
+        cx: &mut Context<T>,
+    ) -> Self {
+        // Create delegate with theme-based column count
+        let mut delegate = GlyphGridDelegate::new(
+            all_glyphs().to_vec(),
+            crate::ui::theme::theme().emoji.columns,
+        );
+
+        // Set up confirm callback (copy glyph and hide)
+        delegate.set_on_confirm(move |glyph| {
+            on_copied(copy_to_clipboard(&glyph.glyph));
+        });
+
+        // Create list state
+        let list_state = cx.new(|cx| ListState::new(delegate, window, cx));
+
+        // Subscribe to input for filtering
+        let list_state_for_search = list_state.clone();
+        let subscription = cx.subscribe(input_state, move |_this, input, event, cx| {
+            if let InputEvent::Change = event {
+                let query = input.read(cx).value().to_string();
+                list_state_for_search.update(cx, |state, cx| {
+                    state.delegate_mut().set_query(query);
+                    cx.notify();
+                });
+            }
+        });
+
+        Self {
+            list_state,
+            _input_subscription: subscription,
+        }
+    }
+
+    /// Get the list state for rendering.
+    pub fn list_state(&self) -> &Entity<ListState<GlyphGridDelegate>> {
+        &self.list_state
+    }
+
+    /// Update input placeholder when entering glyph mode.
+    pub fn setup_input(
+        input_state: &mut InputState,
+        window: &mut Window,
+        cx: &mut Context<InputState>,
+    ) {
+        super::base::setup_list_mode_input(input_state, "Search icons...", window, cx);
+    }
+
+    /// Restore input placeholder when exiting glyph mode.
+    pub fn restore_input(
+        input_state: &mut InputState,
+        window: &mut Window,
+        cx: &mut Context<InputState>,
+    ) {
+        super::base::restore_main_input(input_state, window, cx);
+    }
+}
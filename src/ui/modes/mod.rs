@@ -1,6 +1,6 @@
 //! Mode-specific handlers for the launcher.
 //!
-//! Each mode (AI, Emoji, Clipboard, Theme) has its own handler that encapsulates
+//! Each mode (AI, Emoji, Icons, Clipboard, Theme, Calculator) has its own handler that encapsulates
 //! the mode-specific logic, state, and UI coordination. This keeps the main
 //! launcher clean and focused on routing/coordination.
 //!
@@ -18,12 +18,19 @@
 
 pub mod ai_mode;
 pub mod base;
+pub mod calculator_mode;
 pub mod clipboard_mode;
 pub mod emoji_mode;
+pub mod glyph_mode;
 pub mod theme_mode;
 
 pub use ai_mode::{AiModeAccess, AiModeHandler};
-pub use base::{DEFAULT_PLACEHOLDER, clear_input_value, restore_main_input, setup_list_mode_input};
+pub use base::{
+    DEFAULT_PLACEHOLDER, OnCopied, SavedSelection, SelectableDelegate, clear_input_value,
+    restore_main_input, setup_list_mode_input,
+};
+pub use calculator_mode::CalculatorModeHandler;
 pub use clipboard_mode::ClipboardModeHandler;
 pub use emoji_mode::EmojiModeHandler;
+pub use glyph_mode::GlyphModeHandler;
 pub use theme_mode::ThemeModeHandler;
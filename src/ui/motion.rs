@@ -0,0 +1,159 @@
+//! Reduced motion preference and the launcher's transitions.
+//!
+//! The `reduce_motion` config option overrides the desktop's setting, read
+//! once from the XDG appearance portal. The launcher fades in when opened,
+//! out when closed, and fades the highlight of a newly selected row in.
+//! With reduced motion all of them snap to the final state instead.
+
+use gpui::{
+    Animation, AnimationExt, AnyElement, ElementId, Hsla, IntoElement, Rgba, Styled, ease_out_quint,
+};
+use std::sync::OnceLock;
+use std::time::Duration;
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::OwnedValue;
+
+/// How long the launcher takes to fade in.
+pub const OPEN_DURATION: Duration = Duration::from_millis(120);
+
+/// How long the launcher takes to fade out before its window is removed.
+pub const CLOSE_DURATION: Duration = Duration::from_millis(90);
+
+/// How long a newly selected row takes to be fully highlighted.
+pub const SELECTION_DURATION: Duration = Duration::from_millis(80);
+
+/// The desktop's preference, detected on first use.
+static SYSTEM_REDUCE_MOTION: OnceLock<Option<bool>> = OnceLock::new();
+
+/// Whether animations should be skipped.
+pub fn reduce_motion() -> bool {
+    let system = *SYSTEM_REDUCE_MOTION.get_or_init(system_reduce_motion);
+    effective_reduce_motion(crate::config::config().reduce_motion, system)
+}
+
+/// How long a transition of `full` length takes, zero with reduced motion.
+pub fn duration(full: Duration) -> Duration {
+    transition_duration(full, reduce_motion())
+}
+
+/// Fade the launcher in, or out while it is `closing`.
+pub fn fade_launcher<E>(element: E, closing: bool) -> AnyElement
+where
+    E: IntoElement + Styled + 'static,
+{
+    let (id, full) = if closing {
+        ("launcher-close", CLOSE_DURATION)
+    } else {
+        ("launcher-open", OPEN_DURATION)
+    };
+    let duration = duration(full);
+    if duration.is_zero() {
+        return element.into_any_element();
+    }
+    element
+        .with_animation(
+            id,
+            Animation::new(duration).with_easing(ease_out_quint()),
+            move |element, delta| element.opacity(if closing { 1.0 - delta } else { delta }),
+        )
+        .into_any_element()
+}
+
+/// Fade a selected row's background from `from` to `to`.
+///
+/// `id` must differ per row, so the fade starts over whenever the
+/// selection moves to another row.
+pub fn highlight_selection<E>(row: E, id: impl Into<ElementId>, from: Hsla, to: Hsla) -> AnyElement
+where
+    E: IntoElement + Styled + 'static,
+{
+    let duration = duration(SELECTION_DURATION);
+    if duration.is_zero() {
+        return row.into_any_element();
+    }
+    row.with_animation(
+        id,
+        Animation::new(duration).with_easing(ease_out_quint()),
+        move |row, delta| row.bg(mix(from, to, delta)),
+    )
+    .into_any_element()
+}
+
+/// Combine the config override with the detected system preference.
+///
+/// An explicit config value wins; otherwise the system preference is
+/// followed, and motion is allowed when it is unknown.
+fn effective_reduce_motion(config: Option<bool>, system: Option<bool>) -> bool {
+    config.or(system).unwrap_or(false)
+}
+
+/// The length of a transition, skipped entirely with reduced motion.
+fn transition_duration(full: Duration, reduce_motion: bool) -> Duration {
+    if reduce_motion { Duration::ZERO } else { full }
+}
+
+/// Interpolate between two colors in RGB, `t` going from 0 to 1.
+fn mix(from: Hsla, to: Hsla, t: f32) -> Hsla {
+    let (from, to) = (Rgba::from(from), Rgba::from(to));
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    Rgba {
+        r: lerp(from.r, to.r),
+        g: lerp(from.g, to.g),
+        b: lerp(from.b, to.b),
+        a: lerp(from.a, to.a),
+    }
+    .into()
+}
+
+/// Read `reduced-motion` from the appearance portal.
+///
+/// Returns None when the portal is unavailable or too old to know the key.
+fn system_reduce_motion() -> Option<bool> {
+    let connection = Connection::session().ok()?;
+    let settings = Proxy::new(
+        &connection,
+        "org.freedesktop.portal.Desktop",
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.Settings",
+    )
+    .ok()?;
+    let value: OwnedValue = settings
+        .call("ReadOne", &("org.freedesktop.appearance", "reduced-motion"))
+        .ok()?;
+    // 0: no preference, 1: reduce motion
+    u32::try_from(value).ok().map(|value| value == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::hsla;
+
+    #[test]
+    fn test_effective_reduce_motion() {
+        // No config override follows the system
+        assert!(effective_reduce_motion(None, Some(true)));
+        assert!(!effective_reduce_motion(None, Some(false)));
+        assert!(!effective_reduce_motion(None, None));
+
+        // The config override wins over the system
+        assert!(effective_reduce_motion(Some(true), Some(false)));
+        assert!(effective_reduce_motion(Some(true), None));
+        assert!(!effective_reduce_motion(Some(false), Some(true)));
+    }
+
+    #[test]
+    fn test_transitions_snap_with_reduced_motion() {
+        assert_eq!(transition_duration(OPEN_DURATION, false), OPEN_DURATION);
+        assert_eq!(transition_duration(OPEN_DURATION, true), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_mix() {
+        let from = hsla(0.0, 0.0, 0.0, 0.0);
+        let to = hsla(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(mix(from, to, 0.0).a, 0.0);
+        assert!((mix(from, to, 0.5).a - 0.5).abs() < 0.001);
+        assert!((mix(from, to, 1.0).l - 1.0).abs() < 0.001);
+    }
+}
@@ -616,6 +616,12 @@ impl LauncherTheme {
             base
         }
     }
+
+    /// Size of glyphs drawn inside an icon box (SVG icons and the calculator sign).
+    /// Scales with `icon_size` so larger icons stay crisp instead of padded.
+    pub fn icon_glyph_size(&self) -> Pixels {
+        self.icon_size * (2.0 / 3.0)
+    }
 }
 
 /// Global theme instance (cached for performance, synced from config).
@@ -651,3 +657,17 @@ pub fn sync_theme_from_config() {
     let mut write_lock = THEME.write().unwrap();
     *write_lock = Some(loaded_theme);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icon_glyph_size_tracks_icon_size() {
+        let mut theme = LauncherTheme::default();
+        assert_eq!(theme.icon_glyph_size(), px(16.0));
+
+        theme.icon_size = px(36.0);
+        assert_eq!(theme.icon_glyph_size(), px(24.0));
+    }
+}
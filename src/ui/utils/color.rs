@@ -18,6 +18,29 @@ impl Color {
         Self { r, g, b, a }
     }
 
+    /// Create a new color from HSL components (hue: 0-360, saturation: 0-100, lightness: 0-100)
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let s = (s / 100.0).clamp(0.0, 1.0);
+        let l = (l / 100.0).clamp(0.0, 1.0);
+
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let m = l - c / 2.0;
+
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+
+        let to_u8 = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+        Self::from_rgb(to_u8(r), to_u8(g), to_u8(b))
+    }
+
     /// Convert to HSL format (hue: 0-360, saturation: 0-100, lightness: 0-100)
     pub fn to_hsl(&self) -> (u16, u8, u8) {
         let r = self.r as f32 / 255.0;
@@ -83,14 +106,29 @@ impl Color {
         let (h, s, l) = self.to_hsl();
         format!("hsl({}, {}%, {}%)", h, s, l)
     }
+
+    /// Convert to a GPUI color for rendering swatches
+    pub fn to_hsla(&self) -> gpui::Hsla {
+        gpui::Rgba {
+            r: self.r as f32 / 255.0,
+            g: self.g as f32 / 255.0,
+            b: self.b as f32 / 255.0,
+            a: self.a as f32 / 255.0,
+        }
+        .into()
+    }
 }
 
-/// Try to parse a color string (hex, rgb, rgba, hsl, etc.)
+/// Try to parse a color string (hex, rgb, rgba, hsl, hsla)
 pub fn parse_color(text: &str) -> Option<Color> {
     let text = text.trim();
 
     // Try hex format: #RGB, #RRGGBB, #RRGGBBAA
     if let Some(hex) = text.strip_prefix('#') {
+        // Slicing below assumes single-byte characters
+        if !hex.is_ascii() {
+            return None;
+        }
         if hex.len() == 3 {
             // #RGB -> #RRGGBB
             let r = u8::from_str_radix(&hex[0..1].repeat(2), 16).ok()?;
@@ -113,29 +151,71 @@ pub fn parse_color(text: &str) -> Option<Color> {
         }
     }
 
-    // Try rgb/rgba format: rgb(r, g, b) or rgba(r, g, b, a)
+    // Try rgb/rgba format: rgb(r, g, b), rgba(r, g, b, a) or rgb(r g b / a)
     if text.starts_with("rgb(") || text.starts_with("rgba(") {
         let start = if text.starts_with("rgba(") { 5 } else { 4 };
         let end = text.rfind(')')?;
         let values = &text[start..end];
 
-        let parts: Vec<&str> = values.split(',').map(|s| s.trim()).collect();
-        if parts.len() >= 3 {
+        let parts = split_components(values);
+        if matches!(parts.len(), 3 | 4) {
             let r = parts[0].parse::<u8>().ok()?;
             let g = parts[1].parse::<u8>().ok()?;
             let b = parts[2].parse::<u8>().ok()?;
-            let a = if parts.len() >= 4 {
-                (parts[3].parse::<f32>().ok()? * 255.0) as u8
-            } else {
-                255
+            let a = match parts.get(3) {
+                Some(alpha) => parse_alpha(alpha)?,
+                None => 255,
             };
             return Some(Color { r, g, b, a });
         }
     }
 
+    // Try hsl/hsla format: hsl(h, s%, l%), hsla(h, s%, l%, a) or hsl(h s% l% / a)
+    if text.starts_with("hsl(") || text.starts_with("hsla(") {
+        let start = if text.starts_with("hsla(") { 5 } else { 4 };
+        let end = text.rfind(')')?;
+        let values = &text[start..end];
+
+        let parts = split_components(values);
+        if matches!(parts.len(), 3 | 4) {
+            let h = parts[0].trim_end_matches("deg").parse::<f32>().ok()?;
+            let s = parts[1].trim_end_matches('%').parse::<f32>().ok()?;
+            let l = parts[2].trim_end_matches('%').parse::<f32>().ok()?;
+            let mut color = Color::from_hsl(h, s, l);
+            if let Some(alpha) = parts.get(3) {
+                color.a = parse_alpha(alpha)?;
+            }
+            return Some(color);
+        }
+    }
+
     None
 }
 
+/// Split the components of a functional color notation.
+///
+/// Accepts both the comma-separated syntax `hsla(220, 100%, 60%, 0.5)` and
+/// the space-separated CSS syntax `hsl(220 100% 60% / 0.5)`.
+fn split_components(values: &str) -> Vec<&str> {
+    if values.contains(',') {
+        values.split(',').map(|s| s.trim()).collect()
+    } else {
+        values
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+}
+
+/// Parse an alpha value written as a number from 0 to 1 or as a percentage.
+fn parse_alpha(text: &str) -> Option<u8> {
+    let alpha = match text.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+        None => text.parse::<f32>().ok()?,
+    };
+    Some((alpha.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,6 +247,47 @@ mod tests {
         assert_eq!(color.a, 255);
     }
 
+    #[test]
+    fn test_parse_hsl() {
+        let color = parse_color("hsl(220,100%,60%)").unwrap();
+        assert_eq!(color, Color::from_rgb(51, 119, 255));
+    }
+
+    #[test]
+    fn test_parse_space_separated() {
+        assert_eq!(
+            parse_color("hsl(220 100% 60%)"),
+            Some(Color::from_rgb(51, 119, 255))
+        );
+        assert_eq!(
+            parse_color("rgb(255 128 64 / 50%)"),
+            Some(Color::from_rgba(255, 128, 64, 128))
+        );
+    }
+
+    #[test]
+    fn test_parse_alpha() {
+        assert_eq!(parse_color("rgba(255, 128, 64, 0.5)").unwrap().a, 128);
+        assert_eq!(parse_color("hsla(220, 100%, 60%, 0.25)").unwrap().a, 64);
+        assert_eq!(parse_color("hsla(220, 100%, 60%, 50%)").unwrap().a, 128);
+        assert_eq!(parse_color("hsl(220 100% 60% / 1)").unwrap().a, 255);
+        assert!(parse_color("rgba(255, 128, 64, half)").is_none());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse_color("#3366fg").is_none());
+        assert!(parse_color("hsl(a, b, c)").is_none());
+        assert!(parse_color("firefox").is_none());
+    }
+
+    #[test]
+    fn test_to_hsl_string() {
+        let color = parse_color("#3366ff").unwrap();
+        assert_eq!(color.to_hsl_string(), "hsl(225, 100%, 60%)");
+        assert_eq!(color.to_rgb_string(), "rgb(51, 102, 255)");
+    }
+
     #[test]
     fn test_to_hex() {
         let color = Color::from_rgb(255, 128, 64);
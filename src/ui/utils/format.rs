@@ -0,0 +1,66 @@
+use std::time::SystemTime;
+
+/// Units for [`format_size`], in steps of 1000.
+const SIZE_UNITS: [&str; 5] = ["KB", "MB", "GB", "TB", "PB"];
+
+/// Format a byte count for display, e.g. "532 B" or "1.2 MB".
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1000 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    // Round first so 999_950 bytes show as "1.0 MB" rather than "1000.0 KB"
+    while (value * 10.0).round() >= 10_000.0 && unit < SIZE_UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", SIZE_UNITS[unit])
+}
+
+/// Format how long ago `time` was, e.g. "5 mins ago" or "3 days ago".
+pub fn format_age(time: SystemTime, now: SystemTime) -> String {
+    let Ok(age) = now.duration_since(time) else {
+        // Modified in the future, e.g. after a clock change
+        return "Just now".to_string();
+    };
+    let secs = age.as_secs();
+    let (count, unit) = match secs {
+        0..60 => return "Just now".to_string(),
+        60..3600 => (secs / 60, "min"),
+        3600..86400 => (secs / 3600, "hour"),
+        86400..2_592_000 => (secs / 86400, "day"),
+        2_592_000..31_536_000 => (secs / 2_592_000, "month"),
+        _ => (secs / 31_536_000, "year"),
+    };
+    format!("{count} {unit}{} ago", if count > 1 { "s" } else { "" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(999), "999 B");
+        assert_eq!(format_size(1000), "1.0 KB");
+        assert_eq!(format_size(1_234_567), "1.2 MB");
+        assert_eq!(format_size(999_950), "1.0 MB");
+        assert_eq!(format_size(5_000_000_000), "5.0 GB");
+        assert_eq!(format_size(u64::MAX), "18446.7 PB");
+    }
+
+    #[test]
+    fn test_format_age() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(100_000_000);
+        let ago = |secs| format_age(now - Duration::from_secs(secs), now);
+        assert_eq!(ago(30), "Just now");
+        assert_eq!(ago(60), "1 min ago");
+        assert_eq!(ago(3 * 3600), "3 hours ago");
+        assert_eq!(ago(2 * 86400), "2 days ago");
+        assert_eq!(ago(400 * 86400), "1 year ago");
+        assert_eq!(format_age(now + Duration::from_secs(5), now), "Just now");
+    }
+}
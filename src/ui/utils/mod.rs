@@ -1,8 +1,10 @@
 pub mod color;
 mod file_type;
+mod format;
 
 pub use color::{Color, parse_color};
 pub use file_type::{
     FileType, classify_file, is_image_ext, is_text_ext, should_preview_as_image,
     should_preview_as_text,
 };
+pub use format::{format_age, format_size};
@@ -1,10 +1,58 @@
 //! AI response view for displaying streaming responses.
 
-use crate::ui::markdown::render_markdown_with_id;
+use crate::ui::markdown::{MarkdownStream, render_markdown_stream};
 use crate::ui::theme::theme;
-use gpui::{App, Div, ElementId, SharedString, Window, div, prelude::*};
+use gpui::{App, Div, ElementId, ScrollHandle, SharedString, Window, div, point, prelude::*, px};
 use gpui_component::scroll::ScrollableElement;
 use llm::chat::ChatMessage;
+use std::cell::Cell;
+
+/// Distance from the bottom (in pixels) that still counts as "at the bottom".
+const FOLLOW_THRESHOLD: f32 = 24.0;
+
+/// Keeps a scroll view pinned to the bottom while content grows, until the
+/// user scrolls away from the bottom.
+#[derive(Clone, Debug)]
+struct ScrollFollow {
+    /// Whether the view follows new content.
+    following: Cell<bool>,
+    /// The offset last applied by following, to tell user scrolls apart.
+    last_applied: Cell<Option<f32>>,
+}
+
+impl ScrollFollow {
+    fn new() -> Self {
+        Self {
+            following: Cell::new(true),
+            last_applied: Cell::new(None),
+        }
+    }
+
+    /// Update the follow state from the current scroll position.
+    ///
+    /// Offsets are negative going down, `max` is the maximum scroll distance.
+    /// Returns the offset to scroll to, if the view should follow.
+    fn update(&self, offset: f32, max: f32) -> Option<f32> {
+        let at_bottom = -offset >= max - FOLLOW_THRESHOLD;
+
+        if self.following.get() {
+            let user_scrolled = self.last_applied.get().is_some_and(|last| last != offset);
+            if user_scrolled && !at_bottom {
+                self.following.set(false);
+                self.last_applied.set(None);
+                return None;
+            }
+        } else if at_bottom {
+            // Resume following once the user scrolls back to the bottom
+            self.following.set(true);
+        } else {
+            return None;
+        }
+
+        self.last_applied.set(Some(-max));
+        Some(-max)
+    }
+}
 
 /// View for displaying AI response with streaming support.
 #[derive(Clone)]
@@ -15,6 +63,12 @@ pub struct AiResponseView {
     is_streaming: bool,
     /// Error message if the request failed
     error: Option<String>,
+    /// Incrementally split markdown, one per message
+    markdown: Vec<MarkdownStream>,
+    /// Scroll handle of the message list
+    scroll_handle: ScrollHandle,
+    /// Auto-scroll state while streaming
+    follow: ScrollFollow,
 }
 
 impl AiResponseView {
@@ -27,12 +81,16 @@ impl AiResponseView {
             ],
             is_streaming: true,
             error: None,
+            markdown: vec![MarkdownStream::default(), MarkdownStream::default()],
+            scroll_handle: ScrollHandle::new(),
+            follow: ScrollFollow::new(),
         }
     }
 
     /// Append a token to the latest assistant response.
     pub fn append_token(&mut self, token: &str) {
         self.messages.last_mut().unwrap().content.push_str(token);
+        self.markdown.last_mut().unwrap().push(token);
     }
 
     /// Mark streaming as complete.
@@ -46,6 +104,10 @@ impl AiResponseView {
             .push(ChatMessage::user().content(message).build());
         self.messages
             .push(ChatMessage::assistant().content("").build());
+        self.markdown.push(MarkdownStream::default());
+        self.markdown.push(MarkdownStream::default());
+        // Follow the new answer again
+        self.follow = ScrollFollow::new();
     }
 
     /// Set an error message.
@@ -135,7 +197,7 @@ impl AiResponseView {
                 llm::chat::ChatRole::Assistant => {
                     messages_container = messages_container.child(self.render_assistant_message(
                         i,
-                        &msg.content,
+                        &self.markdown[i],
                         is_streaming_msg,
                         window,
                         cx,
@@ -145,11 +207,22 @@ impl AiResponseView {
             }
         }
 
+        // Stick to the bottom while streaming unless the user scrolled up
+        if self.is_streaming {
+            let offset = f32::from(self.scroll_handle.offset().y);
+            let max = f32::from(self.scroll_handle.max_offset().height);
+            if let Some(target) = self.follow.update(offset, max) {
+                self.scroll_handle.set_offset(point(px(0.0), px(target)));
+            }
+        }
+
         div()
             .id("ai-response-scroll")
             .flex_1()
             .w_full()
-            .overflow_y_scrollbar()
+            .overflow_y_scroll()
+            .track_scroll(&self.scroll_handle)
+            .vertical_scrollbar(&self.scroll_handle)
             .child(messages_container)
     }
 
@@ -187,7 +260,7 @@ impl AiResponseView {
     fn render_assistant_message(
         &self,
         index: usize,
-        content: &str,
+        markdown: &MarkdownStream,
         is_streaming: bool,
         window: &mut Window,
         cx: &mut App,
@@ -199,7 +272,7 @@ impl AiResponseView {
             .w_full()
             .flex_shrink_0();
 
-        if content.is_empty() && is_streaming {
+        if markdown.text().is_empty() && is_streaming {
             // Show "Thinking..." placeholder
             wrapper.child(
                 div()
@@ -210,19 +283,36 @@ impl AiResponseView {
             )
         } else {
             // Render markdown content with optional streaming cursor
-            let display_content = if is_streaming {
-                format!("{} \u{258C}", content) // Add cursor character
-            } else {
-                content.to_string()
-            };
-
+            let cursor = is_streaming.then_some(" \u{258C}");
             let markdown_id = format!("ai-markdown-{}", index);
-            wrapper.child(render_markdown_with_id(
-                markdown_id,
-                &display_content,
+            wrapper.child(render_markdown_stream(
+                &markdown_id,
+                markdown,
+                cursor,
                 window,
                 cx,
             ))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_follow_stops_when_user_scrolls_up() {
+        let follow = ScrollFollow::new();
+        assert_eq!(follow.update(0.0, 100.0), Some(-100.0));
+
+        // Content grew, position unchanged since last follow: keep following
+        assert_eq!(follow.update(-100.0, 250.0), Some(-250.0));
+
+        // User scrolled up
+        assert_eq!(follow.update(-50.0, 300.0), None);
+        assert_eq!(follow.update(-50.0, 400.0), None);
+
+        // User scrolled back to the bottom
+        assert_eq!(follow.update(-400.0, 400.0), Some(-400.0));
+    }
+}
@@ -1,16 +1,25 @@
 //! Rendering functions for clipboard history view.
 
 use crate::assets::PhosphorIcon;
-use crate::clipboard::{ClipboardContent, ClipboardItem};
+use crate::clipboard::{ClipboardContent, ClipboardItem, ClipboardPreview};
 use crate::ui::theme::theme;
 use crate::ui::utils::color::{Color, parse_color};
+use crate::ui::utils::format_age;
 use gpui::{Div, ElementId, SharedString, Stateful, div, img, prelude::*, px, svg};
 use std::fs;
 use std::path::PathBuf;
 use std::time::SystemTime;
 
 /// Render a clipboard item in the list.
-pub fn render_clipboard_item(item: &ClipboardItem, selected: bool, row: usize) -> Stateful<Div> {
+///
+/// `mark` is the 1-based batch-copy order of a marked item.
+pub fn render_clipboard_item(
+    item: &ClipboardItem,
+    preview: &ClipboardPreview,
+    selected: bool,
+    mark: Option<usize>,
+    row: usize,
+) -> Stateful<Div> {
     let t = theme();
 
     let bg = if selected {
@@ -19,11 +28,53 @@ pub fn render_clipboard_item(item: &ClipboardItem, selected: bool, row: usize) -
         t.item_background
     };
 
-    // Format timestamp
-    let timestamp_str = format_timestamp(&item.timestamp);
+    // Format timestamp, with a line count for multi-line entries
+    let mut timestamp_str = format_age(item.timestamp, SystemTime::now());
+    if let Some(label) = preview.line_count_label() {
+        timestamp_str = format!("{} · {}", timestamp_str, label);
+    }
+
+    let content = div()
+        .flex_1()
+        .flex()
+        .flex_col()
+        .justify_center()
+        .overflow_hidden();
 
-    // Get preview text
-    let preview = get_item_preview(item);
+    // Expanded entries grow to fit their lines
+    let content = if preview.is_expanded() {
+        content.min_h(t.item_content_height)
+    } else {
+        content.h(t.item_content_height)
+    };
+
+    let content = preview
+        .lines
+        .iter()
+        .fold(content, |content, line| {
+            content.child(
+                div()
+                    .w_full()
+                    .text_sm()
+                    .line_height(t.item_title_line_height)
+                    .text_color(t.item_title_color)
+                    .whitespace_nowrap()
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .child(SharedString::from(line.clone())),
+            )
+        })
+        .child(
+            div()
+                .w_full()
+                .text_xs()
+                .h(t.layout.item_description_height)
+                .text_color(t.item_description_color)
+                .whitespace_nowrap()
+                .overflow_hidden()
+                .text_ellipsis()
+                .child(SharedString::from(timestamp_str)),
+        );
 
     div()
         .id(ElementId::NamedInteger("clipboard-item".into(), row as u64))
@@ -40,43 +91,29 @@ pub fn render_clipboard_item(item: &ClipboardItem, selected: bool, row: usize) -
         .gap_2()
         // Icon (type-specific)
         .child(render_item_icon(item))
-        // Content: preview text and timestamp
-        .child(
-            div()
-                .flex_1()
-                .h(t.item_content_height)
-                .flex()
-                .flex_col()
-                .justify_center()
-                .overflow_hidden()
-                .child(
-                    div()
-                        .w_full()
-                        .text_sm()
-                        .line_height(t.item_title_line_height)
-                        .text_color(t.item_title_color)
-                        .whitespace_nowrap()
-                        .overflow_hidden()
-                        .text_ellipsis()
-                        .child(SharedString::from(preview)),
-                )
-                .child(
-                    div()
-                        .w_full()
-                        .text_xs()
-                        .h(t.layout.item_description_height)
-                        .text_color(t.item_description_color)
-                        .whitespace_nowrap()
-                        .overflow_hidden()
-                        .text_ellipsis()
-                        .child(SharedString::from(timestamp_str)),
-                ),
-        )
+        // Content: preview lines and timestamp
+        .child(content)
+        // Batch-copy order badge
+        .children(mark.map(render_mark_badge))
 }
 
-/// Get preview text for a clipboard item.
-fn get_item_preview(item: &ClipboardItem) -> String {
-    item.preview()
+/// Render the badge showing the batch-copy order of a marked item.
+fn render_mark_badge(position: usize) -> Div {
+    let t = theme();
+
+    div()
+        .flex_shrink_0()
+        .px(t.action_indicator.key_padding_x)
+        .pt(t.action_indicator.key_padding_top)
+        .pb(t.action_indicator.key_padding_bottom)
+        .bg(t.action_indicator.key_background)
+        .border_1()
+        .border_color(t.action_indicator.key_border)
+        .rounded(t.action_indicator.key_border_radius)
+        .text_size(t.action_indicator.key_font_size)
+        .line_height(t.action_indicator.key_line_height)
+        .text_color(t.action_indicator.key_color)
+        .child(SharedString::from(position.to_string()))
 }
 
 /// Render the appropriate icon for a clipboard item.
@@ -213,31 +250,11 @@ fn render_icon_container(icon: PhosphorIcon) -> Div {
         .child(
             svg()
                 .path(icon.path())
-                .size_4()
+                .size(t.icon_glyph_size())
                 .text_color(t.icon_placeholder_color),
         )
 }
 
-/// Format a SystemTime as a relative or absolute timestamp.
-fn format_timestamp(time: &SystemTime) -> String {
-    let now = SystemTime::now();
-    if let Ok(duration) = now.duration_since(*time) {
-        let secs = duration.as_secs();
-        if secs < 60 {
-            return "Just now".to_string();
-        } else if secs < 3600 {
-            let mins = secs / 60;
-            return format!("{} min{} ago", mins, if mins > 1 { "s" } else { "" });
-        } else if secs < 86400 {
-            let hours = secs / 3600;
-            return format!("{} hour{} ago", hours, if hours > 1 { "s" } else { "" });
-        }
-    }
-
-    // Fall back to a simple format
-    "Earlier".to_string()
-}
-
 /// Render the preview panel for the selected clipboard item.
 pub fn render_preview_panel(item: Option<&ClipboardItem>) -> Div {
     let t = theme();
@@ -0,0 +1,76 @@
+//! Rendering functions for the picker grid views.
+
+use crate::ui::delegates::GridItem;
+use crate::ui::theme::theme;
+use gpui::{Div, ElementId, Stateful, div, prelude::*};
+
+/// Render a single cell in the grid.
+///
+/// Cells marked for batch copy are highlighted and outlined.
+pub fn render_grid_cell<T: GridItem>(
+    item: &T,
+    selected: bool,
+    marked: bool,
+    index: usize,
+) -> Stateful<Div> {
+    let theme = theme();
+
+    let bg = if selected || marked {
+        theme.emoji.cell_selected_bg
+    } else {
+        gpui::hsla(0.0, 0.0, 0.0, 0.0) // transparent
+    };
+
+    let cell = div()
+        .id(ElementId::NamedInteger("grid-cell".into(), index as u64))
+        .w(theme.emoji.cell_size)
+        .h(theme.emoji.cell_size)
+        .flex()
+        .items_center()
+        .justify_center()
+        .bg(bg)
+        .rounded(theme.emoji.cell_border_radius)
+        .child(item.render_cell_content());
+
+    if marked {
+        cell.border_1()
+            .border_color(theme.action_indicator.key_border)
+    } else {
+        cell
+    }
+}
+
+/// Render a row of grid cells.
+///
+/// `marked` holds whether each item of the row is marked for batch copy.
+pub fn render_grid_row<T: GridItem>(
+    items: &[&T],
+    start_index: usize,
+    selected_index: Option<usize>,
+    marked: &[bool],
+    columns: usize,
+) -> Div {
+    let theme = theme();
+
+    let mut row = div()
+        .w_full()
+        .flex()
+        .flex_row()
+        .justify_center()
+        .gap(theme.emoji.cell_gap);
+
+    for (i, item) in items.iter().enumerate() {
+        let global_idx = start_index + i;
+        let selected = selected_index == Some(global_idx);
+        let is_marked = marked.get(i).copied().unwrap_or(false);
+        row = row.child(render_grid_cell(*item, selected, is_marked, global_idx));
+    }
+
+    // Pad with empty cells if row is not full
+    let remaining = columns.saturating_sub(items.len());
+    for _ in 0..remaining {
+        row = row.child(div().w(theme.emoji.cell_size).h(theme.emoji.cell_size));
+    }
+
+    row
+}
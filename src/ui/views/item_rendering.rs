@@ -4,10 +4,18 @@
 //! maintaining visual equivalence with the old implementation.
 
 use crate::assets::PhosphorIcon;
+use crate::config::{ActionIndicatorStyle, config};
 use crate::items::{DisplayItem, IconProvider, ListItem};
 use crate::ui::theme::theme;
-use gpui::{Div, ElementId, SharedString, Stateful, div, img, prelude::*, px, svg};
+use gpui::{Div, ElementId, Hsla, SharedString, Stateful, div, hsla, img, prelude::*, px, svg};
 use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Icon shown on selected submenu rows.
+pub const SUBMENU_INDICATOR_ICON: PhosphorIcon = PhosphorIcon::CaretRight;
+
+/// Key hint shown next to action labels.
+const ENTER_KEY_HINT: &str = "↵";
 
 /// Render any list item based on its type.
 /// This is the main dispatch function for item rendering.
@@ -18,8 +26,11 @@ pub fn render_item(item: &ListItem, selected: bool, row: usize) -> Stateful<Div>
         ListItem::Action(act) => render_action(act, selected, row),
         ListItem::Submenu(sub) => render_submenu(sub, selected, row),
         ListItem::Calculator(calc) => render_calculator(calc, selected, row),
+        ListItem::Color(color) => render_color(color, selected, row),
+        ListItem::Path(path) => render_path(path, selected, row),
         ListItem::Search(search) => render_search(search, selected, row),
         ListItem::Ai(ai) => render_ai(ai, selected, row),
+        ListItem::Suggestion(suggestion) => render_suggestion(suggestion, selected, row),
         ListItem::Theme(theme) => crate::ui::views::render_theme_item(theme, selected, row),
     }
 }
@@ -31,15 +42,19 @@ fn render_application(
     row: usize,
 ) -> Stateful<Div> {
     let mut item = item_container(row, selected)
-        .child(render_icon(app.icon_path.as_ref()))
+        .child(render_icon(app.icon_path.as_ref(), &app.name, &app.id))
         .child(render_text_content(
             &app.name,
             app.description.as_deref(),
             selected,
         ));
 
+    if app.hidden {
+        item = item.child(render_hidden_badge());
+    }
+
     if selected {
-        item = item.child(render_action_indicator("Open"));
+        item = item.children(render_action_indicator("Open"));
     }
 
     item
@@ -48,7 +63,11 @@ fn render_application(
 /// Render a window item.
 fn render_window(win: &crate::items::WindowItem, selected: bool, row: usize) -> Stateful<Div> {
     let mut item = item_container(row, selected)
-        .child(render_icon(win.icon_path.as_ref()))
+        .child(render_icon(
+            win.icon_path.as_ref(),
+            &win.app_name,
+            &win.app_id,
+        ))
         .child(render_text_content(
             &win.title,
             Some(&win.description),
@@ -56,7 +75,10 @@ fn render_window(win: &crate::items::WindowItem, selected: bool, row: usize) ->
         ));
 
     if selected {
-        item = item.child(render_action_indicator("Switch"));
+        item = item.children(render_action_indicators(
+            "Switch",
+            win.secondary_action_label(),
+        ));
     }
 
     item
@@ -74,7 +96,33 @@ fn render_action(act: &crate::items::ActionItem, selected: bool, row: usize) ->
         ));
 
     if selected {
-        item = item.child(render_action_indicator("Run"));
+        item = item.children(render_action_indicator(act.action_label()));
+    }
+
+    item
+}
+
+/// Render a typed file or directory path.
+fn render_path(path: &crate::items::PathItem, selected: bool, row: usize) -> Stateful<Div> {
+    let icon = path.icon_name().and_then(PhosphorIcon::from_name);
+    let description = if config().show_file_metadata {
+        path.description_with_metadata(SystemTime::now())
+    } else {
+        path.description.clone()
+    };
+    let mut item = item_container(row, selected)
+        .child(render_phosphor_icon(icon))
+        .child(render_text_content(
+            &path.name,
+            Some(&description),
+            selected,
+        ));
+
+    if selected {
+        item = item.children(render_action_indicators(
+            "Open",
+            path.secondary_action_label(),
+        ));
     }
 
     item
@@ -92,8 +140,7 @@ fn render_submenu(sub: &crate::items::SubmenuItem, selected: bool, row: usize) -
         ));
 
     if selected {
-        // Show arrow to indicate submenu
-        item = item.child(render_action_indicator("→"));
+        item = item.children(render_submenu_indicator());
     }
 
     item
@@ -141,7 +188,10 @@ fn render_calculator(
 
     // Add action indicator when selected
     if selected {
-        container = container.child(render_action_indicator("Copy"));
+        container = container.children(render_action_indicators(
+            "Copy",
+            calc.secondary_action_label(),
+        ));
     }
 
     container
@@ -167,14 +217,14 @@ fn render_calculator_icon() -> Div {
         .rounded_sm()
         .child(
             div()
-                .text_sm()
+                .text_size(theme.icon_glyph_size())
                 .font_weight(gpui::FontWeight::BOLD)
                 .text_color(icon_color)
                 .child(SharedString::from("=")),
         )
 }
 
-/// Render the calculator text content (result only).
+/// Render the calculator text content (result, plus the delta of `A + B%`).
 fn render_calculator_content(calc: &crate::items::CalculatorItem, selected: bool) -> Div {
     let theme = theme();
 
@@ -184,36 +234,120 @@ fn render_calculator_content(calc: &crate::items::CalculatorItem, selected: bool
         theme.item_title_color
     };
 
-    let max_width = theme.max_text_width(px(crate::config::launcher_size().0), selected);
+    let max_width = theme.max_text_width(
+        px(crate::config::launcher_size().0),
+        reserves_action_indicator(selected),
+    );
 
-    div()
-        .h(theme.item_content_height)
-        .max_w(max_width)
+    let mut result = div()
+        .w_full()
         .flex()
-        .flex_col()
-        .justify_center()
+        .flex_row()
+        .gap_1()
+        .text_sm()
+        .line_height(theme.item_title_line_height)
+        .whitespace_nowrap()
         .overflow_hidden()
         .child(
             div()
-                .w_full()
-                .text_sm()
-                .line_height(theme.item_title_line_height)
                 .text_color(result_color)
-                .whitespace_nowrap()
                 .overflow_hidden()
                 .text_ellipsis()
                 .child(SharedString::from(calc.display_result.clone())),
-        )
+        );
+
+    if let Some(delta) = &calc.delta {
+        result = result.child(
+            div()
+                .flex_shrink_0()
+                .text_color(theme.item_description_color)
+                .child(SharedString::from(format!("({delta})"))),
+        );
+    }
+
+    let content = div()
+        .min_h(theme.item_content_height)
+        .max_w(max_width)
+        .flex()
+        .flex_col()
+        .justify_center()
+        .overflow_hidden()
+        .child(result);
+
+    if calc.bindings.is_empty() {
+        return content;
+    }
+
+    // Names and values in two columns so the values line up
+    let column = || div().flex().flex_col().whitespace_nowrap();
+    let names = column().children(
+        calc.bindings
+            .iter()
+            .map(|(name, _)| SharedString::from(format!("{name} ="))),
+    );
+    let values = column().overflow_hidden().children(
+        calc.bindings
+            .iter()
+            .map(|(_, value)| SharedString::from(value.clone())),
+    );
+
+    content.child(
+        div()
+            .flex()
+            .flex_row()
+            .gap_2()
+            .pb_1()
+            .text_xs()
+            .text_color(theme.item_description_color)
+            .child(names)
+            .child(values),
+    )
+}
+
+/// Render a color item with a swatch and the color in all formats.
+fn render_color(color: &crate::items::ColorItem, selected: bool, row: usize) -> Stateful<Div> {
+    let theme = theme();
+    let size = theme.icon_size;
+
+    let swatch = div()
+        .w(size)
+        .h(size)
+        .flex_shrink_0()
+        .bg(color.color.to_hsla())
+        .border_1()
+        .border_color(theme.item_description_color)
+        .rounded_sm();
+
+    let mut item = item_container(row, selected)
+        .child(swatch)
+        .child(render_text_content(
+            &color.input,
+            Some(&color.display),
+            selected,
+        ));
+
+    if selected {
+        item = item.children(render_action_indicators(
+            "Copy",
+            color.secondary_action_label(),
+        ));
+    }
+
+    item
 }
 
 /// Render a search item.
 fn render_search(search: &crate::items::SearchItem, selected: bool, row: usize) -> Stateful<Div> {
+    let icon = match search.icon_path() {
+        Some(path) => render_icon(Some(path), &search.provider.name, &search.id),
+        None => render_phosphor_icon(Some(search.icon())),
+    };
     let mut item = item_container(row, selected)
-        .child(render_phosphor_icon(Some(search.icon())))
+        .child(icon)
         .child(render_text_content(&search.name, None, selected));
 
     if selected {
-        item = item.child(render_action_indicator("Open"));
+        item = item.children(render_action_indicator("Open"));
     }
 
     item
@@ -226,12 +360,46 @@ fn render_ai(ai: &crate::items::AiItem, selected: bool, row: usize) -> Stateful<
         .child(render_text_content(&ai.name, ai.description(), selected));
 
     if selected {
-        item = item.child(render_action_indicator("Ask"));
+        item = item.children(render_action_indicator("Ask"));
+    }
+
+    item
+}
+
+/// Render a suggested query from the query history.
+fn render_suggestion(
+    suggestion: &crate::items::SuggestionItem,
+    selected: bool,
+    row: usize,
+) -> Stateful<Div> {
+    let mut item = item_container(row, selected)
+        .child(render_phosphor_icon(Some(suggestion.icon())))
+        .child(render_text_content(&suggestion.query, None, selected));
+
+    if selected {
+        item = item.children(render_action_indicator("Use"));
     }
 
     item
 }
 
+/// Render the placeholder row of a section whose results are still loading.
+pub fn render_loading_row(label: &str) -> Div {
+    let theme = theme();
+
+    div()
+        .mx(theme.item_margin_x)
+        .my(theme.item_margin_y)
+        .px(theme.item_padding_x)
+        .py(theme.item_padding_y)
+        .h(theme.item_content_height)
+        .flex()
+        .items_center()
+        .text_sm()
+        .text_color(theme.item_description_color)
+        .child(SharedString::from(label.to_string()))
+}
+
 /// Create the base container for a list item with selection styling.
 pub fn item_container(row: usize, selected: bool) -> Stateful<Div> {
     let theme = theme();
@@ -258,8 +426,9 @@ pub fn item_container(row: usize, selected: bool) -> Stateful<Div> {
         .gap_2()
 }
 
-/// Render an icon from a file path, with fallback placeholder.
-pub fn render_icon(icon_path: Option<&PathBuf>) -> Div {
+/// Render an icon from a file path, falling back to a monogram of `name`
+/// colored by `id`.
+pub fn render_icon(icon_path: Option<&PathBuf>, name: &str, id: &str) -> Div {
     let theme = theme();
     let size = theme.icon_size;
 
@@ -278,16 +447,70 @@ pub fn render_icon(icon_path: Option<&PathBuf>) -> Div {
         }
     }
 
-    // Fallback: show a subtle placeholder
-    icon_container
-        .bg(theme.icon_placeholder_background)
+    // Fallback: a colored monogram so icon-less entries stay distinguishable
+    let Some(letter) = monogram(name) else {
+        return icon_container
+            .bg(theme.icon_placeholder_background)
+            .rounded_sm()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(theme.icon_placeholder_color)
+                    .child(SharedString::from("?")),
+            );
+    };
+
+    icon_container.bg(monogram_color(id)).rounded_sm().child(
+        div()
+            .text_size(theme.icon_glyph_size())
+            .font_weight(gpui::FontWeight::SEMIBOLD)
+            .text_color(gpui::white())
+            .child(SharedString::from(letter)),
+    )
+}
+
+/// The uppercased first character of a name, with any combining marks
+/// that follow it.
+fn monogram(name: &str) -> Option<String> {
+    let mut chars = name.trim_start().chars();
+    let first = chars.next()?;
+    let marks = chars.take_while(|c| is_combining_mark(*c));
+    Some(first.to_uppercase().chain(marks).collect())
+}
+
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// A stable background color for an id's monogram.
+///
+/// Uses FNV-1a so the color doesn't change between builds.
+fn monogram_color(id: &str) -> Hsla {
+    let hash = id.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    hsla((hash % 360) as f32 / 360.0, 0.45, 0.45, 1.0)
+}
+
+/// Render the badge marking a NoDisplay/Hidden application.
+fn render_hidden_badge() -> Div {
+    let theme = theme();
+    div()
+        .flex_shrink_0()
+        .px_1()
         .rounded_sm()
-        .child(
-            div()
-                .text_sm()
-                .text_color(theme.icon_placeholder_color)
-                .child(SharedString::from("?")),
-        )
+        .border_1()
+        .border_color(theme.item_description_color)
+        .text_xs()
+        .text_color(theme.item_description_color)
+        .child(SharedString::from("hidden"))
 }
 
 /// Render a Phosphor icon from embedded SVG assets.
@@ -309,7 +532,7 @@ pub fn render_phosphor_icon(icon: Option<PhosphorIcon>) -> Div {
         icon_container.child(
             svg()
                 .path(icon.path())
-                .size_4()
+                .size(theme.icon_glyph_size())
                 .text_color(theme.icon_placeholder_color),
         )
     } else {
@@ -337,7 +560,10 @@ pub fn render_text_content(name: &str, description: Option<&str>, selected: bool
         .text_ellipsis()
         .child(SharedString::from(name.to_string()));
 
-    let max_width = theme.max_text_width(px(crate::config::launcher_size().0), selected);
+    let max_width = theme.max_text_width(
+        px(crate::config::launcher_size().0),
+        reserves_action_indicator(selected),
+    );
 
     let mut content = div()
         .h(theme.item_content_height)
@@ -366,11 +592,70 @@ pub fn render_text_content(name: &str, description: Option<&str>, selected: bool
     content
 }
 
-/// Render the action indicator shown on selected items.
-pub fn render_action_indicator(label: &str) -> Div {
+/// Render the action indicator shown on selected items, unless the
+/// `action_indicator` config hides it.
+pub fn render_action_indicator(label: &str) -> Option<Div> {
     let theme = theme();
 
-    div()
+    action_indicator_container(
+        config().action_indicator,
+        div()
+            .text_xs()
+            .text_color(theme.action_indicator.label_color)
+            .child(SharedString::from(label.to_string())),
+    )
+}
+
+/// Render the action indicator with a hint for the secondary (shift+enter)
+/// action, if there is one.
+pub fn render_action_indicators(label: &str, secondary: Option<&str>) -> Option<Div> {
+    let Some(secondary) = secondary else {
+        return render_action_indicator(label);
+    };
+    let theme = theme();
+
+    action_indicator_container(
+        config().action_indicator,
+        div()
+            .flex()
+            .flex_row()
+            .gap_2()
+            .text_xs()
+            .child(
+                div()
+                    .text_color(theme.item_description_color)
+                    .child(SharedString::from(format!("⇧ {secondary}"))),
+            )
+            .child(
+                div()
+                    .text_color(theme.action_indicator.label_color)
+                    .child(SharedString::from(label.to_string())),
+            ),
+    )
+}
+
+/// Render the indicator shown on selected submenu items.
+pub fn render_submenu_indicator() -> Option<Div> {
+    let theme = theme();
+
+    action_indicator_container(
+        config().action_indicator,
+        svg()
+            .path(SUBMENU_INDICATOR_ICON.path())
+            .size_3()
+            .text_color(theme.action_indicator.label_color),
+    )
+}
+
+/// Positioned container holding an indicator label and, depending on
+/// `style`, the Enter key box. `None` when the indicator is hidden.
+fn action_indicator_container(style: ActionIndicatorStyle, label: impl IntoElement) -> Option<Div> {
+    if style == ActionIndicatorStyle::Hidden {
+        return None;
+    }
+    let theme = theme();
+
+    let mut container = div()
         .absolute()
         .right(theme.action_indicator.right_position)
         .top_0()
@@ -379,13 +664,10 @@ pub fn render_action_indicator(label: &str) -> Div {
         .flex_row()
         .items_center()
         .gap_2()
-        .child(
-            div()
-                .text_xs()
-                .text_color(theme.action_indicator.label_color)
-                .child(SharedString::from(label.to_string())),
-        )
-        .child(
+        .child(label);
+
+    if let Some(hint) = key_hint(style) {
+        container = container.child(
             // Kbd-style box for Enter key
             div()
                 .px(theme.action_indicator.key_padding_x)
@@ -398,6 +680,64 @@ pub fn render_action_indicator(label: &str) -> Div {
                 .text_size(theme.action_indicator.key_font_size)
                 .line_height(theme.action_indicator.key_line_height)
                 .text_color(theme.action_indicator.key_color)
-                .child(SharedString::from("↵")),
-        )
+                .child(SharedString::from(hint)),
+        );
+    }
+
+    Some(container)
+}
+
+/// Whether a row leaves room for the action indicator.
+fn reserves_action_indicator(selected: bool) -> bool {
+    selected && config().action_indicator != ActionIndicatorStyle::Hidden
+}
+
+/// The key box text shown beside the action label, if any.
+fn key_hint(style: ActionIndicatorStyle) -> Option<&'static str> {
+    match style {
+        ActionIndicatorStyle::Key => Some(ENTER_KEY_HINT),
+        ActionIndicatorStyle::Label | ActionIndicatorStyle::Hidden => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_item_handles_every_variant() {
+        for (row, item) in crate::test_utils::mock_list_item_variants()
+            .iter()
+            .enumerate()
+        {
+            let _ = render_item(item, false, row);
+            let _ = render_item(item, true, row);
+        }
+    }
+
+    #[test]
+    fn test_action_indicator_style() {
+        assert!(action_indicator_container(ActionIndicatorStyle::Hidden, div()).is_none());
+        assert!(action_indicator_container(ActionIndicatorStyle::Label, div()).is_some());
+        assert!(action_indicator_container(ActionIndicatorStyle::Key, div()).is_some());
+
+        assert_eq!(key_hint(ActionIndicatorStyle::Key), Some("↵"));
+        assert_eq!(key_hint(ActionIndicatorStyle::Label), None);
+        assert_eq!(key_hint(ActionIndicatorStyle::Hidden), None);
+    }
+
+    #[test]
+    fn test_monogram_uses_first_grapheme() {
+        assert_eq!(monogram("firefox").as_deref(), Some("F"));
+        assert_eq!(monogram("  Zed").as_deref(), Some("Z"));
+        assert_eq!(monogram("e\u{0301}clair").as_deref(), Some("E\u{0301}"));
+        assert_eq!(monogram("Über").as_deref(), Some("Ü"));
+        assert_eq!(monogram(""), None);
+    }
+
+    #[test]
+    fn test_monogram_color_is_deterministic() {
+        assert_eq!(monogram_color("firefox"), monogram_color("firefox"));
+        assert_ne!(monogram_color("firefox"), monogram_color("kitty"));
+    }
 }
@@ -5,15 +5,17 @@
 
 pub mod ai_view;
 pub mod clipboard_rendering;
-mod emoji_rendering;
+mod grid_rendering;
 mod item_rendering;
+mod scratchpad_rendering;
 mod theme_rendering;
 
 pub use ai_view::AiResponseView;
 pub use clipboard_rendering::render_clipboard_item;
-pub use emoji_rendering::{render_emoji_cell, render_emoji_row};
+pub use grid_rendering::{render_grid_cell, render_grid_row};
 pub use item_rendering::{
-    item_container, render_action_indicator, render_icon, render_item, render_phosphor_icon,
-    render_text_content,
+    item_container, render_action_indicator, render_icon, render_item, render_loading_row,
+    render_phosphor_icon, render_submenu_indicator, render_text_content,
 };
+pub use scratchpad_rendering::render_scratchpad;
 pub use theme_rendering::render_theme_item;
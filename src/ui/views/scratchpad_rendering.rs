@@ -0,0 +1,46 @@
+//! Rendering functions for the calculator scratchpad view.
+
+use crate::calculator::Scratchpad;
+use crate::items::ListItem;
+use crate::ui::theme::theme;
+use gpui::{Div, ScrollHandle, SharedString, div, prelude::*};
+use gpui_component::scroll::ScrollableElement;
+
+use super::render_item;
+
+/// Render the scratchpad history, oldest line first.
+pub fn render_scratchpad(scratchpad: &Scratchpad, scroll_handle: &ScrollHandle) -> Div {
+    let t = theme();
+
+    if scratchpad.is_empty() {
+        return div().flex_1().flex().items_center().justify_center().child(
+            div()
+                .text_sm()
+                .text_color(t.empty_state_color)
+                .child(SharedString::from(
+                    "Type an expression and press Enter. Use ans for the previous result.",
+                )),
+        );
+    }
+
+    let selected = scratchpad.selected_index();
+    let lines = scratchpad.lines().iter().enumerate().map(|(row, line)| {
+        render_item(
+            &ListItem::Calculator(line.clone()),
+            selected == Some(row),
+            row,
+        )
+    });
+
+    div().flex_1().overflow_hidden().py_2().child(
+        div()
+            .id("scratchpad-scroll")
+            .size_full()
+            .flex()
+            .flex_col()
+            .overflow_y_scroll()
+            .track_scroll(scroll_handle)
+            .vertical_scrollbar(scroll_handle)
+            .children(lines),
+    )
+}